@@ -1,4 +1,3 @@
-use std::collections::HashMap;
 use std::time::SystemTime;
 
 use crossbeam::channel::unbounded;
@@ -6,8 +5,14 @@ use crossbeam::channel::unbounded;
 use eframe::{run_native, App, CreationContext, NativeOptions};
 
 use egui::{CentralPanel, Context, Layout, TopBottomPanel};
-use tracing::{info, trace};
-use widgets::Theme;
+use netstrat::{
+    export::image::Capture,
+    log::LevelHandle,
+    power::LowPower,
+    preferences::{Preferences, PREFERENCES_KEY},
+};
+use tracing::{info, metadata::LevelFilter, trace};
+use widgets::{LayoutSwitcher, Shortcuts, StatusBar, Theme, ToastsOverlay, WorkspaceMenu};
 
 mod netstrat;
 mod network;
@@ -15,36 +20,106 @@ mod sources;
 mod widgets;
 mod windows;
 use tokio;
-use windows::{AppWindow, SymbolsGraph};
+use windows::{
+    AppWindow, BacktestWindow, CacheManager, CorrelationMatrix, CsvImport, DebugWindow,
+    DepthOfMarket, FundingHistory, GraphGrid, LiveTrading, NetworkSimulation, PaperTrading,
+    Screener, SettingsWindow, SymbolsGraph, TimeAndSales, UpdateNotice,
+};
 
 struct TemplateApp {
     windows: Vec<Box<dyn AppWindow>>,
     theme: Theme,
+    layouts: LayoutSwitcher,
+    workspaces: WorkspaceMenu,
+    low_power: LowPower,
+    capture: Capture,
+    shortcuts: Shortcuts,
+    status_bar: StatusBar,
+    toasts: ToastsOverlay,
 }
 
 impl TemplateApp {
-    fn new(_ctx: &CreationContext<'_>) -> Self {
+    fn new(ctx: &CreationContext<'_>, log_level: LevelHandle) -> Self {
         info!("Creating app...");
 
         let (s, r) = unbounded();
-
-        let mut visibility_map = HashMap::new();
-        visibility_map.insert("debug".to_string(), false);
-
-        Self {
-            windows: vec![Box::new(SymbolsGraph::new(s, r, true))],
+        let screener = Screener::new(s.clone(), false);
+
+        let mut app = Self {
+            windows: vec![
+                Box::new(SymbolsGraph::new(s, r, true)),
+                Box::new(GraphGrid::new(false)),
+                Box::new(DepthOfMarket::new(false)),
+                Box::new(TimeAndSales::new(false)),
+                Box::new(FundingHistory::new(false)),
+                Box::new(CorrelationMatrix::new(false)),
+                Box::new(BacktestWindow::new(false)),
+                Box::new(PaperTrading::new(false)),
+                Box::new(LiveTrading::new(false)),
+                Box::new(screener),
+                Box::new(CacheManager::new(false)),
+                Box::new(CsvImport::new(false)),
+                Box::new(NetworkSimulation::new(false)),
+                Box::new(DebugWindow::new(log_level, false)),
+                Box::new(SettingsWindow::new(false)),
+                Box::new(UpdateNotice::new(false)),
+            ],
             theme: Theme::new(),
+            layouts: LayoutSwitcher::default(),
+            workspaces: WorkspaceMenu::default(),
+            low_power: LowPower::default(),
+            capture: Capture::default(),
+            shortcuts: Shortcuts::default(),
+            status_bar: StatusBar,
+            toasts: ToastsOverlay,
+        };
+
+        if let Some(storage) = ctx.storage {
+            let prefs: Preferences =
+                eframe::get_value(storage, PREFERENCES_KEY).unwrap_or_default();
+            info!("Restoring preferences.");
+
+            if let Some(theme) = &prefs.theme {
+                app.theme.select(theme);
+            }
+            app.windows.iter_mut().for_each(|w| {
+                if let Some(layout) = prefs.windows.get(w.name()) {
+                    w.apply_layout(layout);
+                }
+            });
         }
+
+        app
     }
 }
 
 impl App for TemplateApp {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        info!("Saving preferences.");
+
+        let prefs = Preferences {
+            theme: Some(self.theme.selected().to_string()),
+            windows: self
+                .windows
+                .iter()
+                .map(|w| (w.name().to_string(), w.layout()))
+                .collect(),
+        };
+        eframe::set_value(storage, PREFERENCES_KEY, &prefs);
+    }
+
     fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
         let start = SystemTime::now();
 
+        self.low_power.update(ctx);
+        self.capture.update(ctx);
+        self.shortcuts.update(ctx);
+
         TopBottomPanel::top("header").show(ctx, |ui| {
             ui.with_layout(Layout::left_to_right(), |ui| {
                 ui.add(&mut self.theme);
+                self.layouts.ui(ui, &mut self.windows);
+                self.workspaces.ui(ui, &mut self.windows);
 
                 self.windows.iter_mut().for_each(|w| {
                     w.as_mut().toggle_btn(ui);
@@ -52,9 +127,14 @@ impl App for TemplateApp {
             });
         });
 
+        TopBottomPanel::bottom("status bar").show(ctx, |ui| {
+            ui.add(&mut self.status_bar);
+        });
+
         CentralPanel::default().show(ctx, |ui| {
             self.windows.iter_mut().for_each(|w| w.show(ui));
         });
+        self.toasts.show(ctx);
 
         trace!(
             "time elapsed per frame: {:?}",
@@ -67,11 +147,12 @@ impl App for TemplateApp {
 
 #[tokio::main]
 async fn main() {
-    tracing_subscriber::fmt::init();
+    let to_file = netstrat::settings::Settings::loaded().log_to_file;
+    let log_level = netstrat::log::init(LevelFilter::INFO, to_file);
 
     run_native(
         "hedgegraph",
         NativeOptions::default(),
-        Box::new(|cc| Box::new(TemplateApp::new(cc))),
+        Box::new(|cc| Box::new(TemplateApp::new(cc, log_level))),
     );
 }
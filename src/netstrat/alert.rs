@@ -0,0 +1,66 @@
+/// Price alert which triggers once the price crosses its level.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Alert {
+    pub price: f64,
+    /// Whether the level was above the price when the alert was armed.
+    above: bool,
+    pub triggered: bool,
+}
+
+impl Alert {
+    /// Creates an alert armed against the last known price.
+    pub fn new(price: f64, last: f64) -> Self {
+        Self {
+            price,
+            above: price >= last,
+            triggered: false,
+        }
+    }
+
+    /// Moves the alert to the new level and arms it again.
+    pub fn move_to(&mut self, price: f64, last: f64) {
+        *self = Self::new(price, last);
+    }
+
+    /// Checks the price against the level. Returns true if the alert has just triggered.
+    pub fn check(&mut self, price: f64) -> bool {
+        if self.triggered {
+            return false;
+        }
+
+        self.triggered = match self.above {
+            true => price >= self.price,
+            false => price <= self.price,
+        };
+
+        self.triggered
+    }
+}
+
+#[cfg(test)]
+mod alert_tests {
+    use super::*;
+
+    #[test]
+    fn test_check_crossing() {
+        let mut up = Alert::new(110.0, 100.0);
+        assert!(!up.check(105.0));
+        assert!(up.check(111.0));
+        assert!(!up.check(112.0), "alert triggers once");
+
+        let mut down = Alert::new(90.0, 100.0);
+        assert!(!down.check(95.0));
+        assert!(down.check(90.0));
+    }
+
+    #[test]
+    fn test_move_rearms() {
+        let mut alert = Alert::new(110.0, 100.0);
+        assert!(alert.check(120.0));
+
+        alert.move_to(100.0, 120.0);
+        assert!(!alert.triggered);
+        assert!(!alert.check(105.0));
+        assert!(alert.check(99.0));
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt::Display;
+
+use crate::{netstrat::data::Data, sources::binance::Kline};
+
+/// Max relative price change between neighbour klines considered valid.
+const MAX_PRICE_CHANGE: f32 = 0.5;
+
+/// Problem found in klines before they are written to the archive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Anomaly {
+    /// Klines are missing between the timestamps.
+    Gap { from: i64, to: i64 },
+    /// Several klines have the same open time.
+    Duplicate { t_open: i64 },
+    /// Kline prices are inconsistent or jump too far from the previous close.
+    OutOfRange { t_open: i64, price: f32 },
+}
+
+impl Display for Anomaly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Anomaly::Gap { from, to } => write!(
+                f,
+                "gap from {} to {}",
+                Data::format_ts(*from as f64),
+                Data::format_ts(*to as f64)
+            ),
+            Anomaly::Duplicate { t_open } => {
+                write!(f, "duplicate kline at {}", Data::format_ts(*t_open as f64))
+            }
+            Anomaly::OutOfRange { t_open, price } => write!(
+                f,
+                "out of range price {price} at {}",
+                Data::format_ts(*t_open as f64)
+            ),
+        }
+    }
+}
+
+/// Detects gaps, duplicates and out of range prices in klines sorted by open time.
+/// Step is the expected distance between open times of neighbour klines in milliseconds.
+pub fn detect(klines: &[Kline], step: i64) -> Vec<Anomaly> {
+    let mut res = vec![];
+    klines.iter().enumerate().for_each(|(i, k)| {
+        let inconsistent = k.low <= 0.0
+            || k.low > k.high
+            || [k.open, k.close].iter().any(|p| *p < k.low || *p > k.high);
+        if inconsistent {
+            res.push(Anomaly::OutOfRange {
+                t_open: k.t_open,
+                price: k.close,
+            });
+        }
+
+        if i == 0 {
+            return;
+        }
+
+        let prev = &klines[i - 1];
+        if k.t_open == prev.t_open {
+            res.push(Anomaly::Duplicate { t_open: k.t_open });
+            return;
+        }
+        if k.t_open - prev.t_open > step {
+            res.push(Anomaly::Gap {
+                from: prev.t_close,
+                to: k.t_open,
+            });
+        }
+
+        if !inconsistent && prev.close > 0.0 {
+            let change = [k.high, k.low]
+                .iter()
+                .map(|p| (p - prev.close).abs() / prev.close)
+                .fold(0.0, f32::max);
+            if change > MAX_PRICE_CHANGE {
+                res.push(Anomaly::OutOfRange {
+                    t_open: k.t_open,
+                    price: match k.high - prev.close > prev.close - k.low {
+                        true => k.high,
+                        false => k.low,
+                    },
+                });
+            }
+        }
+    });
+
+    res
+}
+
+#[cfg(test)]
+mod anomaly_tests {
+    use super::*;
+
+    fn kline(t_open: i64, price: f32) -> Kline {
+        Kline {
+            t_open,
+            t_close: t_open + 9,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_detect() {
+        let klines = vec![
+            kline(0, 1.0),
+            kline(10, 1.1),
+            kline(10, 1.1),
+            kline(40, 1.0),
+            kline(50, 3.0),
+        ];
+
+        assert_eq!(
+            detect(&klines, 10),
+            vec![
+                Anomaly::Duplicate { t_open: 10 },
+                Anomaly::Gap { from: 19, to: 40 },
+                Anomaly::OutOfRange {
+                    t_open: 50,
+                    price: 3.0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detect_inconsistent() {
+        let mut k = kline(0, 1.0);
+        k.low = 2.0;
+
+        assert_eq!(
+            detect(&[k], 10),
+            vec![Anomaly::OutOfRange {
+                t_open: 0,
+                price: 1.0
+            }]
+        );
+        assert!(detect(&[kline(0, 1.0), kline(10, 1.2)], 10).is_empty());
+    }
+}
@@ -0,0 +1,15 @@
+use quick_error::quick_error;
+
+quick_error! {
+    #[derive(Debug)]
+    pub enum ArchiveError {
+        Io(err: std::io::Error) {
+            from()
+            display("{}", err)
+        }
+        Csv(err: csv::Error) {
+            from()
+            display("{}", err)
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    sync::{Mutex, PoisonError},
+};
+
+use chrono::Utc;
+use tracing::{error, info, warn};
+
+use crate::sources::binance::{Interval, Kline};
+
+use self::{anomaly::Anomaly, errors::ArchiveError};
+
+pub mod anomaly;
+pub mod errors;
+
+const ARCHIVE_DIR: &str = "archive";
+const EXT: &str = "csv";
+const ANOMALIES_LOG: &str = "anomalies.log";
+
+/// Serializes background writes so merges into the same file do not race.
+static WRITES: Mutex<()> = Mutex::new(());
+
+/// Summary of klines archived for a symbol and interval.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub symbol: String,
+    pub interval: Interval,
+    pub start: i64,
+    pub end: i64,
+    pub count: usize,
+    pub size: u64,
+}
+
+/// Archive is a local storage of downloaded klines.
+/// Klines of every symbol and interval are kept in a separate csv file sorted by open time.
+#[derive(Debug, Clone)]
+pub struct Archive {
+    dir: PathBuf,
+}
+
+impl Default for Archive {
+    fn default() -> Self {
+        Self::new(PathBuf::from(ARCHIVE_DIR))
+    }
+}
+
+impl Archive {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Merges klines into the archive replacing klines with the same open time.
+    pub fn store(
+        &self,
+        symbol: &str,
+        interval: Interval,
+        klines: &[Kline],
+    ) -> Result<(), ArchiveError> {
+        let mut merged: BTreeMap<i64, Kline> = self
+            .load(symbol, interval)?
+            .into_iter()
+            .map(|k| (k.t_open, k))
+            .collect();
+        klines.iter().for_each(|k| {
+            merged.insert(k.t_open, *k);
+        });
+
+        self.write(symbol, interval, merged.values())
+    }
+
+    /// Logs anomalies and merges klines into the archive on a blocking thread.
+    /// Failures are logged.
+    pub fn record(
+        &self,
+        symbol: String,
+        interval: Interval,
+        klines: Vec<Kline>,
+        anomalies: Vec<Anomaly>,
+    ) {
+        let archive = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let _lock = WRITES.lock().unwrap_or_else(PoisonError::into_inner);
+            if let Err(err) = archive.log_anomalies(&symbol, interval, &anomalies) {
+                error!("Failed to log data anomalies: {err}.");
+            }
+            if let Err(err) = archive.store(&symbol, interval, &klines) {
+                error!("Failed to archive klines: {err}.");
+            }
+        });
+    }
+
+    /// Loads all archived klines for the symbol and interval.
+    pub fn load(&self, symbol: &str, interval: Interval) -> Result<Vec<Kline>, ArchiveError> {
+        let path = self.path(symbol, interval);
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut rdr = csv::Reader::from_reader(File::open(path)?);
+        rdr.deserialize()
+            .map(|k| k.map_err(ArchiveError::from))
+            .collect()
+    }
+
+    /// Lists archived symbols and intervals. Files not recognized as archive are skipped.
+    pub fn list(&self) -> Result<Vec<Entry>, ArchiveError> {
+        if !self.dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut res = vec![];
+        for dir_entry in fs::read_dir(&self.dir)? {
+            let path = dir_entry?.path();
+            let (symbol, interval) = match Archive::parse_name(&path) {
+                Some(parsed) => parsed,
+                None => continue,
+            };
+
+            let klines = self.load(&symbol, interval)?;
+            res.push(Entry {
+                start: klines.first().map(|k| k.t_open).unwrap_or_default(),
+                end: klines.last().map(|k| k.t_close).unwrap_or_default(),
+                count: klines.len(),
+                size: fs::metadata(&path)?.len(),
+                symbol,
+                interval,
+            });
+        }
+        res.sort_by(|a, b| (&a.symbol, a.interval.as_str()).cmp(&(&b.symbol, b.interval.as_str())));
+
+        Ok(res)
+    }
+
+    pub fn delete(&self, symbol: &str, interval: Interval) -> Result<(), ArchiveError> {
+        let path = self.path(symbol, interval);
+        if path.exists() {
+            fs::remove_file(&path)?;
+            info!("Deleted archive file: {path:?}.");
+        }
+
+        Ok(())
+    }
+
+    /// Replaces archived klines of the symbol and interval with the given ones.
+    pub fn replace(
+        &self,
+        symbol: &str,
+        interval: Interval,
+        klines: &[Kline],
+    ) -> Result<(), ArchiveError> {
+        self.delete(symbol, interval)?;
+        self.store(symbol, interval, klines)
+    }
+
+    /// Rewrites every archive file sorted and without duplicates, removing empty ones.
+    /// Returns the number of bytes freed.
+    pub fn vacuum(&self) -> Result<u64, ArchiveError> {
+        let mut freed = 0;
+        for entry in self.list()? {
+            if entry.count == 0 {
+                self.delete(&entry.symbol, entry.interval)?;
+                freed += entry.size;
+                continue;
+            }
+
+            let klines = self.load(&entry.symbol, entry.interval)?;
+            self.replace(&entry.symbol, entry.interval, &klines)?;
+
+            let size = fs::metadata(self.path(&entry.symbol, entry.interval))?.len();
+            freed += entry.size.saturating_sub(size);
+        }
+
+        info!("Vacuumed archive, freed {freed} bytes.");
+
+        Ok(freed)
+    }
+
+    /// Appends anomalies found in klines of the symbol and interval to the anomalies log.
+    /// Anomalies already logged for the symbol and interval are skipped, so reloads of
+    /// the same range do not repeat them.
+    pub fn log_anomalies(
+        &self,
+        symbol: &str,
+        interval: Interval,
+        anomalies: &[Anomaly],
+    ) -> Result<(), ArchiveError> {
+        if anomalies.is_empty() {
+            return Ok(());
+        }
+
+        let path = self.dir.join(ANOMALIES_LOG);
+        let logged: HashSet<String> = match path.exists() {
+            true => fs::read_to_string(&path)?
+                .lines()
+                .filter_map(|l| l.split_once('\t').map(|(_, entry)| entry.to_string()))
+                .collect(),
+            false => HashSet::new(),
+        };
+        let entries: Vec<String> = anomalies
+            .iter()
+            .map(|a| format!("{symbol}\t{}\t{a}", interval.as_str()))
+            .filter(|e| !logged.contains(e))
+            .collect();
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        warn!(
+            "Found {} new anomalies in {symbol} {} klines.",
+            entries.len(),
+            interval.as_str()
+        );
+
+        fs::create_dir_all(&self.dir)?;
+        let mut f = OpenOptions::new().create(true).append(true).open(path)?;
+        let now = Utc::now().to_rfc3339();
+        for e in entries {
+            writeln!(f, "{now}\t{e}")?;
+        }
+
+        Ok(())
+    }
+
+    fn write<'a>(
+        &self,
+        symbol: &str,
+        interval: Interval,
+        klines: impl Iterator<Item = &'a Kline>,
+    ) -> Result<(), ArchiveError> {
+        fs::create_dir_all(&self.dir)?;
+
+        let mut wtr = csv::Writer::from_writer(File::create(self.path(symbol, interval))?);
+        for k in klines {
+            wtr.serialize(k)?;
+        }
+        wtr.flush()?;
+
+        Ok(())
+    }
+
+    fn path(&self, symbol: &str, interval: Interval) -> PathBuf {
+        self.dir
+            .join(format!("{}-{}.{}", symbol, interval.as_str(), EXT))
+    }
+
+    fn parse_name(path: &std::path::Path) -> Option<(String, Interval)> {
+        if path.extension()? != EXT {
+            return None;
+        }
+
+        let (symbol, interval) = path.file_stem()?.to_str()?.rsplit_once('-')?;
+        Some((symbol.to_string(), Interval::parse(interval)?))
+    }
+}
+
+#[cfg(test)]
+mod archive_tests {
+    use super::*;
+
+    fn archive(name: &str) -> Archive {
+        let dir =
+            std::env::temp_dir().join(format!("netstrat-archive-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        Archive::new(dir)
+    }
+
+    fn kline(t_open: i64, close: f32) -> Kline {
+        Kline {
+            t_open,
+            t_close: t_open + 9,
+            close,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_store_merges() {
+        let a = archive("store");
+        a.store(
+            "BTCUSDT",
+            Interval::Minute,
+            &[kline(10, 1.0), kline(0, 1.0)],
+        )
+        .unwrap();
+        a.store(
+            "BTCUSDT",
+            Interval::Minute,
+            &[kline(10, 2.0), kline(20, 3.0)],
+        )
+        .unwrap();
+
+        assert_eq!(
+            a.load("BTCUSDT", Interval::Minute).unwrap(),
+            vec![kline(0, 1.0), kline(10, 2.0), kline(20, 3.0)]
+        );
+        assert!(a.load("BTCUSDT", Interval::Hour).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_list_and_delete() {
+        let a = archive("list");
+        a.store("ETHUSDT", Interval::Hour, &[kline(0, 1.0), kline(10, 1.0)])
+            .unwrap();
+        a.store("BTCUSDT", Interval::Day, &[kline(5, 1.0)]).unwrap();
+
+        let entries = a.list().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].symbol, "BTCUSDT");
+        assert_eq!(entries[0].interval, Interval::Day);
+        assert_eq!((entries[1].start, entries[1].end), (0, 19));
+        assert_eq!(entries[1].count, 2);
+        assert!(entries[1].size > 0);
+
+        a.delete("BTCUSDT", Interval::Day).unwrap();
+        assert_eq!(a.list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_log_anomalies() {
+        let a = archive("anomalies");
+        let anomalies = vec![Anomaly::Duplicate { t_open: 0 }];
+        a.log_anomalies("BTCUSDT", Interval::Minute, &anomalies)
+            .unwrap();
+        // reloads of the same range log nothing new
+        a.log_anomalies("BTCUSDT", Interval::Minute, &anomalies)
+            .unwrap();
+        a.log_anomalies("BTCUSDT", Interval::Hour, &anomalies)
+            .unwrap();
+        let anomalies = vec![
+            Anomaly::Duplicate { t_open: 0 },
+            Anomaly::Duplicate { t_open: 60_000 },
+        ];
+        a.log_anomalies("BTCUSDT", Interval::Minute, &anomalies)
+            .unwrap();
+
+        let log = fs::read_to_string(a.dir.join(ANOMALIES_LOG)).unwrap();
+        assert_eq!(log.lines().count(), 3);
+        assert_eq!(
+            log.lines()
+                .filter(|l| l.contains("BTCUSDT\t1m\tduplicate"))
+                .count(),
+            2
+        );
+        assert!(log.lines().any(|l| l.contains("BTCUSDT\t1h\tduplicate")));
+        // log is not listed as archived klines
+        assert!(a.list().unwrap().is_empty());
+    }
+}
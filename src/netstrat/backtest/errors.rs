@@ -0,0 +1,13 @@
+use quick_error::quick_error;
+
+use crate::netstrat::script::errors::ScriptError;
+
+quick_error! {
+    #[derive(Debug)]
+    pub enum BacktestError {
+        Script(err: ScriptError) {
+            from()
+            display("strategy script failed: {}", err)
+        }
+    }
+}
@@ -0,0 +1,296 @@
+use super::data::Data;
+
+use self::{errors::BacktestError, rules::Rules};
+
+pub mod errors;
+pub mod rules;
+
+/// Capital and costs of a backtest.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Config {
+    pub capital: f64,
+    /// Fee taken from every fill as a fraction of its value.
+    pub fee: f64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            capital: 10_000.0,
+            fee: 0.001,
+        }
+    }
+}
+
+/// Closed long position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trade {
+    pub entry_time: i64,
+    pub entry_price: f64,
+    pub exit_time: i64,
+    pub exit_price: f64,
+    /// Profit after fees.
+    pub pnl: f64,
+    /// Profit after fees as a fraction of the entered capital.
+    pub ret: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Stats {
+    pub trades: usize,
+    /// Fraction of trades with positive profit.
+    pub win_rate: f64,
+    /// Gross profit divided by gross loss, none without losing trades.
+    pub profit_factor: Option<f64>,
+    /// Largest fall of equity from its peak as a fraction of the peak.
+    pub max_drawdown: f64,
+    pub total_return: f64,
+}
+
+impl Stats {
+    fn new(trades: &[Trade], equity: &[(i64, f64)], capital: f64) -> Self {
+        let wins = trades.iter().filter(|t| t.pnl > 0.0).count();
+        let profit: f64 = trades.iter().map(|t| t.pnl.max(0.0)).sum();
+        let loss: f64 = trades.iter().map(|t| (-t.pnl).max(0.0)).sum();
+
+        let mut peak = capital;
+        let mut max_drawdown: f64 = 0.0;
+        equity.iter().for_each(|(_, e)| {
+            peak = peak.max(*e);
+            if peak > 0.0 {
+                max_drawdown = max_drawdown.max((peak - e) / peak);
+            }
+        });
+        let last = equity.last().map_or(capital, |(_, e)| *e);
+
+        Self {
+            trades: trades.len(),
+            win_rate: match trades.is_empty() {
+                true => 0.0,
+                false => wins as f64 / trades.len() as f64,
+            },
+            profit_factor: (loss > 0.0).then_some(profit / loss),
+            max_drawdown,
+            total_return: last / capital - 1.0,
+        }
+    }
+}
+
+/// Trades, equity by kline close time and stats of a backtest.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Report {
+    pub trades: Vec<Trade>,
+    pub equity: Vec<(i64, f64)>,
+    pub stats: Stats,
+}
+
+/// Open long position with the capital it was entered with.
+struct Position {
+    time: i64,
+    price: f64,
+    qty: f64,
+    capital: f64,
+}
+
+impl Position {
+    fn close(self, time: i64, price: f64, fee: f64) -> (f64, Trade) {
+        let cash = self.qty * price * (1.0 - fee);
+        let pnl = cash - self.capital;
+        let trade = Trade {
+            entry_time: self.time,
+            entry_price: self.price,
+            exit_time: time,
+            exit_price: price,
+            pnl,
+            ret: pnl / self.capital,
+        };
+        (cash, trade)
+    }
+}
+
+/// Runs the rules bar by bar over the klines going long with all capital.
+///
+/// Rules are checked at the close of every bar and orders are filled at the open of the
+/// next bar, so rules can not trade on prices they have not seen yet.
+/// A position still open after the last bar is closed at its close.
+pub fn run(data: &Data, rules: &mut dyn Rules, config: &Config) -> Result<Report, BacktestError> {
+    let klines = &data.vals;
+    rules.prepare(data)?;
+
+    let mut cash = config.capital;
+    let mut position: Option<Position> = None;
+    let mut order: Option<bool> = None;
+    let mut report = Report::default();
+
+    for (i, k) in klines.iter().enumerate() {
+        let open = k.open as f64;
+        match (order.take(), position.take()) {
+            (Some(true), None) => {
+                position = Some(Position {
+                    time: k.t_open,
+                    price: open,
+                    qty: cash * (1.0 - config.fee) / open,
+                    capital: cash,
+                });
+                cash = 0.0;
+            }
+            (Some(false), Some(p)) => {
+                let (closed, trade) = p.close(k.t_open, open, config.fee);
+                cash = closed;
+                report.trades.push(trade);
+            }
+            (_, p) => position = p,
+        }
+
+        let close = k.close as f64;
+        let held = position.as_ref().map_or(0.0, |p| p.qty * close);
+        report.equity.push((k.t_close, cash + held));
+
+        order = match position.is_some() {
+            true => rules.exit(i).then_some(false),
+            false => rules.entry(i).then_some(true),
+        };
+    }
+
+    if let (Some(p), Some(k)) = (position, klines.last()) {
+        let (closed, trade) = p.close(k.t_close, k.close as f64, config.fee);
+        report.trades.push(trade);
+        if let Some(last) = report.equity.last_mut() {
+            last.1 = closed;
+        }
+    }
+
+    report.stats = Stats::new(&report.trades, &report.equity, config.capital);
+    Ok(report)
+}
+
+#[cfg(test)]
+mod backtest_tests {
+    use super::*;
+    use crate::sources::binance::Kline;
+
+    /// Enters and exits at fixed bars.
+    struct Fixed {
+        entries: Vec<usize>,
+        exits: Vec<usize>,
+    }
+
+    impl Rules for Fixed {
+        fn name(&self) -> String {
+            "fixed".to_string()
+        }
+
+        fn prepare(&mut self, _: &Data) -> Result<(), BacktestError> {
+            Ok(())
+        }
+
+        fn entry(&self, i: usize) -> bool {
+            self.entries.contains(&i)
+        }
+
+        fn exit(&self, i: usize) -> bool {
+            self.exits.contains(&i)
+        }
+    }
+
+    fn data(prices: &[(f32, f32)]) -> Data {
+        Data::new(
+            prices
+                .iter()
+                .enumerate()
+                .map(|(i, (open, close))| Kline {
+                    t_open: i as i64 * 10,
+                    t_close: i as i64 * 10 + 9,
+                    open: *open,
+                    close: *close,
+                    high: open.max(*close),
+                    low: open.min(*close),
+                    ..Default::default()
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_run() {
+        let data = data(&[
+            (10.0, 10.0),
+            (10.0, 12.0),
+            (12.0, 20.0),
+            (20.0, 8.0),
+            (8.0, 9.0),
+        ]);
+        let mut rules = Fixed {
+            entries: vec![0, 3],
+            exits: vec![1],
+        };
+        let config = Config {
+            capital: 100.0,
+            fee: 0.0,
+        };
+
+        let report = run(&data, &mut rules, &config).unwrap();
+        assert_eq!(
+            report.trades[0],
+            Trade {
+                entry_time: 10,
+                entry_price: 10.0,
+                exit_time: 20,
+                exit_price: 12.0,
+                pnl: 20.0,
+                ret: 0.2,
+            }
+        );
+        // entered at the open of the last bar and closed at its close
+        assert_eq!(report.trades[1].entry_time, 40);
+        assert_eq!(report.trades[1].exit_time, 49);
+        assert!((report.trades[1].pnl - 15.0).abs() < 1e-9);
+        assert_eq!(
+            report.equity,
+            vec![
+                (9, 100.0),
+                (19, 120.0),
+                (29, 120.0),
+                (39, 120.0),
+                (49, 135.0)
+            ]
+        );
+
+        assert_eq!(report.stats.trades, 2);
+        assert_eq!(report.stats.win_rate, 1.0);
+        assert_eq!(report.stats.profit_factor, None);
+        assert!((report.stats.total_return - 0.35).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_run_fees_and_drawdown() {
+        let data = data(&[(10.0, 10.0), (10.0, 5.0), (5.0, 5.0)]);
+        let mut rules = Fixed {
+            entries: vec![0],
+            exits: vec![1],
+        };
+        let config = Config {
+            capital: 100.0,
+            fee: 0.01,
+        };
+
+        let report = run(&data, &mut rules, &config).unwrap();
+        let trade = report.trades[0];
+        assert!((trade.pnl - (99.0 / 10.0 * 5.0 * 0.99 - 100.0)).abs() < 1e-9);
+        assert_eq!(report.stats.win_rate, 0.0);
+        assert!(report.stats.profit_factor.unwrap().abs() < 1e-9);
+        assert!((report.stats.max_drawdown - (1.0 - 49.005 / 100.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_run_empty() {
+        let mut rules = Fixed {
+            entries: vec![],
+            exits: vec![],
+        };
+
+        let report = run(&Data::default(), &mut rules, &Config::default()).unwrap();
+        assert!(report.trades.is_empty());
+        assert_eq!(report.stats.total_return, 0.0);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::netstrat::{
+    data::Data,
+    indicators::{closes, ma::ema},
+    script::{Script, Signals},
+};
+
+use super::errors::BacktestError;
+
+/// Entry and exit rules of a long only strategy checked at the close of every bar.
+pub trait Rules {
+    fn name(&self) -> String;
+    /// Computes series of all bars before the run, so checks stay cheap.
+    fn prepare(&mut self, data: &Data) -> Result<(), BacktestError>;
+    /// Enter at the open of the next bar.
+    fn entry(&self, i: usize) -> bool;
+    /// Exit the position at the open of the next bar.
+    fn exit(&self, i: usize) -> bool;
+}
+
+/// Enters when the fast EMA of closes crosses above the slow one and exits when it
+/// crosses back below.
+pub struct MaCross {
+    pub fast: usize,
+    pub slow: usize,
+    fast_ma: Vec<Option<f64>>,
+    slow_ma: Vec<Option<f64>>,
+}
+
+impl Default for MaCross {
+    fn default() -> Self {
+        Self::new(12, 26)
+    }
+}
+
+impl MaCross {
+    pub fn new(fast: usize, slow: usize) -> Self {
+        Self {
+            fast,
+            slow,
+            fast_ma: vec![],
+            slow_ma: vec![],
+        }
+    }
+
+    /// Difference of the averages at the bar and at the previous bar.
+    fn spreads(&self, i: usize) -> Option<(f64, f64)> {
+        let prev = i.checked_sub(1)?;
+        let spread =
+            |i: usize| Some(self.fast_ma.get(i).copied()?? - self.slow_ma.get(i).copied()??);
+        Some((spread(prev)?, spread(i)?))
+    }
+}
+
+impl Rules for MaCross {
+    fn name(&self) -> String {
+        format!("EMA cross({}, {})", self.fast, self.slow)
+    }
+
+    fn prepare(&mut self, data: &Data) -> Result<(), BacktestError> {
+        let closes: Vec<Option<f64>> = closes(data).into_iter().map(Some).collect();
+        self.fast_ma = ema(&closes, self.fast);
+        self.slow_ma = ema(&closes, self.slow);
+        Ok(())
+    }
+
+    fn entry(&self, i: usize) -> bool {
+        matches!(self.spreads(i), Some((prev, cur)) if prev <= 0.0 && cur > 0.0)
+    }
+
+    fn exit(&self, i: usize) -> bool {
+        matches!(self.spreads(i), Some((prev, cur)) if prev >= 0.0 && cur < 0.0)
+    }
+}
+
+/// Rules computed by a strategy script, so strategies can be changed without recompiling.
+pub struct ScriptRules {
+    script: Script,
+    signals: Signals,
+}
+
+impl ScriptRules {
+    pub fn new(script: Script) -> Self {
+        Self {
+            script,
+            signals: Signals::default(),
+        }
+    }
+}
+
+impl Rules for ScriptRules {
+    fn name(&self) -> String {
+        self.script.name.clone()
+    }
+
+    fn prepare(&mut self, data: &Data) -> Result<(), BacktestError> {
+        self.signals = self.script.signals(&data.vals)?;
+        Ok(())
+    }
+
+    fn entry(&self, i: usize) -> bool {
+        self.signals.entry.get(i).copied().unwrap_or_default()
+    }
+
+    fn exit(&self, i: usize) -> bool {
+        self.signals.exit.get(i).copied().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod rules_tests {
+    use super::*;
+    use crate::netstrat::backtest::{run, Config};
+    use crate::sources::binance::Kline;
+
+    #[test]
+    fn test_ma_cross() {
+        let closes = [5.0, 5.0, 5.0, 4.0, 3.0, 6.0, 9.0, 9.0, 2.0, 1.0];
+        let data = Data::new(
+            closes
+                .iter()
+                .map(|close| Kline {
+                    close: *close,
+                    ..Default::default()
+                })
+                .collect(),
+        );
+
+        let mut rules = MaCross::new(1, 3);
+        rules.prepare(&data).unwrap();
+
+        let entries: Vec<usize> = (0..closes.len()).filter(|i| rules.entry(*i)).collect();
+        let exits: Vec<usize> = (0..closes.len()).filter(|i| rules.exit(*i)).collect();
+        assert_eq!(entries, vec![5]);
+        assert_eq!(exits, vec![3, 8]);
+        assert_eq!(rules.name(), "EMA cross(1, 3)");
+
+        // the same strategy written as a script gives the same signals
+        let script = Script::compile(
+            "ema cross",
+            r#"
+                let fast = ema(close, 1);
+                let slow = ema(close, 3);
+                #{ entry: crosses_above(fast, slow), exit: crosses_below(fast, slow) }
+            "#,
+        )
+        .unwrap();
+        let mut scripted = ScriptRules::new(script);
+        scripted.prepare(&data).unwrap();
+        assert!((0..closes.len()).all(|i| scripted.entry(i) == rules.entry(i)));
+        assert!((0..closes.len()).all(|i| scripted.exit(i) == rules.exit(i)));
+    }
+
+    #[test]
+    fn test_empty_klines() {
+        let config = Config::default();
+        let report = run(&Data::default(), &mut MaCross::default(), &config).unwrap();
+        assert!(report.trades.is_empty());
+
+        let script = Script::compile("script", "#{ entry: [], exit: [] }").unwrap();
+        let report = run(&Data::default(), &mut ScriptRules::new(script), &config).unwrap();
+        assert!(report.trades.is_empty());
+        assert!(report.equity.is_empty());
+    }
+}
@@ -0,0 +1,239 @@
+use crate::sources::binance::Kline;
+
+use super::indicators::atr::atr;
+
+/// Limits bricks count so a tiny brick size can not exhaust memory.
+const MAX_BRICKS: usize = 100_000;
+
+/// Size of a Renko brick.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Brick {
+    /// Fixed price difference.
+    Fixed(f64),
+    /// Last Average True Range value of the given period.
+    Atr(usize),
+}
+
+impl Brick {
+    fn size(&self, klines: &[Kline]) -> Option<f64> {
+        let size = match self {
+            Brick::Fixed(size) => Some(*size),
+            Brick::Atr(period) => atr(klines, *period).last().copied().flatten(),
+        };
+
+        size.filter(|s| *s > 0.0)
+    }
+}
+
+/// How klines are drawn on the price chart.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ChartType {
+    #[default]
+    Candles,
+    HeikinAshi,
+    Renko(Brick),
+    /// Close prices joined by a line.
+    Line,
+    /// Close prices line filled down to the lowest price.
+    Area,
+}
+
+impl ChartType {
+    pub fn all() -> [ChartType; 5] {
+        [
+            ChartType::Candles,
+            ChartType::HeikinAshi,
+            ChartType::Renko(Brick::Atr(14)),
+            ChartType::Line,
+            ChartType::Area,
+        ]
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            ChartType::Candles => "candles",
+            ChartType::HeikinAshi => "Heikin-Ashi",
+            ChartType::Renko(_) => "Renko",
+            ChartType::Line => "line",
+            ChartType::Area => "area",
+        }
+    }
+
+    /// Returns klines to draw for the chart type.
+    /// Source klines are returned if no Renko brick can be built from them.
+    pub fn apply(&self, klines: &[Kline]) -> Vec<Kline> {
+        match self {
+            ChartType::Candles | ChartType::Line | ChartType::Area => klines.to_vec(),
+            ChartType::HeikinAshi => heikin_ashi(klines),
+            ChartType::Renko(brick) => match brick.size(klines).map(|s| renko(klines, s)) {
+                Some(bricks) if !bricks.is_empty() => bricks,
+                _ => klines.to_vec(),
+            },
+        }
+    }
+}
+
+/// Heikin-Ashi candles averaging prices of the kline with the previous Heikin-Ashi candle.
+/// Volume and times are kept from the source klines.
+pub fn heikin_ashi(klines: &[Kline]) -> Vec<Kline> {
+    let mut res: Vec<Kline> = Vec::with_capacity(klines.len());
+    klines.iter().for_each(|k| {
+        let close = (k.open + k.high + k.low + k.close) / 4.0;
+        let open = match res.last() {
+            Some(prev) => (prev.open + prev.close) / 2.0,
+            None => (k.open + k.close) / 2.0,
+        };
+
+        res.push(Kline {
+            open,
+            close,
+            high: k.high.max(open).max(close),
+            low: k.low.min(open).min(close),
+            ..*k
+        });
+    });
+
+    res
+}
+
+/// Renko bricks built from closes. A brick is added every time the close moves by the brick
+/// size beyond the last brick, so reversals need a move of 2 bricks.
+/// Bricks formed by the same kline split its time span evenly and the volume traded
+/// since the previous brick is assigned to the next one.
+pub fn renko(klines: &[Kline], size: f64) -> Vec<Kline> {
+    let size = size as f32;
+    let mut res: Vec<Kline> = vec![];
+    let (mut low, mut high) = match klines.first() {
+        Some(k) => (k.close, k.close),
+        None => return res,
+    };
+    let mut volume = 0.0;
+
+    klines.iter().for_each(|k| {
+        volume += k.volume;
+
+        let mut bricks = vec![];
+        while k.close >= high + size && res.len() + bricks.len() < MAX_BRICKS {
+            bricks.push((high, high + size));
+            (low, high) = (high, high + size);
+        }
+        while k.close <= low - size && res.len() + bricks.len() < MAX_BRICKS {
+            bricks.push((low, low - size));
+            (low, high) = (low - size, low);
+        }
+        if bricks.is_empty() {
+            return;
+        }
+
+        let step = (k.t_close - k.t_open) / bricks.len() as i64;
+        let n = bricks.len();
+        bricks
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (open, close))| {
+                let t_open = k.t_open + step * i as i64;
+                res.push(Kline {
+                    t_open,
+                    t_close: t_open + step,
+                    open,
+                    close,
+                    high: open.max(close),
+                    low: open.min(close),
+                    volume: volume / n as f32,
+                    ..Default::default()
+                });
+            });
+        volume = 0.0;
+    });
+
+    res
+}
+
+#[cfg(test)]
+mod chart_type_tests {
+    use super::*;
+
+    fn kline(open: f32, high: f32, low: f32, close: f32) -> Kline {
+        Kline {
+            open,
+            high,
+            low,
+            close,
+            volume: 10.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_heikin_ashi() {
+        let klines = vec![kline(10.0, 14.0, 8.0, 12.0), kline(12.0, 13.0, 11.0, 12.0)];
+
+        let res = heikin_ashi(&klines);
+        assert_eq!(res.len(), 2);
+        assert_eq!(
+            (res[0].open, res[0].high, res[0].low, res[0].close),
+            (11.0, 14.0, 8.0, 11.0)
+        );
+        assert_eq!(
+            (res[1].open, res[1].high, res[1].low, res[1].close),
+            (11.0, 13.0, 11.0, 12.0)
+        );
+        assert_eq!(res[1].volume, 10.0);
+    }
+
+    fn close(t_open: i64, close: f32) -> Kline {
+        Kline {
+            t_open,
+            t_close: t_open + 100,
+            close,
+            volume: 1.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_renko() {
+        let klines = vec![
+            close(0, 10.0),
+            close(100, 12.5),
+            close(200, 11.5),
+            close(300, 9.5),
+            close(400, 8.0),
+        ];
+
+        let res = renko(&klines, 1.0);
+        let bricks: Vec<(f32, f32)> = res.iter().map(|k| (k.open, k.close)).collect();
+        assert_eq!(
+            bricks,
+            vec![
+                (10.0, 11.0),
+                (11.0, 12.0),
+                (11.0, 10.0),
+                (10.0, 9.0),
+                (9.0, 8.0)
+            ]
+        );
+        assert_eq!((res[0].t_open, res[0].t_close), (100, 150));
+        assert_eq!((res[1].t_open, res[1].t_close), (150, 200));
+        assert_eq!((res[2].t_open, res[2].t_close), (300, 400));
+        assert_eq!(res[0].volume, 1.0);
+        assert_eq!(res[2].volume, 2.0);
+        assert_eq!(res[4].volume, 0.5);
+    }
+
+    #[test]
+    fn test_renko_without_brick_size() {
+        let klines = vec![close(0, 10.0)];
+
+        assert_eq!(ChartType::Renko(Brick::Atr(14)).apply(&klines), klines);
+        assert_eq!(ChartType::Renko(Brick::Fixed(0.0)).apply(&klines), klines);
+        assert_eq!(ChartType::Renko(Brick::Fixed(1.0)).apply(&klines), klines);
+    }
+
+    #[test]
+    fn test_candles_unchanged() {
+        let klines = vec![kline(10.0, 14.0, 8.0, 12.0)];
+
+        assert_eq!(ChartType::Candles.apply(&klines), klines);
+    }
+}
@@ -0,0 +1,103 @@
+use crate::sources::binance::Kline;
+
+/// Converts klines prices into another quote currency using rates klines.
+///
+/// Every price field is multiplied by the close of the rate kline with the same
+/// open time. If there is no such kline, the last known rate is used.
+/// Klines preceding the first known rate are skipped.
+pub fn convert(klines: &[Kline], rates: &[Kline]) -> Vec<Kline> {
+    let mut sorted_rates = rates.to_vec();
+    sorted_rates.sort_by_key(|r| r.t_open);
+
+    let mut rate_idx = 0;
+    let mut rate: Option<f32> = None;
+    klines
+        .iter()
+        .filter_map(|k| {
+            while rate_idx < sorted_rates.len() && sorted_rates[rate_idx].t_open <= k.t_open {
+                rate = Some(sorted_rates[rate_idx].close);
+                rate_idx += 1;
+            }
+
+            let r = rate?;
+            Some(Kline {
+                open: k.open * r,
+                high: k.high * r,
+                low: k.low * r,
+                close: k.close * r,
+                quote_asset_volume: k.quote_asset_volume * r,
+                taker_buy_quote_asset_volume: k.taker_buy_quote_asset_volume * r,
+                ..*k
+            })
+        })
+        .collect()
+}
+
+/// Inverts klines prices. Used when only the reversed conversion pair is listed.
+pub fn invert(klines: &[Kline]) -> Vec<Kline> {
+    klines
+        .iter()
+        .map(|k| Kline {
+            open: 1.0 / k.open,
+            high: 1.0 / k.low,
+            low: 1.0 / k.high,
+            close: 1.0 / k.close,
+            ..*k
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod convert_tests {
+    use super::*;
+
+    fn kline(t_open: i64, price: f32) -> Kline {
+        Kline {
+            t_open,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_convert() {
+        let klines = vec![kline(1, 1.0), kline(2, 2.0), kline(3, 3.0)];
+        let rates = vec![kline(3, 10.0), kline(2, 5.0)];
+
+        assert_eq!(
+            convert(&klines, &rates),
+            vec![kline(2, 10.0), kline(3, 30.0)]
+        );
+    }
+
+    #[test]
+    fn test_convert_missing_rate() {
+        let klines = vec![kline(1, 1.0), kline(2, 2.0), kline(3, 3.0)];
+        let rates = vec![kline(1, 2.0)];
+
+        assert_eq!(
+            convert(&klines, &rates),
+            vec![kline(1, 2.0), kline(2, 4.0), kline(3, 6.0)]
+        );
+    }
+
+    #[test]
+    fn test_invert() {
+        let k = Kline {
+            open: 2.0,
+            high: 4.0,
+            low: 1.0,
+            close: 2.0,
+            ..Default::default()
+        };
+
+        let inv = invert(&[k]);
+        assert_eq!(inv[0].open, 0.5);
+        assert_eq!(inv[0].high, 1.0);
+        assert_eq!(inv[0].low, 0.25);
+        assert_eq!(inv[0].close, 0.5);
+    }
+}
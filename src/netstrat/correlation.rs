@@ -0,0 +1,304 @@
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::Utc;
+use poll_promise::Promise;
+use tracing::{error, info};
+
+use super::toasts::Toasts;
+use crate::sources::binance::{errors::ClientError, Client, Interval, Kline};
+
+/// Klines requested per page.
+const LIMIT: usize = 1000;
+
+/// Correlations between every pair of series, none where they can not be computed.
+pub type Matrix = Vec<Vec<Option<f64>>>;
+
+type Download = (String, Promise<Result<Vec<Kline>, ClientError>>);
+
+/// Close to close returns of klines opened at times present in every series,
+/// so returns of different symbols cover the same periods.
+pub fn aligned_returns(series: &[Vec<Kline>]) -> Vec<Vec<f64>> {
+    let common = series
+        .iter()
+        .map(|klines| klines.iter().map(|k| k.t_open).collect::<BTreeSet<i64>>())
+        .reduce(|acc, times| acc.intersection(&times).copied().collect())
+        .unwrap_or_default();
+
+    series
+        .iter()
+        .map(|klines| {
+            let closes: Vec<f64> = klines
+                .iter()
+                .filter(|k| common.contains(&k.t_open))
+                .map(|k| k.close as f64)
+                .collect();
+            closes
+                .windows(2)
+                .map(|w| match w[0] != 0.0 {
+                    true => w[1] / w[0] - 1.0,
+                    false => 0.0,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Pearson correlation of the series, none if they are too short or one of them is flat.
+pub fn pearson(a: &[f64], b: &[f64]) -> Option<f64> {
+    let n = a.len().min(b.len());
+    if n < 2 {
+        return None;
+    }
+
+    let mean = |v: &[f64]| v[..n].iter().sum::<f64>() / n as f64;
+    let (mean_a, mean_b) = (mean(a), mean(b));
+    let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    for i in 0..n {
+        let (da, db) = (a[i] - mean_a, b[i] - mean_b);
+        cov += da * db;
+        var_a += da * da;
+        var_b += db * db;
+    }
+    if var_a == 0.0 || var_b == 0.0 {
+        return None;
+    }
+
+    Some(cov / (var_a * var_b).sqrt())
+}
+
+/// Correlations of returns between every pair of the series.
+pub fn matrix(series: &[Vec<Kline>]) -> Matrix {
+    let returns = aligned_returns(series);
+    returns
+        .iter()
+        .map(|a| returns.iter().map(|b| pearson(a, b)).collect())
+        .collect()
+}
+
+/// Sensitivity of returns of a symbol to returns of a benchmark.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Beta {
+    pub beta: f64,
+    pub corr: f64,
+    /// Number of returns opened at the same times the stats are computed from.
+    pub returns: usize,
+}
+
+/// Beta and correlation of the klines against the benchmark klines,
+/// none if there are not enough aligned returns or the benchmark is flat.
+pub fn beta(klines: &[Kline], benchmark: &[Kline]) -> Option<Beta> {
+    let returns = aligned_returns(&[klines.to_vec(), benchmark.to_vec()]);
+    let (a, b) = (&returns[0], &returns[1]);
+    let corr = pearson(a, b)?;
+
+    let n = b.len() as f64;
+    let (mean_a, mean_b) = (a.iter().sum::<f64>() / n, b.iter().sum::<f64>() / n);
+    let cov: f64 = a
+        .iter()
+        .zip(b)
+        .map(|(a, b)| (a - mean_a) * (b - mean_b))
+        .sum();
+    let var: f64 = b.iter().map(|b| (b - mean_b).powi(2)).sum();
+
+    Some(Beta {
+        beta: cov / var,
+        corr,
+        returns: b.len(),
+    })
+}
+
+/// Correlation of returns of the klines with returns of the other klines opened at the same
+/// times over the last window returns, for every kline. None until the window is filled
+/// with returns of both series and while the other series has gaps in the window.
+pub fn rolling(klines: &[Kline], other: &[Kline], window: usize) -> Vec<Option<f64>> {
+    let closes: HashMap<i64, f64> = other.iter().map(|k| (k.t_open, k.close as f64)).collect();
+    let ret = |from: f64, to: f64| match from != 0.0 {
+        true => to / from - 1.0,
+        false => 0.0,
+    };
+    let returns: Vec<Option<(f64, f64)>> = klines
+        .iter()
+        .enumerate()
+        .map(|(i, k)| {
+            let prev = klines.get(i.checked_sub(1)?)?;
+            let (other_prev, other) = (closes.get(&prev.t_open)?, closes.get(&k.t_open)?);
+            Some((
+                ret(prev.close as f64, k.close as f64),
+                ret(*other_prev, *other),
+            ))
+        })
+        .collect();
+
+    (0..klines.len())
+        .map(|i| {
+            let start = (i + 1).checked_sub(window)?;
+            let pairs = returns[start..=i]
+                .iter()
+                .copied()
+                .collect::<Option<Vec<_>>>()?;
+            let (a, b): (Vec<f64>, Vec<f64>) = pairs.into_iter().unzip();
+            pearson(&a, &b)
+        })
+        .collect()
+}
+
+/// Upper cased symbols of comma separated input.
+pub fn parse_symbols(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(|s| s.trim().to_uppercase())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Downloads klines of symbols over the last lookback klines
+/// and computes the correlation matrix once all of them arrive.
+#[derive(Default)]
+pub struct Loader {
+    downloads: Vec<Download>,
+}
+
+impl Loader {
+    /// Starts downloads of the symbols replacing running ones.
+    pub fn start(&mut self, symbols: Vec<String>, interval: Interval, lookback: usize) {
+        let end = Utc::now().timestamp_millis();
+        let start = end - lookback as i64 * interval.millis();
+
+        self.downloads = symbols
+            .into_iter()
+            .map(|symbol| {
+                let name = symbol.clone();
+                let promise = Promise::spawn_async(async move {
+                    Client::kline_range(symbol, interval, start, end, LIMIT).await
+                });
+                (name, promise)
+            })
+            .collect();
+
+        info!(
+            "Loading {lookback} klines of {} symbols for correlations.",
+            self.downloads.len()
+        );
+    }
+
+    pub fn loading(&self) -> bool {
+        !self.downloads.is_empty()
+    }
+
+    /// Loaded symbols with their correlation matrix once all downloads finish.
+    /// Symbols which failed to load are reported and left out.
+    pub fn poll(&mut self) -> Option<(Vec<String>, Matrix)> {
+        if !self.loading() || self.downloads.iter().any(|(_, p)| p.ready().is_none()) {
+            return None;
+        }
+
+        let mut symbols = vec![];
+        let mut series = vec![];
+        for (symbol, promise) in self.downloads.drain(..) {
+            match promise.block_and_take() {
+                Ok(klines) => {
+                    symbols.push(symbol);
+                    series.push(klines);
+                }
+                Err(err) => {
+                    error!("Failed to load klines of {symbol} for correlations: {err}.");
+                    Toasts::error(format!("Failed to load {symbol} klines"), err);
+                }
+            }
+        }
+
+        Some((symbols, matrix(&series)))
+    }
+}
+
+#[cfg(test)]
+mod correlation_tests {
+    use super::*;
+
+    fn klines(closes: &[(i64, f32)]) -> Vec<Kline> {
+        closes
+            .iter()
+            .map(|(t_open, close)| Kline {
+                t_open: *t_open,
+                close: *close,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_aligned_returns() {
+        let a = klines(&[(0, 1.0), (1, 2.0), (2, 1.0), (3, 1.5)]);
+        let b = klines(&[(1, 10.0), (3, 20.0)]);
+
+        let res = aligned_returns(&[a, b]);
+        assert_eq!(res, vec![vec![-0.25], vec![1.0]]);
+        assert!(aligned_returns(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_symbols() {
+        assert_eq!(
+            parse_symbols(" btcusdt,,ETHUSDT ,"),
+            vec!["BTCUSDT", "ETHUSDT"]
+        );
+        assert!(parse_symbols("").is_empty());
+    }
+
+    #[test]
+    fn test_pearson() {
+        let a = [1.0, 2.0, 3.0, 4.0];
+
+        assert_eq!(pearson(&a, &[2.0, 4.0, 6.0, 8.0]), Some(1.0));
+        assert_eq!(pearson(&a, &[4.0, 3.0, 2.0, 1.0]), Some(-1.0));
+        assert_eq!(pearson(&a, &[1.0, 1.0, 1.0, 1.0]), None);
+        assert_eq!(pearson(&a[..1], &a[..1]), None);
+    }
+
+    #[test]
+    fn test_rolling() {
+        let a = klines(&[(0, 1.0), (1, 2.0), (2, 1.0), (3, 2.0), (4, 5.0)]);
+        let b = klines(&[(0, 2.0), (1, 4.0), (2, 2.0), (3, 1.0), (4, 0.25)]);
+
+        let res = rolling(&a, &b, 2);
+        assert_eq!(res.len(), 5);
+        assert_eq!(&res[..3], &[None, None, Some(1.0)]);
+        // flat returns of the other series
+        assert_eq!(res[3], None);
+        assert!((res[4].unwrap() + 1.0).abs() < 1e-9);
+
+        let gapped = klines(&[(0, 2.0), (1, 4.0), (3, 1.0), (4, 0.5)]);
+        assert_eq!(rolling(&a, &gapped, 2), vec![None; 5]);
+        assert_eq!(rolling(&a, &b, 10), vec![None; 5]);
+    }
+
+    #[test]
+    fn test_beta() {
+        let benchmark = klines(&[(0, 100.0), (1, 110.0), (2, 99.0), (3, 99.0)]);
+        let doubled = klines(&[(0, 10.0), (1, 12.0), (2, 9.6), (3, 9.6), (4, 1.0)]);
+
+        let res = beta(&doubled, &benchmark).unwrap();
+        assert!((res.beta - 2.0).abs() < 1e-6);
+        assert!((res.corr - 1.0).abs() < 1e-6);
+        assert_eq!(res.returns, 3);
+
+        assert_eq!(beta(&doubled, &benchmark[..1]), None);
+        assert_eq!(
+            beta(&benchmark, &klines(&[(0, 1.0), (1, 1.0), (2, 1.0)])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_matrix() {
+        let a = klines(&[(0, 1.0), (1, 2.0), (2, 1.0), (3, 3.0)]);
+        let b = klines(&[(0, 2.0), (1, 4.0), (2, 2.0), (3, 6.0)]);
+        let c = klines(&[(0, 3.0), (1, 1.5), (2, 3.0), (3, 1.0)]);
+
+        let res = matrix(&[a, b, c]);
+        assert_eq!(res.len(), 3);
+        assert_eq!(res[0][1], Some(1.0));
+        assert!(res[0][2].unwrap() < -0.9);
+        assert_eq!(res[1][0], res[0][1]);
+    }
+}
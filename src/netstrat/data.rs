@@ -6,6 +6,12 @@ use tracing::{debug, info};
 
 use crate::sources::binance::Kline;
 
+use super::{session::Session, theme::ChartColors};
+
+const HOUR_MILLIS: f64 = 60.0 * 60.0 * 1000.0;
+const DAY_MILLIS: i64 = 24 * 60 * 60 * 1000;
+const YEAR_MILLIS: f64 = 365.0 * DAY_MILLIS as f64;
+
 #[derive(Default, Clone)]
 pub struct Data {
     pub vals: Vec<Kline>,
@@ -14,6 +20,9 @@ pub struct Data {
     max_y: f64,
     min_y: f64,
     max_vol: f64,
+    volume_z_scores: Vec<f64>,
+    /// Trading session used by session based indicators.
+    pub session: Session,
 }
 
 impl Data {
@@ -61,6 +70,8 @@ impl Data {
             "Computed data props: max_x: {max_x},  min_x: {min_x}, max_y: {max_y}, min_y: {min_y}, max_vol: {max_vol}."
         );
 
+        let volume_z_scores = Data::z_scores(&vals);
+
         Self {
             vals,
             max_x,
@@ -68,9 +79,39 @@ impl Data {
             max_y,
             min_y,
             max_vol,
+            volume_z_scores,
+            session: Session::default(),
         }
     }
 
+    /// Returns indexes of klines which volume z-score is not less than the threshold.
+    pub fn volume_anomalies(&self, z_score: f64) -> Vec<usize> {
+        self.volume_z_scores
+            .iter()
+            .enumerate()
+            .filter(|(_, z)| **z >= z_score)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    fn z_scores(vals: &[Kline]) -> Vec<f64> {
+        let n = vals.len() as f64;
+        let mean = vals.iter().map(|k| k.volume as f64).sum::<f64>() / n;
+        let variance = vals
+            .iter()
+            .map(|k| (k.volume as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n;
+        let std = variance.sqrt();
+
+        vals.iter()
+            .map(|k| match std > 0.0 {
+                true => (k.volume as f64 - mean) / std,
+                false => 0.0,
+            })
+            .collect()
+    }
+
     pub fn max_x(&self) -> f64 {
         self.max_x
     }
@@ -99,10 +140,133 @@ impl Data {
         datetime.format("%Y-%m-%d %H:%M:%S").to_string()
     }
 
+    /// Formats a time axis label for the visible span and the klines duration in milliseconds:
+    /// times for intraday klines zoomed in, dates when zoomed out or for daily klines and
+    /// months when zoomed out further than a year.
+    pub fn format_axis_ts(ts: f64, span: f64, step: i64) -> String {
+        let fmt = match span {
+            _ if span > YEAR_MILLIS => "%b %Y",
+            _ if step >= DAY_MILLIS || span > 2.0 * DAY_MILLIS as f64 => "%d %b",
+            _ if span > HOUR_MILLIS => "%H:%M",
+            _ => "%H:%M:%S",
+        };
+
+        let secs = (ts / 1000f64) as i64;
+        let naive = NaiveDateTime::from_timestamp(secs, 0);
+        DateTime::<Utc>::from_utc(naive, Utc)
+            .format(fmt)
+            .to_string()
+    }
+
+    /// Duration of a kline in milliseconds.
+    pub fn step(&self) -> i64 {
+        self.vals.first().map_or(0, |k| k.t_close - k.t_open + 1)
+    }
+
+    /// Midnights UTC inside the data time range for intraday klines.
+    pub fn day_starts(&self) -> Vec<i64> {
+        if self.vals.is_empty() || self.step() >= DAY_MILLIS {
+            return vec![];
+        }
+
+        let (from, to) = (self.vals[0].t_open, self.vals[self.vals.len() - 1].t_close);
+        let first = from.div_euclid(DAY_MILLIS) * DAY_MILLIS + DAY_MILLIS;
+        (first..=to).step_by(DAY_MILLIS as usize).collect()
+    }
+
+    /// Klines closed before now, dropping the forming kline at the end.
+    pub fn confirmed(klines: &[Kline], now: i64) -> &[Kline] {
+        let n = klines.iter().rev().take_while(|k| k.t_close >= now).count();
+
+        &klines[..klines.len() - n]
+    }
+
+    /// Color of the kline direction in the applied theme.
     pub fn k_color(k: &Kline) -> Color32 {
-        match k.open > k.close {
-            true => Color32::LIGHT_RED,
-            false => Color32::LIGHT_GREEN,
+        ChartColors::current().kline(k.open > k.close)
+    }
+}
+
+#[cfg(test)]
+mod data_tests {
+    use super::*;
+
+    fn kline(volume: f32) -> Kline {
+        Kline {
+            volume,
+            ..Default::default()
         }
     }
+
+    #[test]
+    fn test_volume_anomalies() {
+        let mut vals = vec![kline(1.0); 9];
+        vals.push(kline(11.0));
+        let data = Data::new(vals);
+
+        // mean 2, std 3: z-score of the spike is 3
+        assert_eq!(data.volume_anomalies(3.0), vec![9]);
+        assert_eq!(data.volume_anomalies(3.1), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_format_axis_ts() {
+        // 2022-07-01 13:45:10 UTC
+        let ts = 1_656_683_110_000.0;
+        let minute = 60 * 1000;
+
+        assert_eq!(Data::format_axis_ts(ts, 600_000.0, minute), "13:45:10");
+        assert_eq!(Data::format_axis_ts(ts, HOUR_MILLIS * 6.0, minute), "13:45");
+        assert_eq!(
+            Data::format_axis_ts(ts, HOUR_MILLIS * 6.0, DAY_MILLIS),
+            "01 Jul"
+        );
+        assert_eq!(
+            Data::format_axis_ts(ts, YEAR_MILLIS / 2.0, minute),
+            "01 Jul"
+        );
+        assert_eq!(
+            Data::format_axis_ts(ts, YEAR_MILLIS * 2.0, minute),
+            "Jul 2022"
+        );
+    }
+
+    #[test]
+    fn test_day_starts() {
+        let hour = 60 * 60 * 1000;
+        let vals = (0..50)
+            .map(|i| Kline {
+                t_open: hour * 2 + i * hour,
+                t_close: hour * 3 + i * hour - 1,
+                ..Default::default()
+            })
+            .collect();
+        let data = Data::new(vals);
+
+        assert_eq!(data.step(), hour);
+        assert_eq!(data.day_starts(), vec![DAY_MILLIS, 2 * DAY_MILLIS]);
+    }
+
+    #[test]
+    fn test_confirmed() {
+        let vals: Vec<Kline> = (0..3)
+            .map(|i| Kline {
+                t_open: i * 10,
+                t_close: i * 10 + 9,
+                ..Default::default()
+            })
+            .collect();
+
+        assert_eq!(Data::confirmed(&vals, 100).len(), 3);
+        assert_eq!(Data::confirmed(&vals, 25).len(), 2);
+        assert_eq!(Data::confirmed(&vals, 0).len(), 0);
+    }
+
+    #[test]
+    fn test_volume_anomalies_constant() {
+        let data = Data::new(vec![kline(1.0); 3]);
+
+        assert_eq!(data.volume_anomalies(0.0), vec![0, 1, 2]);
+        assert_eq!(data.volume_anomalies(1.0), Vec::<usize>::new());
+    }
 }
@@ -0,0 +1,15 @@
+use quick_error::quick_error;
+
+quick_error! {
+    #[derive(Debug)]
+    pub enum DrawingsError {
+        Io(err: std::io::Error) {
+            from()
+            display("{}", err)
+        }
+        Json(err: serde_json::Error) {
+            from()
+            display("{}", err)
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use std::fmt::Display;
+
+use super::Point;
+
+const MINUTE_MILLIS: i64 = 60 * 1000;
+const HOUR_MILLIS: i64 = 60 * MINUTE_MILLIS;
+const DAY_MILLIS: i64 = 24 * HOUR_MILLIS;
+
+/// Distance between 2 points measured with the ruler.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Measurement {
+    pub bars: i64,
+    pub elapsed: i64,
+    pub change: f64,
+    /// Change relative to the start price, none if the start price is zero.
+    pub percent: Option<f64>,
+}
+
+impl Measurement {
+    /// Measures from start to end on a chart with klines of the step duration.
+    pub fn new(start: Point, end: Point, step: i64) -> Self {
+        let elapsed = (end.t - start.t) as i64;
+        let change = end.price - start.price;
+
+        Self {
+            bars: match step > 0 {
+                true => elapsed / step,
+                false => 0,
+            },
+            elapsed,
+            change,
+            percent: (start.price != 0.0).then(|| change / start.price * 100.0),
+        }
+    }
+}
+
+impl Display for Measurement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} bars, {}\n{:+.8}",
+            self.bars,
+            format_duration(self.elapsed),
+            self.change
+        )?;
+        if let Some(percent) = self.percent {
+            write!(f, " ({percent:+.2}%)")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats duration in milliseconds as days, hours and minutes, e.g. "-1d 2h 5m".
+fn format_duration(millis: i64) -> String {
+    let sign = match millis < 0 {
+        true => "-",
+        false => "",
+    };
+    let millis = millis.abs();
+    let (d, h, m) = (
+        millis / DAY_MILLIS,
+        millis % DAY_MILLIS / HOUR_MILLIS,
+        millis % HOUR_MILLIS / MINUTE_MILLIS,
+    );
+
+    match (d, h) {
+        (0, 0) => format!("{sign}{m}m"),
+        (0, _) => format!("{sign}{h}h {m}m"),
+        _ => format!("{sign}{d}d {h}h {m}m"),
+    }
+}
+
+#[cfg(test)]
+mod measure_tests {
+    use super::*;
+
+    #[test]
+    fn test_measurement() {
+        let m = Measurement::new(
+            Point {
+                t: 0.0,
+                price: 200.0,
+            },
+            Point {
+                t: (DAY_MILLIS + 2 * HOUR_MILLIS + 5 * MINUTE_MILLIS) as f64,
+                price: 150.0,
+            },
+            HOUR_MILLIS,
+        );
+
+        assert_eq!(m.bars, 26);
+        assert_eq!(m.change, -50.0);
+        assert_eq!(m.percent, Some(-25.0));
+        assert_eq!(m.to_string(), "26 bars, 1d 2h 5m\n-50.00000000 (-25.00%)");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(5 * MINUTE_MILLIS), "5m");
+        assert_eq!(format_duration(-(HOUR_MILLIS + MINUTE_MILLIS)), "-1h 1m");
+    }
+}
@@ -0,0 +1,182 @@
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::sources::binance::Interval;
+
+use self::errors::DrawingsError;
+
+pub mod errors;
+pub mod measure;
+
+const DRAWINGS_FILE: &str = "drawings.json";
+
+/// Point of a drawing in chart coordinates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Point {
+    /// Time in unix milliseconds.
+    pub t: f64,
+    pub price: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Trendline {
+    pub start: Point,
+    pub end: Point,
+}
+
+/// Drawings made on the chart of a symbol.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SymbolDrawings {
+    #[serde(default)]
+    pub trendlines: Vec<Trendline>,
+    /// Prices of horizontal level lines.
+    #[serde(default)]
+    pub levels: Vec<f64>,
+}
+
+impl SymbolDrawings {
+    pub fn is_empty(&self) -> bool {
+        self.trendlines.is_empty() && self.levels.is_empty()
+    }
+}
+
+/// Drawings of all charts saved in a json file keyed by symbol and interval.
+/// Drawings saved only by symbol are shown on every interval of the symbol until it is edited.
+#[derive(Debug, Clone)]
+pub struct Drawings {
+    path: PathBuf,
+    symbols: BTreeMap<String, SymbolDrawings>,
+}
+
+impl Default for Drawings {
+    fn default() -> Self {
+        Self::new(PathBuf::from(DRAWINGS_FILE))
+    }
+}
+
+impl Drawings {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            symbols: BTreeMap::new(),
+        }
+    }
+
+    pub fn load(&mut self) -> Result<(), DrawingsError> {
+        if !self.path.exists() {
+            return Ok(());
+        }
+
+        self.symbols = serde_json::from_str(&fs::read_to_string(&self.path)?)?;
+        Ok(())
+    }
+
+    fn key(symbol: &str, interval: Interval) -> String {
+        format!("{symbol} {}", interval.as_str())
+    }
+
+    pub fn get(&self, symbol: &str, interval: Interval) -> SymbolDrawings {
+        self.symbols
+            .get(&Self::key(symbol, interval))
+            .or_else(|| self.symbols.get(symbol))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Replaces drawings of the chart and saves drawings of all charts.
+    /// The file is read again first to keep drawings saved by other charts.
+    pub fn save(
+        &mut self,
+        symbol: &str,
+        interval: Interval,
+        drawings: SymbolDrawings,
+    ) -> Result<(), DrawingsError> {
+        self.load()?;
+        let key = Self::key(symbol, interval);
+        self.symbols.remove(symbol);
+        match drawings.is_empty() {
+            true => self.symbols.remove(&key),
+            false => self.symbols.insert(key, drawings),
+        };
+
+        fs::write(&self.path, serde_json::to_string_pretty(&self.symbols)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod drawings_tests {
+    use super::*;
+
+    #[test]
+    fn test_save_per_chart() {
+        let path =
+            std::env::temp_dir().join(format!("netstrat-drawings-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let line = Trendline {
+            start: Point { t: 1.0, price: 2.0 },
+            end: Point { t: 3.0, price: 4.0 },
+        };
+        let mut drawings = Drawings::new(path.clone());
+        drawings.load().unwrap();
+        drawings
+            .save(
+                "BTCUSDT",
+                Interval::Hour,
+                SymbolDrawings {
+                    trendlines: vec![line],
+                    levels: vec![5.0],
+                },
+            )
+            .unwrap();
+        drawings
+            .save("ETHUSDT", Interval::Hour, SymbolDrawings::default())
+            .unwrap();
+
+        let mut loaded = Drawings::new(path.clone());
+        loaded.load().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.get("BTCUSDT", Interval::Hour).trendlines, vec![line]);
+        assert_eq!(loaded.get("BTCUSDT", Interval::Hour).levels, vec![5.0]);
+        assert!(loaded.get("BTCUSDT", Interval::Day).is_empty());
+        assert!(loaded.get("ETHUSDT", Interval::Hour).is_empty());
+        assert_eq!(loaded.symbols.len(), 1);
+    }
+
+    #[test]
+    fn test_legacy_symbol_drawings() {
+        let path = std::env::temp_dir().join(format!(
+            "netstrat-drawings-legacy-{}.json",
+            std::process::id()
+        ));
+        let mut drawings = Drawings::new(path.clone());
+        drawings.symbols.insert(
+            "BTCUSDT".to_string(),
+            SymbolDrawings {
+                trendlines: vec![],
+                levels: vec![5.0],
+            },
+        );
+
+        assert_eq!(drawings.get("BTCUSDT", Interval::Minute).levels, vec![5.0]);
+        assert_eq!(drawings.get("BTCUSDT", Interval::Day).levels, vec![5.0]);
+
+        drawings
+            .save(
+                "BTCUSDT",
+                Interval::Minute,
+                SymbolDrawings {
+                    trendlines: vec![],
+                    levels: vec![6.0],
+                },
+            )
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(drawings.get("BTCUSDT", Interval::Minute).levels, vec![6.0]);
+        assert!(drawings.get("BTCUSDT", Interval::Day).is_empty());
+    }
+}
@@ -0,0 +1,110 @@
+use crate::netstrat::{
+    correlation::{Beta, Matrix},
+    screener::Match,
+};
+
+use super::Table;
+
+/// Formats missing values as empty cells.
+fn cell(v: Option<f64>) -> String {
+    v.map_or(String::new(), |v| v.to_string())
+}
+
+/// Builds the correlation matrix table with a row and a column per symbol.
+pub fn correlation_table(symbols: &[String], matrix: &Matrix) -> Table {
+    let headers: Vec<&str> = std::iter::once("symbol")
+        .chain(symbols.iter().map(|s| s.as_str()))
+        .collect();
+    let mut table = Table::new(&headers);
+    matrix.iter().zip(symbols).for_each(|(row, symbol)| {
+        table.push(
+            std::iter::once(symbol.clone())
+                .chain(row.iter().map(|v| cell(*v)))
+                .collect(),
+        );
+    });
+
+    table
+}
+
+/// Builds the table with beta and correlation of the symbol against the benchmark.
+pub fn beta_table(symbol: &str, benchmark: &str, stats: Beta) -> Table {
+    let mut table = Table::new(&["symbol", "benchmark", "beta", "corr", "returns"]);
+    table.push(vec![
+        symbol.to_string(),
+        benchmark.to_string(),
+        stats.beta.to_string(),
+        stats.corr.to_string(),
+        stats.returns.to_string(),
+    ]);
+
+    table
+}
+
+/// Builds the table of symbols matched by the screener.
+pub fn matches_table(matches: &[Match]) -> Table {
+    let mut table = Table::new(&["symbol", "change", "rsi", "spike"]);
+    matches.iter().for_each(|m| {
+        table.push(vec![
+            m.symbol.symbol.clone(),
+            m.change.to_string(),
+            cell(m.rsi),
+            cell(m.spike),
+        ]);
+    });
+
+    table
+}
+
+#[cfg(test)]
+mod analytics_tests {
+    use super::*;
+    use crate::sources::binance::Symbol;
+
+    #[test]
+    fn test_correlation_table() {
+        let symbols = vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()];
+        let matrix = vec![vec![Some(1.0), Some(0.5)], vec![Some(0.5), None]];
+
+        let table = correlation_table(&symbols, &matrix);
+
+        assert_eq!(table.headers, vec!["symbol", "BTCUSDT", "ETHUSDT"]);
+        assert_eq!(
+            table.rows,
+            vec![vec!["BTCUSDT", "1", "0.5"], vec!["ETHUSDT", "0.5", ""]]
+        );
+    }
+
+    #[test]
+    fn test_beta_table() {
+        let stats = Beta {
+            beta: 1.5,
+            corr: 0.75,
+            returns: 10,
+        };
+
+        let table = beta_table("ETHUSDT", "BTCUSDT", stats);
+
+        assert_eq!(
+            table.rows,
+            vec![vec!["ETHUSDT", "BTCUSDT", "1.5", "0.75", "10"]]
+        );
+    }
+
+    #[test]
+    fn test_matches_table() {
+        let mut symbol = Symbol::default();
+        symbol.symbol = "BTCUSDT".to_string();
+        let matches = vec![Match {
+            symbol,
+            change: 5.0,
+            rsi: Some(25.0),
+            spike: None,
+        }];
+
+        let table = matches_table(&matches);
+
+        assert_eq!(table.headers, vec!["symbol", "change", "rsi", "spike"]);
+        assert_eq!(table.rows, vec![vec!["BTCUSDT", "5", "25", ""]]);
+    }
+}
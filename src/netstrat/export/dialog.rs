@@ -0,0 +1,21 @@
+use std::path::PathBuf;
+
+use poll_promise::Promise;
+use rfd::AsyncFileDialog;
+
+/// Asks for the destination of an export in the native save dialog suggesting {name}.{ext}.
+/// Resolves to the picked path without the extension, which exporters append,
+/// or None if the dialog was cancelled.
+pub fn pick_destination(name: &str, ext: &str) -> Promise<Option<PathBuf>> {
+    let dialog = AsyncFileDialog::new()
+        .set_title("export")
+        .set_file_name(format!("{name}.{ext}"))
+        .add_filter(ext, &[ext]);
+
+    Promise::spawn_async(async move {
+        dialog
+            .save_file()
+            .await
+            .map(|file| file.path().with_extension(""))
+    })
+}
@@ -0,0 +1,37 @@
+use quick_error::quick_error;
+
+use crate::sources::binance::errors::ClientError;
+
+quick_error! {
+    #[derive(Debug)]
+    pub enum ExportError {
+        Io(err: std::io::Error) {
+            from()
+            display("{}", err)
+        }
+        Csv(err: csv::Error) {
+            from()
+            display("{}", err)
+        }
+        Png(err: png::EncodingError) {
+            from()
+            display("{}", err)
+        }
+        Json(err: serde_json::Error) {
+            from()
+            display("{}", err)
+        }
+        Parquet(err: parquet::errors::ParquetError) {
+            from()
+            display("{}", err)
+        }
+        Xlsx(err: rust_xlsxwriter::XlsxError) {
+            from()
+            display("{}", err)
+        }
+        Download(err: ClientError) {
+            from()
+            display("{}", err)
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use std::{
+    fs::File,
+    io::BufWriter,
+    path::PathBuf,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use eframe::glow::{self, HasContext};
+use egui::{Context, Id, LayerId, Order, PaintCallback, PaintCallbackInfo, Rect};
+use poll_promise::Promise;
+use tracing::{error, info};
+
+use super::{dialog::pick_destination, errors::ExportError};
+use crate::netstrat::toasts::Toasts;
+
+const CAPTURE_ID: &str = "image capture";
+/// Frames to wait after the request, so the menu which requested the image is closed on screen.
+const CAPTURE_DELAY_FRAMES: usize = 2;
+
+/// RGBA image with rows going from top to bottom.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+impl Image {
+    /// Builds opaque image from pixels read from OpenGL, where rows go from bottom to top.
+    pub fn from_gl(width: usize, height: usize, pixels: &[u8]) -> Self {
+        let mut res: Vec<u8> = match width {
+            0 => vec![],
+            _ => pixels.chunks(width * 4).rev().flatten().copied().collect(),
+        };
+        res.chunks_mut(4).for_each(|p| p[3] = u8::MAX);
+
+        Self {
+            width,
+            height,
+            pixels: res,
+        }
+    }
+}
+
+/// Writes image to {name}.png file. Returns the file name.
+pub fn image_png(name: &str, image: &Image) -> Result<String, ExportError> {
+    let file_name = format!("{}.png", name);
+    let mut encoder = png::Encoder::new(
+        BufWriter::new(File::create(&file_name)?),
+        image.width as u32,
+        image.height as u32,
+    );
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()?.write_image_data(&image.pixels)?;
+
+    info!("Exported image to file: {file_name}.");
+
+    Ok(file_name)
+}
+
+/// Part of the screen requested to be saved as a png file.
+#[derive(Debug, Clone)]
+pub struct CaptureRequest {
+    pub name: String,
+    pub rect: Rect,
+}
+
+/// Saves parts of the screen requested by widgets to png files picked by the user.
+///
+/// Egui can not render widgets off-screen, so a few frames after the request, when the
+/// requested part is drawn without menus, a paint callback on top of all layers reads it
+/// from the back buffer right after the rest of the frame is painted.
+#[derive(Default)]
+pub struct Capture {
+    pending: Option<(CaptureRequest, usize)>,
+    captured: Option<(String, Arc<Mutex<Option<Image>>>)>,
+    saving: Option<(Image, Promise<Option<PathBuf>>)>,
+}
+
+impl Capture {
+    pub fn request(ctx: &Context, req: CaptureRequest) {
+        ctx.data().insert_temp(Id::new(CAPTURE_ID), req);
+        ctx.request_repaint();
+    }
+
+    pub fn update(&mut self, ctx: &Context) {
+        let requested = ctx.data().get_temp::<CaptureRequest>(Id::new(CAPTURE_ID));
+        if let Some(req) = requested {
+            ctx.data().remove::<CaptureRequest>(Id::new(CAPTURE_ID));
+            self.pending = Some((req, CAPTURE_DELAY_FRAMES));
+        }
+
+        self.poll_saving();
+        self.poll_captured(ctx);
+
+        match self.pending.take() {
+            Some((req, 0)) => self.read_screen(ctx, req),
+            Some((req, frames)) => {
+                self.pending = Some((req, frames - 1));
+                ctx.request_repaint();
+            }
+            None => {}
+        };
+    }
+
+    /// Adds the paint callback reading the requested rect to the frame.
+    fn read_screen(&mut self, ctx: &Context, req: CaptureRequest) {
+        let slot = Arc::new(Mutex::new(None));
+        let res = slot.clone();
+        let callback = PaintCallback {
+            rect: req.rect.intersect(ctx.input().screen_rect()),
+            callback: Arc::new(move |info, painter| {
+                if let Some(painter) = painter.downcast_ref::<egui_glow::Painter>() {
+                    let image = read_pixels(painter.gl(), info);
+                    *res.lock().unwrap_or_else(PoisonError::into_inner) = Some(image);
+                }
+            }),
+        };
+        ctx.layer_painter(LayerId::new(Order::Debug, Id::new(CAPTURE_ID)))
+            .add(callback);
+        ctx.request_repaint();
+
+        self.captured = Some((req.name, slot));
+    }
+
+    fn poll_captured(&mut self, ctx: &Context) {
+        let (name, slot) = match self.captured.take() {
+            Some(captured) => captured,
+            None => return,
+        };
+
+        let image = slot.lock().unwrap_or_else(PoisonError::into_inner).take();
+        match image {
+            Some(image) => {
+                info!("Captured image {name}.");
+                self.saving = Some((image, pick_destination(&name, "png")));
+            }
+            None => {
+                error!("Failed to capture image {name}: nothing was painted.");
+                Toasts::error("Failed to export image", "the chart is out of the screen");
+            }
+        }
+        ctx.request_repaint();
+    }
+
+    fn poll_saving(&mut self) {
+        let ready = match &self.saving {
+            Some((_, destination)) => destination.ready().is_some(),
+            None => false,
+        };
+        if !ready {
+            return;
+        }
+        let (image, destination) = match self.saving.take() {
+            Some(saving) => saving,
+            None => return,
+        };
+
+        let name = match destination.block_and_take() {
+            Some(path) => path.to_string_lossy().to_string(),
+            None => {
+                info!("Image export cancelled.");
+                return;
+            }
+        };
+        match image_png(&name, &image) {
+            Ok(file_name) => Toasts::info(format!("Exported {file_name}")),
+            Err(err) => {
+                error!("Failed to export image {name}: {err}.");
+                Toasts::error("Failed to export image", err);
+            }
+        }
+    }
+}
+
+/// Reads the viewport of the paint callback from the framebuffer being painted.
+fn read_pixels(gl: &glow::Context, info: &PaintCallbackInfo) -> Image {
+    let viewport = info.viewport_in_pixels();
+    let (width, height) = (
+        viewport.width_px.round().max(0.0) as i32,
+        viewport.height_px.round().max(0.0) as i32,
+    );
+
+    let mut pixels = vec![0; (width * height * 4) as usize];
+    unsafe {
+        gl.pixel_store_i32(glow::PACK_ALIGNMENT, 1);
+        gl.read_pixels(
+            viewport.left_px.round() as i32,
+            viewport.from_bottom_px.round() as i32,
+            width,
+            height,
+            glow::RGBA,
+            glow::UNSIGNED_BYTE,
+            glow::PixelPackData::Slice(&mut pixels),
+        );
+    }
+
+    Image::from_gl(width as usize, height as usize, &pixels)
+}
+
+#[cfg(test)]
+mod image_tests {
+    use super::*;
+
+    #[test]
+    fn test_from_gl() {
+        let pixels = [1, 1, 1, 0, 2, 2, 2, 0];
+
+        let image = Image::from_gl(1, 2, &pixels);
+
+        assert_eq!(image.pixels, vec![2, 2, 2, 255, 1, 1, 1, 255]);
+    }
+
+    #[test]
+    fn test_image_png() {
+        let image = Image::from_gl(2, 2, &[10; 16]);
+
+        let name = std::env::temp_dir().join("netstrat_test_image_png");
+        let file_name = image_png(name.to_str().unwrap(), &image).unwrap();
+
+        let content = std::fs::read(&file_name).unwrap();
+        std::fs::remove_file(&file_name).unwrap();
+
+        assert_eq!(content[..8], [137, 80, 78, 71, 13, 10, 26, 10]);
+    }
+}
@@ -0,0 +1,449 @@
+use std::fs::File;
+
+use tracing::info;
+
+use crate::{netstrat::bounds::Bounds, sources::binance::Kline};
+
+use self::{errors::ExportError, template::ExportTemplate, writer::KlinesWriter};
+
+pub mod analytics;
+pub mod dialog;
+pub mod errors;
+pub mod image;
+pub mod network;
+pub mod template;
+pub mod writer;
+
+/// Table is a shared representation of analytics results for exporting.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Table {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Self {
+        Self {
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows: vec![],
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+}
+
+/// Writes table to {name}.csv file. Returns the file name.
+pub fn table_csv(name: &str, table: &Table) -> Result<String, ExportError> {
+    let file_name = format!("{}.csv", name);
+    let mut wtr = csv::Writer::from_writer(File::create(&file_name)?);
+
+    wtr.write_record(&table.headers)?;
+    for row in &table.rows {
+        wtr.write_record(row)?;
+    }
+    wtr.flush()?;
+
+    info!("Exported table to file: {file_name}.");
+
+    Ok(file_name)
+}
+
+/// Field of a kline written as an export column with the function reading it.
+enum KlineField {
+    /// Unix milliseconds.
+    Time(&'static str, fn(&Kline) -> i64),
+    Int(&'static str, fn(&Kline) -> i64),
+    Float(&'static str, fn(&Kline) -> f32),
+}
+
+impl KlineField {
+    fn name(&self) -> &'static str {
+        match self {
+            KlineField::Time(name, _) | KlineField::Int(name, _) | KlineField::Float(name, _) => {
+                name
+            }
+        }
+    }
+
+    /// Value of the field formatted as csv serializes it.
+    fn text(&self, k: &Kline) -> String {
+        match self {
+            KlineField::Time(_, value) | KlineField::Int(_, value) => value(k).to_string(),
+            KlineField::Float(_, value) => format!("{:?}", value(k)),
+        }
+    }
+}
+
+static KLINE_FIELDS: [KlineField; 11] = [
+    KlineField::Time("t_open", |k| k.t_open),
+    KlineField::Float("open", |k| k.open),
+    KlineField::Float("high", |k| k.high),
+    KlineField::Float("low", |k| k.low),
+    KlineField::Float("close", |k| k.close),
+    KlineField::Float("volume", |k| k.volume),
+    KlineField::Time("t_close", |k| k.t_close),
+    KlineField::Float("quote_asset_volume", |k| k.quote_asset_volume),
+    KlineField::Int("number_of_trades", |k| k.number_of_trades),
+    KlineField::Float("taker_buy_base_asset_volume", |k| {
+        k.taker_buy_base_asset_volume
+    }),
+    KlineField::Float("taker_buy_quote_asset_volume", |k| {
+        k.taker_buy_quote_asset_volume
+    }),
+];
+
+/// Column exported after kline fields with a value per exported kline,
+/// e.g. an indicator series.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    pub name: String,
+    pub vals: Vec<Option<f64>>,
+}
+
+impl Column {
+    fn get(&self, i: usize) -> Option<f64> {
+        self.vals.get(i).copied().flatten()
+    }
+}
+
+/// Klines written to a parquet row group at once, bounding memory of large exports.
+const PARQUET_ROW_GROUP: usize = 100_000;
+
+/// Column name usable in a parquet schema, e.g. "RSI_14_" for "RSI(14)".
+fn parquet_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c.is_alphanumeric() {
+            true => c,
+            false => '_',
+        })
+        .collect()
+}
+
+const XLSX_HEADERS: [&str; 11] = [
+    "open time",
+    "open",
+    "high",
+    "low",
+    "close",
+    "volume",
+    "close time",
+    "quote asset volume",
+    "number of trades",
+    "taker buy base asset volume",
+    "taker buy quote asset volume",
+];
+const XLSX_DATE_FORMAT: &str = "yyyy-mm-dd hh:mm:ss";
+/// Days between the excel epoch 1899-12-30 and the unix epoch.
+const EXCEL_UNIX_EPOCH_DAYS: f64 = 25569.0;
+
+/// Excel serial date of the unix milliseconds, days since 1899-12-30 with the time as fraction.
+fn excel_date(ts: i64) -> f64 {
+    EXCEL_UNIX_EPOCH_DAYS + ts as f64 / 86_400_000.0
+}
+
+/// File format klines are exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum KlinesFormat {
+    #[default]
+    Csv,
+    Json,
+    Ndjson,
+    /// Columnar format for large ranges analyzed with dataframe libraries.
+    Parquet,
+    Xlsx,
+}
+
+impl KlinesFormat {
+    pub fn all() -> [KlinesFormat; 5] {
+        [
+            KlinesFormat::Csv,
+            KlinesFormat::Json,
+            KlinesFormat::Ndjson,
+            KlinesFormat::Parquet,
+            KlinesFormat::Xlsx,
+        ]
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            KlinesFormat::Csv => "csv",
+            KlinesFormat::Json => "json",
+            KlinesFormat::Ndjson => "ndjson",
+            KlinesFormat::Parquet => "parquet",
+            KlinesFormat::Xlsx => "xlsx",
+        }
+    }
+
+    /// Writes fields of the template followed by the columns to {name} file with
+    /// the extension of the format. Returns the file name.
+    pub fn export(
+        &self,
+        name: &str,
+        klines: &[Kline],
+        columns: &[Column],
+        template: &ExportTemplate,
+    ) -> Result<String, ExportError> {
+        let names: Vec<String> = columns.iter().map(|c| c.name.clone()).collect();
+        let mut wtr = KlinesWriter::create(name, *self, template, &names)?;
+        wtr.write(klines, columns)?;
+        wtr.finish()
+    }
+}
+
+/// How klines are exported.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ExportOptions {
+    pub format: KlinesFormat,
+    /// Kline fields written and their headers.
+    pub template: ExportTemplate,
+    /// Appends series of enabled indicators as columns.
+    pub indicators: bool,
+    /// Exports loaded klines within the plot bounds instead of downloading the props range.
+    pub visible_only: bool,
+}
+
+/// Returns klines overlapping the time bounds given in milliseconds.
+pub fn visible_klines(klines: &[Kline], bounds: Bounds) -> Vec<Kline> {
+    klines
+        .iter()
+        .filter(|k| k.t_close >= bounds.0 && k.t_open <= bounds.1)
+        .copied()
+        .collect()
+}
+
+/// Tab separated klines with a header line, pasted as columns into spreadsheets.
+pub fn klines_tsv(klines: &[Kline]) -> String {
+    let headers: Vec<&str> = KLINE_FIELDS.iter().map(|f| f.name()).collect();
+    let mut lines = vec![headers.join("\t")];
+    lines.extend(klines.iter().map(|k| {
+        KLINE_FIELDS
+            .iter()
+            .map(|f| f.text(k))
+            .collect::<Vec<String>>()
+            .join("\t")
+    }));
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod export_tests {
+    use super::*;
+
+    #[test]
+    fn test_table_csv() {
+        let mut table = Table::new(&["symbol", "value"]);
+        table.push(vec!["BTCUSDT".to_string(), "1.5".to_string()]);
+
+        let name = std::env::temp_dir().join("netstrat_test_table_csv");
+        let file_name = table_csv(name.to_str().unwrap(), &table).unwrap();
+
+        let content = std::fs::read_to_string(&file_name).unwrap();
+        std::fs::remove_file(&file_name).unwrap();
+
+        assert_eq!(content, "symbol,value\nBTCUSDT,1.5\n");
+    }
+
+    #[test]
+    fn test_klines_csv() {
+        let klines = vec![Kline {
+            t_open: 0,
+            t_close: 59_999,
+            open: 1.5,
+            close: 2.0,
+            number_of_trades: 3,
+            ..Default::default()
+        }];
+        let columns = vec![Column {
+            name: "MA".to_string(),
+            vals: vec![Some(1.75)],
+        }];
+        let name = std::env::temp_dir().join("netstrat_test_klines_csv");
+
+        let file_name = KlinesFormat::Csv
+            .export(
+                name.to_str().unwrap(),
+                &klines,
+                &columns,
+                &ExportTemplate::default(),
+            )
+            .unwrap();
+        let content = std::fs::read_to_string(&file_name).unwrap();
+        std::fs::remove_file(&file_name).unwrap();
+
+        let mut lines = content.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            "t_open,open,high,low,close,volume,t_close,quote_asset_volume,number_of_trades,\
+             taker_buy_base_asset_volume,taker_buy_quote_asset_volume,MA"
+        );
+        assert_eq!(
+            lines.next().unwrap(),
+            "0,1.5,0.0,0.0,2.0,0.0,59999,0.0,3,0.0,0.0,1.75"
+        );
+    }
+
+    #[test]
+    fn test_klines_json() {
+        let klines: Vec<Kline> = (0..2)
+            .map(|i| Kline {
+                t_open: i * 10,
+                t_close: i * 10 + 9,
+                close: 1.5,
+                ..Default::default()
+            })
+            .collect();
+        let name = std::env::temp_dir().join("netstrat_test_klines_json");
+        let name = name.to_str().unwrap();
+
+        let columns = vec![Column {
+            name: "RSI(14)".to_string(),
+            vals: vec![None, Some(55.5)],
+        }];
+
+        let file_name = KlinesFormat::Json
+            .export(name, &klines, &columns, &ExportTemplate::default())
+            .unwrap();
+        let content = std::fs::read_to_string(&file_name).unwrap();
+        std::fs::remove_file(&file_name).unwrap();
+        let parsed: Vec<Kline> = serde_json::from_str(&content).unwrap();
+        let values: Vec<serde_json::Value> = serde_json::from_str(&content).unwrap();
+        assert!(file_name.ends_with(".json"));
+        assert_eq!(parsed, klines);
+        assert_eq!(values[0]["RSI(14)"], serde_json::Value::Null);
+        assert_eq!(values[1]["RSI(14)"], 55.5);
+
+        let file_name = KlinesFormat::Ndjson
+            .export(name, &klines, &[], &ExportTemplate::default())
+            .unwrap();
+        let content = std::fs::read_to_string(&file_name).unwrap();
+        std::fs::remove_file(&file_name).unwrap();
+        let parsed: Vec<Kline> = content
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert!(file_name.ends_with(".ndjson"));
+        assert_eq!(parsed, klines);
+    }
+
+    #[test]
+    fn test_klines_parquet() {
+        use parquet::{
+            file::reader::{FileReader, SerializedFileReader},
+            record::RowAccessor,
+        };
+
+        let klines: Vec<Kline> = (0..3)
+            .map(|i| Kline {
+                t_open: i * 10,
+                t_close: i * 10 + 9,
+                close: i as f32 + 0.5,
+                number_of_trades: i * 100,
+                ..Default::default()
+            })
+            .collect();
+        let name = std::env::temp_dir().join("netstrat_test_klines_parquet");
+
+        let columns = vec![Column {
+            name: "RSI(14)".to_string(),
+            vals: vec![None, Some(55.5), Some(60.0)],
+        }];
+
+        let file_name = KlinesFormat::Parquet
+            .export(
+                name.to_str().unwrap(),
+                &klines,
+                &columns,
+                &ExportTemplate::default(),
+            )
+            .unwrap();
+        let reader = SerializedFileReader::new(File::open(&file_name).unwrap()).unwrap();
+        let rows: Vec<(i64, f32, i64, Option<f64>)> = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|r| {
+                (
+                    r.get_timestamp_millis(0).unwrap() as i64,
+                    r.get_float(4).unwrap(),
+                    r.get_long(8).unwrap(),
+                    r.get_double(11).ok(),
+                )
+            })
+            .collect();
+        std::fs::remove_file(&file_name).unwrap();
+
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 3);
+        assert_eq!(
+            rows,
+            vec![
+                (0, 0.5, 0, None),
+                (10, 1.5, 100, Some(55.5)),
+                (20, 2.5, 200, Some(60.0))
+            ]
+        );
+        assert_eq!(parquet_name("RSI(14)"), "RSI_14_");
+    }
+
+    #[test]
+    fn test_klines_xlsx() {
+        // 2022-01-01 12:00:00 utc
+        assert_eq!(excel_date(1_641_038_400_000), 44562.5);
+        assert_eq!(excel_date(0), EXCEL_UNIX_EPOCH_DAYS);
+
+        let klines = vec![Kline::default(); 2];
+        let name = std::env::temp_dir().join("netstrat_test_klines_xlsx");
+
+        let file_name = KlinesFormat::Xlsx
+            .export(
+                name.to_str().unwrap(),
+                &klines,
+                &[],
+                &ExportTemplate::default(),
+            )
+            .unwrap();
+        let content = std::fs::read(&file_name).unwrap();
+        std::fs::remove_file(&file_name).unwrap();
+
+        assert_eq!(&content[..2], b"PK", "xlsx is a zip archive");
+    }
+
+    #[test]
+    fn test_klines_tsv() {
+        let klines = vec![Kline {
+            t_open: 0,
+            t_close: 59_999,
+            close: 2.5,
+            number_of_trades: 7,
+            ..Default::default()
+        }];
+
+        let res = klines_tsv(&klines);
+        let lines: Vec<&str> = res.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("t_open\topen\thigh"));
+        assert_eq!(
+            lines[1],
+            "0\t0.0\t0.0\t0.0\t2.5\t0.0\t59999\t0.0\t7\t0.0\t0.0"
+        );
+    }
+
+    #[test]
+    fn test_visible_klines() {
+        let klines: Vec<Kline> = (0..5)
+            .map(|i| Kline {
+                t_open: i * 10,
+                t_close: i * 10 + 9,
+                ..Default::default()
+            })
+            .collect();
+
+        let res = visible_klines(&klines, Bounds(15, 25));
+        assert_eq!(
+            res.iter().map(|k| k.t_open).collect::<Vec<_>>(),
+            vec![10, 20]
+        );
+        assert!(visible_klines(&klines, Bounds(50, 60)).is_empty());
+    }
+}
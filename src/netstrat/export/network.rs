@@ -0,0 +1,189 @@
+use std::fmt::Write as _;
+
+use tracing::info;
+
+use super::errors::ExportError;
+use crate::netstrat::market_graph::{Edge, MarketGraph};
+
+/// Layout units are scaled to points so tools keep the drawn layout readable.
+const POSITION_SCALE: f32 = 100.0;
+
+/// Formats the symbols network is exported to for graph tools like Graphviz or Gephi.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NetworkFormat {
+    Dot,
+    Graphml,
+}
+
+impl NetworkFormat {
+    pub fn all() -> [NetworkFormat; 2] {
+        [NetworkFormat::Dot, NetworkFormat::Graphml]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NetworkFormat::Dot => "dot",
+            NetworkFormat::Graphml => "graphml",
+        }
+    }
+
+    /// Writes the graph with its edges to {name}.{ext} file. Returns the file name.
+    pub fn export(
+        &self,
+        name: &str,
+        graph: &MarketGraph,
+        edges: &[Edge],
+    ) -> Result<String, ExportError> {
+        let file_name = format!("{name}.{}", self.as_str());
+        let content = match self {
+            NetworkFormat::Dot => dot(graph, edges),
+            NetworkFormat::Graphml => graphml(graph, edges),
+        };
+        std::fs::write(&file_name, content)?;
+
+        info!("Exported network to file: {file_name}.");
+
+        Ok(file_name)
+    }
+}
+
+/// Undirected Graphviz graph with pinned node positions and correlations as edge weights.
+pub fn dot(graph: &MarketGraph, edges: &[Edge]) -> String {
+    let mut out = String::from("graph market {\n");
+    graph
+        .symbols
+        .iter()
+        .zip(&graph.positions)
+        .for_each(|(symbol, p)| {
+            let _ = writeln!(
+                out,
+                "  \"{}\" [pos=\"{:.1},{:.1}!\"];",
+                dot_escape(symbol),
+                p.x * POSITION_SCALE,
+                0.0 - p.y * POSITION_SCALE,
+            );
+        });
+    edges.iter().for_each(|e| {
+        let _ = writeln!(
+            out,
+            "  \"{}\" -- \"{}\" [weight={:.4}, corr={:.4}, label=\"{:+.2}\"];",
+            dot_escape(&graph.symbols[e.a]),
+            dot_escape(&graph.symbols[e.b]),
+            e.corr.abs(),
+            e.corr,
+            e.corr,
+        );
+    });
+    out.push_str("}\n");
+    out
+}
+
+/// GraphML document with node positions and edge correlations as attributes.
+/// Weights are absolute correlations since graph tools expect them positive.
+pub fn graphml(graph: &MarketGraph, edges: &[Edge]) -> String {
+    let mut out = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+        "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+        "  <key id=\"x\" for=\"node\" attr.name=\"x\" attr.type=\"float\"/>\n",
+        "  <key id=\"y\" for=\"node\" attr.name=\"y\" attr.type=\"float\"/>\n",
+        "  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"double\"/>\n",
+        "  <key id=\"corr\" for=\"edge\" attr.name=\"corr\" attr.type=\"double\"/>\n",
+        "  <graph id=\"market\" edgedefault=\"undirected\">\n",
+    ));
+    graph
+        .symbols
+        .iter()
+        .zip(&graph.positions)
+        .enumerate()
+        .for_each(|(i, (symbol, p))| {
+            let _ = writeln!(
+                out,
+                concat!(
+                    "    <node id=\"n{}\"><data key=\"label\">{}</data>",
+                    "<data key=\"x\">{:.1}</data><data key=\"y\">{:.1}</data></node>",
+                ),
+                i,
+                xml_escape(symbol),
+                p.x * POSITION_SCALE,
+                p.y * POSITION_SCALE,
+            );
+        });
+    edges.iter().enumerate().for_each(|(i, e)| {
+        let _ = writeln!(
+            out,
+            concat!(
+                "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\">",
+                "<data key=\"weight\">{:.4}</data><data key=\"corr\">{:.4}</data></edge>",
+            ),
+            i,
+            e.a,
+            e.b,
+            e.corr.abs(),
+            e.corr,
+        );
+    });
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod network_tests {
+    use egui::Pos2;
+
+    use super::*;
+
+    fn graph() -> (MarketGraph, Vec<Edge>) {
+        let mut graph = MarketGraph::new(
+            vec!["A\"1".to_string(), "B&2".to_string()],
+            vec![vec![Some(1.0), Some(-0.75)], vec![Some(-0.75), Some(1.0)]],
+        );
+        graph.positions = vec![Pos2::new(0.5, 0.25), Pos2::new(-1.0, 0.0)];
+        let edges = graph.edges(0.0);
+        (graph, edges)
+    }
+
+    #[test]
+    fn test_dot() {
+        let (graph, edges) = graph();
+
+        assert_eq!(
+            dot(&graph, &edges),
+            concat!(
+                "graph market {\n",
+                "  \"A\\\"1\" [pos=\"50.0,-25.0!\"];\n",
+                "  \"B&2\" [pos=\"-100.0,0.0!\"];\n",
+                "  \"A\\\"1\" -- \"B&2\" [weight=0.7500, corr=-0.7500, label=\"-0.75\"];\n",
+                "}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_graphml() {
+        let (graph, edges) = graph();
+        let res = graphml(&graph, &edges);
+
+        assert!(res.contains(concat!(
+            "<node id=\"n1\"><data key=\"label\">B&amp;2</data>",
+            "<data key=\"x\">-100.0</data><data key=\"y\">0.0</data></node>"
+        )));
+        assert!(res.contains(concat!(
+            "<edge id=\"e0\" source=\"n0\" target=\"n1\">",
+            "<data key=\"weight\">0.7500</data><data key=\"corr\">-0.7500</data></edge>"
+        )));
+        assert!(res.contains("A&quot;1"));
+        assert!(res.ends_with("</graph>\n</graphml>\n"));
+    }
+}
@@ -0,0 +1,148 @@
+use chrono::{TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::KLINE_FIELDS;
+
+/// How open and close times are written to text formats.
+/// Parquet and xlsx keep their typed timestamps and dates.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum TimeFormat {
+    /// Unix milliseconds.
+    #[default]
+    Epoch,
+    /// Utc time in RFC 3339, e.g. "2022-01-01T12:00:00.000Z".
+    Iso,
+}
+
+impl TimeFormat {
+    pub fn all() -> [TimeFormat; 2] {
+        [TimeFormat::Epoch, TimeFormat::Iso]
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            TimeFormat::Epoch => "epoch ms",
+            TimeFormat::Iso => "iso 8601",
+        }
+    }
+
+    pub fn format(&self, ts: i64) -> String {
+        match self {
+            TimeFormat::Epoch => ts.to_string(),
+            TimeFormat::Iso => Utc
+                .timestamp_millis(ts)
+                .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+                .to_string(),
+        }
+    }
+}
+
+/// Kline field exported as a column of a template.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateColumn {
+    pub field: String,
+    /// Written instead of the default header of the format unless empty.
+    #[serde(default)]
+    pub header: String,
+}
+
+/// Kline fields exported in the order of the columns, saved in settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportTemplate {
+    pub name: String,
+    pub columns: Vec<TemplateColumn>,
+    #[serde(default)]
+    pub time_format: TimeFormat,
+}
+
+impl Default for ExportTemplate {
+    fn default() -> Self {
+        Self::new("all fields")
+    }
+}
+
+impl ExportTemplate {
+    /// Template exporting all kline fields under their default headers.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            columns: KLINE_FIELDS
+                .iter()
+                .map(|f| TemplateColumn {
+                    field: f.name().to_string(),
+                    header: String::new(),
+                })
+                .collect(),
+            time_format: TimeFormat::Epoch,
+        }
+    }
+
+    /// Kline fields which are not exported, so they can be added.
+    pub fn missing(&self) -> Vec<&'static str> {
+        KLINE_FIELDS
+            .iter()
+            .map(|f| f.name())
+            .filter(|name| !self.columns.iter().any(|c| c.field == *name))
+            .collect()
+    }
+
+    /// Swaps the column with the previous one.
+    pub fn move_up(&mut self, i: usize) {
+        if i > 0 && i < self.columns.len() {
+            self.columns.swap(i - 1, i);
+        }
+    }
+
+    /// Indexes of the exported kline fields with their headers.
+    /// Headers are empty if not renamed and columns of unknown fields are skipped.
+    pub(super) fn fields(&self) -> Vec<(usize, &str)> {
+        self.columns
+            .iter()
+            .filter_map(|c| {
+                let i = KLINE_FIELDS.iter().position(|f| f.name() == c.field)?;
+                Some((i, c.header.as_str()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod template_tests {
+    use super::*;
+
+    #[test]
+    fn test_time_format() {
+        // 2022-01-01 12:00:00 utc
+        let ts = 1_641_038_400_000;
+        assert_eq!(TimeFormat::Epoch.format(ts), "1641038400000");
+        assert_eq!(TimeFormat::Iso.format(ts), "2022-01-01T12:00:00.000Z");
+    }
+
+    #[test]
+    fn test_fields() {
+        let mut template = ExportTemplate::default();
+        assert_eq!(template.fields().len(), KLINE_FIELDS.len());
+        assert!(template.missing().is_empty());
+
+        template.columns = vec![
+            TemplateColumn {
+                field: "close".to_string(),
+                header: "Close".to_string(),
+            },
+            TemplateColumn {
+                field: "t_open".to_string(),
+                header: String::new(),
+            },
+            TemplateColumn {
+                field: "removed".to_string(),
+                header: String::new(),
+            },
+        ];
+        assert_eq!(template.fields(), vec![(4, "Close"), (0, "")]);
+        assert_eq!(template.missing().len(), KLINE_FIELDS.len() - 2);
+
+        template.move_up(1);
+        template.move_up(0);
+        assert_eq!(template.fields(), vec![(0, ""), (4, "Close")]);
+    }
+}
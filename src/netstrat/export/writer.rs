@@ -0,0 +1,479 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    sync::Arc,
+};
+
+use parquet::{
+    data_type::{DoubleType, FloatType, Int64Type},
+    file::{properties::WriterProperties, writer::SerializedFileWriter},
+    schema::parser::parse_message_type,
+};
+use rust_xlsxwriter::{Format, Workbook};
+use serde::{ser::SerializeMap, Serialize, Serializer};
+use tracing::info;
+
+use crate::sources::binance::Kline;
+
+use super::{
+    errors::ExportError,
+    excel_date, parquet_name,
+    template::{ExportTemplate, TimeFormat},
+    Column, KlineField, KlinesFormat, KLINE_FIELDS, PARQUET_ROW_GROUP, XLSX_DATE_FORMAT,
+    XLSX_HEADERS,
+};
+
+/// Kline field written as a column under the header.
+struct Field {
+    kline: &'static KlineField,
+    header: String,
+}
+
+/// Open file of the format klines are appended to.
+enum Sink {
+    Csv(csv::Writer<File>),
+    Json {
+        wtr: BufWriter<File>,
+        /// Whether no row was written yet, so the next one goes without a separator.
+        first: bool,
+    },
+    Ndjson(BufWriter<File>),
+    Parquet {
+        wtr: SerializedFileWriter<File>,
+        /// Klines with their column values waiting for a full row group.
+        klines: Vec<Kline>,
+        columns: Vec<Vec<Option<f64>>>,
+    },
+    /// Spreadsheets are zipped on save, so the workbook is kept until the export is finished.
+    Xlsx {
+        workbook: Box<Workbook>,
+        date: Format,
+    },
+}
+
+/// Writes klines to a file in batches, e.g. pages of a download as they arrive.
+/// Fields follow the template and extra columns fixed on creation go after them,
+/// every batch has values for the extra columns.
+pub struct KlinesWriter {
+    file_name: String,
+    fields: Vec<Field>,
+    time_format: TimeFormat,
+    /// Klines written so far.
+    rows: usize,
+    sink: Sink,
+}
+
+impl KlinesWriter {
+    /// Creates {name} file with the extension of the format and writes its header.
+    pub fn create(
+        name: &str,
+        format: KlinesFormat,
+        template: &ExportTemplate,
+        columns: &[String],
+    ) -> Result<Self, ExportError> {
+        let file_name = format!("{}.{}", name, format.as_str());
+        let fields: Vec<Field> = template
+            .fields()
+            .into_iter()
+            .map(|(i, header)| Field {
+                kline: &KLINE_FIELDS[i],
+                header: match (header.is_empty(), format) {
+                    (false, _) => header.to_string(),
+                    (true, KlinesFormat::Xlsx) => XLSX_HEADERS[i].to_string(),
+                    (true, _) => KLINE_FIELDS[i].name().to_string(),
+                },
+            })
+            .collect();
+
+        let sink = match format {
+            KlinesFormat::Csv => {
+                let mut wtr = csv::Writer::from_writer(File::create(&file_name)?);
+                let headers = fields
+                    .iter()
+                    .map(|f| f.header.clone())
+                    .chain(columns.iter().cloned());
+                wtr.write_record(headers)?;
+                Sink::Csv(wtr)
+            }
+            KlinesFormat::Json => {
+                let mut wtr = BufWriter::new(File::create(&file_name)?);
+                wtr.write_all(b"[")?;
+                Sink::Json { wtr, first: true }
+            }
+            KlinesFormat::Ndjson => Sink::Ndjson(BufWriter::new(File::create(&file_name)?)),
+            KlinesFormat::Parquet => {
+                let schema = Arc::new(parse_message_type(&parquet_schema(&fields, columns))?);
+                let props = Arc::new(WriterProperties::builder().build());
+                Sink::Parquet {
+                    wtr: SerializedFileWriter::new(File::create(&file_name)?, schema, props)?,
+                    klines: vec![],
+                    columns: vec![vec![]; columns.len()],
+                }
+            }
+            KlinesFormat::Xlsx => {
+                let header = Format::new().set_bold();
+                let mut workbook = Box::new(Workbook::new());
+                let sheet = workbook.add_worksheet();
+                let headers = fields
+                    .iter()
+                    .map(|f| f.header.as_str())
+                    .chain(columns.iter().map(|c| c.as_str()));
+                for (col, title) in headers.enumerate() {
+                    sheet.write_string_with_format(0, col as u16, title, &header)?;
+                }
+                for (col, field) in fields.iter().enumerate() {
+                    if let KlineField::Time(..) = field.kline {
+                        sheet.set_column_width(col as u16, 20)?;
+                    }
+                }
+                sheet.set_freeze_panes(1, 0)?;
+                Sink::Xlsx {
+                    workbook,
+                    date: Format::new().set_num_format(XLSX_DATE_FORMAT),
+                }
+            }
+        };
+
+        Ok(Self {
+            file_name,
+            fields,
+            time_format: template.time_format,
+            rows: 0,
+            sink,
+        })
+    }
+
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    /// Appends klines with values of the extra columns aligned to them.
+    pub fn write(&mut self, klines: &[Kline], columns: &[Column]) -> Result<(), ExportError> {
+        let fields = &self.fields;
+        let time_format = self.time_format;
+        let json_rows = || {
+            klines.iter().enumerate().map(move |(i, kline)| JsonRow {
+                kline,
+                fields,
+                time_format,
+                columns: columns
+                    .iter()
+                    .map(|c| (c.name.as_str(), c.get(i)))
+                    .collect(),
+            })
+        };
+
+        match &mut self.sink {
+            Sink::Csv(wtr) => {
+                for (i, k) in klines.iter().enumerate() {
+                    let row = fields.iter().map(|f| text(f.kline, k, time_format)).chain(
+                        columns
+                            .iter()
+                            .map(|c| c.get(i).map(|v| v.to_string()).unwrap_or_default()),
+                    );
+                    wtr.write_record(row)?;
+                }
+            }
+            Sink::Json { wtr, first } => {
+                for row in json_rows() {
+                    if !*first {
+                        wtr.write_all(b",")?;
+                    }
+                    serde_json::to_writer(&mut *wtr, &row)?;
+                    *first = false;
+                }
+            }
+            Sink::Ndjson(wtr) => {
+                for row in json_rows() {
+                    serde_json::to_writer(&mut *wtr, &row)?;
+                    wtr.write_all(b"\n")?;
+                }
+            }
+            Sink::Parquet {
+                wtr,
+                klines: buffered,
+                columns: buffered_columns,
+            } => {
+                buffered.extend_from_slice(klines);
+                for (vals, column) in buffered_columns.iter_mut().zip(columns) {
+                    vals.extend((0..klines.len()).map(|i| column.get(i)));
+                }
+                while buffered.len() >= PARQUET_ROW_GROUP {
+                    let rest = buffered.split_off(PARQUET_ROW_GROUP);
+                    let rest_columns: Vec<Vec<Option<f64>>> = buffered_columns
+                        .iter_mut()
+                        .map(|vals| vals.split_off(PARQUET_ROW_GROUP))
+                        .collect();
+                    write_row_group(wtr, fields, buffered, buffered_columns)?;
+                    *buffered = rest;
+                    *buffered_columns = rest_columns;
+                }
+            }
+            Sink::Xlsx { workbook, date } => {
+                let sheet = workbook.worksheet_from_index(0)?;
+                for (i, k) in klines.iter().enumerate() {
+                    let row = (self.rows + i) as u32 + 1;
+                    for (col, field) in fields.iter().enumerate() {
+                        let col = col as u16;
+                        match field.kline {
+                            KlineField::Time(_, value) => {
+                                sheet.write_number_with_format(
+                                    row,
+                                    col,
+                                    excel_date(value(k)),
+                                    date,
+                                )?;
+                            }
+                            KlineField::Int(_, value) => {
+                                sheet.write_number(row, col, value(k) as f64)?;
+                            }
+                            KlineField::Float(_, value) => {
+                                sheet.write_number(row, col, value(k))?;
+                            }
+                        }
+                    }
+                    for (n, column) in columns.iter().enumerate() {
+                        if let Some(v) = column.get(i) {
+                            sheet.write_number(row, (fields.len() + n) as u16, v)?;
+                        }
+                    }
+                }
+            }
+        }
+        self.rows += klines.len();
+
+        Ok(())
+    }
+
+    /// Writes buffered rows and the trailer of the format. Returns the file name.
+    pub fn finish(self) -> Result<String, ExportError> {
+        match self.sink {
+            Sink::Csv(mut wtr) => wtr.flush()?,
+            Sink::Json { mut wtr, .. } => {
+                wtr.write_all(b"]")?;
+                wtr.flush()?;
+            }
+            Sink::Ndjson(mut wtr) => wtr.flush()?,
+            Sink::Parquet {
+                mut wtr,
+                klines,
+                columns,
+            } => {
+                if !klines.is_empty() {
+                    write_row_group(&mut wtr, &self.fields, &klines, &columns)?;
+                }
+                wtr.close()?;
+            }
+            Sink::Xlsx { mut workbook, .. } => workbook.save(&self.file_name)?,
+        }
+
+        info!("Exported {} klines to file: {}.", self.rows, self.file_name);
+
+        Ok(self.file_name)
+    }
+}
+
+/// Value of the field formatted as csv serializes it with times in the format.
+fn text(field: &KlineField, k: &Kline, time_format: TimeFormat) -> String {
+    match field {
+        KlineField::Time(_, value) => time_format.format(value(k)),
+        _ => field.text(k),
+    }
+}
+
+/// Kline serialized as a json object with fields of the template followed by extra columns.
+struct JsonRow<'a> {
+    kline: &'a Kline,
+    fields: &'a [Field],
+    time_format: TimeFormat,
+    columns: Vec<(&'a str, Option<f64>)>,
+}
+
+impl Serialize for JsonRow<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.fields.len() + self.columns.len()))?;
+        for field in self.fields {
+            match field.kline {
+                KlineField::Time(_, value) if self.time_format == TimeFormat::Iso => {
+                    map.serialize_entry(&field.header, &self.time_format.format(value(self.kline)))?
+                }
+                KlineField::Time(_, value) | KlineField::Int(_, value) => {
+                    map.serialize_entry(&field.header, &value(self.kline))?
+                }
+                KlineField::Float(_, value) => {
+                    map.serialize_entry(&field.header, &value(self.kline))?
+                }
+            }
+        }
+        for (name, value) in &self.columns {
+            map.serialize_entry(name, value)?;
+        }
+
+        map.end()
+    }
+}
+
+/// Schema of the fields followed by optional columns of the names.
+fn parquet_schema(fields: &[Field], columns: &[String]) -> String {
+    let fields: String = fields
+        .iter()
+        .map(|f| {
+            let name = parquet_name(&f.header);
+            match f.kline {
+                KlineField::Time(..) => {
+                    format!("REQUIRED INT64 {name} (TIMESTAMP(MILLIS, true)); ")
+                }
+                KlineField::Int(..) => format!("REQUIRED INT64 {name}; "),
+                KlineField::Float(..) => format!("REQUIRED FLOAT {name}; "),
+            }
+        })
+        .chain(
+            columns
+                .iter()
+                .map(|c| format!("OPTIONAL DOUBLE {}; ", parquet_name(c))),
+        )
+        .collect();
+
+    format!("message kline {{ {fields}}}")
+}
+
+fn write_row_group(
+    wtr: &mut SerializedFileWriter<File>,
+    fields: &[Field],
+    klines: &[Kline],
+    columns: &[Vec<Option<f64>>],
+) -> Result<(), ExportError> {
+    let mut group = wtr.next_row_group()?;
+    for field in fields {
+        let mut col = match group.next_column()? {
+            Some(col) => col,
+            None => break,
+        };
+        match field.kline {
+            KlineField::Time(_, value) | KlineField::Int(_, value) => {
+                let vals: Vec<i64> = klines.iter().map(value).collect();
+                col.typed::<Int64Type>().write_batch(&vals, None, None)?;
+            }
+            KlineField::Float(_, value) => {
+                let vals: Vec<f32> = klines.iter().map(value).collect();
+                col.typed::<FloatType>().write_batch(&vals, None, None)?;
+            }
+        }
+        col.close()?;
+    }
+    for vals in columns {
+        let mut col = match group.next_column()? {
+            Some(col) => col,
+            None => break,
+        };
+        let levels: Vec<i16> = vals.iter().map(|v| v.is_some() as i16).collect();
+        let present: Vec<f64> = vals.iter().flatten().copied().collect();
+        col.typed::<DoubleType>()
+            .write_batch(&present, Some(&levels), None)?;
+        col.close()?;
+    }
+    group.close()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod writer_tests {
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    use crate::netstrat::export::template::TemplateColumn;
+
+    use super::*;
+
+    #[test]
+    fn test_write_batches() {
+        let klines: Vec<Kline> = (0..5)
+            .map(|i| Kline {
+                t_open: i * 10,
+                t_close: i * 10 + 9,
+                ..Default::default()
+            })
+            .collect();
+        let name = std::env::temp_dir().join("netstrat_test_write_batches");
+        let name = name.to_str().unwrap();
+        let template = ExportTemplate::default();
+
+        for format in KlinesFormat::all() {
+            let mut wtr = KlinesWriter::create(name, format, &template, &[]).unwrap();
+            klines
+                .chunks(2)
+                .for_each(|page| wtr.write(page, &[]).unwrap());
+
+            let file_name = wtr.finish().unwrap();
+            let content = std::fs::read(&file_name).unwrap();
+            let reader = (format == KlinesFormat::Parquet)
+                .then(|| SerializedFileReader::new(File::open(&file_name).unwrap()).unwrap());
+            std::fs::remove_file(&file_name).unwrap();
+
+            let parsed: Vec<Kline> = match format {
+                KlinesFormat::Csv => csv::Reader::from_reader(content.as_slice())
+                    .deserialize()
+                    .map(|r| r.unwrap())
+                    .collect(),
+                KlinesFormat::Json => serde_json::from_slice(&content).unwrap(),
+                KlinesFormat::Ndjson => content
+                    .split(|b| *b == b'\n')
+                    .filter(|l| !l.is_empty())
+                    .map(|l| serde_json::from_slice(l).unwrap())
+                    .collect(),
+                KlinesFormat::Parquet => {
+                    let metadata = reader.unwrap().metadata().file_metadata().clone();
+                    assert_eq!(metadata.num_rows(), 5);
+                    continue;
+                }
+                KlinesFormat::Xlsx => {
+                    assert_eq!(&content[..2], b"PK");
+                    continue;
+                }
+            };
+            assert_eq!(parsed, klines, "{}", format.as_str());
+        }
+    }
+
+    #[test]
+    fn test_template() {
+        let klines = vec![Kline {
+            t_open: 1_641_038_400_000,
+            close: 2.5,
+            ..Default::default()
+        }];
+        let template = ExportTemplate {
+            name: "short".to_string(),
+            columns: vec![
+                TemplateColumn {
+                    field: "close".to_string(),
+                    header: "Close".to_string(),
+                },
+                TemplateColumn {
+                    field: "t_open".to_string(),
+                    header: String::new(),
+                },
+            ],
+            time_format: TimeFormat::Iso,
+        };
+        let name = std::env::temp_dir().join("netstrat_test_template");
+        let name = name.to_str().unwrap();
+
+        let mut wtr = KlinesWriter::create(name, KlinesFormat::Csv, &template, &[]).unwrap();
+        wtr.write(&klines, &[]).unwrap();
+        let file_name = wtr.finish().unwrap();
+        let content = std::fs::read_to_string(&file_name).unwrap();
+        std::fs::remove_file(&file_name).unwrap();
+        assert_eq!(content, "Close,t_open\n2.5,2022-01-01T12:00:00.000Z\n");
+
+        let mut wtr = KlinesWriter::create(name, KlinesFormat::Ndjson, &template, &[]).unwrap();
+        wtr.write(&klines, &[]).unwrap();
+        let file_name = wtr.finish().unwrap();
+        let content = std::fs::read_to_string(&file_name).unwrap();
+        std::fs::remove_file(&file_name).unwrap();
+        assert_eq!(
+            content,
+            "{\"Close\":2.5,\"t_open\":\"2022-01-01T12:00:00.000Z\"}\n"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use crate::sources::binance::futures::{Income, Position};
+
+/// Aggregated funding payments history.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Funding {
+    /// Cumulative funding by time. Positive values mean funding was received.
+    pub cumulative: Vec<(i64, f64)>,
+    pub paid: f64,
+    pub received: f64,
+}
+
+impl Funding {
+    pub fn new(incomes: &[Income]) -> Self {
+        let mut res = Self {
+            cumulative: cumulative(incomes),
+            ..Default::default()
+        };
+        incomes.iter().for_each(|i| match i.income < 0.0 {
+            true => res.paid -= i.income,
+            false => res.received += i.income,
+        });
+
+        res
+    }
+
+    pub fn total(&self) -> f64 {
+        self.received - self.paid
+    }
+}
+
+/// Profit and loss of the symbol positions shown next to their funding.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PositionPnl {
+    /// Cumulative realized pnl of closed positions by time.
+    pub realized: Vec<(i64, f64)>,
+    /// Cumulative realized pnl with funding payments by time.
+    pub net: Vec<(i64, f64)>,
+    pub position: Option<Position>,
+}
+
+impl PositionPnl {
+    pub fn new(pnl: &[Income], funding: &[Income], position: Option<Position>) -> Self {
+        let all: Vec<Income> = pnl.iter().chain(funding).cloned().collect();
+
+        Self {
+            realized: cumulative(pnl),
+            net: cumulative(&all),
+            position,
+        }
+    }
+
+    pub fn realized_total(&self) -> f64 {
+        self.realized.last().map_or(0.0, |(_, v)| *v)
+    }
+
+    pub fn unrealized(&self) -> f64 {
+        self.position.as_ref().map_or(0.0, |p| p.unrealized_pnl)
+    }
+
+    /// Returns realized pnl with funding and the unrealized pnl of the open position.
+    pub fn total(&self) -> f64 {
+        self.net.last().map_or(0.0, |(_, v)| *v) + self.unrealized()
+    }
+}
+
+/// Returns running sums of incomes ordered by time.
+fn cumulative(incomes: &[Income]) -> Vec<(i64, f64)> {
+    let mut sorted = incomes.to_vec();
+    sorted.sort_by_key(|i| i.time);
+
+    let mut total = 0.0;
+    sorted
+        .iter()
+        .map(|i| {
+            total += i.income;
+            (i.time, total)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod funding_tests {
+    use super::*;
+
+    fn income(time: i64, income: f64) -> Income {
+        Income {
+            time,
+            income,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_funding_new() {
+        let f = Funding::new(&[income(3, -0.5), income(1, 1.0), income(2, 0.25)]);
+
+        assert_eq!(f.cumulative, vec![(1, 1.0), (2, 1.25), (3, 0.75)]);
+        assert_eq!(f.paid, 0.5);
+        assert_eq!(f.received, 1.25);
+        assert_eq!(f.total(), 0.75);
+    }
+
+    #[test]
+    fn test_funding_empty() {
+        assert_eq!(Funding::new(&[]), Funding::default());
+    }
+
+    #[test]
+    fn test_position_pnl_new() {
+        let position = Position {
+            unrealized_pnl: -1.0,
+            ..Default::default()
+        };
+
+        let pnl = PositionPnl::new(
+            &[income(4, 2.0), income(2, -1.0)],
+            &[income(3, 0.5), income(1, 0.25)],
+            Some(position),
+        );
+
+        assert_eq!(pnl.realized, vec![(2, -1.0), (4, 1.0)]);
+        assert_eq!(pnl.net, vec![(1, 0.25), (2, -0.75), (3, -0.25), (4, 1.75)]);
+        assert_eq!(pnl.realized_total(), 1.0);
+        assert_eq!(pnl.total(), 0.75);
+    }
+
+    #[test]
+    fn test_position_pnl_empty() {
+        let pnl = PositionPnl::new(&[], &[], None);
+
+        assert_eq!(pnl.realized_total(), 0.0);
+        assert_eq!(pnl.total(), 0.0);
+    }
+}
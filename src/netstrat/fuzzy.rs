@@ -0,0 +1,80 @@
+use std::cmp::Reverse;
+
+const PREFIX_BONUS: i64 = 10;
+const CONSECUTIVE_BONUS: i64 = 5;
+
+/// Scores how well the candidate matches the query.
+///
+/// Query chars must appear in the candidate in the same order, case is ignored.
+/// Matches at the beginning and consecutive matches are scored higher,
+/// shorter candidates win among equal matches. Returns None if there is no match.
+pub fn score(query: &str, candidate: &str) -> Option<i64> {
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let mut score = 0;
+    let mut matched = 0;
+    let mut prev_match: Option<usize> = None;
+    let candidate = candidate.to_lowercase();
+    for (i, ch) in candidate.chars().enumerate() {
+        if matched == query.len() {
+            break;
+        }
+        if ch != query[matched] {
+            continue;
+        }
+
+        score += 1;
+        if i == 0 {
+            score += PREFIX_BONUS;
+        }
+        if i > 0 && prev_match == Some(i - 1) {
+            score += CONSECUTIVE_BONUS;
+        }
+
+        prev_match = Some(i);
+        matched += 1;
+    }
+
+    if matched < query.len() {
+        return None;
+    }
+
+    Some(score * 100 - candidate.chars().count() as i64)
+}
+
+/// Filters items matching the query and sorts them by score, best first.
+pub fn rank<'a, T>(query: &str, items: &'a [T], key: impl Fn(&T) -> &str) -> Vec<&'a T> {
+    let mut scored: Vec<(i64, &T)> = items
+        .iter()
+        .filter_map(|i| Some((score(query, key(i))?, i)))
+        .collect();
+    scored.sort_by_key(|(score, _)| Reverse(*score));
+
+    scored.into_iter().map(|(_, i)| i).collect()
+}
+
+#[cfg(test)]
+mod fuzzy_tests {
+    use super::*;
+
+    #[test]
+    fn test_score() {
+        assert!(score("btcu", "BTCUSDT").is_some());
+        assert!(score("bu", "BTCUSDT").is_some());
+        assert_eq!(score("ub", "BTCUSDT"), None);
+        assert_eq!(score("", "BTCUSDT"), Some(0));
+    }
+
+    #[test]
+    fn test_rank() {
+        let items = vec!["WBTCBTC", "BTCUSDT", "BNBTCUSD", "ETHUSDT", "BTCUSDTPERP"];
+
+        assert_eq!(
+            rank("btcu", &items, |s| s),
+            vec![&"BTCUSDT", &"BTCUSDTPERP", &"BNBTCUSD"]
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use std::{fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use crate::{netstrat::bounds::Bounds, sources::binance::Interval};
+
+use super::{errors::DefaultsError, props::Props};
+
+const DEFAULTS_FILE: &str = "symbol_classes.json";
+const HOUR_MILLIS: i64 = 60 * 60 * 1000;
+
+/// Default interval and time range for a group of symbols.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SymbolClass {
+    pub name: String,
+    pub symbols: Vec<String>,
+    /// Interval in binance representation, e.g. "1h".
+    pub interval: String,
+    /// Length of the time range ending now.
+    pub hours: i64,
+}
+
+impl SymbolClass {
+    pub fn presets() -> Vec<Self> {
+        vec![
+            Self {
+                name: "majors".to_string(),
+                symbols: ["BTCUSDT", "ETHUSDT", "BNBUSDT", "XRPUSDT", "SOLUSDT"]
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect(),
+                interval: "1m".to_string(),
+                hours: 24,
+            },
+            Self {
+                name: "small caps".to_string(),
+                symbols: vec![],
+                interval: "1h".to_string(),
+                hours: 90 * 24,
+            },
+        ]
+    }
+
+    /// Returns props of the time range ending at now given in milliseconds.
+    pub fn props(&self, now: i64) -> Option<Props> {
+        let interval = Interval::parse(&self.interval)?;
+        let span = Bounds(now - self.hours * HOUR_MILLIS, now);
+
+        Some(Props::default().with_span(span, interval))
+    }
+}
+
+/// Symbol classes set by the user in a json file.
+/// Presets are used until the user saves classes.
+#[derive(Debug, Clone)]
+pub struct SymbolClasses {
+    path: PathBuf,
+    pub classes: Vec<SymbolClass>,
+}
+
+impl Default for SymbolClasses {
+    fn default() -> Self {
+        Self::new(PathBuf::from(DEFAULTS_FILE))
+    }
+}
+
+impl SymbolClasses {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            classes: SymbolClass::presets(),
+        }
+    }
+
+    /// Returns saved classes falling back to presets on errors.
+    pub fn loaded() -> Self {
+        let mut res = Self::default();
+        if let Err(err) = res.load() {
+            error!("Failed to load symbol classes: {err}.");
+        }
+
+        res
+    }
+
+    pub fn load(&mut self) -> Result<(), DefaultsError> {
+        if !self.path.exists() {
+            return Ok(());
+        }
+
+        self.classes = serde_json::from_str(&fs::read_to_string(&self.path)?)?;
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), DefaultsError> {
+        fs::write(&self.path, serde_json::to_string_pretty(&self.classes)?)?;
+        Ok(())
+    }
+
+    /// Returns default props of the first class containing the symbol.
+    pub fn props(&self, symbol: &str, now: i64) -> Option<Props> {
+        self.classes
+            .iter()
+            .find(|c| c.symbols.iter().any(|s| s.eq_ignore_ascii_case(symbol)))?
+            .props(now)
+    }
+}
+
+#[cfg(test)]
+mod defaults_tests {
+    use super::*;
+
+    const NOW: i64 = 1_656_633_600_000;
+
+    #[test]
+    fn test_props() {
+        let classes = SymbolClasses::new(PathBuf::new());
+
+        let props = classes.props("btcusdt", NOW).unwrap();
+        assert_eq!(props.interval, Interval::Minute);
+        assert_eq!(
+            props.bounds.vals(),
+            vec![Bounds(NOW - 24 * HOUR_MILLIS, NOW)]
+        );
+        assert_eq!(props.end_time().timestamp_millis(), NOW);
+
+        assert_eq!(classes.props("UNKNOWN", NOW), None);
+    }
+
+    #[test]
+    fn test_save_load() {
+        let path = std::env::temp_dir().join(format!(
+            "netstrat-symbol-classes-{}.json",
+            std::process::id()
+        ));
+        let mut classes = SymbolClasses::new(path.clone());
+        classes.classes[1].symbols.push("DOGEUSDT".to_string());
+        classes.save().unwrap();
+
+        let mut loaded = SymbolClasses::new(path.clone());
+        loaded.load().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.classes, classes.classes);
+        assert_eq!(
+            loaded.props("DOGEUSDT", NOW).unwrap().interval,
+            Interval::Hour
+        );
+    }
+}
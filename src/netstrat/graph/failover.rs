@@ -0,0 +1,65 @@
+use crate::sources::binance::Source;
+
+/// Consecutive failed downloads after which an alternate source is looked for.
+const ERRORS_BEFORE_FAILOVER: usize = 3;
+
+/// Tracks download failures of the charted symbol and the source it is loaded from.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Failover {
+    pub source: Source,
+    errors: usize,
+    /// Alternates already offered for the symbol, so each is checked only once.
+    checked: Vec<Source>,
+}
+
+impl Failover {
+    /// Counts a failed download. Returns an alternate source to check once failures of the
+    /// primary source repeat.
+    pub fn report_error(&mut self) -> Option<Source> {
+        self.errors += 1;
+        if self.errors < ERRORS_BEFORE_FAILOVER || !self.source.is_primary() {
+            return None;
+        }
+
+        let next = Source::alternates()
+            .into_iter()
+            .find(|s| !self.checked.contains(s))?;
+        self.checked.push(next);
+
+        Some(next)
+    }
+
+    pub fn report_success(&mut self) {
+        self.errors = 0;
+    }
+
+    pub fn switch(&mut self, source: Source) {
+        self.source = source;
+        self.errors = 0;
+    }
+
+    /// Whether klines are loaded from another source than the primary one.
+    pub fn is_substituted(&self) -> bool {
+        !self.source.is_primary()
+    }
+}
+
+#[cfg(test)]
+mod failover_tests {
+    use super::*;
+
+    #[test]
+    fn test_offers_alternate_after_repeated_errors() {
+        let mut failover = Failover::default();
+        assert_eq!(failover.report_error(), None);
+        failover.report_success();
+        assert_eq!(failover.report_error(), None);
+        assert_eq!(failover.report_error(), None);
+        assert_eq!(failover.report_error(), Some(Source::BinanceUs));
+        assert_eq!(failover.report_error(), None, "alternate is offered once");
+
+        failover.switch(Source::BinanceUs);
+        assert!(failover.is_substituted());
+        (0..ERRORS_BEFORE_FAILOVER).for_each(|_| assert_eq!(failover.report_error(), None));
+    }
+}
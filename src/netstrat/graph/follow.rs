@@ -0,0 +1,88 @@
+use crate::netstrat::bounds::Bounds;
+
+/// Part of the visible time span left empty after the newest kline.
+const RIGHT_MARGIN: f64 = 0.05;
+
+/// Pins the right edge of the plot to the newest kline as klines arrive.
+/// Following stops once the newest kline is panned out of view.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Follow {
+    pub enabled: bool,
+    /// Time of the newest charted kline.
+    newest: Option<i64>,
+    /// Time of the newest kline the plot was last moved to.
+    followed: Option<i64>,
+}
+
+impl Follow {
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.followed = None;
+    }
+
+    pub fn set_newest(&mut self, newest: i64) {
+        self.newest = Some(newest);
+    }
+
+    /// Returns bounds of the visible width ending at the newest kline if it was not followed yet.
+    pub fn update(&mut self, visible: Bounds) -> Option<Bounds> {
+        let newest = self.newest.filter(|_| self.enabled)?;
+        if self.followed == Some(newest) || visible.1 <= visible.0 {
+            return None;
+        }
+        self.followed = Some(newest);
+
+        let width = visible.1 - visible.0;
+        let right = newest + (width as f64 * RIGHT_MARGIN) as i64;
+
+        Some(Bounds(right - width, right))
+    }
+
+    /// Stops following if the followed kline is out of the visible bounds.
+    /// Returns true if following was stopped.
+    pub fn check(&mut self, visible: Bounds) -> bool {
+        match self.followed {
+            Some(followed) if self.enabled && visible.1 < followed => {
+                self.enabled = false;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod follow_tests {
+    use super::*;
+
+    #[test]
+    fn test_follows_newest_kline() {
+        let mut follow = Follow::default();
+        follow.set_newest(1000);
+        assert_eq!(follow.update(Bounds(0, 500)), None, "following is off");
+
+        follow.set_enabled(true);
+        assert_eq!(follow.update(Bounds(0, 0)), None, "plot is not shown yet");
+        assert_eq!(follow.update(Bounds(0, 500)), Some(Bounds(525, 1025)));
+        assert_eq!(follow.update(Bounds(525, 1025)), None, "already followed");
+
+        follow.set_newest(1100);
+        assert!(!follow.check(Bounds(525, 1025)));
+        assert_eq!(follow.update(Bounds(525, 1025)), Some(Bounds(625, 1125)));
+    }
+
+    #[test]
+    fn test_stops_when_panned_back() {
+        let mut follow = Follow::default();
+        follow.set_enabled(true);
+        follow.set_newest(1000);
+        follow.update(Bounds(0, 500));
+
+        assert!(!follow.check(Bounds(520, 1020)));
+        assert!(follow.check(Bounds(400, 900)));
+        assert!(!follow.enabled);
+
+        follow.set_newest(1100);
+        assert_eq!(follow.update(Bounds(400, 900)), None);
+    }
+}
@@ -15,6 +15,14 @@ use super::{
 pub struct LoadingState {
     pub pages: Pages,
     pub has_error: bool,
+    /// Klines received so far.
+    pub candles: usize,
+    /// Unix milliseconds the first page was requested at.
+    started: Option<i64>,
+    /// Unix milliseconds the pending page was requested at.
+    requested: Option<i64>,
+    /// Milliseconds the last page took to arrive.
+    latency: Option<i64>,
 }
 
 impl LoadingState {
@@ -42,4 +50,88 @@ impl LoadingState {
 
         self.pages.turned_pages as f32 / self.pages.len() as f32
     }
+
+    pub fn report_request(&mut self, now: i64) {
+        self.started.get_or_insert(now);
+        self.requested = Some(now);
+    }
+
+    pub fn report_page(&mut self, candles: usize, now: i64) {
+        self.candles += candles;
+        self.latency = self.requested.take().map(|t| now - t);
+    }
+
+    /// Remaining milliseconds extrapolated from the time spent on turned pages.
+    pub fn eta(&self, now: i64) -> Option<i64> {
+        let turned = self.pages.turned_pages as i64;
+        let started = self.started.filter(|_| turned > 0)?;
+        let remaining = (self.pages.len() as i64 - turned).max(0);
+
+        Some((now - started) * remaining / turned)
+    }
+
+    /// Klines received per second since the download started.
+    pub fn speed(&self, now: i64) -> Option<f64> {
+        let elapsed = now - self.started?;
+        match elapsed > 0 {
+            true => Some(self.candles as f64 * 1000.0 / elapsed as f64),
+            false => None,
+        }
+    }
+
+    /// Pages, klines, remaining time, speed and latency of the download, e.g.
+    /// "page 3/10 · 3000 candles · 12s left · 1500 candles/s · 250 ms".
+    pub fn summary(&self, now: i64) -> String {
+        let mut res = vec![
+            format!(
+                "page {}/{}",
+                (self.pages.turned_pages + 1).min(self.pages.len()),
+                self.pages.len()
+            ),
+            format!("{} candles", self.candles),
+        ];
+        if let Some(eta) = self.eta(now) {
+            res.push(format!("{}s left", (eta + 999) / 1000));
+        }
+        if let Some(speed) = self.speed(now) {
+            res.push(format!("{speed:.0} candles/s"));
+        }
+        if let Some(latency) = self.latency {
+            res.push(format!("{latency} ms"));
+        }
+
+        res.join(" · ")
+    }
+}
+
+#[cfg(test)]
+mod loading_state_tests {
+    use super::*;
+    use crate::netstrat::bounds::Bounds;
+
+    #[test]
+    fn test_summary() {
+        let bounds = BoundsSet::new(vec![Bounds(0, 1000)]);
+        let mut state = LoadingState::new(&bounds, 10, 25).unwrap();
+        assert_eq!(state.pages.len(), 4);
+        assert_eq!(state.summary(0), "page 1/4 · 0 candles");
+
+        state.report_request(0);
+        state.report_page(25, 2_000);
+        state.turn_page();
+        state.report_request(2_000);
+        assert_eq!(state.eta(2_000), Some(6_000));
+        assert_eq!(
+            state.summary(2_000),
+            "page 2/4 · 25 candles · 6s left · 12 candles/s · 2000 ms"
+        );
+
+        state.report_page(25, 2_500);
+        state.turn_page();
+        assert_eq!(state.eta(2_500), Some(2_500));
+        assert_eq!(
+            state.summary(2_500),
+            "page 3/4 · 50 candles · 3s left · 20 candles/s · 500 ms"
+        );
+    }
 }
@@ -1,4 +1,8 @@
 pub mod state;
+pub mod defaults;
+pub mod errors;
+pub mod failover;
+pub mod follow;
 pub mod loading_state;
 pub mod props;
 pub mod pages;
\ No newline at end of file
@@ -1,4 +1,4 @@
-use chrono::{Date, DateTime, Duration, NaiveTime, Timelike, Utc};
+use chrono::{Date, DateTime, Duration, NaiveDateTime, NaiveTime, Timelike, Utc};
 
 use crate::{
     netstrat::bounds::{Bounds, BoundsSet},
@@ -36,6 +36,22 @@ impl Props {
     pub fn is_valid(&self) -> bool {
         self.start_time() < self.end_time()
     }
+
+    /// Returns props covering the same time span given in milliseconds but with another interval.
+    pub fn with_span(&self, span: Bounds, interval: Interval) -> Self {
+        let start = NaiveDateTime::from_timestamp(span.0.div_euclid(1000), 0);
+        let end = NaiveDateTime::from_timestamp(span.1.div_euclid(1000), 0);
+
+        Self {
+            date_start: Date::from_utc(start.date(), Utc),
+            date_end: Date::from_utc(end.date(), Utc),
+            time_start: start.time(),
+            time_end: end.time(),
+            bounds: BoundsSet::new(vec![span]),
+            interval,
+            limit: self.limit,
+        }
+    }
 }
 
 impl Default for Props {
@@ -59,3 +75,20 @@ impl Default for Props {
         p
     }
 }
+
+#[cfg(test)]
+mod props_tests {
+    use super::*;
+
+    #[test]
+    fn test_with_span() {
+        let props = Props::default();
+        let span = Bounds(1_600_000_000_000, 1_600_086_400_000);
+
+        let res = props.with_span(span, Interval::Hour);
+        assert_eq!(res.interval, Interval::Hour);
+        assert_eq!(res.start_time().timestamp_millis(), span.0);
+        assert_eq!(res.end_time().timestamp_millis(), span.1);
+        assert_eq!(res.bounds, BoundsSet::new(vec![span]));
+    }
+}
@@ -47,10 +47,6 @@ impl State {
     }
 
     fn step(i: Interval) -> usize {
-        match i {
-            Interval::Minute => 60 * 1000,
-            Interval::Hour => 60 * 60 * 1000,
-            Interval::Day => 60 * 60 * 24 * 1000,
-        }
+        i.millis() as usize
     }
 }
@@ -0,0 +1,23 @@
+use quick_error::quick_error;
+
+use super::Field;
+
+quick_error! {
+    #[derive(Debug)]
+    pub enum ImportError {
+        Io(err: std::io::Error) {
+            from()
+            display("{}", err)
+        }
+        Csv(err: csv::Error) {
+            from()
+            display("{}", err)
+        }
+        Unmapped(field: Field) {
+            display("column for {} is not picked", field.as_str())
+        }
+        Parse(row: usize, field: Field, val: String) {
+            display("row {}: failed to parse {} from {:?}", row, field.as_str(), val)
+        }
+    }
+}
@@ -0,0 +1,322 @@
+use std::{collections::BTreeMap, fs::File, path::Path};
+
+use chrono::{FixedOffset, NaiveDateTime, TimeZone};
+
+use crate::sources::binance::{Interval, Kline};
+
+use self::errors::ImportError;
+
+pub mod errors;
+
+const HOUR_SECS: i32 = 60 * 60;
+
+/// Kline field which is read from a csv column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Field {
+    Time,
+    Open,
+    High,
+    Low,
+    Close,
+    Volume,
+}
+
+impl Field {
+    pub fn all() -> [Field; 6] {
+        [
+            Field::Time,
+            Field::Open,
+            Field::High,
+            Field::Low,
+            Field::Close,
+            Field::Volume,
+        ]
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Field::Time => "time",
+            Field::Open => "open",
+            Field::High => "high",
+            Field::Low => "low",
+            Field::Close => "close",
+            Field::Volume => "volume",
+        }
+    }
+
+    fn required(&self) -> bool {
+        *self != Field::Volume
+    }
+
+    /// Lowercase header names commonly used by other tools for the field.
+    fn aliases(&self) -> &[&str] {
+        match self {
+            Field::Time => &["time", "timestamp", "date", "datetime", "open time", "t"],
+            Field::Open => &["open", "o"],
+            Field::High => &["high", "h"],
+            Field::Low => &["low", "l"],
+            Field::Close => &["close", "c", "price", "last"],
+            Field::Volume => &["volume", "vol", "v"],
+        }
+    }
+}
+
+/// Representation of timestamps in the time column.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimeUnit {
+    Seconds,
+    Millis,
+    Micros,
+    /// Date and time text in chrono format, e.g. "%Y-%m-%d %H:%M:%S".
+    Text(String),
+}
+
+impl TimeUnit {
+    pub fn all() -> [TimeUnit; 4] {
+        [
+            TimeUnit::Seconds,
+            TimeUnit::Millis,
+            TimeUnit::Micros,
+            TimeUnit::Text("%Y-%m-%d %H:%M:%S".to_string()),
+        ]
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            TimeUnit::Seconds => "seconds",
+            TimeUnit::Millis => "milliseconds",
+            TimeUnit::Micros => "microseconds",
+            TimeUnit::Text(_) => "text",
+        }
+    }
+}
+
+/// First rows of a csv file shown while picking the mapping.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Preview {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Preview {
+    /// Reads headers and up to `limit` rows. Columns are numbered when the file has no headers.
+    pub fn read(path: &Path, has_headers: bool, limit: usize) -> Result<Self, ImportError> {
+        let mut rdr = reader(path, has_headers)?;
+        let mut rows = vec![];
+        for rec in rdr.records().take(limit) {
+            rows.push(rec?.iter().map(|v| v.to_string()).collect::<Vec<_>>());
+        }
+
+        let headers = match has_headers {
+            true => rdr.headers()?.iter().map(|h| h.to_string()).collect(),
+            false => (0..rows.first().map(|r| r.len()).unwrap_or_default())
+                .map(|i| format!("column {}", i + 1))
+                .collect(),
+        };
+
+        Ok(Self { headers, rows })
+    }
+}
+
+/// Mapping of csv columns to kline fields with the way timestamps are written.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mapping {
+    pub has_headers: bool,
+    pub columns: BTreeMap<Field, usize>,
+    pub unit: TimeUnit,
+    /// Offset from UTC of text timestamps. Numeric timestamps are always UTC.
+    pub utc_offset_hours: i32,
+}
+
+impl Default for Mapping {
+    fn default() -> Self {
+        Self {
+            has_headers: true,
+            columns: BTreeMap::new(),
+            unit: TimeUnit::Millis,
+            utc_offset_hours: 0,
+        }
+    }
+}
+
+impl Mapping {
+    /// Maps columns by well known header names.
+    pub fn guess(&mut self, headers: &[String]) {
+        self.columns = Field::all()
+            .into_iter()
+            .filter_map(|f| {
+                let i = headers
+                    .iter()
+                    .position(|h| f.aliases().contains(&h.trim().to_lowercase().as_str()))?;
+                Some((f, i))
+            })
+            .collect();
+    }
+
+    /// Reads klines of the interval from the file sorted by open time.
+    pub fn import(&self, path: &Path, interval: Interval) -> Result<Vec<Kline>, ImportError> {
+        if let Some(f) = Field::all()
+            .into_iter()
+            .find(|f| f.required() && !self.columns.contains_key(f))
+        {
+            return Err(ImportError::Unmapped(f));
+        }
+
+        let mut res = vec![];
+        for (i, rec) in reader(path, self.has_headers)?.records().enumerate() {
+            res.push(self.kline(i + 1, &rec?, interval)?);
+        }
+        res.sort_by_key(|k| k.t_open);
+
+        Ok(res)
+    }
+
+    fn kline(
+        &self,
+        row: usize,
+        rec: &csv::StringRecord,
+        interval: Interval,
+    ) -> Result<Kline, ImportError> {
+        let val = |f: Field| {
+            self.columns
+                .get(&f)
+                .map(|i| rec.get(*i).unwrap_or_default().trim())
+        };
+        let err = |f: Field| ImportError::Parse(row, f, val(f).unwrap_or_default().to_string());
+        let num = |f: Field| -> Result<f32, ImportError> {
+            match val(f) {
+                Some(v) => v.parse().map_err(|_| err(f)),
+                None => Ok(0.0),
+            }
+        };
+
+        let t_open = val(Field::Time)
+            .and_then(|v| self.parse_time(v))
+            .ok_or_else(|| err(Field::Time))?;
+
+        Ok(Kline {
+            t_open,
+            t_close: t_open + interval.millis() - 1,
+            open: num(Field::Open)?,
+            high: num(Field::High)?,
+            low: num(Field::Low)?,
+            close: num(Field::Close)?,
+            volume: num(Field::Volume)?,
+            ..Default::default()
+        })
+    }
+
+    /// Converts the timestamp to unix milliseconds.
+    fn parse_time(&self, v: &str) -> Option<i64> {
+        match &self.unit {
+            TimeUnit::Seconds => Some((v.parse::<f64>().ok()? * 1000.0) as i64),
+            TimeUnit::Millis => Some(v.parse::<f64>().ok()? as i64),
+            TimeUnit::Micros => Some(v.parse::<i64>().ok()? / 1000),
+            TimeUnit::Text(format) => {
+                let offset = FixedOffset::east_opt(self.utc_offset_hours * HOUR_SECS)?;
+                let naive = NaiveDateTime::parse_from_str(v, format).ok()?;
+                Some(
+                    offset
+                        .from_local_datetime(&naive)
+                        .single()?
+                        .timestamp_millis(),
+                )
+            }
+        }
+    }
+}
+
+fn reader(path: &Path, has_headers: bool) -> Result<csv::Reader<File>, ImportError> {
+    Ok(csv::ReaderBuilder::new()
+        .has_headers(has_headers)
+        .flexible(true)
+        .from_reader(File::open(path)?))
+}
+
+#[cfg(test)]
+mod import_tests {
+    use std::fs;
+
+    use super::*;
+
+    fn file(name: &str, content: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "netstrat-import-{}-{}.csv",
+            name,
+            std::process::id()
+        ));
+        fs::write(&path, content).unwrap();
+
+        path
+    }
+
+    #[test]
+    fn test_guess_and_import() {
+        let path = file(
+            "guess",
+            "Date,Vol,O,H,L,C\n2022-01-02 00:00:00,5,2,4,1,3\n2022-01-01 00:00:00,1,1,2,0.5,1.5\n",
+        );
+        let preview = Preview::read(&path, true, 10).unwrap();
+        assert_eq!(preview.rows.len(), 2);
+
+        let mut mapping = Mapping {
+            unit: TimeUnit::Text("%Y-%m-%d %H:%M:%S".to_string()),
+            utc_offset_hours: 2,
+            ..Default::default()
+        };
+        mapping.guess(&preview.headers);
+        assert_eq!(mapping.columns.get(&Field::Time), Some(&0));
+        assert_eq!(mapping.columns.get(&Field::Volume), Some(&1));
+
+        let klines = mapping.import(&path, Interval::Day).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        // 2022-01-01 00:00 at UTC+2 is 2021-12-31 22:00 UTC.
+        assert_eq!(klines[0].t_open, 1640988000000);
+        assert_eq!(
+            klines[0].t_close,
+            1640988000000 + Interval::Day.millis() - 1
+        );
+        assert_eq!(klines[1].close, 3.0);
+        assert_eq!(klines[1].volume, 5.0);
+    }
+
+    #[test]
+    fn test_import_numeric_without_headers() {
+        let path = file("numeric", "1640995200,1,2,0.5,1.5\n");
+        let mapping = Mapping {
+            has_headers: false,
+            columns: BTreeMap::from([
+                (Field::Time, 0),
+                (Field::Open, 1),
+                (Field::High, 2),
+                (Field::Low, 3),
+                (Field::Close, 4),
+            ]),
+            unit: TimeUnit::Seconds,
+            ..Default::default()
+        };
+
+        let klines = mapping.import(&path, Interval::Minute).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(klines[0].t_open, 1640995200000);
+        assert_eq!(klines[0].volume, 0.0);
+    }
+
+    #[test]
+    fn test_import_errors() {
+        let path = file("errors", "time,open,high,low,close\n1,x,2,0.5,1.5\n");
+        let mut mapping = Mapping::default();
+        assert!(matches!(
+            mapping.import(&path, Interval::Minute),
+            Err(ImportError::Unmapped(Field::Time))
+        ));
+
+        mapping.guess(&Preview::read(&path, true, 1).unwrap().headers);
+        let res = mapping.import(&path, Interval::Minute);
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(res, Err(ImportError::Parse(1, Field::Open, _))));
+    }
+}
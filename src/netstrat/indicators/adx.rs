@@ -0,0 +1,109 @@
+use crate::sources::binance::Kline;
+
+use super::atr::true_range;
+
+/// Average Directional Index with directional indicators aligned with the input.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Dmi {
+    pub plus_di: Vec<Option<f64>>,
+    pub minus_di: Vec<Option<f64>>,
+    pub adx: Vec<Option<f64>>,
+}
+
+/// Computes +DI, -DI and ADX using Wilder's smoothing. Directional indicators start
+/// after `period` moves and ADX needs another `period` values of DX to warm up.
+pub fn adx(klines: &[Kline], period: usize) -> Dmi {
+    let n = klines.len();
+    let mut res = Dmi {
+        plus_di: vec![None; n],
+        minus_di: vec![None; n],
+        adx: vec![None; n],
+    };
+    if period == 0 || n <= period {
+        return res;
+    }
+
+    let tr = true_range(klines);
+    let (mut plus_dm, mut minus_dm) = (vec![0.0; n], vec![0.0; n]);
+    for i in 1..n {
+        let up = (klines[i].high - klines[i - 1].high) as f64;
+        let down = (klines[i - 1].low - klines[i].low) as f64;
+        if up > down && up > 0.0 {
+            plus_dm[i] = up;
+        }
+        if down > up && down > 0.0 {
+            minus_dm[i] = down;
+        }
+    }
+
+    let p = period as f64;
+    let (mut s_tr, mut s_plus, mut s_minus) = (
+        tr[1..=period].iter().sum::<f64>(),
+        plus_dm[1..=period].iter().sum::<f64>(),
+        minus_dm[1..=period].iter().sum::<f64>(),
+    );
+    let mut dx = vec![0.0; n];
+    for i in period..n {
+        if i > period {
+            s_tr = s_tr - s_tr / p + tr[i];
+            s_plus = s_plus - s_plus / p + plus_dm[i];
+            s_minus = s_minus - s_minus / p + minus_dm[i];
+        }
+
+        let (plus_di, minus_di) = match s_tr == 0.0 {
+            true => (0.0, 0.0),
+            false => (100.0 * s_plus / s_tr, 100.0 * s_minus / s_tr),
+        };
+        res.plus_di[i] = Some(plus_di);
+        res.minus_di[i] = Some(minus_di);
+        dx[i] = match plus_di + minus_di == 0.0 {
+            true => 0.0,
+            false => 100.0 * (plus_di - minus_di).abs() / (plus_di + minus_di),
+        };
+    }
+
+    let first = 2 * period - 1;
+    if n <= first {
+        return res;
+    }
+
+    let mut prev = dx[period..=first].iter().sum::<f64>() / p;
+    res.adx[first] = Some(prev);
+    dx.iter().enumerate().skip(first + 1).for_each(|(i, v)| {
+        prev = (prev * (p - 1.0) + v) / p;
+        res.adx[i] = Some(prev);
+    });
+
+    res
+}
+
+#[cfg(test)]
+mod adx_tests {
+    use super::*;
+    use crate::netstrat::indicators::kline;
+
+    #[test]
+    fn test_adx_uptrend() {
+        let klines: Vec<Kline> = (0..6)
+            .map(|i| kline(2.0 + i as f32, 1.0 + i as f32, 1.5 + i as f32))
+            .collect();
+
+        let res = adx(&klines, 2);
+        assert_eq!(res.plus_di[..2], [None, None]);
+        assert!(res.plus_di[2..]
+            .iter()
+            .all(|v| v.unwrap() > 0.0 && v.unwrap() <= 100.0));
+        assert!(res.minus_di[2..].iter().all(|v| *v == Some(0.0)));
+        assert_eq!(res.adx[..3], [None, None, None]);
+        assert!(res.adx[3..].iter().all(|v| *v == Some(100.0)));
+    }
+
+    #[test]
+    fn test_adx_short() {
+        let klines = vec![kline(2.0, 1.0, 1.5), kline(3.0, 2.0, 2.5)];
+
+        let res = adx(&klines, 2);
+        assert_eq!(res.plus_di, vec![None, None]);
+        assert_eq!(res.adx, vec![None, None]);
+    }
+}
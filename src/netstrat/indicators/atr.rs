@@ -0,0 +1,69 @@
+use crate::sources::binance::Kline;
+
+/// Computes true range for every kline. The first kline has no previous close,
+/// so its true range is just high - low.
+pub fn true_range(klines: &[Kline]) -> Vec<f64> {
+    klines
+        .iter()
+        .enumerate()
+        .map(|(i, k)| {
+            let range = (k.high - k.low) as f64;
+            if i == 0 {
+                return range;
+            }
+
+            let prev_close = klines[i - 1].close as f64;
+            range
+                .max((k.high as f64 - prev_close).abs())
+                .max((k.low as f64 - prev_close).abs())
+        })
+        .collect()
+}
+
+/// Computes Average True Range using Wilder's smoothing. Result is aligned with the input.
+pub fn atr(klines: &[Kline], period: usize) -> Vec<Option<f64>> {
+    let mut res = vec![None; klines.len()];
+    if period == 0 || klines.len() < period {
+        return res;
+    }
+
+    let tr = true_range(klines);
+    let mut prev = tr[..period].iter().sum::<f64>() / period as f64;
+    res[period - 1] = Some(prev);
+    for i in period..tr.len() {
+        prev = (prev * (period - 1) as f64 + tr[i]) / period as f64;
+        res[i] = Some(prev);
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod atr_tests {
+    use super::*;
+    use crate::netstrat::indicators::kline;
+
+    #[test]
+    fn test_true_range() {
+        let klines = vec![
+            kline(2.0, 1.0, 1.5),
+            kline(4.0, 3.0, 3.5),
+            kline(3.0, 1.0, 2.0),
+        ];
+
+        // gap up uses previous close, wide candle uses its own range
+        assert_eq!(true_range(&klines), vec![1.0, 2.5, 2.5]);
+    }
+
+    #[test]
+    fn test_atr() {
+        let klines = vec![
+            kline(2.0, 1.0, 1.5),
+            kline(2.5, 1.5, 2.0),
+            kline(5.0, 2.0, 4.0),
+        ];
+
+        assert_eq!(atr(&klines, 2), vec![None, Some(1.0), Some(2.0)]);
+        assert_eq!(atr(&klines, 4), vec![None, None, None]);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::sources::binance::Kline;
+
+/// Ichimoku Kinko Hyo series.
+///
+/// Tenkan, kijun and chikou are aligned with the input. Senkou spans are displaced
+/// forward, so they are longer than the input by the displacement and their tail
+/// values belong to candles which are not formed yet.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Ichimoku {
+    pub tenkan: Vec<Option<f64>>,
+    pub kijun: Vec<Option<f64>>,
+    pub senkou_a: Vec<Option<f64>>,
+    pub senkou_b: Vec<Option<f64>>,
+    pub chikou: Vec<Option<f64>>,
+}
+
+pub fn ichimoku(
+    klines: &[Kline],
+    tenkan_period: usize,
+    kijun_period: usize,
+    senkou_b_period: usize,
+    displacement: usize,
+) -> Ichimoku {
+    let n = klines.len();
+    let tenkan = midpoint(klines, tenkan_period);
+    let kijun = midpoint(klines, kijun_period);
+
+    let mut senkou_a = vec![None; n + displacement];
+    let mut senkou_b = vec![None; n + displacement];
+    midpoint(klines, senkou_b_period)
+        .into_iter()
+        .enumerate()
+        .for_each(|(i, b)| {
+            senkou_a[i + displacement] = tenkan[i].zip(kijun[i]).map(|(t, k)| (t + k) / 2.0);
+            senkou_b[i + displacement] = b;
+        });
+
+    let chikou = (0..n)
+        .map(|i| Some(klines.get(i + displacement)?.close as f64))
+        .collect();
+
+    Ichimoku {
+        tenkan,
+        kijun,
+        senkou_a,
+        senkou_b,
+        chikou,
+    }
+}
+
+/// Computes middle of the highest high and the lowest low over the period.
+fn midpoint(klines: &[Kline], period: usize) -> Vec<Option<f64>> {
+    (0..klines.len())
+        .map(|i| {
+            if period == 0 || i + 1 < period {
+                return None;
+            }
+
+            let window = &klines[i + 1 - period..=i];
+            let high = window.iter().map(|k| k.high).fold(f32::MIN, f32::max);
+            let low = window.iter().map(|k| k.low).fold(f32::MAX, f32::min);
+            Some((high + low) as f64 / 2.0)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod ichimoku_tests {
+    use super::*;
+    use crate::netstrat::indicators::kline;
+
+    #[test]
+    fn test_midpoint() {
+        let klines = vec![
+            kline(2.0, 1.0, 1.5),
+            kline(4.0, 2.0, 3.0),
+            kline(3.0, 0.0, 1.5),
+        ];
+        assert_eq!(midpoint(&klines, 2), vec![None, Some(2.5), Some(2.0)]);
+    }
+
+    #[test]
+    fn test_ichimoku_displacement() {
+        let klines = vec![
+            kline(2.0, 0.0, 1.0),
+            kline(4.0, 2.0, 3.0),
+            kline(6.0, 4.0, 5.0),
+        ];
+        let res = ichimoku(&klines, 1, 2, 2, 2);
+
+        assert_eq!(res.tenkan, vec![Some(1.0), Some(3.0), Some(5.0)]);
+        assert_eq!(res.kijun, vec![None, Some(2.0), Some(4.0)]);
+        assert_eq!(res.senkou_a, vec![None, None, None, Some(2.5), Some(4.5)]);
+        assert_eq!(res.senkou_b, vec![None, None, None, Some(2.0), Some(4.0)]);
+        assert_eq!(res.chikou, vec![Some(5.0), None, None]);
+    }
+}
@@ -0,0 +1,53 @@
+/// Computes exponential moving average seeded with simple average of the first period values.
+///
+/// Leading None values are skipped, so it can be applied to other indicators output.
+/// Result is aligned with the input.
+pub fn ema(values: &[Option<f64>], period: usize) -> Vec<Option<f64>> {
+    let mut res = vec![None; values.len()];
+    if period == 0 {
+        return res;
+    }
+
+    let start = match values.iter().position(|v| v.is_some()) {
+        Some(start) => start,
+        None => return res,
+    };
+    if values.len() < start + period {
+        return res;
+    }
+
+    let k = 2.0 / (period as f64 + 1.0);
+    let mut prev = values[start..start + period]
+        .iter()
+        .map(|v| v.unwrap_or_default())
+        .sum::<f64>()
+        / period as f64;
+    res[start + period - 1] = Some(prev);
+
+    for i in start + period..values.len() {
+        if let Some(v) = values[i] {
+            prev = v * k + prev * (1.0 - k);
+        }
+        res[i] = Some(prev);
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod ma_tests {
+    use super::*;
+
+    #[test]
+    fn test_ema() {
+        // k = 0.5 for period 3
+        let res = ema(&[Some(1.0), Some(2.0), Some(3.0), Some(5.0)], 3);
+        assert_eq!(res, vec![None, None, Some(2.0), Some(3.5)]);
+    }
+
+    #[test]
+    fn test_ema_leading_none() {
+        let res = ema(&[None, Some(2.0), Some(4.0), Some(6.0)], 2);
+        assert_eq!(res, vec![None, None, Some(3.0), Some(5.0)]);
+    }
+}
@@ -0,0 +1,62 @@
+use super::ma::ema;
+
+/// Moving Average Convergence Divergence series aligned with the input.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Macd {
+    pub macd: Vec<Option<f64>>,
+    pub signal: Vec<Option<f64>>,
+    pub histogram: Vec<Option<f64>>,
+}
+
+pub fn macd(closes: &[f64], fast: usize, slow: usize, signal: usize) -> Macd {
+    let vals: Vec<Option<f64>> = closes.iter().map(|c| Some(*c)).collect();
+    let fast_ema = ema(&vals, fast);
+    let slow_ema = ema(&vals, slow);
+
+    let macd: Vec<Option<f64>> = fast_ema
+        .iter()
+        .zip(slow_ema.iter())
+        .map(|(f, s)| Some((*f)? - (*s)?))
+        .collect();
+    let signal = ema(&macd, signal);
+    let histogram = macd
+        .iter()
+        .zip(signal.iter())
+        .map(|(m, s)| Some((*m)? - (*s)?))
+        .collect();
+
+    Macd {
+        macd,
+        signal,
+        histogram,
+    }
+}
+
+#[cfg(test)]
+mod macd_tests {
+    use super::*;
+
+    #[test]
+    fn test_macd_constant() {
+        let res = macd(&[10.0; 6], 2, 3, 2);
+
+        assert_eq!(
+            res.macd,
+            vec![None, None, Some(0.0), Some(0.0), Some(0.0), Some(0.0)]
+        );
+        assert_eq!(
+            res.signal,
+            vec![None, None, None, Some(0.0), Some(0.0), Some(0.0)]
+        );
+        assert_eq!(res.histogram, res.signal);
+    }
+
+    #[test]
+    fn test_macd_rising() {
+        let res = macd(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 2, 3, 2);
+
+        // linear series: both emas lag by constant values after warmup
+        assert_eq!(res.macd[5], Some(0.5));
+        assert!(res.histogram[5].unwrap().abs() < 1e-9);
+    }
+}
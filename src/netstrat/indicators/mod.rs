@@ -0,0 +1,32 @@
+pub mod adx;
+pub mod atr;
+pub mod ichimoku;
+pub mod ma;
+pub mod macd;
+pub mod obv;
+pub mod pivots;
+pub mod psar;
+pub mod rsi;
+pub mod supertrend;
+pub mod vwap;
+
+use super::data::Data;
+#[cfg(test)]
+use crate::sources::binance::Kline;
+
+/// Extracts close prices from data as f64 values.
+pub fn closes(data: &Data) -> Vec<f64> {
+    data.vals.iter().map(|k| k.close as f64).collect()
+}
+
+/// Builds a kline with the prices for indicator tests. Other fields are set with
+/// the struct update syntax.
+#[cfg(test)]
+pub fn kline(high: f32, low: f32, close: f32) -> Kline {
+    Kline {
+        high,
+        low,
+        close,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,41 @@
+use crate::sources::binance::Kline;
+
+/// Computes On-Balance Volume: volume is added when close rises against the previous close
+/// and subtracted when it falls. The first kline starts the sum at zero.
+pub fn obv(klines: &[Kline]) -> Vec<f64> {
+    let mut res = Vec::with_capacity(klines.len());
+    let mut prev = 0.0;
+    klines.iter().enumerate().for_each(|(i, k)| {
+        if i > 0 {
+            let prev_close = klines[i - 1].close;
+            if k.close > prev_close {
+                prev += k.volume as f64;
+            } else if k.close < prev_close {
+                prev -= k.volume as f64;
+            }
+        }
+        res.push(prev);
+    });
+
+    res
+}
+
+#[cfg(test)]
+mod obv_tests {
+    use super::*;
+    use crate::netstrat::indicators::kline;
+
+    #[test]
+    fn test_obv() {
+        let klines: Vec<Kline> = [(1.0, 10.0), (2.0, 5.0), (2.0, 7.0), (1.5, 3.0)]
+            .into_iter()
+            .map(|(close, volume)| Kline {
+                volume,
+                ..kline(close, close, close)
+            })
+            .collect();
+
+        assert_eq!(obv(&klines), vec![0.0, 5.0, 5.0, 2.0]);
+        assert!(obv(&[]).is_empty());
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{netstrat::session::Session, sources::binance::Kline};
+
+const DAY_MILLIS: i64 = 24 * 60 * 60 * 1000;
+// 1970-01-01 was a Thursday, shift makes weeks start on Monday.
+const WEEK_SHIFT_DAYS: i64 = 3;
+
+/// Higher timeframe the pivot levels are computed from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PivotPeriod {
+    /// Trading session, which is a calendar day for continuous markets.
+    Day,
+    Week,
+}
+
+impl PivotPeriod {
+    fn bucket(&self, ts: i64, session: &Session) -> Option<i64> {
+        match self {
+            PivotPeriod::Day => session.start(ts),
+            PivotPeriod::Week => Some((ts.div_euclid(DAY_MILLIS) + WEEK_SHIFT_DAYS).div_euclid(7)),
+        }
+    }
+}
+
+/// Classic pivot levels valid between start and end of a period.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Pivots {
+    pub start: i64,
+    pub end: i64,
+    pub p: f64,
+    pub r1: f64,
+    pub r2: f64,
+    pub r3: f64,
+    pub s1: f64,
+    pub s2: f64,
+    pub s3: f64,
+}
+
+impl Pivots {
+    fn new(start: i64, end: i64, high: f64, low: f64, close: f64) -> Self {
+        let p = (high + low + close) / 3.0;
+        Self {
+            start,
+            end,
+            p,
+            r1: 2.0 * p - low,
+            r2: p + (high - low),
+            r3: high + 2.0 * (p - low),
+            s1: 2.0 * p - high,
+            s2: p - (high - low),
+            s3: low - 2.0 * (high - p),
+        }
+    }
+
+    /// Returns levels with their names from the highest to the lowest.
+    pub fn levels(&self) -> [(&str, f64); 7] {
+        [
+            ("R3", self.r3),
+            ("R2", self.r2),
+            ("R1", self.r1),
+            ("P", self.p),
+            ("S1", self.s1),
+            ("S2", self.s2),
+            ("S3", self.s3),
+        ]
+    }
+}
+
+/// Computes pivot levels for every period of klines using high, low and close of the
+/// previous period. The first loaded period has no previous one and is skipped.
+/// Klines outside of trading sessions are ignored.
+pub fn pivots(klines: &[Kline], period: PivotPeriod, session: &Session) -> Vec<Pivots> {
+    let mut groups: Vec<(i64, Vec<&Kline>)> = vec![];
+    klines.iter().for_each(|k| {
+        let bucket = match period.bucket(k.t_open, session) {
+            Some(bucket) => bucket,
+            None => return,
+        };
+        match groups.last_mut() {
+            Some((b, group)) if *b == bucket => group.push(k),
+            _ => groups.push((bucket, vec![k])),
+        }
+    });
+
+    groups
+        .windows(2)
+        .map(|w| {
+            let prev = &w[0].1;
+            let high = prev.iter().map(|k| k.high).fold(f32::MIN, f32::max);
+            let low = prev.iter().map(|k| k.low).fold(f32::MAX, f32::min);
+            let close = prev.last().unwrap().close;
+
+            let cur = &w[1].1;
+            Pivots::new(
+                cur.first().unwrap().t_open,
+                cur.last().unwrap().t_close,
+                high as f64,
+                low as f64,
+                close as f64,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod pivots_tests {
+    use super::*;
+    use crate::netstrat::indicators::kline;
+
+    #[test]
+    fn test_daily_pivots() {
+        let half = DAY_MILLIS / 2;
+        let klines: Vec<Kline> = [
+            (0, kline(12.0, 9.0, 10.0)),
+            (half, kline(11.0, 6.0, 8.0)),
+            (DAY_MILLIS, kline(9.0, 7.0, 8.0)),
+            (DAY_MILLIS + half, kline(10.0, 8.0, 9.0)),
+        ]
+        .into_iter()
+        .map(|(t_open, k)| Kline {
+            t_open,
+            t_close: t_open + half - 1,
+            ..k
+        })
+        .collect();
+
+        let res = pivots(&klines, PivotPeriod::Day, &Session::crypto());
+        assert_eq!(res.len(), 1);
+
+        let p = res[0];
+        assert_eq!((p.start, p.end), (DAY_MILLIS, 2 * DAY_MILLIS - 1));
+        assert_eq!(p.p, 26.0 / 3.0);
+        assert_eq!(p.r1, 2.0 * p.p - 6.0);
+        assert_eq!(p.s1, 2.0 * p.p - 12.0);
+        assert_eq!(p.r2, p.p + 6.0);
+        assert_eq!(p.s2, p.p - 6.0);
+    }
+
+    #[test]
+    fn test_weekly_bucket() {
+        // 1970-01-05 is Monday
+        let monday = 4 * DAY_MILLIS;
+        let bucket = |ts| PivotPeriod::Week.bucket(ts, &Session::crypto()).unwrap();
+        assert_eq!(bucket(monday - 1) + 1, bucket(monday));
+        assert_eq!(bucket(monday), bucket(monday + 6 * DAY_MILLIS));
+    }
+}
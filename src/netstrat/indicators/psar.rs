@@ -0,0 +1,91 @@
+use crate::sources::binance::Kline;
+
+/// Computes Parabolic SAR. Acceleration factor starts at step, grows by step on every
+/// new extreme point and is capped by max. The first kline has no value.
+pub fn psar(klines: &[Kline], step: f64, max: f64) -> Vec<Option<f64>> {
+    let n = klines.len();
+    let mut res = vec![None; n];
+    if n < 2 {
+        return res;
+    }
+
+    let high = |i: usize| klines[i].high as f64;
+    let low = |i: usize| klines[i].low as f64;
+
+    let mut up = klines[1].close >= klines[0].close;
+    let (mut sar, mut ep) = match up {
+        true => (low(0), high(0)),
+        false => (high(0), low(0)),
+    };
+    let mut af = step;
+
+    for (i, v) in res.iter_mut().enumerate().skip(1) {
+        sar += af * (ep - sar);
+        let prev = i.saturating_sub(2);
+
+        if up {
+            sar = sar.min(low(i - 1)).min(low(prev));
+            if low(i) < sar {
+                up = false;
+                sar = ep;
+                ep = low(i);
+                af = step;
+            } else if high(i) > ep {
+                ep = high(i);
+                af = (af + step).min(max);
+            }
+        } else {
+            sar = sar.max(high(i - 1)).max(high(prev));
+            if high(i) > sar {
+                up = true;
+                sar = ep;
+                ep = high(i);
+                af = step;
+            } else if low(i) < ep {
+                ep = low(i);
+                af = (af + step).min(max);
+            }
+        }
+
+        *v = Some(sar);
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod psar_tests {
+    use super::*;
+    use crate::netstrat::indicators::kline;
+
+    #[test]
+    fn test_psar_uptrend() {
+        let klines = vec![
+            kline(2.0, 1.0, 1.5),
+            kline(3.0, 2.0, 2.5),
+            kline(4.0, 3.0, 3.5),
+            kline(5.0, 4.0, 4.5),
+        ];
+
+        let res = psar(&klines, 0.1, 0.2);
+        assert_eq!(res[0], None);
+        assert_eq!(res[1], Some(1.0));
+        // sar may not rise above the two previous lows
+        assert_eq!(res[2], Some(1.0));
+        // af is capped at 0.2: 1.0 + 0.2 * (4.0 - 1.0)
+        assert!((res[3].unwrap() - 1.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_psar_reversal() {
+        let klines = vec![
+            kline(2.0, 1.0, 1.5),
+            kline(3.0, 2.0, 2.5),
+            kline(1.0, 0.5, 0.75),
+        ];
+
+        let res = psar(&klines, 0.1, 0.2);
+        // price breaks below sar, so it flips to the previous extreme point
+        assert_eq!(res[2], Some(3.0));
+    }
+}
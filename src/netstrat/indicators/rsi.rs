@@ -0,0 +1,75 @@
+/// Computes Relative Strength Index using Wilder's smoothing.
+///
+/// Result is aligned with the input: values for the first `period` closes are None
+/// because there is not enough history to compute them.
+pub fn rsi(closes: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut res = vec![None; closes.len()];
+    if period == 0 || closes.len() <= period {
+        return res;
+    }
+
+    let mut avg_gain = 0.0;
+    let mut avg_loss = 0.0;
+    for i in 1..=period {
+        let diff = closes[i] - closes[i - 1];
+        if diff > 0.0 {
+            avg_gain += diff;
+        } else {
+            avg_loss -= diff;
+        }
+    }
+    avg_gain /= period as f64;
+    avg_loss /= period as f64;
+    res[period] = Some(value(avg_gain, avg_loss));
+
+    for i in period + 1..closes.len() {
+        let diff = closes[i] - closes[i - 1];
+        let (gain, loss) = match diff > 0.0 {
+            true => (diff, 0.0),
+            false => (0.0, -diff),
+        };
+
+        avg_gain = (avg_gain * (period - 1) as f64 + gain) / period as f64;
+        avg_loss = (avg_loss * (period - 1) as f64 + loss) / period as f64;
+        res[i] = Some(value(avg_gain, avg_loss));
+    }
+
+    res
+}
+
+fn value(avg_gain: f64, avg_loss: f64) -> f64 {
+    if avg_loss == 0.0 {
+        return 100.0;
+    }
+
+    100.0 - 100.0 / (1.0 + avg_gain / avg_loss)
+}
+
+#[cfg(test)]
+mod rsi_tests {
+    use super::*;
+
+    #[test]
+    fn test_rsi_not_enough_data() {
+        assert_eq!(rsi(&[1.0, 2.0], 2), vec![None, None]);
+        assert_eq!(rsi(&[1.0, 2.0, 3.0], 0), vec![None, None, None]);
+    }
+
+    #[test]
+    fn test_rsi_monotonic() {
+        let up = rsi(&[1.0, 2.0, 3.0, 4.0, 5.0], 2);
+        assert_eq!(up, vec![None, None, Some(100.0), Some(100.0), Some(100.0)]);
+
+        let down = rsi(&[5.0, 4.0, 3.0, 2.0, 1.0], 2);
+        assert_eq!(down, vec![None, None, Some(0.0), Some(0.0), Some(0.0)]);
+    }
+
+    #[test]
+    fn test_rsi_smoothing() {
+        // gains: 2, 0; losses: 0, 1 -> avg gain 1, avg loss 0.5
+        // next diff +1 -> avg gain 1, avg loss 0.25
+        let res = rsi(&[10.0, 12.0, 11.0, 12.0], 2);
+        assert_eq!(res[2], Some(100.0 - 100.0 / 3.0));
+        assert_eq!(res[3], Some(80.0));
+    }
+}
@@ -0,0 +1,76 @@
+use crate::sources::binance::Kline;
+
+use super::atr::atr;
+
+/// Computes SuperTrend line using ATR bands around the high-low midpoint.
+///
+/// Every value holds the line level and whether the trend is up, in which case the
+/// line follows the lower band. Values are empty until ATR warms up.
+pub fn supertrend(klines: &[Kline], period: usize, multiplier: f64) -> Vec<Option<(f64, bool)>> {
+    let mut res = vec![None; klines.len()];
+    let mut prev: Option<(f64, f64, bool)> = None;
+
+    atr(klines, period)
+        .into_iter()
+        .enumerate()
+        .for_each(|(i, a)| {
+            let a = match a {
+                Some(a) => a,
+                None => return,
+            };
+
+            let k = &klines[i];
+            let mid = (k.high + k.low) as f64 / 2.0;
+            let close = k.close as f64;
+            let (mut upper, mut lower) = (mid + multiplier * a, mid - multiplier * a);
+
+            let up = match prev {
+                None => close >= mid,
+                Some((prev_upper, prev_lower, prev_up)) => {
+                    let prev_close = klines[i - 1].close as f64;
+                    if upper > prev_upper && prev_close <= prev_upper {
+                        upper = prev_upper;
+                    }
+                    if lower < prev_lower && prev_close >= prev_lower {
+                        lower = prev_lower;
+                    }
+
+                    match prev_up {
+                        true => close >= lower,
+                        false => close > upper,
+                    }
+                }
+            };
+
+            prev = Some((upper, lower, up));
+            res[i] = Some((if up { lower } else { upper }, up));
+        });
+
+    res
+}
+
+#[cfg(test)]
+mod supertrend_tests {
+    use super::*;
+    use crate::netstrat::indicators::kline;
+
+    #[test]
+    fn test_supertrend() {
+        let klines = vec![
+            kline(11.0, 9.0, 10.5),
+            kline(12.0, 10.0, 11.5),
+            kline(13.0, 11.0, 12.5),
+            kline(8.0, 6.0, 6.5),
+        ];
+
+        let res = supertrend(&klines, 2, 1.0);
+        assert_eq!(res[0], None);
+        // atr is 2.0 and close is above the midpoint, so the line follows the lower band
+        assert_eq!(res[1], Some((9.0, true)));
+        // lower band only moves up in the uptrend
+        assert_eq!(res[2].map(|(_, up)| up), Some(true));
+        assert!(res[2].unwrap().0 > 9.0);
+        // close breaks below the lower band and the trend flips
+        assert_eq!(res[3].map(|(_, up)| up), Some(false));
+    }
+}
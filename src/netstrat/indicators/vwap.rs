@@ -0,0 +1,59 @@
+use crate::{netstrat::session::Session, sources::binance::Kline};
+
+/// Computes Volume Weighted Average Price of typical prices reset at every session open.
+/// Klines outside of sessions have no value.
+pub fn vwap(klines: &[Kline], session: &Session) -> Vec<Option<f64>> {
+    let mut current = None;
+    let (mut pv, mut vol) = (0.0, 0.0);
+
+    klines
+        .iter()
+        .map(|k| {
+            let start = session.start(k.t_open)?;
+            if current != Some(start) {
+                current = Some(start);
+                pv = 0.0;
+                vol = 0.0;
+            }
+
+            let typical = (k.high + k.low + k.close) as f64 / 3.0;
+            pv += typical * k.volume as f64;
+            vol += k.volume as f64;
+
+            match vol > 0.0 {
+                true => Some(pv / vol),
+                false => Some(typical),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod vwap_tests {
+    use super::*;
+    use crate::netstrat::indicators::kline;
+
+    const DAY: i64 = 24 * 60 * 60 * 1000;
+
+    #[test]
+    fn test_vwap_resets() {
+        let klines: Vec<Kline> = [
+            (0, 1.0, 1.0),
+            (1, 4.0, 3.0),
+            (DAY, 10.0, 0.0),
+            (DAY + 1, 20.0, 1.0),
+        ]
+        .into_iter()
+        .map(|(t_open, price, volume)| Kline {
+            t_open,
+            volume,
+            ..kline(price, price, price)
+        })
+        .collect();
+
+        assert_eq!(
+            vwap(&klines, &Session::crypto()),
+            vec![Some(1.0), Some(3.25), Some(10.0), Some(20.0)]
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::price_scale::PriceScale;
+
+use self::errors::LayoutError;
+
+pub mod errors;
+
+const LAYOUTS_FILE: &str = "layouts.json";
+
+/// State of an app window kept in a layout.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WindowLayout {
+    pub visible: bool,
+    /// Charted symbol. It is restored with preferences and workspaces
+    /// but not kept in named layouts.
+    #[serde(default)]
+    pub symbol: Option<String>,
+    /// Chart interval in binance representation, e.g. "1m".
+    #[serde(default)]
+    pub interval: Option<String>,
+    /// Names of enabled chart indicators in the drawing order.
+    #[serde(default)]
+    pub indicators: Vec<String>,
+    /// Mode of the chart price axis.
+    #[serde(default)]
+    pub scale: Option<PriceScale>,
+    /// Charts of all tabs of the window. Fields above describe the active one.
+    #[serde(default)]
+    pub tabs: Vec<WindowLayout>,
+    /// Rows and columns of a window splitting its area into charts.
+    #[serde(default)]
+    pub grid: Option<[usize; 2]>,
+}
+
+impl WindowLayout {
+    fn chart(interval: &str, indicators: &[&str]) -> Self {
+        Self {
+            visible: true,
+            symbol: None,
+            interval: Some(interval.to_string()),
+            indicators: indicators.iter().map(|i| i.to_string()).collect(),
+            scale: None,
+            tabs: vec![],
+            grid: None,
+        }
+    }
+}
+
+/// Named set of window states which can be switched to at once.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Layout {
+    pub name: String,
+    pub windows: BTreeMap<String, WindowLayout>,
+}
+
+impl Layout {
+    pub fn presets() -> Vec<Self> {
+        vec![
+            Self {
+                name: "scalping".to_string(),
+                windows: BTreeMap::from([
+                    (
+                        "graph".to_string(),
+                        WindowLayout::chart("1m", &["VWAP", "RSI"]),
+                    ),
+                    (
+                        "dom".to_string(),
+                        WindowLayout {
+                            visible: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "tape".to_string(),
+                        WindowLayout {
+                            visible: true,
+                            ..Default::default()
+                        },
+                    ),
+                ]),
+            },
+            Self {
+                name: "macro".to_string(),
+                windows: BTreeMap::from([
+                    (
+                        "graph".to_string(),
+                        WindowLayout::chart("1d", &["Ichimoku", "MACD"]),
+                    ),
+                    (
+                        "grid".to_string(),
+                        WindowLayout {
+                            grid: Some([2, 2]),
+                            ..WindowLayout::chart("1d", &["MACD"])
+                        },
+                    ),
+                    (
+                        "correlation".to_string(),
+                        WindowLayout {
+                            visible: true,
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "funding".to_string(),
+                        WindowLayout {
+                            visible: true,
+                            ..Default::default()
+                        },
+                    ),
+                ]),
+            },
+        ]
+    }
+}
+
+/// Layouts saved by the user in a json file.
+/// Presets are used until the user saves the first layout.
+#[derive(Debug, Clone)]
+pub struct Layouts {
+    path: PathBuf,
+    pub layouts: Vec<Layout>,
+}
+
+impl Default for Layouts {
+    fn default() -> Self {
+        Self::new(PathBuf::from(LAYOUTS_FILE))
+    }
+}
+
+impl Layouts {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            layouts: Layout::presets(),
+        }
+    }
+
+    /// Reads saved layouts keeping presets if nothing was saved yet.
+    pub fn load(&mut self) -> Result<(), LayoutError> {
+        if !self.path.exists() {
+            return Ok(());
+        }
+
+        self.layouts = serde_json::from_str(&fs::read_to_string(&self.path)?)?;
+        Ok(())
+    }
+
+    /// Adds the layout replacing a layout with the same name and saves layouts.
+    pub fn save(&mut self, layout: Layout) -> Result<(), LayoutError> {
+        match self.layouts.iter_mut().find(|l| l.name == layout.name) {
+            Some(existing) => *existing = layout,
+            None => self.layouts.push(layout),
+        }
+
+        self.write()
+    }
+
+    pub fn delete(&mut self, name: &str) -> Result<(), LayoutError> {
+        self.layouts.retain(|l| l.name != name);
+
+        self.write()
+    }
+
+    fn write(&self) -> Result<(), LayoutError> {
+        fs::write(&self.path, serde_json::to_string_pretty(&self.layouts)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod layout_tests {
+    use super::*;
+
+    fn layouts(name: &str) -> Layouts {
+        let path = std::env::temp_dir().join(format!(
+            "netstrat-layouts-{}-{}.json",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+
+        Layouts::new(path)
+    }
+
+    fn get<'a>(layouts: &'a Layouts, name: &str) -> Option<&'a Layout> {
+        layouts.layouts.iter().find(|l| l.name == name)
+    }
+
+    #[test]
+    fn test_load_without_file_keeps_presets() {
+        let mut layouts = layouts("presets");
+        layouts.load().unwrap();
+
+        assert_eq!(layouts.layouts, Layout::presets());
+    }
+
+    #[test]
+    fn test_save_replaces_by_name() {
+        let mut layouts = layouts("replace");
+        let mut layout = get(&layouts, "scalping").unwrap().clone();
+        layout
+            .windows
+            .insert("cache".to_string(), WindowLayout::default());
+        layouts.save(layout.clone()).unwrap();
+
+        let mut loaded = Layouts::new(layouts.path.clone());
+        loaded.load().unwrap();
+        assert_eq!(loaded.layouts.len(), Layout::presets().len());
+        assert_eq!(get(&loaded, "scalping"), Some(&layout));
+
+        loaded.delete("scalping").unwrap();
+        let mut loaded = Layouts::new(layouts.path.clone());
+        loaded.load().unwrap();
+        assert_eq!(get(&loaded, "scalping"), None);
+
+        fs::remove_file(&layouts.path).unwrap();
+    }
+
+    #[test]
+    fn test_scale_is_kept() {
+        let layout = WindowLayout {
+            scale: Some(PriceScale::Fixed { min: 1.0, max: 2.0 }),
+            ..WindowLayout::chart("1h", &[])
+        };
+
+        let json = serde_json::to_string(&layout).unwrap();
+        assert_eq!(serde_json::from_str::<WindowLayout>(&json).unwrap(), layout);
+        assert_eq!(
+            serde_json::from_str::<WindowLayout>(r#"{"visible":true}"#)
+                .unwrap()
+                .scale,
+            None
+        );
+    }
+
+    #[test]
+    fn test_grid_is_kept() {
+        let layout = WindowLayout {
+            grid: Some([2, 3]),
+            tabs: vec![WindowLayout::chart("1d", &[]); 6],
+            ..WindowLayout::chart("1d", &[])
+        };
+
+        let json = serde_json::to_string(&layout).unwrap();
+        assert_eq!(serde_json::from_str::<WindowLayout>(&json).unwrap(), layout);
+        assert_eq!(
+            serde_json::from_str::<WindowLayout>(r#"{"visible":true}"#)
+                .unwrap()
+                .grid,
+            None
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::Command,
+    sync::Mutex,
+};
+
+use directories_next::ProjectDirs;
+use tracing::{error, metadata::LevelFilter};
+use tracing_subscriber::{fmt, prelude::*, reload, Registry};
+
+/// Levels selectable at runtime from the most to the least severe.
+pub const LEVELS: [LevelFilter; 5] = [
+    LevelFilter::ERROR,
+    LevelFilter::WARN,
+    LevelFilter::INFO,
+    LevelFilter::DEBUG,
+    LevelFilter::TRACE,
+];
+
+const LOG_FILE: &str = "netstrat.log";
+/// Size a log file grows to before it is rotated.
+const MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+/// Rotated files kept next to the current one.
+const KEPT_FILES: usize = 4;
+
+/// Handle changing the level of the installed subscriber.
+pub type LevelHandle = reload::Handle<LevelFilter, Registry>;
+
+/// Installs the global subscriber printing logs to stdout with a level filter
+/// that can be changed while the app runs. Logs are also written to rotating files
+/// in the log dir if file logging is enabled.
+pub fn init(level: LevelFilter, to_file: bool) -> LevelHandle {
+    let (filter, handle) = reload::Layer::new(level);
+    let (file, file_err) = match to_file {
+        true => match RollingFile::open(log_dir(), MAX_FILE_BYTES, KEPT_FILES) {
+            Ok(file) => (Some(file), None),
+            Err(err) => (None, Some(err)),
+        },
+        false => (None, None),
+    };
+    let file_layer = file.map(|file| fmt::layer().with_ansi(false).with_writer(Mutex::new(file)));
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer())
+        .with(file_layer)
+        .init();
+
+    if let Some(err) = file_err {
+        error!("Failed to open log file: {err}.");
+    }
+
+    handle
+}
+
+/// Directory with log files in the platform data dir, or the working dir if there is none.
+pub fn log_dir() -> PathBuf {
+    ProjectDirs::from("", "", "netstrat")
+        .map(|dirs| dirs.data_dir().join("logs"))
+        .unwrap_or_else(|| PathBuf::from("logs"))
+}
+
+/// Opens the directory in the file manager of the platform.
+pub fn open_dir(dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+
+    let opener = match std::env::consts::OS {
+        "windows" => "explorer",
+        "macos" => "open",
+        _ => "xdg-open",
+    };
+    Command::new(opener).arg(dir).spawn()?;
+
+    Ok(())
+}
+
+/// Log file renamed to a numbered backup once it reaches the size limit.
+/// The oldest backup is dropped so at most `kept` backups stay in the dir.
+pub struct RollingFile {
+    dir: PathBuf,
+    max_bytes: u64,
+    kept: usize,
+    file: File,
+    written: u64,
+}
+
+impl RollingFile {
+    pub fn open(dir: PathBuf, max_bytes: u64, kept: usize) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join(LOG_FILE))?;
+        let written = file.metadata()?.len();
+
+        Ok(Self {
+            dir,
+            max_bytes,
+            kept,
+            file,
+            written,
+        })
+    }
+
+    fn path(&self, backup: usize) -> PathBuf {
+        match backup {
+            0 => self.dir.join(LOG_FILE),
+            n => self.dir.join(format!("{LOG_FILE}.{n}")),
+        }
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+
+        let oldest = self.path(self.kept);
+        if oldest.exists() {
+            fs::remove_file(oldest)?;
+        }
+        for n in (0..self.kept).rev() {
+            let from = self.path(n);
+            if from.exists() {
+                fs::rename(from, self.path(n + 1))?;
+            }
+        }
+
+        self.file = File::create(self.path(0))?;
+        self.written = 0;
+
+        Ok(())
+    }
+}
+
+impl Write for RollingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written > 0 && self.written + buf.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+
+        let n = self.file.write(buf)?;
+        self.written += n as u64;
+
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod log_tests {
+    use super::*;
+
+    #[test]
+    fn test_rolling_file() {
+        let dir = std::env::temp_dir().join(format!("netstrat-logs-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut file = RollingFile::open(dir.clone(), 10, 2).unwrap();
+        ["first\n", "second\n", "third\n", "fourth\n"]
+            .iter()
+            .for_each(|line| file.write_all(line.as_bytes()).unwrap());
+        file.flush().unwrap();
+
+        let read = |name: &str| fs::read_to_string(dir.join(name)).unwrap();
+        assert_eq!(read(LOG_FILE), "fourth\n");
+        assert_eq!(read("netstrat.log.1"), "third\n");
+        assert_eq!(read("netstrat.log.2"), "second\n");
+        assert!(!dir.join("netstrat.log.3").exists());
+
+        let mut reopened = RollingFile::open(dir.clone(), 10, 2).unwrap();
+        reopened.write_all(b"fifth\n").unwrap();
+        assert_eq!(read(LOG_FILE), "fifth\n");
+        assert_eq!(read("netstrat.log.1"), "fourth\n");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,415 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    f32::consts::TAU,
+};
+
+use egui::{Pos2, Vec2};
+
+use super::correlation::Matrix;
+use crate::sources::binance::{Symbol, Ticker};
+
+/// Label propagation stops after this many rounds even if labels still change.
+const MAX_ROUNDS: usize = 100;
+/// Ideal distance between connected nodes in layout units.
+const SPRING: f32 = 0.3;
+/// Pull of every node towards the center keeping unconnected nodes in view.
+const GRAVITY: f32 = 0.05;
+/// Largest move of a node in one layout step.
+const MAX_STEP: f32 = 0.05;
+
+/// Limits symbols of the network so it stays readable.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NodeFilter {
+    /// Quote asset of the symbols, any if empty.
+    pub quote: String,
+    /// Smallest 24h quote volume.
+    pub min_volume: f64,
+    /// Keeps only symbols with the largest 24h quote volume, all if 0.
+    pub top: usize,
+}
+
+impl NodeFilter {
+    pub fn needs_tickers(&self) -> bool {
+        self.min_volume > 0.0 || self.top > 0
+    }
+
+    /// Candidates passing the filter, sorted by volume if the filter needs tickers.
+    /// Symbols missing in the listing are dropped when filtered by the quote asset
+    /// and symbols without tickers count as having no volume.
+    pub fn apply(
+        &self,
+        candidates: &[String],
+        symbols: &[Symbol],
+        tickers: &[Ticker],
+    ) -> Vec<String> {
+        let quotes: HashMap<&str, &str> = symbols
+            .iter()
+            .map(|s| (s.symbol.as_str(), s.quote_asset.as_str()))
+            .collect();
+        let volumes: HashMap<&str, f64> = tickers
+            .iter()
+            .map(|t| (t.symbol.as_str(), t.quote_volume))
+            .collect();
+        let volume = |symbol: &str| volumes.get(symbol).copied().unwrap_or_default();
+
+        let mut res: Vec<String> = candidates
+            .iter()
+            .filter(|c| {
+                self.quote.is_empty() || quotes.get(c.as_str()) == Some(&self.quote.as_str())
+            })
+            .filter(|c| volume(c) >= self.min_volume)
+            .cloned()
+            .collect();
+        if self.needs_tickers() {
+            res.sort_by(|a, b| volume(b).total_cmp(&volume(a)));
+        }
+        if self.top > 0 {
+            res.truncate(self.top);
+        }
+        res
+    }
+}
+
+/// Symbols connected by the correlation of their returns.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Edge {
+    pub a: usize,
+    pub b: usize,
+    pub corr: f64,
+}
+
+/// Graph of symbols laid out by forces: all nodes push each other away
+/// and edges pull their nodes together stronger the larger the correlation is.
+/// Positions are in layout units around the origin.
+pub struct MarketGraph {
+    pub symbols: Vec<String>,
+    pub positions: Vec<Pos2>,
+    matrix: Matrix,
+}
+
+impl MarketGraph {
+    /// Places the nodes on a circle.
+    pub fn new(symbols: Vec<String>, matrix: Matrix) -> Self {
+        let n = symbols.len().max(1) as f32;
+        let positions = (0..symbols.len())
+            .map(|i| {
+                Pos2::new(
+                    0.5 * (TAU * i as f32 / n).cos(),
+                    0.5 * (TAU * i as f32 / n).sin(),
+                )
+            })
+            .collect();
+
+        Self {
+            symbols,
+            positions,
+            matrix,
+        }
+    }
+
+    pub fn corr(&self, a: usize, b: usize) -> Option<f64> {
+        self.matrix.get(a)?.get(b).copied().flatten()
+    }
+
+    /// Pairs of distinct symbols with absolute correlation of at least the threshold.
+    pub fn edges(&self, threshold: f64) -> Vec<Edge> {
+        let n = self.symbols.len();
+        (0..n)
+            .flat_map(|a| (a + 1..n).map(move |b| (a, b)))
+            .filter_map(|(a, b)| {
+                let corr = self.corr(a, b)?;
+                (corr.abs() >= threshold).then_some(Edge { a, b, corr })
+            })
+            .collect()
+    }
+
+    /// Minimum spanning tree of the correlation distances sqrt(2 (1 - corr)),
+    /// connecting all symbols through their strongest correlations without cycles.
+    /// Pairs without correlation are never connected, so the tree may be a forest.
+    pub fn spanning_tree(&self) -> Vec<Edge> {
+        let mut candidates = self.edges(0.0);
+        candidates.sort_by(|x, y| distance(x.corr).total_cmp(&distance(y.corr)));
+
+        let mut roots: Vec<usize> = (0..self.symbols.len()).collect();
+        fn root(roots: &mut [usize], mut i: usize) -> usize {
+            while roots[i] != i {
+                roots[i] = roots[roots[i]];
+                i = roots[i];
+            }
+            i
+        }
+
+        candidates
+            .into_iter()
+            .filter(|e| {
+                let (a, b) = (root(&mut roots, e.a), root(&mut roots, e.b));
+                roots[a] = b;
+                a != b
+            })
+            .collect()
+    }
+
+    /// Cluster of every node found by label propagation: each node takes the label
+    /// with the largest sum of positive correlations among its neighbours until labels settle.
+    /// Clusters are numbered by their first node, so nodes without edges get their own ones.
+    pub fn clusters(&self, edges: &[Edge]) -> Vec<usize> {
+        let n = self.symbols.len();
+        let mut neighbours = vec![vec![]; n];
+        edges.iter().filter(|e| e.corr > 0.0).for_each(|e| {
+            neighbours[e.a].push((e.b, e.corr));
+            neighbours[e.b].push((e.a, e.corr));
+        });
+
+        let mut labels: Vec<usize> = (0..n).collect();
+        for _ in 0..MAX_ROUNDS {
+            let mut changed = false;
+            for i in 0..n {
+                let mut weights = BTreeMap::new();
+                neighbours[i].iter().for_each(|(j, corr)| {
+                    *weights.entry(labels[*j]).or_insert(0.0) += corr;
+                });
+                let best = weights
+                    .into_iter()
+                    .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)))
+                    .map(|(label, _)| label);
+                if let Some(label) = best.filter(|l| *l != labels[i]) {
+                    labels[i] = label;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let mut numbers = BTreeMap::new();
+        labels
+            .into_iter()
+            .map(|label| {
+                let next = numbers.len();
+                *numbers.entry(label).or_insert(next)
+            })
+            .collect()
+    }
+
+    /// Moves nodes one step along the forces leaving the pinned node in place.
+    /// Returns the largest move, so the layout can stop once it settles.
+    pub fn step(&mut self, edges: &[Edge], pinned: Option<usize>) -> f32 {
+        let n = self.positions.len();
+        let mut forces = vec![Vec2::ZERO; n];
+
+        for a in 0..n {
+            for b in a + 1..n {
+                let mut delta = self.positions[a] - self.positions[b];
+                if delta.length() < 1e-4 {
+                    delta = Vec2::new((a as f32).cos(), (b as f32).sin()) * 1e-3;
+                }
+                let push = delta.normalized() * SPRING * SPRING / delta.length().max(0.01);
+                forces[a] += push;
+                forces[b] -= push;
+            }
+        }
+
+        edges.iter().for_each(|e| {
+            let delta = self.positions[e.b] - self.positions[e.a];
+            let pull = delta * delta.length() / SPRING * e.corr.abs() as f32;
+            forces[e.a] += pull;
+            forces[e.b] -= pull;
+        });
+
+        let mut largest: f32 = 0.0;
+        forces.into_iter().enumerate().for_each(|(i, force)| {
+            if pinned == Some(i) {
+                return;
+            }
+            let force = force - self.positions[i].to_vec2() * GRAVITY;
+            let step = force * 0.1;
+            let step = match step.length() > MAX_STEP {
+                true => step.normalized() * MAX_STEP,
+                false => step,
+            };
+            self.positions[i] += step;
+            largest = largest.max(step.length());
+        });
+
+        largest
+    }
+
+    /// Node closest to the position within the radius.
+    pub fn node_at(&self, pos: Pos2, radius: f32) -> Option<usize> {
+        self.positions
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (i, p.distance(pos)))
+            .filter(|(_, d)| *d <= radius)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+}
+
+/// Metric distance of correlated series, 0 for perfectly correlated and 2 for opposite ones.
+fn distance(corr: f64) -> f64 {
+    (2.0 * (1.0 - corr)).max(0.0).sqrt()
+}
+
+#[cfg(test)]
+mod market_graph_tests {
+    use super::*;
+
+    fn graph() -> MarketGraph {
+        MarketGraph::new(
+            vec!["A".to_string(), "B".to_string(), "C".to_string()],
+            vec![
+                vec![Some(1.0), Some(0.9), Some(0.1)],
+                vec![Some(0.9), Some(1.0), Some(-0.6)],
+                vec![Some(0.1), Some(-0.6), None],
+            ],
+        )
+    }
+
+    #[test]
+    fn test_node_filter() {
+        let listed = |symbol: &str, quote: &str| {
+            let mut s = Symbol::default();
+            s.symbol = symbol.to_string();
+            s.quote_asset = quote.to_string();
+            s
+        };
+        let ticker = |symbol: &str, quote_volume: f64| Ticker {
+            symbol: symbol.to_string(),
+            quote_volume,
+            ..Default::default()
+        };
+        let symbols = [
+            listed("BTCUSDT", "USDT"),
+            listed("ETHUSDT", "USDT"),
+            listed("ETHBTC", "BTC"),
+            listed("SOLUSDT", "USDT"),
+        ];
+        let tickers = [
+            ticker("BTCUSDT", 300.0),
+            ticker("ETHUSDT", 200.0),
+            ticker("ETHBTC", 500.0),
+            ticker("SOLUSDT", 50.0),
+        ];
+        let candidates = ["SOLUSDT", "ETHUSDT", "ETHBTC", "BTCUSDT", "NEWUSDT"].map(String::from);
+
+        let mut filter = NodeFilter::default();
+        assert_eq!(
+            filter.apply(&candidates, &symbols, &tickers),
+            candidates.to_vec()
+        );
+
+        filter.quote = "USDT".to_string();
+        assert_eq!(
+            filter.apply(&candidates, &symbols, &tickers),
+            vec!["SOLUSDT", "ETHUSDT", "BTCUSDT"]
+        );
+
+        filter.top = 2;
+        assert_eq!(
+            filter.apply(&candidates, &symbols, &tickers),
+            vec!["BTCUSDT", "ETHUSDT"]
+        );
+
+        filter = NodeFilter {
+            min_volume: 250.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            filter.apply(&candidates, &symbols, &tickers),
+            vec!["ETHBTC", "BTCUSDT"]
+        );
+    }
+
+    #[test]
+    fn test_edges() {
+        let graph = graph();
+
+        assert_eq!(graph.edges(0.5).len(), 2);
+        assert_eq!(
+            graph.edges(0.8),
+            vec![Edge {
+                a: 0,
+                b: 1,
+                corr: 0.9
+            }]
+        );
+        assert_eq!(graph.edges(0.0).len(), 3);
+        assert!(graph.edges(1.0).is_empty());
+    }
+
+    #[test]
+    fn test_spanning_tree() {
+        let graph = graph();
+        assert_eq!(
+            graph.spanning_tree(),
+            vec![
+                Edge {
+                    a: 0,
+                    b: 1,
+                    corr: 0.9
+                },
+                Edge {
+                    a: 0,
+                    b: 2,
+                    corr: 0.1
+                }
+            ]
+        );
+
+        let disconnected = MarketGraph::new(
+            vec!["A".to_string(), "B".to_string()],
+            vec![vec![Some(1.0), None], vec![None, Some(1.0)]],
+        );
+        assert!(disconnected.spanning_tree().is_empty());
+    }
+
+    #[test]
+    fn test_clusters() {
+        let symbols = ["A", "B", "C", "D", "E"].map(String::from).to_vec();
+        let mut matrix = vec![vec![Some(0.0); 5]; 5];
+        [
+            (0, 1, 0.9),
+            (1, 2, 0.8),
+            (0, 2, 0.7),
+            (3, 4, 0.9),
+            (2, 3, -0.9),
+        ]
+        .into_iter()
+        .for_each(|(a, b, corr)| {
+            matrix[a][b] = Some(corr);
+            matrix[b][a] = Some(corr);
+        });
+        let graph = MarketGraph::new(symbols, matrix);
+
+        assert_eq!(graph.clusters(&graph.edges(0.5)), vec![0, 0, 0, 1, 1]);
+        assert_eq!(graph.clusters(&[]), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_step() {
+        let mut graph = graph();
+        let edges = graph.edges(0.8);
+        let start = graph.positions[0].distance(graph.positions[1]);
+
+        for _ in 0..200 {
+            graph.step(&edges, Some(2));
+        }
+        assert!(graph.positions[0].distance(graph.positions[1]) < start);
+        assert!(graph.positions[0].distance(graph.positions[2]) > SPRING);
+        assert_eq!(
+            graph.positions[2],
+            MarketGraph::new(vec!["A".into(); 3], vec![]).positions[2]
+        );
+        assert!(graph.step(&edges, None) < MAX_STEP);
+    }
+
+    #[test]
+    fn test_node_at() {
+        let graph = graph();
+
+        assert_eq!(graph.node_at(Pos2::new(0.49, 0.0), 0.05), Some(0));
+        assert_eq!(graph.node_at(Pos2::ZERO, 0.05), None);
+    }
+}
@@ -1,3 +1,41 @@
+pub mod archive;
+pub mod backtest;
 pub mod bounds;
+pub mod chart_type;
 pub mod graph;
-pub mod data;
\ No newline at end of file
+pub mod alert;
+pub mod convert;
+pub mod correlation;
+pub mod data;
+pub mod drawings;
+pub mod export;
+pub mod funding;
+pub mod fuzzy;
+pub mod import;
+pub mod indicators;
+pub mod layout;
+pub mod log;
+pub mod market_graph;
+pub mod notify;
+pub mod order_flow;
+pub mod paper;
+pub mod power;
+pub mod preferences;
+pub mod price_scale;
+pub mod recent;
+pub mod replay;
+pub mod screener;
+pub mod script;
+pub mod session;
+pub mod settings;
+pub mod shortcuts;
+pub mod sparkline;
+pub mod status;
+pub mod symbol_info;
+pub mod symbol_sort;
+pub mod theme;
+pub mod toasts;
+pub mod venues;
+pub mod volume_profile;
+pub mod watchlist;
+pub mod workspace;
@@ -0,0 +1,82 @@
+use serde::Serialize;
+use tracing::{error, info};
+
+use crate::network::rest::Rest;
+
+use super::toasts::Toasts;
+
+pub use self::telegram::Telegram;
+
+mod telegram;
+
+/// Body of the webhook request sent when a price alert triggers.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AlertPayload {
+    pub event: &'static str,
+    pub symbol: String,
+    pub interval: String,
+    /// Level of the alert.
+    pub price: f64,
+    /// Close of the kline the alert triggered on.
+    pub last: f64,
+    /// Unix milliseconds of the trigger.
+    pub time: i64,
+}
+
+impl AlertPayload {
+    pub fn new(symbol: &str, interval: &str, price: f64, last: f64, time: i64) -> Self {
+        Self {
+            event: "alert",
+            symbol: symbol.to_string(),
+            interval: interval.to_string(),
+            price,
+            last,
+            time,
+        }
+    }
+}
+
+/// Posts the payload as json to the url in the background. Failures are logged and toasted.
+pub fn webhook(url: &str, payload: &impl Serialize) {
+    post("webhook", url.to_string(), payload);
+}
+
+/// Posts the payload in the background. The url is not logged as it may contain secrets,
+/// like webhook keys or the bot token.
+fn post(service: &'static str, url: String, payload: &impl Serialize) {
+    let body = match serde_json::to_string(payload) {
+        Ok(body) => body,
+        Err(err) => {
+            error!("Failed to serialize {service} payload: {err}.");
+            return;
+        }
+    };
+
+    tokio::spawn(async move {
+        match Rest::new().post_json_secret(&url, body).await {
+            Ok(resp) if resp.status().is_success() => info!("Notified {service}."),
+            Ok(resp) => {
+                error!("{service} responded with {}.", resp.status());
+                Toasts::error(format!("Failed to notify {service}"), resp.status());
+            }
+            Err(err) => {
+                error!("Failed to notify {service}: {err}.");
+                Toasts::error(format!("Failed to notify {service}"), err);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod notify_tests {
+    use super::*;
+
+    #[test]
+    fn test_alert_payload() {
+        let payload = AlertPayload::new("BTCUSDT", "1h", 30000.0, 30012.5, 1_600_000_000_000);
+        assert_eq!(
+            serde_json::to_string(&payload).unwrap(),
+            r#"{"event":"alert","symbol":"BTCUSDT","interval":"1h","price":30000.0,"last":30012.5,"time":1600000000000}"#
+        );
+    }
+}
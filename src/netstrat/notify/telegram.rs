@@ -0,0 +1,93 @@
+use serde::Serialize;
+
+use crate::netstrat::settings::Settings;
+
+use super::post;
+
+const API_URL: &str = "https://api.telegram.org";
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Message<'a> {
+    chat_id: &'a str,
+    text: &'a str,
+}
+
+/// Telegram bot sending messages to a chat.
+#[derive(Clone, PartialEq)]
+pub struct Telegram {
+    token: String,
+    chat_id: String,
+}
+
+impl Telegram {
+    /// Bot from the settings, none unless both the token and the chat id are set.
+    pub fn from_settings(settings: &Settings) -> Option<Self> {
+        let token = settings.telegram_token.trim();
+        let chat_id = settings.telegram_chat_id.trim();
+        if token.is_empty() || chat_id.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            token: token.to_string(),
+            chat_id: chat_id.to_string(),
+        })
+    }
+
+    /// Sends the message in the background if the bot is set in the settings.
+    pub fn notify(settings: &Settings, text: impl ToString) {
+        if let Some(bot) = Telegram::from_settings(settings) {
+            bot.send(&text.to_string());
+        }
+    }
+
+    pub fn send(&self, text: &str) {
+        let message = Message {
+            chat_id: &self.chat_id,
+            text,
+        };
+        post("telegram", self.url(), &message);
+    }
+
+    fn url(&self) -> String {
+        format!("{API_URL}/bot{}/sendMessage", self.token)
+    }
+}
+
+impl std::fmt::Debug for Telegram {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Telegram")
+            .field("chat_id", &self.chat_id)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod telegram_tests {
+    use super::*;
+
+    #[test]
+    fn test_from_settings() {
+        let mut settings = Settings::default();
+        settings.telegram_token = "123:abc".to_string();
+        assert_eq!(Telegram::from_settings(&settings), None);
+
+        settings.telegram_chat_id = " 42 ".to_string();
+        let bot = Telegram::from_settings(&settings).unwrap();
+        assert_eq!(bot.url(), "https://api.telegram.org/bot123:abc/sendMessage");
+        assert_eq!(bot.chat_id, "42");
+        assert!(!format!("{bot:?}").contains("abc"), "token is not printed");
+    }
+
+    #[test]
+    fn test_message() {
+        let message = Message {
+            chat_id: "42",
+            text: "BTCUSDT reached 30000",
+        };
+        assert_eq!(
+            serde_json::to_string(&message).unwrap(),
+            r#"{"chat_id":"42","text":"BTCUSDT reached 30000"}"#
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use crate::sources::binance::Trade;
+
+/// Price level of the order book with the quantity resting up to it from the best price.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Level {
+    pub price: f64,
+    pub qty: f64,
+    pub cumulative: f64,
+}
+
+/// Returns up to rows levels of one side of the book sorted from the best price.
+pub fn levels(side: &[(f64, f64)], rows: usize) -> Vec<Level> {
+    let mut cumulative = 0.0;
+    side.iter()
+        .take(rows)
+        .map(|(price, qty)| {
+            cumulative += qty;
+            Level {
+                price: *price,
+                qty: *qty,
+                cumulative,
+            }
+        })
+        .collect()
+}
+
+/// Latest trades of a symbol merged from overlapping polls.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Tape {
+    /// Trades from the oldest to the newest.
+    pub trades: Vec<Trade>,
+    capacity: usize,
+}
+
+impl Tape {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            trades: vec![],
+            capacity,
+        }
+    }
+
+    /// Appends trades newer than the last kept one dropping the oldest above capacity.
+    pub fn push(&mut self, trades: &[Trade]) {
+        let last = self.trades.last().map_or(i64::MIN, |t| t.id);
+        self.trades
+            .extend(trades.iter().filter(|t| t.id > last).cloned());
+
+        let excess = self.trades.len().saturating_sub(self.capacity);
+        self.trades.drain(..excess);
+    }
+
+    pub fn clear(&mut self) {
+        self.trades.clear();
+    }
+
+    /// Returns bought and sold quantity of the kept trades.
+    pub fn volume(&self) -> (f64, f64) {
+        self.trades
+            .iter()
+            .fold((0.0, 0.0), |(bought, sold), t| match t.is_buyer_maker {
+                true => (bought, sold + t.qty),
+                false => (bought + t.qty, sold),
+            })
+    }
+}
+
+#[cfg(test)]
+mod order_flow_tests {
+    use super::*;
+
+    fn trade(id: i64, qty: f64, is_buyer_maker: bool) -> Trade {
+        Trade {
+            id,
+            qty,
+            is_buyer_maker,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_levels() {
+        let side = [(10.0, 1.0), (9.0, 2.0), (8.0, 3.0)];
+
+        let levels = levels(&side, 2);
+
+        assert_eq!(
+            levels,
+            vec![
+                Level {
+                    price: 10.0,
+                    qty: 1.0,
+                    cumulative: 1.0
+                },
+                Level {
+                    price: 9.0,
+                    qty: 2.0,
+                    cumulative: 3.0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tape_push_skips_known_trades() {
+        let mut tape = Tape::new(3);
+
+        tape.push(&[trade(1, 1.0, false), trade(2, 1.0, true)]);
+        tape.push(&[
+            trade(2, 1.0, true),
+            trade(3, 2.0, false),
+            trade(4, 0.5, true),
+        ]);
+
+        let ids: Vec<i64> = tape.trades.iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![2, 3, 4]);
+        assert_eq!(tape.volume(), (2.0, 1.5));
+    }
+}
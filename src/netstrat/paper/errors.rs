@@ -0,0 +1,16 @@
+use quick_error::quick_error;
+
+quick_error! {
+    #[derive(Debug)]
+    pub enum PaperError {
+        InvalidQuantity(qty: f64) {
+            display("quantity must be positive, got {}", qty)
+        }
+        InvalidPrice(price: f64) {
+            display("limit price must be positive, got {}", price)
+        }
+        UnknownOrder(id: usize) {
+            display("no open order {}", id)
+        }
+    }
+}
@@ -0,0 +1,453 @@
+use std::collections::BTreeMap;
+
+use crate::sources::binance::Kline;
+
+use self::errors::PaperError;
+
+use super::replay::Side;
+
+pub mod errors;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrderKind {
+    /// Filled at the close of the next received kline.
+    Market,
+    /// Filled once a kline opened after the order reaches the price.
+    Limit(f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Order {
+    pub id: usize,
+    pub symbol: String,
+    pub side: Side,
+    pub qty: f64,
+    pub kind: OrderKind,
+    /// Time the order was placed at.
+    pub time: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fill {
+    pub order: usize,
+    pub symbol: String,
+    pub side: Side,
+    pub qty: f64,
+    pub price: f64,
+    pub fee: f64,
+    pub time: i64,
+}
+
+/// Position in a symbol, negative quantity is short.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Position {
+    pub qty: f64,
+    /// Average price the quantity was entered at.
+    pub entry: f64,
+    /// Profit of closed quantity without fees.
+    pub realized: f64,
+    /// Last received price of the symbol.
+    pub last: Option<f64>,
+}
+
+impl Position {
+    pub fn unrealized(&self) -> f64 {
+        self.last.map_or(0.0, |last| self.qty * (last - self.entry))
+    }
+
+    fn apply(&mut self, side: Side, qty: f64, price: f64) {
+        let signed = match side {
+            Side::Buy => qty,
+            Side::Sell => -qty,
+        };
+
+        if self.qty == 0.0 || self.qty.signum() == signed.signum() {
+            self.entry = (self.entry * self.qty + price * signed) / (self.qty + signed);
+            self.qty += signed;
+            return;
+        }
+
+        let closed = signed.abs().min(self.qty.abs()) * self.qty.signum();
+        self.realized += closed * (price - self.entry);
+        self.qty += signed;
+        if self.qty.abs() < f64::EPSILON {
+            self.qty = 0.0;
+        } else if self.qty.signum() == signed.signum() {
+            // the position is reversed, the rest is entered at the fill price
+            self.entry = price;
+        }
+    }
+}
+
+/// Profit and loss of a portfolio at a time, fees are included in realized profit.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PnlPoint {
+    pub time: i64,
+    pub realized: f64,
+    pub unrealized: f64,
+}
+
+impl PnlPoint {
+    pub fn total(&self) -> f64 {
+        self.realized + self.unrealized
+    }
+}
+
+/// Replays fills in time order and marks positions at the price of every fill.
+/// Returns a point after each fill.
+pub fn fills_history(fills: &[Fill]) -> Vec<PnlPoint> {
+    let mut fills: Vec<&Fill> = fills.iter().collect();
+    fills.sort_by_key(|f| f.time);
+
+    let mut positions: BTreeMap<&str, Position> = BTreeMap::new();
+    let mut fees = 0.0;
+    fills
+        .into_iter()
+        .map(|f| {
+            let position = positions.entry(&f.symbol).or_default();
+            position.apply(f.side, f.qty, f.price);
+            position.last = Some(f.price);
+            fees += f.fee;
+
+            PnlPoint {
+                time: f.time,
+                realized: positions.values().map(|p| p.realized).sum::<f64>() - fees,
+                unrealized: positions.values().map(|p| p.unrealized()).sum(),
+            }
+        })
+        .collect()
+}
+
+/// Paper trading account simulating fills of orders against received klines.
+///
+/// Nothing is sent to the exchange: market orders fill at the latest close and limit
+/// orders fill at their price, or at the open if a kline gaps through it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Portfolio {
+    pub capital: f64,
+    /// Fee taken from every fill as a fraction of its value.
+    pub fee: f64,
+    pub orders: Vec<Order>,
+    pub fills: Vec<Fill>,
+    pub positions: BTreeMap<String, Position>,
+    pub fees: f64,
+    /// Profit and loss after every update, at most one point per kline close time.
+    pub history: Vec<PnlPoint>,
+    next_id: usize,
+}
+
+impl Default for Portfolio {
+    fn default() -> Self {
+        Self::new(10_000.0, 0.001)
+    }
+}
+
+impl Portfolio {
+    pub fn new(capital: f64, fee: f64) -> Self {
+        Self {
+            capital,
+            fee,
+            orders: vec![],
+            fills: vec![],
+            positions: BTreeMap::new(),
+            fees: 0.0,
+            history: vec![],
+            next_id: 1,
+        }
+    }
+
+    /// Queues the order until klines of its symbol arrive. Returns the order id.
+    pub fn place(
+        &mut self,
+        symbol: &str,
+        side: Side,
+        qty: f64,
+        kind: OrderKind,
+        time: i64,
+    ) -> Result<usize, PaperError> {
+        if qty.is_nan() || qty <= 0.0 {
+            return Err(PaperError::InvalidQuantity(qty));
+        }
+        if let OrderKind::Limit(price) = kind {
+            if price.is_nan() || price <= 0.0 {
+                return Err(PaperError::InvalidPrice(price));
+            }
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.orders.push(Order {
+            id,
+            symbol: symbol.to_string(),
+            side,
+            qty,
+            kind,
+            time,
+        });
+
+        Ok(id)
+    }
+
+    pub fn cancel(&mut self, id: usize) -> Result<Order, PaperError> {
+        let i = self
+            .orders
+            .iter()
+            .position(|o| o.id == id)
+            .ok_or(PaperError::UnknownOrder(id))?;
+        Ok(self.orders.remove(i))
+    }
+
+    /// Symbols with open orders or positions, which need klines to be received.
+    pub fn symbols(&self) -> Vec<String> {
+        let mut res: Vec<String> = self
+            .positions
+            .iter()
+            .filter(|(_, p)| p.qty != 0.0)
+            .map(|(s, _)| s.clone())
+            .chain(self.orders.iter().map(|o| o.symbol.clone()))
+            .collect();
+        res.sort();
+        res.dedup();
+        res
+    }
+
+    /// Fills open orders of the symbol against the latest received klines of it and
+    /// updates its last price. Returns the new fills.
+    pub fn update(&mut self, symbol: &str, klines: &[Kline]) -> Vec<Fill> {
+        let last = match klines.last() {
+            Some(last) => last,
+            None => return vec![],
+        };
+
+        let mut fills = vec![];
+        let mut open = vec![];
+        for order in self.orders.drain(..) {
+            let price = match (order.symbol == symbol, order.kind) {
+                (false, _) => None,
+                (true, OrderKind::Market) => Some((last.close as f64, last.t_close)),
+                (true, OrderKind::Limit(limit)) => klines
+                    .iter()
+                    .filter(|k| k.t_open >= order.time)
+                    .find_map(|k| limit_fill(order.side, limit, k).map(|p| (p, k.t_open))),
+            };
+            match price {
+                Some((price, time)) => fills.push(Fill {
+                    order: order.id,
+                    symbol: order.symbol,
+                    side: order.side,
+                    qty: order.qty,
+                    price,
+                    fee: order.qty * price * self.fee,
+                    time,
+                }),
+                None => open.push(order),
+            }
+        }
+        self.orders = open;
+
+        let position = self.positions.entry(symbol.to_string()).or_default();
+        fills.iter().for_each(|f| {
+            position.apply(f.side, f.qty, f.price);
+            self.fees += f.fee;
+        });
+        position.last = Some(last.close as f64);
+        self.fills.extend(fills.iter().cloned());
+        self.record(last.t_close);
+
+        fills
+    }
+
+    fn record(&mut self, time: i64) {
+        let point = PnlPoint {
+            time,
+            realized: self.realized(),
+            unrealized: self.unrealized(),
+        };
+        match self.history.last_mut() {
+            Some(last) if last.time >= time => {
+                *last = PnlPoint {
+                    time: last.time,
+                    ..point
+                }
+            }
+            _ => self.history.push(point),
+        }
+    }
+
+    pub fn realized(&self) -> f64 {
+        self.positions.values().map(|p| p.realized).sum::<f64>() - self.fees
+    }
+
+    pub fn unrealized(&self) -> f64 {
+        self.positions.values().map(|p| p.unrealized()).sum()
+    }
+
+    pub fn equity(&self) -> f64 {
+        self.capital + self.realized() + self.unrealized()
+    }
+}
+
+/// Price a limit order fills at within the kline.
+fn limit_fill(side: Side, limit: f64, k: &Kline) -> Option<f64> {
+    let (open, high, low) = (k.open as f64, k.high as f64, k.low as f64);
+    match side {
+        Side::Buy => (low <= limit).then_some(open.min(limit)),
+        Side::Sell => (high >= limit).then_some(open.max(limit)),
+    }
+}
+
+#[cfg(test)]
+mod paper_tests {
+    use super::*;
+
+    fn kline(t: i64, open: f32, high: f32, low: f32, close: f32) -> Kline {
+        Kline {
+            t_open: t,
+            t_close: t + 59,
+            open,
+            high,
+            low,
+            close,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_market_order() {
+        let mut portfolio = Portfolio::new(1000.0, 0.0);
+        portfolio
+            .place("BTCUSDT", Side::Buy, 2.0, OrderKind::Market, 0)
+            .unwrap();
+        assert!(portfolio
+            .update("ETHUSDT", &[kline(0, 1.0, 1.0, 1.0, 1.0)])
+            .is_empty());
+
+        let fills = portfolio.update("BTCUSDT", &[kline(0, 9.0, 11.0, 8.0, 10.0)]);
+        assert_eq!(fills.len(), 1);
+        assert_eq!((fills[0].price, fills[0].time), (10.0, 59));
+        assert!(portfolio.orders.is_empty());
+
+        portfolio.update("BTCUSDT", &[kline(60, 10.0, 13.0, 10.0, 12.0)]);
+        assert_eq!(portfolio.unrealized(), 4.0);
+        assert_eq!(portfolio.equity(), 1004.0);
+        assert_eq!(portfolio.symbols(), vec!["BTCUSDT".to_string()]);
+    }
+
+    #[test]
+    fn test_limit_order() {
+        let mut portfolio = Portfolio::new(1000.0, 0.01);
+        portfolio
+            .place("BTCUSDT", Side::Buy, 1.0, OrderKind::Limit(9.0), 60)
+            .unwrap();
+        portfolio
+            .place("BTCUSDT", Side::Sell, 1.0, OrderKind::Limit(20.0), 60)
+            .unwrap();
+
+        // klines opened before the orders do not fill them
+        let klines = [
+            kline(0, 10.0, 10.0, 5.0, 10.0),
+            kline(60, 10.0, 10.0, 9.5, 10.0),
+        ];
+        assert!(portfolio.update("BTCUSDT", &klines).is_empty());
+
+        let fills = portfolio.update("BTCUSDT", &[kline(120, 8.0, 10.0, 7.0, 9.0)]);
+        assert_eq!(fills[0].price, 8.0, "gapped through the limit");
+        assert_eq!(portfolio.orders.len(), 1);
+
+        let fills = portfolio.update("BTCUSDT", &[kline(180, 15.0, 21.0, 15.0, 18.0)]);
+        assert_eq!(fills[0].price, 20.0);
+        let position = portfolio.positions["BTCUSDT"];
+        assert_eq!((position.qty, position.realized), (0.0, 12.0));
+        assert!((portfolio.fees - 0.28).abs() < 1e-9);
+        assert!((portfolio.realized() - 11.72).abs() < 1e-9);
+        assert!(portfolio.symbols().is_empty());
+    }
+
+    #[test]
+    fn test_position_reverse() {
+        let mut position = Position::default();
+        position.apply(Side::Buy, 1.0, 10.0);
+        position.apply(Side::Buy, 1.0, 20.0);
+        assert_eq!((position.qty, position.entry), (2.0, 15.0));
+
+        position.apply(Side::Sell, 3.0, 25.0);
+        assert_eq!(position.qty, -1.0);
+        assert_eq!(position.entry, 25.0);
+        assert_eq!(position.realized, 20.0);
+
+        position.last = Some(20.0);
+        assert_eq!(position.unrealized(), 5.0);
+    }
+
+    #[test]
+    fn test_history() {
+        let mut portfolio = Portfolio::new(1000.0, 0.0);
+        portfolio
+            .place("BTCUSDT", Side::Buy, 1.0, OrderKind::Market, 0)
+            .unwrap();
+        portfolio.update("BTCUSDT", &[kline(0, 10.0, 10.0, 10.0, 10.0)]);
+        portfolio.update("BTCUSDT", &[kline(0, 10.0, 12.0, 10.0, 12.0)]);
+        portfolio.update("BTCUSDT", &[kline(60, 12.0, 15.0, 12.0, 15.0)]);
+
+        let totals: Vec<(i64, f64)> = portfolio
+            .history
+            .iter()
+            .map(|p| (p.time, p.total()))
+            .collect();
+        assert_eq!(
+            totals,
+            vec![(59, 2.0), (119, 5.0)],
+            "one point per close time"
+        );
+    }
+
+    #[test]
+    fn test_fills_history() {
+        let fill = |time: i64, side: Side, price: f64, fee: f64| Fill {
+            order: 0,
+            symbol: "BTCUSDT".to_string(),
+            side,
+            qty: 1.0,
+            price,
+            fee,
+            time,
+        };
+        let history = fills_history(&[
+            fill(2, Side::Sell, 15.0, 0.5),
+            fill(1, Side::Buy, 10.0, 0.5),
+        ]);
+
+        assert_eq!(
+            history,
+            vec![
+                PnlPoint {
+                    time: 1,
+                    realized: -0.5,
+                    unrealized: 0.0
+                },
+                PnlPoint {
+                    time: 2,
+                    realized: 4.0,
+                    unrealized: 0.0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_orders() {
+        let mut portfolio = Portfolio::default();
+        assert!(portfolio
+            .place("BTCUSDT", Side::Buy, 0.0, OrderKind::Market, 0)
+            .is_err());
+        assert!(portfolio
+            .place("BTCUSDT", Side::Buy, 1.0, OrderKind::Limit(-1.0), 0)
+            .is_err());
+        assert!(portfolio.cancel(1).is_err());
+
+        let id = portfolio
+            .place("BTCUSDT", Side::Sell, 1.0, OrderKind::Market, 0)
+            .unwrap();
+        assert_eq!(portfolio.cancel(id).unwrap().side, Side::Sell);
+    }
+}
@@ -0,0 +1,87 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use egui::{Context, Id};
+use tracing::info;
+
+const IDLE_SECS: f64 = 10.0;
+const BACKGROUND_REPAINT_MILLIS: u64 = 1000;
+const LOW_POWER_ID: &str = "low power";
+
+/// Tracks user activity and switches the app into low-power mode when nobody interacts
+/// with it for a while.
+///
+/// egui 0.18 does not tell whether the window is focused or minimized, so inactivity is
+/// the only signal used. This also throttles a focused chart watched without input.
+///
+/// In low-power mode widgets stop requesting continuous repaints and pause non-essential
+/// work like the market graph layout. A background ticker repaints once a second while
+/// the mode is on, so pending downloads are still polled. Any input event switches
+/// low-power mode off on the very same frame and stops the ticker.
+#[derive(Default)]
+pub struct LowPower {
+    last_activity: f64,
+    on: bool,
+    /// Keeps the ticker repainting while set.
+    ticker: Option<Arc<AtomicBool>>,
+}
+
+impl LowPower {
+    pub fn update(&mut self, ctx: &Context) {
+        let (time, active) = {
+            let input = ctx.input();
+            (
+                input.time,
+                !input.events.is_empty() || input.pointer.is_moving() || input.pointer.any_down(),
+            )
+        };
+        if active {
+            self.last_activity = time;
+        }
+
+        let on = time - self.last_activity >= IDLE_SECS;
+        if on != self.on {
+            info!("Low-power mode switched: {on}.");
+            self.on = on;
+            match on {
+                true => self.start_ticker(ctx),
+                false => self.stop_ticker(),
+            }
+        }
+
+        ctx.data().insert_temp(Id::new(LOW_POWER_ID), on);
+    }
+
+    /// Returns true if the app is idle and widgets should avoid non-essential work.
+    pub fn is_on(ctx: &Context) -> bool {
+        ctx.data()
+            .get_temp(Id::new(LOW_POWER_ID))
+            .unwrap_or_default()
+    }
+
+    fn start_ticker(&mut self, ctx: &Context) {
+        self.stop_ticker();
+
+        let running = Arc::new(AtomicBool::new(true));
+        let (ctx, ticking) = (ctx.clone(), running.clone());
+        thread::spawn(move || {
+            while ticking.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(BACKGROUND_REPAINT_MILLIS));
+                ctx.request_repaint();
+            }
+        });
+        self.ticker = Some(running);
+    }
+
+    fn stop_ticker(&mut self) {
+        if let Some(running) = self.ticker.take() {
+            running.store(false, Ordering::Relaxed);
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::layout::WindowLayout;
+
+/// Key of preferences in the eframe storage.
+pub const PREFERENCES_KEY: &str = "preferences";
+
+/// UI state saved into the eframe storage and restored on start.
+/// Window geometry and panel sizes are persisted by eframe itself with the egui memory.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Preferences {
+    #[serde(default)]
+    pub theme: Option<String>,
+    /// States of app windows by their names.
+    #[serde(default)]
+    pub windows: BTreeMap<String, WindowLayout>,
+}
+
+#[cfg(test)]
+mod preferences_tests {
+    use super::*;
+
+    #[test]
+    fn test_restore_preferences() {
+        let prefs = Preferences {
+            theme: Some("light".to_string()),
+            windows: BTreeMap::from([(
+                "graph".to_string(),
+                WindowLayout {
+                    visible: true,
+                    symbol: Some("ETHUSDT".to_string()),
+                    interval: Some("1h".to_string()),
+                    ..Default::default()
+                },
+            )]),
+        };
+
+        let restored: Preferences =
+            serde_json::from_str(&serde_json::to_string(&prefs).unwrap()).unwrap();
+        assert_eq!(restored, prefs);
+        assert_eq!(
+            serde_json::from_str::<Preferences>("{}").unwrap(),
+            Preferences::default()
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+/// Lower bound of prices on the log scale, as log of zero is undefined.
+const MIN_LOG_PRICE: f64 = 1e-12;
+
+/// Mode of the candles price axis.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PriceScale {
+    /// Fits all loaded klines.
+    #[default]
+    Auto,
+    /// Shows user defined price range. Prices are drawn as they are,
+    /// so the ones outside of the range are out of view.
+    Fixed { min: f64, max: f64 },
+    /// Draws prices as percentage change from the close of the first visible kline,
+    /// so series of different symbols rebased to the same edge can be compared.
+    Percent,
+    /// Draws logarithms of prices so equal percentage moves have equal heights.
+    Log,
+}
+
+impl PriceScale {
+    /// Returns true if prices are drawn as they are.
+    pub fn is_linear(self) -> bool {
+        matches!(self, PriceScale::Auto | PriceScale::Fixed { .. })
+    }
+
+    /// Maps a price to the y coordinate of the plot. Base is the price of 0% on the percent scale.
+    pub fn y_map(self, base: f64) -> impl Fn(f64) -> f64 + Copy {
+        move |v| match self {
+            PriceScale::Percent => (v / base - 1.0) * 100.0,
+            PriceScale::Log => v.max(MIN_LOG_PRICE).ln(),
+            PriceScale::Auto | PriceScale::Fixed { .. } => v,
+        }
+    }
+
+    /// Maps the y coordinate of the plot back to a price.
+    pub fn unmap(self, base: f64) -> impl Fn(f64) -> f64 + Copy {
+        move |v| match self {
+            PriceScale::Percent => (v / 100.0 + 1.0) * base,
+            PriceScale::Log => v.exp(),
+            PriceScale::Auto | PriceScale::Fixed { .. } => v,
+        }
+    }
+}
+
+#[cfg(test)]
+mod price_scale_tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_keeps_prices() {
+        let scale = PriceScale::Fixed {
+            min: 10.0,
+            max: 20.0,
+        };
+
+        assert_eq!(scale.y_map(1.0)(25.0), 25.0);
+        assert_eq!(scale.y_map(1.0)(5.0), 5.0);
+        assert_eq!(scale.unmap(1.0)(25.0), 25.0);
+    }
+
+    #[test]
+    fn test_unmap() {
+        [PriceScale::Auto, PriceScale::Percent, PriceScale::Log]
+            .into_iter()
+            .for_each(|scale| {
+                let price = scale.unmap(50.0)(scale.y_map(50.0)(75.0));
+                assert!((price - 75.0).abs() < 1e-9, "{scale:?}: {price}");
+            });
+        assert_eq!(PriceScale::Percent.y_map(50.0)(75.0), 50.0);
+    }
+}
@@ -0,0 +1,40 @@
+/// Number of recently charted symbols kept.
+pub const RECENT_LIMIT: usize = 8;
+
+/// Recently charted symbols, the latest first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recent {
+    symbols: Vec<String>,
+}
+
+impl Recent {
+    /// Moves the symbol to the front dropping the oldest ones over the limit.
+    pub fn push(&mut self, symbol: &str) {
+        self.symbols.retain(|s| s != symbol);
+        self.symbols.insert(0, symbol.to_string());
+        self.symbols.truncate(RECENT_LIMIT);
+    }
+
+    pub fn symbols(&self) -> &[String] {
+        &self.symbols
+    }
+}
+
+#[cfg(test)]
+mod recent_tests {
+    use super::*;
+
+    #[test]
+    fn test_push() {
+        let mut recent = Recent::default();
+        recent.push("BTCUSDT");
+        recent.push("ETHUSDT");
+        recent.push("BTCUSDT");
+        assert_eq!(recent.symbols(), ["BTCUSDT", "ETHUSDT"]);
+
+        (0..RECENT_LIMIT).for_each(|i| recent.push(&format!("S{i}")));
+        assert_eq!(recent.symbols().len(), RECENT_LIMIT);
+        assert_eq!(recent.symbols()[0], format!("S{}", RECENT_LIMIT - 1));
+        assert!(!recent.symbols().contains(&"BTCUSDT".to_string()));
+    }
+}
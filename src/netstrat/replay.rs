@@ -0,0 +1,108 @@
+use std::fmt::Display;
+
+/// Direction of a simulated market order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+/// Simulated market order filled at the replayed close price.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trade {
+    pub t: i64,
+    pub side: Side,
+    pub price: f64,
+    /// Size of the order in units, 2 when a position is reversed.
+    pub size: f64,
+}
+
+impl Display for Trade {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} {} @ {}", self.side, self.size, self.price)
+    }
+}
+
+/// Trade simulator of replay mode holding at most one unit long or short.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Simulator {
+    /// 1 for long, -1 for short and 0 for flat.
+    pub position: f64,
+    entry: f64,
+    pub realized: f64,
+    pub trades: Vec<Trade>,
+}
+
+impl Simulator {
+    /// Goes one unit long closing a short position first.
+    pub fn buy(&mut self, t: i64, price: f64) -> Option<Trade> {
+        self.target(t, price, 1.0)
+    }
+
+    /// Goes one unit short closing a long position first.
+    pub fn sell(&mut self, t: i64, price: f64) -> Option<Trade> {
+        self.target(t, price, -1.0)
+    }
+
+    /// Closes the position.
+    pub fn flat(&mut self, t: i64, price: f64) -> Option<Trade> {
+        self.target(t, price, 0.0)
+    }
+
+    pub fn unrealized(&self, price: f64) -> f64 {
+        self.position * (price - self.entry)
+    }
+
+    pub fn total(&self, price: f64) -> f64 {
+        self.realized + self.unrealized(price)
+    }
+
+    /// Trades the difference to the target position. Returns nothing if already there.
+    fn target(&mut self, t: i64, price: f64, position: f64) -> Option<Trade> {
+        let size = position - self.position;
+        if size == 0.0 {
+            return None;
+        }
+
+        self.realized += self.unrealized(price);
+        self.position = position;
+        self.entry = price;
+
+        let trade = Trade {
+            t,
+            side: match size > 0.0 {
+                true => Side::Buy,
+                false => Side::Sell,
+            },
+            price,
+            size: size.abs(),
+        };
+        self.trades.push(trade);
+
+        Some(trade)
+    }
+}
+
+#[cfg(test)]
+mod replay_tests {
+    use super::*;
+
+    #[test]
+    fn test_pnl() {
+        let mut sim = Simulator::default();
+        assert!(sim.flat(0, 100.0).is_none());
+
+        sim.buy(1, 100.0).unwrap();
+        assert!(sim.buy(2, 105.0).is_none(), "already long");
+        assert_eq!(sim.unrealized(110.0), 10.0);
+
+        let reverse = sim.sell(3, 110.0).unwrap();
+        assert_eq!((reverse.side, reverse.size), (Side::Sell, 2.0));
+        assert_eq!(sim.realized, 10.0);
+
+        sim.flat(4, 104.0).unwrap();
+        assert_eq!(sim.position, 0.0);
+        assert_eq!(sim.total(1000.0), 16.0);
+        assert_eq!(sim.trades.len(), 3);
+    }
+}
@@ -0,0 +1,341 @@
+use chrono::Utc;
+use poll_promise::Promise;
+use tracing::{error, info};
+
+use crate::sources::binance::{errors::ClientError, Client, Info, Interval, Kline, Symbol, Ticker};
+
+use super::indicators::rsi::rsi;
+
+pub const RSI_PERIOD: usize = 14;
+/// Klines the volume of the last kline is compared with.
+pub const SPIKE_LOOKBACK: usize = 20;
+/// Klines loaded per symbol, enough for the smoothed RSI to settle.
+const KLINES: usize = 100;
+/// Kline downloads running at once, so a scan stays within request limits.
+const CONCURRENCY: usize = 4;
+
+/// Listed symbols with their 24h tickers the scan starts from.
+pub type Market = (Result<Info, ClientError>, Result<Vec<Ticker>, ClientError>);
+
+/// Conditions a symbol must meet to be listed by the screener.
+/// Disabled conditions are none.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conditions {
+    /// Quote asset of scanned symbols, all symbols if empty.
+    pub quote: String,
+    /// Minimal 24h price change in percent.
+    pub min_change: Option<f64>,
+    /// Maximal RSI of klines of the interval.
+    pub max_rsi: Option<f64>,
+    /// Minimal ratio of the last kline volume to the average of the previous ones.
+    pub volume_spike: Option<f64>,
+    /// Interval of klines RSI and volume spikes are checked on.
+    pub interval: Interval,
+}
+
+impl Default for Conditions {
+    fn default() -> Self {
+        Self {
+            quote: "USDT".to_string(),
+            min_change: Some(5.0),
+            max_rsi: None,
+            volume_spike: None,
+            interval: Interval::FourHours,
+        }
+    }
+}
+
+/// Symbol meeting all conditions with the values they were checked on.
+#[derive(Debug, Clone)]
+pub struct Match {
+    pub symbol: Symbol,
+    pub change: f64,
+    pub rsi: Option<f64>,
+    pub spike: Option<f64>,
+}
+
+impl Conditions {
+    pub fn needs_klines(&self) -> bool {
+        self.max_rsi.is_some() || self.volume_spike.is_some()
+    }
+
+    /// Active symbols of the quote asset passing ticker conditions.
+    pub fn candidates(&self, symbols: &[Symbol], tickers: &[Ticker]) -> Vec<(Symbol, f64)> {
+        symbols
+            .iter()
+            .filter(|s| s.active())
+            .filter(|s| self.quote.is_empty() || s.quote_asset == self.quote.to_uppercase())
+            .filter_map(|s| {
+                let ticker = tickers.iter().find(|t| t.symbol == s.symbol)?;
+                let passes = self
+                    .min_change
+                    .is_none_or(|min| ticker.price_change_percent >= min);
+                passes.then(|| (s.clone(), ticker.price_change_percent))
+            })
+            .collect()
+    }
+
+    /// RSI and volume spike of the klines if they pass kline conditions.
+    pub fn check(&self, klines: &[Kline]) -> Option<(Option<f64>, Option<f64>)> {
+        let rsi = match self.max_rsi {
+            Some(max) => {
+                let closes: Vec<f64> = klines.iter().map(|k| k.close as f64).collect();
+                let last = rsi(&closes, RSI_PERIOD).last().copied().flatten()?;
+                (last <= max).then_some(Some(last))?
+            }
+            None => None,
+        };
+        let spike = match self.volume_spike {
+            Some(min) => {
+                let ratio = volume_spike(klines)?;
+                (ratio >= min).then_some(Some(ratio))?
+            }
+            None => None,
+        };
+
+        Some((rsi, spike))
+    }
+}
+
+/// Ratio of the last kline volume to the average volume of the previous klines.
+fn volume_spike(klines: &[Kline]) -> Option<f64> {
+    let (last, previous) = klines.split_last()?;
+    let previous = &previous[previous.len().saturating_sub(SPIKE_LOOKBACK)..];
+    if previous.is_empty() {
+        return None;
+    }
+
+    let avg = previous.iter().map(|k| k.volume as f64).sum::<f64>() / previous.len() as f64;
+    (avg > 0.0).then(|| last.volume as f64 / avg)
+}
+
+type Download = (Symbol, f64, Promise<Result<Vec<Kline>, ClientError>>);
+
+/// Scan of candidates downloading klines of a few symbols at a time.
+pub struct Scan {
+    conditions: Conditions,
+    queue: Vec<(Symbol, f64)>,
+    running: Vec<Download>,
+    pub matches: Vec<Match>,
+    pub total: usize,
+    pub failed: usize,
+}
+
+impl Scan {
+    /// Starts the scan of the loaded market, fails if symbols or tickers failed to load.
+    pub fn from_market(conditions: Conditions, market: Market) -> Result<Self, ClientError> {
+        let (info, tickers) = market;
+
+        Ok(Self::new(conditions, &info?.symbols, &tickers?))
+    }
+
+    /// Starts the scan of candidates passing the ticker conditions.
+    /// Candidates are matched right away if no klines are needed.
+    pub fn new(conditions: Conditions, symbols: &[Symbol], tickers: &[Ticker]) -> Self {
+        let candidates = conditions.candidates(symbols, tickers);
+        info!(
+            "Screening {} of {} symbols: {conditions:?}.",
+            candidates.len(),
+            symbols.len()
+        );
+
+        let mut scan = Self {
+            total: candidates.len(),
+            conditions,
+            queue: vec![],
+            running: vec![],
+            matches: vec![],
+            failed: 0,
+        };
+        match scan.conditions.needs_klines() {
+            true => {
+                scan.queue = candidates;
+                // scanned from the front of the list
+                scan.queue.reverse();
+            }
+            false => {
+                scan.matches = candidates
+                    .into_iter()
+                    .map(|(symbol, change)| Match {
+                        symbol,
+                        change,
+                        rsi: None,
+                        spike: None,
+                    })
+                    .collect()
+            }
+        }
+
+        scan
+    }
+
+    pub fn done(&self) -> usize {
+        self.total - self.queue.len() - self.running.len()
+    }
+
+    pub fn finished(&self) -> bool {
+        self.queue.is_empty() && self.running.is_empty()
+    }
+
+    /// Checks arrived klines and starts downloads of the next candidates.
+    pub fn poll(&mut self) {
+        let (ready, running): (Vec<Download>, Vec<Download>) = self
+            .running
+            .drain(..)
+            .partition(|(_, _, p)| p.ready().is_some());
+        self.running = running;
+
+        ready
+            .into_iter()
+            .for_each(|(symbol, change, promise)| match promise.block_and_take() {
+                Ok(klines) => {
+                    if let Some((rsi, spike)) = self.conditions.check(&klines) {
+                        self.matches.push(Match {
+                            symbol,
+                            change,
+                            rsi,
+                            spike,
+                        });
+                    }
+                }
+                Err(err) => {
+                    error!(
+                        "Failed to load klines of {} for screening: {err}.",
+                        symbol.symbol
+                    );
+                    self.failed += 1;
+                }
+            });
+
+        while self.running.len() < CONCURRENCY {
+            let (symbol, change) = match self.queue.pop() {
+                Some(candidate) => candidate,
+                None => break,
+            };
+            let interval = self.conditions.interval;
+            let end = Utc::now().timestamp_millis();
+            let start = end - KLINES as i64 * interval.millis();
+            let name = symbol.symbol.clone();
+            let promise = Promise::spawn_async(async move {
+                Client::kline_range(name, interval, start, end, KLINES).await
+            });
+            self.running.push((symbol, change, promise));
+        }
+    }
+}
+
+#[cfg(test)]
+mod screener_tests {
+    use super::*;
+
+    fn symbol(name: &str, quote: &str, status: &str) -> Symbol {
+        let mut symbol = Symbol::default();
+        symbol.symbol = name.to_string();
+        symbol.quote_asset = quote.to_string();
+        symbol.status = status.to_string();
+        symbol
+    }
+
+    fn ticker(name: &str, change: f64) -> Ticker {
+        Ticker {
+            symbol: name.to_string(),
+            price_change_percent: change,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_candidates() {
+        let symbols = [
+            symbol("BTCUSDT", "USDT", "TRADING"),
+            symbol("ETHUSDT", "USDT", "TRADING"),
+            symbol("ETHBTC", "BTC", "TRADING"),
+            symbol("OLDUSDT", "USDT", "BREAK"),
+            symbol("NEWUSDT", "USDT", "TRADING"),
+        ];
+        let tickers = [
+            ticker("BTCUSDT", 6.0),
+            ticker("ETHUSDT", 2.0),
+            ticker("ETHBTC", 8.0),
+            ticker("OLDUSDT", 9.0),
+        ];
+
+        let conditions = Conditions::default();
+        let names = |c: &Conditions| -> Vec<String> {
+            c.candidates(&symbols, &tickers)
+                .into_iter()
+                .map(|(s, _)| s.symbol)
+                .collect()
+        };
+        assert_eq!(names(&conditions), vec!["BTCUSDT"]);
+
+        let all = Conditions {
+            quote: String::new(),
+            min_change: None,
+            ..Default::default()
+        };
+        assert_eq!(names(&all), vec!["BTCUSDT", "ETHUSDT", "ETHBTC"]);
+    }
+
+    #[test]
+    fn test_check() {
+        let klines: Vec<Kline> = (0..30)
+            .map(|i| Kline {
+                close: 100.0 - i as f32,
+                volume: if i == 29 { 30.0 } else { 10.0 },
+                ..Default::default()
+            })
+            .collect();
+
+        let conditions = Conditions {
+            max_rsi: Some(30.0),
+            volume_spike: Some(2.0),
+            ..Default::default()
+        };
+        assert_eq!(conditions.check(&klines), Some((Some(0.0), Some(3.0))));
+
+        let spiky = Conditions {
+            volume_spike: Some(4.0),
+            ..Default::default()
+        };
+        assert_eq!(spiky.check(&klines), None);
+
+        let oversold = Conditions {
+            max_rsi: Some(30.0),
+            ..Default::default()
+        };
+        assert_eq!(oversold.check(&klines[..10]), None, "not enough klines");
+        assert_eq!(Conditions::default().check(&[]), Some((None, None)));
+    }
+
+    #[test]
+    fn test_scan_without_klines() {
+        let symbols = [symbol("BTCUSDT", "USDT", "TRADING")];
+        let scan = Scan::new(Conditions::default(), &symbols, &[ticker("BTCUSDT", 7.0)]);
+
+        assert!(scan.finished());
+        assert_eq!(scan.done(), 1);
+        assert_eq!(scan.matches[0].change, 7.0);
+    }
+
+    #[test]
+    fn test_scan_from_failed_market() {
+        fn failed<T>() -> Result<T, ClientError> {
+            Err(ClientError::Api(-1003, "too many requests".to_string()))
+        }
+        let info = || {
+            Ok(Info {
+                symbols: vec![symbol("BTCUSDT", "USDT", "TRADING")],
+            })
+        };
+        let tickers = || Ok(vec![ticker("BTCUSDT", 7.0)]);
+
+        let err = Scan::from_market(Conditions::default(), (failed(), tickers())).err();
+        assert!(matches!(err, Some(ClientError::Api(-1003, _))));
+        let err = Scan::from_market(Conditions::default(), (info(), failed())).err();
+        assert!(matches!(err, Some(ClientError::Api(-1003, _))));
+
+        let scan = Scan::from_market(Conditions::default(), (info(), tickers())).unwrap();
+        assert_eq!(scan.matches.len(), 1);
+    }
+}
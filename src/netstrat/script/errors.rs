@@ -0,0 +1,22 @@
+use quick_error::quick_error;
+
+quick_error! {
+    #[derive(Debug)]
+    pub enum ScriptError {
+        Io(err: std::io::Error) {
+            from()
+            display("{}", err)
+        }
+        Parse(err: rhai::ParseError) {
+            from()
+            display("{}", err)
+        }
+        Eval(err: Box<rhai::EvalAltResult>) {
+            from()
+            display("{}", err)
+        }
+        Output(msg: String) {
+            display("invalid script output: {}", msg)
+        }
+    }
+}
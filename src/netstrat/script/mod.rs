@@ -0,0 +1,359 @@
+use std::{fs, path::Path};
+
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, Scope, AST};
+use tracing::{error, info};
+
+use crate::{
+    netstrat::indicators::{ma::ema, rsi::rsi},
+    sources::binance::Kline,
+};
+
+use self::errors::ScriptError;
+
+pub mod errors;
+
+pub const SCRIPTS_DIR: &str = "scripts";
+pub const STRATEGIES_DIR: &str = "scripts/strategies";
+const EXT: &str = "rhai";
+const MAX_OPERATIONS: u64 = 10_000_000;
+
+/// Series computed by a script.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Output {
+    /// Draw series over candles instead of a separate pane.
+    pub overlay: bool,
+    pub series: Vec<(String, Vec<Option<f64>>)>,
+}
+
+/// Entry and exit signals computed by a strategy script.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Signals {
+    pub entry: Vec<bool>,
+    pub exit: Vec<bool>,
+}
+
+/// Script is a user defined indicator or strategy written in Rhai.
+///
+/// Scripts get `time`, `open`, `high`, `low`, `close` and `volume` arrays and must
+/// evaluate to a map of series names to arrays aligned with the klines. Empty values
+/// are `()`. Optional `overlay` key set to true draws series over candles.
+///
+/// Strategy scripts evaluate to a map with `entry` and `exit` arrays of bools instead.
+///
+/// Scripts can call `ema(values, period)`, `rsi(values, period)`, `crosses_above(a, b)`
+/// and `crosses_below(a, b)`. Crosses take arrays or numbers and return arrays of bools.
+#[derive(Clone)]
+pub struct Script {
+    pub name: String,
+    ast: AST,
+}
+
+impl Script {
+    pub fn compile(name: &str, src: &str) -> Result<Self, ScriptError> {
+        Ok(Self {
+            name: name.to_string(),
+            ast: Script::engine().compile(src)?,
+        })
+    }
+
+    fn eval(&self, klines: &[Kline]) -> Result<Map, ScriptError> {
+        let column = |f: fn(&Kline) -> f64| -> Array {
+            klines.iter().map(|k| Dynamic::from_float(f(k))).collect()
+        };
+
+        let mut scope = Scope::new();
+        scope.push("time", column(|k| k.t_open as f64));
+        scope.push("open", column(|k| k.open as f64));
+        scope.push("high", column(|k| k.high as f64));
+        scope.push("low", column(|k| k.low as f64));
+        scope.push("close", column(|k| k.close as f64));
+        scope.push("volume", column(|k| k.volume as f64));
+
+        Ok(Script::engine().eval_ast_with_scope(&mut scope, &self.ast)?)
+    }
+
+    pub fn run(&self, klines: &[Kline]) -> Result<Output, ScriptError> {
+        let mut output = Output::default();
+        for (name, val) in self.eval(klines)? {
+            if name == "overlay" {
+                output.overlay = val
+                    .as_bool()
+                    .map_err(|t| ScriptError::Output(format!("overlay must be bool, got {t}")))?;
+                continue;
+            }
+
+            let arr = val.into_array().map_err(|t| {
+                ScriptError::Output(format!("series {name} must be array, got {t}"))
+            })?;
+            let vals = values(arr)
+                .map_err(|t| ScriptError::Output(format!("series {name} has {t} value")))?;
+            output.series.push((name.to_string(), vals));
+        }
+
+        Ok(output)
+    }
+
+    /// Runs a strategy script. Signal arrays shorter than the klines are padded with false.
+    pub fn signals(&self, klines: &[Kline]) -> Result<Signals, ScriptError> {
+        let mut res = self.eval(klines)?;
+        let mut signal = |name: &str| -> Result<Vec<bool>, ScriptError> {
+            let arr = res
+                .remove(name)
+                .ok_or_else(|| ScriptError::Output(format!("missing {name} signals")))?
+                .into_array()
+                .map_err(|t| ScriptError::Output(format!("{name} must be array, got {t}")))?;
+            let mut vals = arr
+                .into_iter()
+                .map(|v| match v.is_unit() {
+                    true => Ok(false),
+                    false => v.as_bool(),
+                })
+                .collect::<Result<Vec<bool>, &str>>()
+                .map_err(|t| ScriptError::Output(format!("{name} has {t} value")))?;
+            vals.resize(klines.len(), false);
+            Ok(vals)
+        };
+
+        Ok(Signals {
+            entry: signal("entry")?,
+            exit: signal("exit")?,
+        })
+    }
+
+    /// Compiles all scripts in the directory. Scripts failing to compile are logged and skipped.
+    pub fn load_dir(dir: &Path) -> Vec<Script> {
+        let res: Vec<Script> = Script::read_dir(dir)
+            .into_iter()
+            .filter_map(|(name, src)| match Script::compile(&name, &src) {
+                Ok(script) => Some(script),
+                Err(err) => {
+                    error!("Failed to compile script {name} in {dir:?}: {err}.");
+                    None
+                }
+            })
+            .collect();
+
+        info!("Loaded {} scripts from {dir:?}.", res.len());
+
+        res
+    }
+
+    /// Reads names and sources of all scripts in the directory sorted by name.
+    /// Scripts failing to read are logged and skipped.
+    pub fn read_dir(dir: &Path) -> Vec<(String, String)> {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(_) => return vec![],
+        };
+
+        let mut res: Vec<(String, String)> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == EXT))
+            .filter_map(|p| {
+                let name = p.file_stem()?.to_str()?.to_string();
+                match fs::read_to_string(&p) {
+                    Ok(src) => Some((name, src)),
+                    Err(err) => {
+                        error!("Failed to read script {p:?}: {err}.");
+                        None
+                    }
+                }
+            })
+            .collect();
+        res.sort_by(|a, b| a.0.cmp(&b.0));
+
+        res
+    }
+
+    fn engine() -> Engine {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine
+            .register_fn("ema", |vals: Array, period: i64| -> Fallible<Array> {
+                Ok(array(ema(&arg(vals)?, period.max(0) as usize)))
+            })
+            .register_fn("rsi", |vals: Array, period: i64| -> Fallible<Array> {
+                let closes: Vec<f64> = arg(vals)?.into_iter().flatten().collect();
+                Ok(array(rsi(&closes, period.max(0) as usize)))
+            });
+        register_crosses(&mut engine, "crosses_above", |prev, cur| {
+            prev <= 0.0 && cur > 0.0
+        });
+        register_crosses(&mut engine, "crosses_below", |prev, cur| {
+            prev >= 0.0 && cur < 0.0
+        });
+        engine
+    }
+}
+
+type Fallible<T> = Result<T, Box<EvalAltResult>>;
+
+/// Converts arguments of registered functions to numbers.
+fn arg(arr: Array) -> Fallible<Vec<Option<f64>>> {
+    values(arr).map_err(|t| format!("expected array of numbers, got {t} value").into())
+}
+
+/// Converts script values to numbers, `()` to None. Fails with the type of other values.
+fn values(arr: Array) -> Result<Vec<Option<f64>>, &'static str> {
+    arr.into_iter()
+        .map(|v| match v.is_unit() {
+            true => Ok(None),
+            false => v
+                .as_float()
+                .or_else(|_| v.as_int().map(|i| i as f64))
+                .map(Some),
+        })
+        .collect()
+}
+
+fn array(vals: Vec<Option<f64>>) -> Array {
+    vals.into_iter()
+        .map(|v| v.map_or(Dynamic::UNIT, Dynamic::from_float))
+        .collect()
+}
+
+/// Registers a cross of `a` over `b` for all combinations of arrays and numbers.
+/// The cross is checked on the spread `a - b` at the previous and the current value.
+fn register_crosses(engine: &mut Engine, name: &str, cross: fn(f64, f64) -> bool) {
+    let crosses = move |a: Vec<Option<f64>>, b: Vec<Option<f64>>| -> Array {
+        let spread: Vec<Option<f64>> = a.iter().zip(&b).map(|(a, b)| Some((*a)? - (*b)?)).collect();
+        (0..spread.len())
+            .map(|i| {
+                let prev = i.checked_sub(1).and_then(|prev| spread[prev]);
+                Dynamic::from_bool(matches!((prev, spread[i]), (Some(p), Some(c)) if cross(p, c)))
+            })
+            .collect()
+    };
+    let number = |v: f64, len: usize| vec![Some(v); len];
+
+    engine
+        .register_fn(name, move |a: Array, b: Array| -> Fallible<Array> {
+            Ok(crosses(arg(a)?, arg(b)?))
+        })
+        .register_fn(name, move |a: Array, b: f64| -> Fallible<Array> {
+            let a = arg(a)?;
+            let len = a.len();
+            Ok(crosses(a, number(b, len)))
+        })
+        .register_fn(name, move |a: Array, b: i64| -> Fallible<Array> {
+            let a = arg(a)?;
+            let len = a.len();
+            Ok(crosses(a, number(b as f64, len)))
+        })
+        .register_fn(name, move |a: f64, b: Array| -> Fallible<Array> {
+            let b = arg(b)?;
+            let len = b.len();
+            Ok(crosses(number(a, len), b))
+        });
+}
+
+#[cfg(test)]
+mod script_tests {
+    use super::*;
+
+    fn kline(close: f32) -> Kline {
+        Kline {
+            close,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        let script = Script::compile(
+            "change",
+            r#"
+                let res = [()];
+                for i in 1..close.len() {
+                    res.push(close[i] - close[i - 1]);
+                }
+                #{ overlay: false, change: res, one: close.map(|c| 1) }
+            "#,
+        )
+        .unwrap();
+
+        let out = script.run(&[kline(1.0), kline(3.0), kline(2.0)]).unwrap();
+        assert!(!out.overlay);
+        assert_eq!(
+            out.series,
+            vec![
+                ("change".to_string(), vec![None, Some(2.0), Some(-1.0)]),
+                ("one".to_string(), vec![Some(1.0), Some(1.0), Some(1.0)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_output() {
+        let script = Script::compile("bad", r#"#{ line: "text" }"#).unwrap();
+        assert!(matches!(
+            script.run(&[kline(1.0)]),
+            Err(ScriptError::Output(_))
+        ));
+
+        let script = Script::compile("bad", "loop {}").unwrap();
+        assert!(matches!(script.run(&[]), Err(ScriptError::Eval(_))));
+        assert!(Script::compile("bad", "let = ;").is_err());
+    }
+
+    #[test]
+    fn test_signals() {
+        let script = Script::compile(
+            "cross",
+            r#"
+                #{
+                    entry: crosses_above(close, 2),
+                    exit: crosses_below(close, ema(close, 2)),
+                }
+            "#,
+        )
+        .unwrap();
+
+        let klines: Vec<Kline> = [1.0, 3.0, 4.0, 1.0].into_iter().map(kline).collect();
+        let signals = script.signals(&klines).unwrap();
+        assert_eq!(signals.entry, vec![false, true, false, false]);
+        assert_eq!(signals.exit, vec![false, false, false, true]);
+
+        let script = Script::compile("short", "#{ entry: [true], exit: [] }").unwrap();
+        let signals = script.signals(&klines[..2]).unwrap();
+        assert_eq!(signals.entry, vec![true, false]);
+        assert_eq!(signals.exit, vec![false, false]);
+    }
+
+    #[test]
+    fn test_invalid_signals() {
+        let script = Script::compile("bad", "#{ entry: [1], exit: [] }").unwrap();
+        assert!(matches!(script.signals(&[]), Err(ScriptError::Output(_))));
+
+        let script = Script::compile("bad", "#{ entry: [] }").unwrap();
+        assert!(matches!(script.signals(&[]), Err(ScriptError::Output(_))));
+
+        let script = Script::compile("bad", r#"#{ entry: rsi(["a"], 2), exit: [] }"#).unwrap();
+        assert!(matches!(script.signals(&[]), Err(ScriptError::Eval(_))));
+    }
+
+    #[test]
+    fn test_example_strategy() {
+        let (name, src) = Script::read_dir(Path::new(STRATEGIES_DIR))
+            .into_iter()
+            .find(|(name, _)| name == "rsi_reversion")
+            .unwrap();
+        let script = Script::compile(&name, &src).unwrap();
+
+        let klines: Vec<Kline> = (0..40).map(|i| kline(100.0 - i as f32)).collect();
+        let signals = script.signals(&klines).unwrap();
+        assert_eq!(signals.entry.len(), 40);
+        assert!(!signals.entry.iter().any(|e| *e));
+    }
+
+    #[test]
+    fn test_example_script() {
+        let scripts = Script::load_dir(Path::new(SCRIPTS_DIR));
+        let momentum = scripts.iter().find(|s| s.name == "momentum").unwrap();
+
+        let klines: Vec<Kline> = (0..12).map(|i| kline(i as f32)).collect();
+        let out = momentum.run(&klines).unwrap();
+        assert_eq!(out.series[0].1[9], None);
+        assert_eq!(out.series[0].1[11], Some(10.0));
+    }
+}
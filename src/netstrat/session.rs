@@ -0,0 +1,177 @@
+use super::bounds::Bounds;
+
+const MINUTE_MILLIS: i64 = 60 * 1000;
+const DAY_MINUTES: u32 = 24 * 60;
+const DAY_MILLIS: i64 = DAY_MINUTES as i64 * MINUTE_MILLIS;
+
+/// Trading session repeated on chosen week days.
+///
+/// Open and close are minutes from midnight UTC. Close not greater than open means the
+/// session ends on the next day, equal open and close define a 24h session.
+/// Week days start from Monday and mark days sessions open on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    pub name: String,
+    pub open: u32,
+    pub close: u32,
+    pub week_days: [bool; 7],
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Session::crypto()
+    }
+}
+
+impl Session {
+    /// Continuous trading with sessions starting at midnight UTC.
+    pub fn crypto() -> Self {
+        Self {
+            name: "24/7".to_string(),
+            open: 0,
+            close: 0,
+            week_days: [true; 7],
+        }
+    }
+
+    /// CME Globex hours: 17:00 - 16:00 Chicago time opening Sunday to Thursday.
+    pub fn cme() -> Self {
+        Self {
+            name: "CME".to_string(),
+            open: 22 * 60,
+            close: 21 * 60,
+            week_days: [true, true, true, true, false, false, true],
+        }
+    }
+
+    /// US equities regular hours: 9:30 - 16:00 New York time on week days.
+    pub fn us_equities() -> Self {
+        Self {
+            name: "US equities".to_string(),
+            open: 13 * 60 + 30,
+            close: 20 * 60,
+            week_days: [true, true, true, true, true, false, false],
+        }
+    }
+
+    /// Whole Saturdays and Sundays UTC.
+    pub fn weekends() -> Self {
+        Self {
+            name: "weekends".to_string(),
+            open: 0,
+            close: 0,
+            week_days: [false, false, false, false, false, true, true],
+        }
+    }
+
+    pub fn presets() -> Vec<Session> {
+        vec![Session::crypto(), Session::cme(), Session::us_equities()]
+    }
+
+    /// Session length in milliseconds.
+    pub fn len(&self) -> i64 {
+        let minutes = match self.close > self.open {
+            true => self.close - self.open,
+            false => self.close + DAY_MINUTES - self.open,
+        };
+
+        minutes as i64 * MINUTE_MILLIS
+    }
+
+    /// Returns open time of the session containing the timestamp.
+    pub fn start(&self, ts: i64) -> Option<i64> {
+        let day = ts.div_euclid(DAY_MILLIS);
+        (day - 1..=day).rev().find_map(|d| {
+            // 1970-01-01 was a Thursday
+            let week_day = (d + 3).rem_euclid(7) as usize;
+            let start = d * DAY_MILLIS + self.open as i64 * MINUTE_MILLIS;
+            match self.week_days[week_day] && start <= ts && ts < start + self.len() {
+                true => Some(start),
+                false => None,
+            }
+        })
+    }
+
+    /// Returns open and close times of sessions overlapping the time range.
+    pub fn windows(&self, from: i64, to: i64) -> Vec<Bounds> {
+        (from.div_euclid(DAY_MILLIS) - 1..=to.div_euclid(DAY_MILLIS))
+            .filter(|d| self.week_days[(d + 3).rem_euclid(7) as usize])
+            .map(|d| {
+                let start = d * DAY_MILLIS + self.open as i64 * MINUTE_MILLIS;
+                Bounds(start, start + self.len())
+            })
+            .filter(|b| b.0 < to && b.1 > from)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod session_tests {
+    use super::*;
+
+    const HOUR: i64 = 60 * MINUTE_MILLIS;
+    // 1970-01-05 is Monday
+    const MONDAY: i64 = 4 * DAY_MILLIS;
+
+    #[test]
+    fn test_crypto() {
+        let s = Session::crypto();
+
+        assert_eq!(s.len(), DAY_MILLIS);
+        assert_eq!(s.start(MONDAY + 5 * HOUR), Some(MONDAY));
+        assert_eq!(s.start(MONDAY - 1), Some(MONDAY - DAY_MILLIS));
+    }
+
+    #[test]
+    fn test_overnight() {
+        let s = Session::cme();
+
+        assert_eq!(s.len(), 23 * HOUR);
+        // Monday session opened on Sunday evening
+        assert_eq!(s.start(MONDAY + 5 * HOUR), Some(MONDAY - 2 * HOUR));
+        // maintenance break
+        assert_eq!(s.start(MONDAY + 21 * HOUR + 30 * MINUTE_MILLIS), None);
+        // no session opens on Friday evening
+        assert_eq!(s.start(MONDAY + 4 * DAY_MILLIS + 23 * HOUR), None);
+    }
+
+    #[test]
+    fn test_windows() {
+        let s = Session::us_equities();
+        let open = 13 * HOUR + 30 * MINUTE_MILLIS;
+
+        // Friday and next Monday sessions
+        assert_eq!(
+            s.windows(MONDAY + 4 * DAY_MILLIS, MONDAY + 7 * DAY_MILLIS + 14 * HOUR),
+            vec![
+                Bounds(
+                    MONDAY + 4 * DAY_MILLIS + open,
+                    MONDAY + 4 * DAY_MILLIS + 20 * HOUR
+                ),
+                Bounds(
+                    MONDAY + 7 * DAY_MILLIS + open,
+                    MONDAY + 7 * DAY_MILLIS + 20 * HOUR
+                ),
+            ]
+        );
+        assert_eq!(
+            Session::weekends().windows(MONDAY, MONDAY + 7 * DAY_MILLIS),
+            vec![
+                Bounds(MONDAY + 5 * DAY_MILLIS, MONDAY + 6 * DAY_MILLIS),
+                Bounds(MONDAY + 6 * DAY_MILLIS, MONDAY + 7 * DAY_MILLIS),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_week_days() {
+        let s = Session::us_equities();
+
+        assert_eq!(
+            s.start(MONDAY + 14 * HOUR),
+            Some(MONDAY + 13 * HOUR + 30 * MINUTE_MILLIS)
+        );
+        assert_eq!(s.start(MONDAY + 5 * DAY_MILLIS + 14 * HOUR), None);
+        assert_eq!(s.start(MONDAY + 21 * HOUR), None);
+    }
+}
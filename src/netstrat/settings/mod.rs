@@ -0,0 +1,181 @@
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use egui::{style::default_text_styles, FontId, TextStyle};
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use crate::netstrat::export::template::ExportTemplate;
+
+use self::errors::SettingsError;
+
+pub mod errors;
+
+const SETTINGS_FILE: &str = "settings.json";
+/// Size of the body text in default egui styles.
+pub const DEFAULT_FONT_SIZE: f32 = 14.0;
+
+/// App preferences saved in a json file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Settings {
+    #[serde(skip)]
+    path: PathBuf,
+    /// Checks the releases feed for a newer version on start.
+    #[serde(default = "enabled")]
+    pub check_updates: bool,
+    /// Switches the chart to an alternate source without asking when the primary one
+    /// keeps failing.
+    #[serde(default)]
+    pub auto_failover: bool,
+    /// Evaluates indicators and alerts on closed klines only, ignoring the forming one.
+    #[serde(default)]
+    pub confirmed_only: bool,
+    /// Zoom of the whole UI on top of the screen scale factor.
+    #[serde(default = "unscaled")]
+    pub ui_scale: f32,
+    /// Size of the body text, other text styles are scaled along.
+    #[serde(default = "default_font_size")]
+    pub font_size: f32,
+    /// Writes downloaded klines with their anomalies to the local archive.
+    #[serde(default = "enabled")]
+    pub archive_klines: bool,
+    /// Copies logs to rotating files in the log dir, applied on restart.
+    #[serde(default)]
+    pub log_to_file: bool,
+    /// Named sets of exported kline fields offered in the export options.
+    #[serde(default)]
+    pub export_templates: Vec<ExportTemplate>,
+    /// URL receiving a json post when a price alert triggers, none if empty.
+    #[serde(default)]
+    pub alert_webhook: String,
+    /// Token of the Telegram bot notifying about alerts and exports.
+    #[serde(default)]
+    pub telegram_token: String,
+    /// Chat the Telegram bot sends messages to.
+    #[serde(default)]
+    pub telegram_chat_id: String,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+fn unscaled() -> f32 {
+    1.0
+}
+
+fn default_font_size() -> f32 {
+    DEFAULT_FONT_SIZE
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new(PathBuf::from(SETTINGS_FILE))
+    }
+}
+
+impl Settings {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            check_updates: enabled(),
+            auto_failover: false,
+            confirmed_only: false,
+            ui_scale: unscaled(),
+            font_size: default_font_size(),
+            archive_klines: enabled(),
+            log_to_file: false,
+            export_templates: vec![],
+            alert_webhook: String::new(),
+            telegram_token: String::new(),
+            telegram_chat_id: String::new(),
+        }
+    }
+
+    /// Default egui text styles scaled to the font size.
+    pub fn text_styles(&self) -> BTreeMap<TextStyle, FontId> {
+        let factor = self.font_size / DEFAULT_FONT_SIZE;
+        default_text_styles()
+            .into_iter()
+            .map(|(style, font)| (style, FontId::new(font.size * factor, font.family)))
+            .collect()
+    }
+
+    /// Reads settings from the default file falling back to defaults on errors.
+    pub fn loaded() -> Self {
+        let mut res = Self::default();
+        if let Err(err) = res.load() {
+            error!("Failed to load settings: {err}.");
+        }
+
+        res
+    }
+
+    pub fn load(&mut self) -> Result<(), SettingsError> {
+        if !self.path.exists() {
+            return Ok(());
+        }
+
+        let path = self.path.clone();
+        *self = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        self.path = path;
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), SettingsError> {
+        fs::write(&self.path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod settings_tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load() {
+        let path =
+            std::env::temp_dir().join(format!("netstrat-settings-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut settings = Settings::new(path.clone());
+        settings.load().unwrap();
+        assert!(settings.check_updates);
+
+        settings.check_updates = false;
+        settings.save().unwrap();
+
+        let mut loaded = Settings::new(path.clone());
+        loaded.load().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, settings);
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let settings: Settings = serde_json::from_str("{}").unwrap();
+
+        assert!(settings.check_updates);
+        assert!(!settings.auto_failover);
+        assert!(!settings.log_to_file);
+        assert!(settings.export_templates.is_empty());
+        assert!(settings.alert_webhook.is_empty());
+        assert_eq!(settings.ui_scale, 1.0);
+        assert_eq!(settings.font_size, DEFAULT_FONT_SIZE);
+    }
+
+    #[test]
+    fn test_text_styles() {
+        let settings = Settings {
+            font_size: DEFAULT_FONT_SIZE * 1.5,
+            ..Default::default()
+        };
+
+        let styles = settings.text_styles();
+        assert_eq!(styles[&TextStyle::Body].size, DEFAULT_FONT_SIZE * 1.5);
+        assert_eq!(
+            styles[&TextStyle::Heading].size,
+            default_text_styles()[&TextStyle::Heading].size * 1.5
+        );
+    }
+}
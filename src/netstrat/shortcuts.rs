@@ -0,0 +1,127 @@
+use egui::{Key, Modifiers};
+
+use crate::sources::binance::Interval;
+
+/// Action triggered by a keyboard shortcut anywhere in the app.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    Help,
+    ToggleDebug,
+    SymbolSearch,
+    NextTheme,
+    SaveImage,
+    Interval(Interval),
+    NewTab,
+    CloseTab,
+    NextTab,
+    PrevTab,
+}
+
+impl Action {
+    pub fn description(&self) -> String {
+        match self {
+            Action::Help => "show keyboard shortcuts".to_string(),
+            Action::ToggleDebug => "toggle debug mode".to_string(),
+            Action::SymbolSearch => "search symbols".to_string(),
+            Action::NextTheme => "switch to the next theme".to_string(),
+            Action::SaveImage => "save chart image".to_string(),
+            Action::Interval(interval) => format!("switch chart to {}", interval.as_str()),
+            Action::NewTab => "open chart tab".to_string(),
+            Action::CloseTab => "close chart tab".to_string(),
+            Action::NextTab => "switch to the next chart tab".to_string(),
+            Action::PrevTab => "switch to the previous chart tab".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Shortcut {
+    pub modifiers: Modifiers,
+    pub key: Key,
+    pub action: Action,
+}
+
+impl Shortcut {
+    fn new(modifiers: Modifiers, key: Key, action: Action) -> Self {
+        Self {
+            modifiers,
+            key,
+            action,
+        }
+    }
+
+    /// Key combination as it is labeled on the keyboard, e.g. "Ctrl+K".
+    pub fn keys(&self) -> String {
+        let key = format!("{:?}", self.key);
+        [
+            (self.modifiers.command, "Ctrl"),
+            (self.modifiers.alt, "Alt"),
+            (self.modifiers.shift, "Shift"),
+        ]
+        .into_iter()
+        .filter(|(on, _)| *on)
+        .map(|(_, name)| name)
+        .chain([key.trim_start_matches("Num")])
+        .collect::<Vec<&str>>()
+        .join("+")
+    }
+}
+
+/// Shortcuts of the app. Keys without modifiers are left to focused widgets,
+/// e.g. to the replay controls.
+pub fn bindings() -> Vec<Shortcut> {
+    vec![
+        Shortcut::new(Modifiers::COMMAND, Key::H, Action::Help),
+        Shortcut::new(Modifiers::COMMAND, Key::D, Action::ToggleDebug),
+        Shortcut::new(Modifiers::COMMAND, Key::K, Action::SymbolSearch),
+        Shortcut::new(Modifiers::COMMAND, Key::T, Action::NextTheme),
+        Shortcut::new(Modifiers::COMMAND, Key::P, Action::SaveImage),
+        Shortcut::new(
+            Modifiers::ALT,
+            Key::Num1,
+            Action::Interval(Interval::Minute),
+        ),
+        Shortcut::new(Modifiers::ALT, Key::Num2, Action::Interval(Interval::Hour)),
+        Shortcut::new(Modifiers::ALT, Key::Num3, Action::Interval(Interval::Day)),
+        Shortcut::new(Modifiers::COMMAND, Key::N, Action::NewTab),
+        Shortcut::new(Modifiers::COMMAND, Key::W, Action::CloseTab),
+        Shortcut::new(Modifiers::COMMAND, Key::Tab, Action::NextTab),
+        Shortcut::new(
+            Modifiers::COMMAND | Modifiers::SHIFT,
+            Key::Tab,
+            Action::PrevTab,
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod shortcuts_tests {
+    use super::*;
+
+    #[test]
+    fn test_keys() {
+        let shortcuts = bindings();
+        let keys = |action| {
+            shortcuts
+                .iter()
+                .find(|s| s.action == action)
+                .map(|s| s.keys())
+                .unwrap()
+        };
+
+        assert_eq!(keys(Action::SymbolSearch), "Ctrl+K");
+        assert_eq!(keys(Action::Interval(Interval::Hour)), "Alt+2");
+        assert_eq!(keys(Action::PrevTab), "Ctrl+Shift+Tab");
+    }
+
+    #[test]
+    fn test_bindings_are_unique() {
+        let shortcuts = bindings();
+        shortcuts.iter().enumerate().for_each(|(i, a)| {
+            shortcuts[i + 1..].iter().for_each(|b| {
+                assert!(a.keys() != b.keys(), "{} is bound twice", a.keys());
+                assert!(a.action != b.action);
+            })
+        });
+    }
+}
@@ -0,0 +1,86 @@
+use crate::sources::binance::{Interval, Kline};
+
+/// Hours of price history shown in a sparkline.
+pub const SPARKLINE_HOURS: i64 = 24;
+
+/// Start of the sparkline span ending at now in unix milliseconds.
+pub fn span_start(now: i64) -> i64 {
+    now - SPARKLINE_HOURS * Interval::Hour.millis()
+}
+
+/// Closes of hourly klines opened within the span ending at now.
+/// Returns None if the klines do not reach the last closed hour, so they have to be downloaded.
+pub fn closes(klines: &[Kline], now: i64) -> Option<Vec<f32>> {
+    let last = klines.last()?;
+    if last.t_close < now - Interval::Hour.millis() {
+        return None;
+    }
+
+    let start = span_start(now);
+    Some(
+        klines
+            .iter()
+            .filter(|k| k.t_open >= start)
+            .map(|k| k.close)
+            .collect(),
+    )
+}
+
+/// Points of the sparkline in [0, 1] on both axes with the highest close at the top (y = 0).
+pub fn normalize(closes: &[f32]) -> Vec<(f32, f32)> {
+    let (min, max) = closes.iter().fold((f32::MAX, f32::MIN), |(min, max), c| {
+        (min.min(*c), max.max(*c))
+    });
+    let height = max - min;
+    let width = closes.len().saturating_sub(1).max(1) as f32;
+
+    closes
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let y = match height > 0.0 {
+                true => (max - c) / height,
+                false => 0.5,
+            };
+            (i as f32 / width, y)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod sparkline_tests {
+    use super::*;
+
+    fn kline(t_open: i64, close: f32) -> Kline {
+        Kline {
+            t_open,
+            t_close: t_open + Interval::Hour.millis() - 1,
+            close,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_closes() {
+        let hour = Interval::Hour.millis();
+        let now = 100 * hour + hour / 2;
+        let klines: Vec<Kline> = (70..100).map(|h| kline(h * hour, h as f32)).collect();
+
+        let res = closes(&klines, now).unwrap();
+        assert_eq!(res.len(), 23);
+        assert_eq!(res.first(), Some(&77.0));
+        assert_eq!(res.last(), Some(&99.0));
+
+        assert_eq!(closes(&klines[..25], now), None, "stale klines");
+        assert_eq!(closes(&[], now), None);
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize(&[1.0, 3.0, 2.0]),
+            vec![(0.0, 1.0), (0.5, 0.0), (1.0, 0.5)]
+        );
+        assert_eq!(normalize(&[2.0, 2.0]), vec![(0.0, 0.5), (1.0, 0.5)]);
+    }
+}
@@ -0,0 +1,139 @@
+use std::sync::RwLock;
+
+use chrono::Utc;
+
+static STATUS: RwLock<Status> = RwLock::new(Status::new());
+
+/// Whether the api answered the latest request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Connectivity {
+    /// Nothing was requested yet.
+    Unknown,
+    Online,
+    Offline,
+}
+
+/// How klines of the active chart are kept up to date.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Feed {
+    #[default]
+    Idle,
+    /// Pages of klines are being downloaded.
+    Loading,
+    /// The chart is pinned to the newest kline.
+    Following,
+}
+
+impl Feed {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Feed::Idle => "idle",
+            Feed::Loading => "loading",
+            Feed::Following => "following",
+        }
+    }
+}
+
+/// Connection and data freshness shown in the status bar.
+/// Requests report their outcome and the active chart reports its klines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Status {
+    /// Unix milliseconds of the last successful request.
+    pub last_success: Option<i64>,
+    /// Error of the latest request if it failed.
+    pub last_error: Option<String>,
+    /// Klines loaded in the active chart.
+    pub candles: usize,
+    pub feed: Feed,
+}
+
+impl Default for Status {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Status {
+    const fn new() -> Self {
+        Self {
+            last_success: None,
+            last_error: None,
+            candles: 0,
+            feed: Feed::Idle,
+        }
+    }
+
+    pub fn current() -> Self {
+        STATUS.read().unwrap().clone()
+    }
+
+    pub fn report_success() {
+        let mut status = STATUS.write().unwrap();
+        status.last_success = Some(Utc::now().timestamp_millis());
+        status.last_error = None;
+    }
+
+    pub fn report_error(err: &str) {
+        STATUS.write().unwrap().last_error = Some(err.to_string());
+    }
+
+    pub fn report_chart(candles: usize, feed: Feed) {
+        let mut status = STATUS.write().unwrap();
+        status.candles = candles;
+        status.feed = feed;
+    }
+
+    pub fn connectivity(&self) -> Connectivity {
+        match (&self.last_error, self.last_success) {
+            (Some(_), _) => Connectivity::Offline,
+            (None, Some(_)) => Connectivity::Online,
+            (None, None) => Connectivity::Unknown,
+        }
+    }
+
+    /// Time passed since the last successful request, e.g. "5s ago" or "2h ago".
+    pub fn freshness(&self, now: i64) -> String {
+        let last = match self.last_success {
+            Some(last) => last,
+            None => return "never".to_string(),
+        };
+
+        let secs = (now - last).max(0) / 1000;
+        match secs {
+            s if s < 60 => format!("{s}s ago"),
+            s if s < 3600 => format!("{}m ago", s / 60),
+            s if s < 86400 => format!("{}h ago", s / 3600),
+            s => format!("{}d ago", s / 86400),
+        }
+    }
+}
+
+#[cfg(test)]
+mod status_tests {
+    use super::*;
+
+    #[test]
+    fn test_connectivity() {
+        let mut status = Status::default();
+        assert_eq!(status.connectivity(), Connectivity::Unknown);
+
+        status.last_success = Some(0);
+        assert_eq!(status.connectivity(), Connectivity::Online);
+
+        status.last_error = Some("timeout".to_string());
+        assert_eq!(status.connectivity(), Connectivity::Offline);
+    }
+
+    #[test]
+    fn test_freshness() {
+        let mut status = Status::default();
+        assert_eq!(status.freshness(0), "never");
+
+        status.last_success = Some(1_000);
+        assert_eq!(status.freshness(6_000), "5s ago");
+        assert_eq!(status.freshness(181_000), "3m ago");
+        assert_eq!(status.freshness(7_201_000), "2h ago");
+        assert_eq!(status.freshness(172_801_000), "2d ago");
+        assert_eq!(status.freshness(0), "0s ago");
+    }
+}
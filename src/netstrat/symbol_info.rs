@@ -0,0 +1,121 @@
+use crate::sources::binance::{Symbol, Ticker};
+
+/// Number of decimals of a price step, e.g. 2 for 0.01.
+pub fn decimals(step: f64) -> usize {
+    let s = step.to_string();
+    match s.split_once('.') {
+        Some((_, fraction)) => fraction.trim_end_matches('0').len(),
+        None => 0,
+    }
+}
+
+/// Labeled values of the symbol info pane.
+/// 24h statistics are added when the ticker of the symbol is loaded.
+pub fn rows(symbol: &Symbol, ticker: Option<&Ticker>) -> Vec<(&'static str, String)> {
+    let precision = symbol.tick_size().map(decimals);
+    let price = |v: f64| match precision {
+        Some(p) => format!("{v:.p$}"),
+        None => v.to_string(),
+    };
+    let optional = |v: Option<String>| v.unwrap_or_else(|| "-".to_string());
+
+    let mut res = vec![
+        ("base asset", symbol.base_asset.clone()),
+        ("quote asset", symbol.quote_asset.clone()),
+        ("status", symbol.status.to_lowercase()),
+        (
+            "price precision",
+            optional(precision.map(|p| p.to_string())),
+        ),
+        (
+            "tick size",
+            optional(symbol.tick_size().map(|t| t.to_string())),
+        ),
+        (
+            "min notional",
+            optional(
+                symbol
+                    .min_notional()
+                    .map(|n| format!("{n} {}", symbol.quote_asset)),
+            ),
+        ),
+    ];
+
+    if let Some(t) = ticker {
+        res.extend([
+            ("last price", price(t.last_price)),
+            ("24h change", format!("{:+.2}%", t.price_change_percent)),
+            ("24h high", price(t.high_price)),
+            ("24h low", price(t.low_price)),
+            (
+                "24h volume",
+                format!("{:.2} {}", t.volume, symbol.base_asset),
+            ),
+            (
+                "24h quote volume",
+                format!("{:.2} {}", t.quote_volume, symbol.quote_asset),
+            ),
+            ("24h trades", t.count.to_string()),
+        ]);
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod symbol_info_tests {
+    use super::*;
+
+    #[test]
+    fn test_decimals() {
+        assert_eq!(decimals(0.01), 2);
+        assert_eq!(decimals(0.00000100), 6);
+        assert_eq!(decimals(1.0), 0);
+        assert_eq!(decimals(10.0), 0);
+    }
+
+    #[test]
+    fn test_rows() {
+        let symbol: Symbol = serde_json::from_str(
+            r#"{
+                "symbol": "BTCUSDT",
+                "status": "TRADING",
+                "baseAsset": "BTC",
+                "baseAssetPrecision": 8,
+                "quoteAsset": "USDT",
+                "quotePrecision": 8,
+                "quoteAssetPrecision": 8,
+                "baseCommissionPrecision": 8,
+                "quoteCommissionPrecision": 8,
+                "icebergAllowed": true,
+                "ocoAllowed": true,
+                "quoteOrderQtyMarketAllowed": true,
+                "allowTrailingStop": true,
+                "isSpotTradingAllowed": true,
+                "isMarginTradingAllowed": true,
+                "filters": [
+                    {"filterType": "PRICE_FILTER", "minPrice": "0.01", "tickSize": "0.01000000"},
+                    {"filterType": "LOT_SIZE", "stepSize": "0.00001000"},
+                    {"filterType": "NOTIONAL", "minNotional": "5.00000000"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let ticker = Ticker {
+            symbol: "BTCUSDT".to_string(),
+            last_price: 20000.5,
+            price_change_percent: -1.5,
+            ..Default::default()
+        };
+
+        let rows = rows(&symbol, Some(&ticker));
+        let get = |label: &str| rows.iter().find(|(l, _)| *l == label).unwrap().1.clone();
+
+        assert_eq!(get("price precision"), "2");
+        assert_eq!(get("min notional"), "5 USDT");
+        assert_eq!(get("status"), "trading");
+        assert_eq!(get("last price"), "20000.50");
+        assert_eq!(get("24h change"), "-1.50%");
+        assert_eq!(self::rows(&symbol, None).len(), 6);
+    }
+}
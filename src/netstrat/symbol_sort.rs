@@ -0,0 +1,139 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::sources::binance::{Symbol, Ticker};
+
+/// Order of the symbol list.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SortMode {
+    #[default]
+    Alphabetical,
+    /// Highest 24h volume in the quote asset first.
+    QuoteVolume,
+    /// Highest 24h price change in percent first.
+    Change,
+}
+
+impl SortMode {
+    pub fn all() -> [SortMode; 3] {
+        [
+            SortMode::Alphabetical,
+            SortMode::QuoteVolume,
+            SortMode::Change,
+        ]
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            SortMode::Alphabetical => "A-Z",
+            SortMode::QuoteVolume => "24h volume",
+            SortMode::Change => "24h change",
+        }
+    }
+
+    fn value(&self, ticker: &Ticker) -> f64 {
+        match self {
+            SortMode::Alphabetical => 0.0,
+            SortMode::QuoteVolume => ticker.quote_volume,
+            SortMode::Change => ticker.price_change_percent,
+        }
+    }
+
+    /// Value of the ticker the list is sorted by, e.g. "+1.25%" or "3.4M".
+    pub fn label(&self, ticker: &Ticker) -> Option<String> {
+        match self {
+            SortMode::Alphabetical => None,
+            SortMode::QuoteVolume => Some(compact(ticker.quote_volume)),
+            SortMode::Change => Some(format!("{:+.2}%", ticker.price_change_percent)),
+        }
+    }
+
+    /// Sorts symbols by the mode. Symbols without ticker data go last.
+    pub fn sort(&self, symbols: &mut [&Symbol], tickers: &HashMap<String, Ticker>) {
+        if *self == SortMode::Alphabetical {
+            symbols.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+            return;
+        }
+
+        let value = |s: &Symbol| tickers.get(&s.symbol).map(|t| self.value(t));
+        symbols.sort_by(|a, b| match (value(a), value(b)) {
+            (Some(a), Some(b)) => b.total_cmp(&a),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        });
+    }
+}
+
+fn compact(v: f64) -> String {
+    [(1e9, "B"), (1e6, "M"), (1e3, "K")]
+        .into_iter()
+        .find(|(unit, _)| v.abs() >= *unit)
+        .map(|(unit, suffix)| format!("{:.1}{suffix}", v / unit))
+        .unwrap_or_else(|| format!("{v:.0}"))
+}
+
+#[cfg(test)]
+mod symbol_sort_tests {
+    use super::*;
+
+    fn symbol(name: &str) -> Symbol {
+        let mut res = Symbol::default();
+        res.symbol = name.to_string();
+        res
+    }
+
+    fn ticker(name: &str, quote_volume: f64, price_change_percent: f64) -> (String, Ticker) {
+        (
+            name.to_string(),
+            Ticker {
+                symbol: name.to_string(),
+                quote_volume,
+                price_change_percent,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_sort() {
+        let symbols = [
+            symbol("ETHUSDT"),
+            symbol("NEWUSDT"),
+            symbol("BTCUSDT"),
+            symbol("ADAUSDT"),
+        ];
+        let tickers = HashMap::from([
+            ticker("BTCUSDT", 500.0, 3.5),
+            ticker("ETHUSDT", 1000.0, -2.0),
+            ticker("ADAUSDT", 700.0, 10.0),
+        ]);
+        let sorted = |mode: SortMode| {
+            let mut res: Vec<&Symbol> = symbols.iter().collect();
+            mode.sort(&mut res, &tickers);
+            res.iter().map(|s| s.symbol.as_str()).collect::<Vec<&str>>()
+        };
+
+        assert_eq!(
+            sorted(SortMode::Alphabetical),
+            vec!["ADAUSDT", "BTCUSDT", "ETHUSDT", "NEWUSDT"]
+        );
+        assert_eq!(
+            sorted(SortMode::QuoteVolume),
+            vec!["ETHUSDT", "ADAUSDT", "BTCUSDT", "NEWUSDT"]
+        );
+        assert_eq!(
+            sorted(SortMode::Change),
+            vec!["ADAUSDT", "BTCUSDT", "ETHUSDT", "NEWUSDT"]
+        );
+    }
+
+    #[test]
+    fn test_label() {
+        let (_, t) = ticker("BTCUSDT", 3_420_000.0, 1.254);
+
+        assert_eq!(SortMode::Alphabetical.label(&t), None);
+        assert_eq!(SortMode::QuoteVolume.label(&t).unwrap(), "3.4M");
+        assert_eq!(SortMode::Change.label(&t).unwrap(), "+1.25%");
+        assert_eq!(compact(950.0), "950");
+    }
+}
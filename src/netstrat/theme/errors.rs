@@ -0,0 +1,15 @@
+use quick_error::quick_error;
+
+quick_error! {
+    #[derive(Debug)]
+    pub enum ThemeError {
+        Io(err: std::io::Error) {
+            from()
+            display("{}", err)
+        }
+        Toml(err: toml::de::Error) {
+            from()
+            display("{}", err)
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use std::{fs, path::Path, sync::RwLock};
+
+use egui::{Color32, Visuals};
+use serde::{de::Error, Deserialize, Deserializer};
+
+use self::errors::ThemeError;
+
+pub mod errors;
+
+pub const THEMES_FILE: &str = "themes.toml";
+
+static CHART_COLORS: RwLock<ChartColors> = RwLock::new(ChartColors::DEFAULT);
+
+/// Colors of rising and falling klines shared by all charts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChartColors {
+    pub up: Color32,
+    pub down: Color32,
+}
+
+impl Default for ChartColors {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl ChartColors {
+    const DEFAULT: Self = Self {
+        up: Color32::LIGHT_GREEN,
+        down: Color32::LIGHT_RED,
+    };
+
+    /// Colors of the applied palette.
+    pub fn current() -> Self {
+        *CHART_COLORS.read().unwrap()
+    }
+
+    pub fn kline(&self, falling: bool) -> Color32 {
+        match falling {
+            true => self.down,
+            false => self.up,
+        }
+    }
+}
+
+/// Palette applied to egui visuals and charts. User defined palettes are read from
+/// [[theme]] tables of the themes toml file with colors given as "#rrggbb".
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Palette {
+    pub name: String,
+    /// Whether the palette is built on top of the dark egui visuals.
+    #[serde(default = "dark")]
+    pub dark: bool,
+    /// Fill of panels and windows.
+    #[serde(deserialize_with = "hex")]
+    pub background: Color32,
+    /// Fill of plots and text edits.
+    #[serde(deserialize_with = "hex")]
+    pub plot: Color32,
+    #[serde(deserialize_with = "hex")]
+    pub text: Color32,
+    /// Color of selections and links.
+    #[serde(deserialize_with = "hex")]
+    pub accent: Color32,
+    #[serde(deserialize_with = "hex")]
+    pub up: Color32,
+    #[serde(deserialize_with = "hex")]
+    pub down: Color32,
+}
+
+fn dark() -> bool {
+    true
+}
+
+fn hex<'de, D: Deserializer<'de>>(d: D) -> Result<Color32, D::Error> {
+    let s = String::deserialize(d)?;
+    parse_hex(&s).ok_or_else(|| D::Error::custom(format!("invalid color {s}, expected #rrggbb")))
+}
+
+fn parse_hex(s: &str) -> Option<Color32> {
+    let s = s.strip_prefix('#')?;
+    if s.len() != 6 || !s.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let v = u32::from_str_radix(s, 16).ok()?;
+    Some(Color32::from_rgb((v >> 16) as u8, (v >> 8) as u8, v as u8))
+}
+
+impl Palette {
+    pub fn visuals(&self) -> Visuals {
+        let mut visuals = match self.dark {
+            true => Visuals::dark(),
+            false => Visuals::light(),
+        };
+        visuals.widgets.noninteractive.bg_fill = self.background;
+        visuals.extreme_bg_color = self.plot;
+        visuals.override_text_color = Some(self.text);
+        visuals.selection.bg_fill = self.accent;
+        visuals.hyperlink_color = self.accent;
+
+        visuals
+    }
+
+    /// Applies the palette to egui and to the colors of charts.
+    pub fn apply(&self, ctx: &egui::Context) {
+        ctx.set_visuals(self.visuals());
+        *CHART_COLORS.write().unwrap() = ChartColors {
+            up: self.up,
+            down: self.down,
+        };
+    }
+}
+
+#[derive(Deserialize)]
+struct ThemesFile {
+    #[serde(default)]
+    theme: Vec<Palette>,
+}
+
+/// Built-in palettes followed by palettes defined by the user.
+/// A user palette with the name of a built-in one replaces it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Themes {
+    pub palettes: Vec<Palette>,
+}
+
+impl Default for Themes {
+    fn default() -> Self {
+        Self {
+            palettes: builtin(),
+        }
+    }
+}
+
+impl Themes {
+    /// Reads user palettes from the toml file if it exists.
+    pub fn load(path: &Path) -> Result<Self, ThemeError> {
+        match path.exists() {
+            true => Self::from_toml(&fs::read_to_string(path)?),
+            false => Ok(Self::default()),
+        }
+    }
+
+    pub fn from_toml(s: &str) -> Result<Self, ThemeError> {
+        let mut res = Self::default();
+        for palette in toml::from_str::<ThemesFile>(s)?.theme {
+            match res.palettes.iter_mut().find(|p| p.name == palette.name) {
+                Some(existing) => *existing = palette,
+                None => res.palettes.push(palette),
+            }
+        }
+
+        Ok(res)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Palette> {
+        self.palettes.iter().find(|p| p.name == name)
+    }
+}
+
+fn builtin() -> Vec<Palette> {
+    let palette =
+        |name: &str, dark, [background, plot, text, accent, up, down]: [Color32; 6]| Palette {
+            name: name.to_string(),
+            dark,
+            background,
+            plot,
+            text,
+            accent,
+            up,
+            down,
+        };
+
+    vec![
+        palette(
+            "dark",
+            true,
+            [
+                Color32::from_gray(27),
+                Color32::from_gray(10),
+                Color32::from_gray(180),
+                Color32::from_rgb(0, 92, 128),
+                Color32::LIGHT_GREEN,
+                Color32::LIGHT_RED,
+            ],
+        ),
+        palette(
+            "light",
+            false,
+            [
+                Color32::from_gray(248),
+                Color32::WHITE,
+                Color32::from_gray(60),
+                Color32::from_rgb(144, 209, 255),
+                Color32::from_rgb(46, 158, 79),
+                Color32::from_rgb(214, 69, 69),
+            ],
+        ),
+        palette(
+            "solarized dark",
+            true,
+            [
+                Color32::from_rgb(0, 43, 54),
+                Color32::from_rgb(7, 54, 66),
+                Color32::from_rgb(147, 161, 161),
+                Color32::from_rgb(38, 139, 210),
+                Color32::from_rgb(133, 153, 0),
+                Color32::from_rgb(220, 50, 47),
+            ],
+        ),
+        palette(
+            "solarized light",
+            false,
+            [
+                Color32::from_rgb(253, 246, 227),
+                Color32::from_rgb(238, 232, 213),
+                Color32::from_rgb(88, 110, 117),
+                Color32::from_rgb(38, 139, 210),
+                Color32::from_rgb(133, 153, 0),
+                Color32::from_rgb(220, 50, 47),
+            ],
+        ),
+        palette(
+            "high contrast",
+            true,
+            [
+                Color32::BLACK,
+                Color32::BLACK,
+                Color32::WHITE,
+                Color32::GOLD,
+                Color32::GREEN,
+                Color32::RED,
+            ],
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod theme_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(parse_hex("#ff8000"), Some(Color32::from_rgb(255, 128, 0)));
+        assert_eq!(parse_hex("ff8000"), None);
+        assert_eq!(parse_hex("#+f8000"), None);
+        assert_eq!(parse_hex("#fff"), None);
+    }
+
+    #[test]
+    fn test_user_palettes() {
+        let themes = Themes::from_toml(
+            r##"
+            [[theme]]
+            name = "ocean"
+            background = "#0b1d2a"
+            plot = "#06121a"
+            text = "#c0d0e0"
+            accent = "#2080c0"
+            up = "#20c0a0"
+            down = "#e05050"
+
+            [[theme]]
+            name = "light"
+            dark = false
+            background = "#ffffff"
+            plot = "#ffffff"
+            text = "#000000"
+            accent = "#0000ff"
+            up = "#00ff00"
+            down = "#ff0000"
+            "##,
+        )
+        .unwrap();
+
+        assert_eq!(themes.palettes.len(), builtin().len() + 1);
+        let ocean = themes.get("ocean").unwrap();
+        assert!(ocean.dark);
+        assert_eq!(ocean.up, Color32::from_rgb(0x20, 0xc0, 0xa0));
+        assert_eq!(themes.get("light").unwrap().text, Color32::BLACK);
+
+        assert!(Themes::from_toml("[[theme]]\nname = \"broken\"\nup = \"green\"").is_err());
+    }
+}
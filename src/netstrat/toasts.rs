@@ -0,0 +1,149 @@
+use std::sync::Mutex;
+
+use chrono::Utc;
+
+static TOASTS: Mutex<Toasts> = Mutex::new(Toasts::new());
+
+/// Seconds a toast stays on screen unless it is expanded or an error.
+pub const TOAST_SECS: i64 = 8;
+/// Toasts shown at once, the oldest ones are dropped first.
+const MAX_TOASTS: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToastKind {
+    Info,
+    Alert,
+    Error,
+}
+
+/// Short message surfaced to the user with optional details shown when expanded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    pub id: usize,
+    pub kind: ToastKind,
+    pub message: String,
+    pub details: Option<String>,
+    pub expanded: bool,
+    /// Unix milliseconds the toast was created at.
+    created: i64,
+}
+
+/// Queue of toasts filled from anywhere in the app and drawn by the overlay.
+/// Errors stay until dismissed, other toasts expire after a few seconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toasts {
+    next_id: usize,
+    toasts: Vec<Toast>,
+}
+
+impl Default for Toasts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Toasts {
+    const fn new() -> Self {
+        Self {
+            next_id: 0,
+            toasts: vec![],
+        }
+    }
+
+    pub fn info(message: impl ToString) {
+        Self::notify(ToastKind::Info, message, None);
+    }
+
+    pub fn alert(message: impl ToString) {
+        Self::notify(ToastKind::Alert, message, None);
+    }
+
+    /// Shows the message with the error as details.
+    pub fn error(message: impl ToString, err: impl ToString) {
+        Self::notify(ToastKind::Error, message, Some(err.to_string()));
+    }
+
+    pub fn notify(kind: ToastKind, message: impl ToString, details: Option<String>) {
+        let now = Utc::now().timestamp_millis();
+        TOASTS
+            .lock()
+            .unwrap()
+            .push(kind, message.to_string(), details, now);
+    }
+
+    /// Returns toasts to draw dropping expired ones.
+    pub fn current() -> Vec<Toast> {
+        let mut toasts = TOASTS.lock().unwrap();
+        toasts.expire(Utc::now().timestamp_millis());
+
+        toasts.toasts.clone()
+    }
+
+    pub fn dismiss(id: usize) {
+        TOASTS.lock().unwrap().toasts.retain(|t| t.id != id);
+    }
+
+    pub fn toggle_expanded(id: usize) {
+        let mut toasts = TOASTS.lock().unwrap();
+        if let Some(t) = toasts.toasts.iter_mut().find(|t| t.id == id) {
+            t.expanded = !t.expanded;
+        }
+    }
+
+    fn push(&mut self, kind: ToastKind, message: String, details: Option<String>, now: i64) {
+        self.toasts.push(Toast {
+            id: self.next_id,
+            kind,
+            message,
+            details,
+            expanded: false,
+            created: now,
+        });
+        self.next_id += 1;
+
+        let excess = self.toasts.len().saturating_sub(MAX_TOASTS);
+        self.toasts.drain(..excess);
+    }
+
+    fn expire(&mut self, now: i64) {
+        self.toasts.retain(|t| {
+            t.kind == ToastKind::Error || t.expanded || now - t.created < TOAST_SECS * 1000
+        });
+    }
+}
+
+#[cfg(test)]
+mod toasts_tests {
+    use super::*;
+
+    #[test]
+    fn test_expire() {
+        let mut toasts = Toasts::default();
+        toasts.push(ToastKind::Info, "exported".to_string(), None, 0);
+        toasts.push(ToastKind::Alert, "alert".to_string(), None, 0);
+        toasts.push(
+            ToastKind::Error,
+            "failed".to_string(),
+            Some("timeout".to_string()),
+            0,
+        );
+        toasts.toasts[1].expanded = true;
+
+        toasts.expire(TOAST_SECS * 1000 - 1);
+        assert_eq!(toasts.toasts.len(), 3);
+
+        toasts.expire(TOAST_SECS * 1000);
+        let left: Vec<&str> = toasts.toasts.iter().map(|t| t.message.as_str()).collect();
+        assert_eq!(left, vec!["alert", "failed"]);
+    }
+
+    #[test]
+    fn test_push_drops_oldest() {
+        let mut toasts = Toasts::default();
+        (0..MAX_TOASTS + 2).for_each(|i| toasts.push(ToastKind::Info, i.to_string(), None, 0));
+
+        assert_eq!(toasts.toasts.len(), MAX_TOASTS);
+        assert_eq!(toasts.toasts[0].id, 2);
+        assert_eq!(toasts.toasts[0].message, "2");
+    }
+}
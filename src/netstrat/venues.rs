@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+
+use crate::sources::binance::Kline;
+
+/// Volumes of venue klines at open times of the base klines, zero where the venue has none.
+pub fn aligned_volumes(base: &[Kline], venue: &[Kline]) -> Vec<f64> {
+    let by_open: HashMap<i64, f32> = venue.iter().map(|k| (k.t_open, k.volume)).collect();
+
+    base.iter()
+        .map(|k| by_open.get(&k.t_open).copied().unwrap_or_default() as f64)
+        .collect()
+}
+
+/// Share of every venue in the total volume.
+pub fn shares(volumes: &[Vec<f64>]) -> Vec<f64> {
+    let totals: Vec<f64> = volumes.iter().map(|v| v.iter().sum()).collect();
+    let sum: f64 = totals.iter().sum();
+
+    totals
+        .iter()
+        .map(|t| match sum > 0.0 {
+            true => t / sum,
+            false => 0.0,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod venues_tests {
+    use super::*;
+
+    fn kline(t_open: i64, volume: f32) -> Kline {
+        Kline {
+            t_open,
+            volume,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_aligned_volumes() {
+        let base = [kline(0, 1.0), kline(10, 2.0), kline(20, 3.0)];
+        let venue = [kline(10, 5.0), kline(30, 7.0)];
+
+        assert_eq!(aligned_volumes(&base, &venue), vec![0.0, 5.0, 0.0]);
+    }
+
+    #[test]
+    fn test_shares() {
+        assert_eq!(shares(&[vec![1.0, 2.0], vec![1.0, 0.0]]), vec![0.75, 0.25]);
+        assert_eq!(shares(&[vec![0.0]]), vec![0.0]);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::sources::binance::Kline;
+
+/// Share of the total volume included into the value area.
+pub const VALUE_AREA_SHARE: f64 = 0.7;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Bin {
+    pub low: f64,
+    pub high: f64,
+    pub volume: f64,
+}
+
+/// Volume at price histogram.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct VolumeProfile {
+    pub bins: Vec<Bin>,
+    /// Index of the point of control: the bin with the highest volume.
+    pub poc: usize,
+    /// Inclusive range of bins indexes forming the value area.
+    pub value_area: (usize, usize),
+}
+
+impl VolumeProfile {
+    /// Computes profile distributing every kline volume uniformly over its low-high range.
+    pub fn new(klines: &[Kline], bins_count: usize) -> Option<Self> {
+        if klines.is_empty() || bins_count == 0 {
+            return None;
+        }
+
+        let low = klines.iter().map(|k| k.low).fold(f32::MAX, f32::min) as f64;
+        let high = klines.iter().map(|k| k.high).fold(f32::MIN, f32::max) as f64;
+        if high <= low {
+            return None;
+        }
+
+        let step = (high - low) / bins_count as f64;
+        let mut bins: Vec<Bin> = (0..bins_count)
+            .map(|i| Bin {
+                low: low + i as f64 * step,
+                high: low + (i + 1) as f64 * step,
+                volume: 0.0,
+            })
+            .collect();
+
+        klines.iter().for_each(|k| {
+            let (k_low, k_high) = (k.low as f64, k.high as f64);
+            if k_high <= k_low {
+                let i = (((k_low - low) / step) as usize).min(bins_count - 1);
+                bins[i].volume += k.volume as f64;
+                return;
+            }
+
+            bins.iter_mut().for_each(|b| {
+                let overlap = b.high.min(k_high) - b.low.max(k_low);
+                if overlap > 0.0 {
+                    b.volume += k.volume as f64 * overlap / (k_high - k_low);
+                }
+            });
+        });
+
+        let poc =
+            bins.iter()
+                .enumerate()
+                .fold(0, |acc, (i, b)| match b.volume > bins[acc].volume {
+                    true => i,
+                    false => acc,
+                });
+        let value_area = Self::value_area(&bins, poc);
+
+        Some(Self {
+            bins,
+            poc,
+            value_area,
+        })
+    }
+
+    /// Expands the area from the point of control towards the bigger neighbour
+    /// until it holds VALUE_AREA_SHARE of the total volume.
+    fn value_area(bins: &[Bin], poc: usize) -> (usize, usize) {
+        let total: f64 = bins.iter().map(|b| b.volume).sum();
+        let (mut lo, mut hi) = (poc, poc);
+        let mut volume = bins[poc].volume;
+
+        while volume < total * VALUE_AREA_SHARE && (lo > 0 || hi < bins.len() - 1) {
+            let below = match lo > 0 {
+                true => bins[lo - 1].volume,
+                false => -1.0,
+            };
+            let above = match hi < bins.len() - 1 {
+                true => bins[hi + 1].volume,
+                false => -1.0,
+            };
+
+            if above >= below {
+                hi += 1;
+                volume += above;
+            } else {
+                lo -= 1;
+                volume += below;
+            }
+        }
+
+        (lo, hi)
+    }
+
+    pub fn max_volume(&self) -> f64 {
+        self.bins[self.poc].volume
+    }
+}
+
+#[cfg(test)]
+mod volume_profile_tests {
+    use super::*;
+
+    fn kline(low: f32, high: f32, volume: f32) -> Kline {
+        Kline {
+            low,
+            high,
+            volume,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_volume_profile_new() {
+        let klines = vec![kline(0.0, 4.0, 4.0), kline(1.0, 2.0, 10.0)];
+        let vp = VolumeProfile::new(&klines, 4).unwrap();
+
+        let volumes: Vec<f64> = vp.bins.iter().map(|b| b.volume).collect();
+        assert_eq!(volumes, vec![1.0, 11.0, 1.0, 1.0]);
+        assert_eq!(vp.poc, 1);
+        // 11 of 14 is already more than 70%
+        assert_eq!(vp.value_area, (1, 1));
+    }
+
+    #[test]
+    fn test_value_area_expands() {
+        let klines = vec![
+            kline(0.0, 1.0, 1.0),
+            kline(1.0, 2.0, 3.0),
+            kline(2.0, 3.0, 4.0),
+            kline(3.0, 4.0, 2.0),
+        ];
+        let vp = VolumeProfile::new(&klines, 4).unwrap();
+
+        assert_eq!(vp.poc, 2);
+        assert_eq!(vp.value_area, (1, 2));
+    }
+
+    #[test]
+    fn test_volume_profile_empty() {
+        assert_eq!(VolumeProfile::new(&[], 10), None);
+        assert_eq!(VolumeProfile::new(&[kline(1.0, 1.0, 1.0)], 10), None);
+    }
+}
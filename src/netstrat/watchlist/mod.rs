@@ -0,0 +1,143 @@
+use std::{fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use self::errors::WatchlistError;
+
+pub mod errors;
+
+const WATCHLISTS_FILE: &str = "watchlists.json";
+const FAVORITES: &str = "favorites";
+
+/// Named list of symbols picked by the user.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Watchlist {
+    pub name: String,
+    pub symbols: Vec<String>,
+}
+
+impl Watchlist {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            symbols: vec![],
+        }
+    }
+}
+
+/// Watchlists saved in a json file on every change.
+/// There is always at least the favorites list.
+#[derive(Debug, Clone)]
+pub struct Watchlists {
+    path: PathBuf,
+    pub lists: Vec<Watchlist>,
+}
+
+impl Default for Watchlists {
+    fn default() -> Self {
+        Self::new(PathBuf::from(WATCHLISTS_FILE))
+    }
+}
+
+impl Watchlists {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            lists: vec![Watchlist::new(FAVORITES)],
+        }
+    }
+
+    pub fn load(&mut self) -> Result<(), WatchlistError> {
+        if !self.path.exists() {
+            return Ok(());
+        }
+
+        let lists: Vec<Watchlist> = serde_json::from_str(&fs::read_to_string(&self.path)?)?;
+        if !lists.is_empty() {
+            self.lists = lists;
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Watchlist> {
+        self.lists.iter().find(|l| l.name == name)
+    }
+
+    pub fn contains(&self, name: &str, symbol: &str) -> bool {
+        self.get(name)
+            .map(|l| l.symbols.iter().any(|s| s == symbol))
+            .unwrap_or(false)
+    }
+
+    /// Adds the symbol to the list or removes it if it is already there.
+    pub fn toggle(&mut self, name: &str, symbol: &str) -> Result<(), WatchlistError> {
+        if let Some(list) = self.lists.iter_mut().find(|l| l.name == name) {
+            match list.symbols.iter().position(|s| s == symbol) {
+                Some(i) => {
+                    list.symbols.remove(i);
+                }
+                None => list.symbols.push(symbol.to_string()),
+            }
+        }
+
+        self.write()
+    }
+
+    /// Adds an empty list unless a list with the name exists.
+    pub fn add(&mut self, name: &str) -> Result<(), WatchlistError> {
+        if self.get(name).is_none() {
+            self.lists.push(Watchlist::new(name));
+        }
+
+        self.write()
+    }
+
+    /// Deletes the list keeping at least one list.
+    pub fn delete(&mut self, name: &str) -> Result<(), WatchlistError> {
+        self.lists.retain(|l| l.name != name);
+        if self.lists.is_empty() {
+            self.lists.push(Watchlist::new(FAVORITES));
+        }
+
+        self.write()
+    }
+
+    fn write(&self) -> Result<(), WatchlistError> {
+        fs::write(&self.path, serde_json::to_string_pretty(&self.lists)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod watchlist_tests {
+    use super::*;
+
+    #[test]
+    fn test_watchlists() {
+        let path =
+            std::env::temp_dir().join(format!("netstrat-watchlists-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut lists = Watchlists::new(path.clone());
+        lists.load().unwrap();
+        assert_eq!(lists.lists, vec![Watchlist::new(FAVORITES)]);
+
+        lists.toggle(FAVORITES, "BTCUSDT").unwrap();
+        lists.toggle(FAVORITES, "ETHUSDT").unwrap();
+        lists.toggle(FAVORITES, "BTCUSDT").unwrap();
+        lists.add("alts").unwrap();
+        lists.toggle("alts", "SOLUSDT").unwrap();
+
+        let mut loaded = Watchlists::new(path.clone());
+        loaded.load().unwrap();
+        assert_eq!(loaded.get(FAVORITES).unwrap().symbols, vec!["ETHUSDT"]);
+        assert!(loaded.contains("alts", "SOLUSDT"));
+        assert!(!loaded.contains("alts", "ETHUSDT"));
+
+        loaded.delete(FAVORITES).unwrap();
+        loaded.delete("alts").unwrap();
+        assert_eq!(loaded.lists, vec![Watchlist::new(FAVORITES)]);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
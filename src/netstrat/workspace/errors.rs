@@ -0,0 +1,21 @@
+use quick_error::quick_error;
+
+use crate::netstrat::drawings::errors::DrawingsError;
+
+quick_error! {
+    #[derive(Debug)]
+    pub enum WorkspaceError {
+        Io(err: std::io::Error) {
+            from()
+            display("{}", err)
+        }
+        Json(err: serde_json::Error) {
+            from()
+            display("{}", err)
+        }
+        Drawings(err: DrawingsError) {
+            from()
+            display("{}", err)
+        }
+    }
+}
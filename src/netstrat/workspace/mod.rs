@@ -0,0 +1,213 @@
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    netstrat::{
+        drawings::{Drawings, SymbolDrawings},
+        layout::WindowLayout,
+    },
+    sources::binance::Interval,
+};
+
+use self::errors::WorkspaceError;
+
+pub mod errors;
+
+const WORKSPACES_DIR: &str = "workspaces";
+
+/// Drawings made on a chart kept in a workspace.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChartDrawings {
+    pub symbol: String,
+    /// Chart interval in binance representation, e.g. "1m".
+    pub interval: String,
+    pub drawings: SymbolDrawings,
+}
+
+/// Analysis setup with states of app windows, open charts and drawings made on them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Workspace {
+    #[serde(default)]
+    pub windows: BTreeMap<String, WindowLayout>,
+    #[serde(default)]
+    pub drawings: Vec<ChartDrawings>,
+}
+
+impl Workspace {
+    /// Symbols and intervals of charts open in the windows.
+    pub fn charts(&self) -> Vec<(String, Interval)> {
+        let mut res: Vec<(String, Interval)> = vec![];
+        self.windows
+            .values()
+            .flat_map(|w| match w.tabs.is_empty() {
+                true => std::slice::from_ref(w),
+                false => w.tabs.as_slice(),
+            })
+            .filter_map(|l| {
+                let interval = Interval::parse(l.interval.as_deref()?)?;
+                Some((l.symbol.clone()?, interval))
+            })
+            .for_each(|chart| {
+                if !res.contains(&chart) {
+                    res.push(chart);
+                }
+            });
+
+        res
+    }
+
+    /// Takes drawings of the open charts from the store.
+    pub fn collect_drawings(&mut self, store: &Drawings) {
+        self.drawings = self
+            .charts()
+            .into_iter()
+            .map(|(symbol, interval)| ChartDrawings {
+                drawings: store.get(&symbol, interval),
+                interval: interval.as_str().to_string(),
+                symbol,
+            })
+            .filter(|d| !d.drawings.is_empty())
+            .collect();
+    }
+
+    /// Saves drawings of the workspace to the store replacing drawings of the same charts.
+    pub fn restore_drawings(&self, store: &mut Drawings) -> Result<(), WorkspaceError> {
+        for d in &self.drawings {
+            if let Some(interval) = Interval::parse(&d.interval) {
+                store.save(&d.symbol, interval, d.drawings.clone())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Workspaces saved as {name}.json files of a directory, so they can be shared by copying.
+#[derive(Debug, Clone)]
+pub struct Workspaces {
+    dir: PathBuf,
+}
+
+impl Default for Workspaces {
+    fn default() -> Self {
+        Self::new(PathBuf::from(WORKSPACES_DIR))
+    }
+}
+
+impl Workspaces {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.json"))
+    }
+
+    /// Sorted names of saved workspaces.
+    pub fn list(&self) -> Result<Vec<String>, WorkspaceError> {
+        if !self.dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut res = vec![];
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+                res.push(name.to_string());
+            }
+        }
+        res.sort();
+
+        Ok(res)
+    }
+
+    pub fn load(&self, name: &str) -> Result<Workspace, WorkspaceError> {
+        Ok(serde_json::from_str(&fs::read_to_string(self.path(name))?)?)
+    }
+
+    pub fn save(&self, name: &str, workspace: &Workspace) -> Result<(), WorkspaceError> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.path(name), serde_json::to_string_pretty(workspace)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod workspace_tests {
+    use super::*;
+
+    fn chart(symbol: &str, interval: &str) -> WindowLayout {
+        WindowLayout {
+            visible: true,
+            symbol: Some(symbol.to_string()),
+            interval: Some(interval.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn workspace() -> Workspace {
+        Workspace {
+            windows: BTreeMap::from([
+                (
+                    "graph".to_string(),
+                    WindowLayout {
+                        tabs: vec![chart("BTCUSDT", "1h"), chart("ETHUSDT", "1d")],
+                        ..chart("BTCUSDT", "1h")
+                    },
+                ),
+                ("props".to_string(), chart("BTCUSDT", "1h")),
+                ("cache".to_string(), WindowLayout::default()),
+            ]),
+            drawings: vec![],
+        }
+    }
+
+    #[test]
+    fn test_charts() {
+        assert_eq!(
+            workspace().charts(),
+            vec![
+                ("BTCUSDT".to_string(), Interval::Hour),
+                ("ETHUSDT".to_string(), Interval::Day)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let tmp = std::env::temp_dir().join(format!("netstrat-workspaces-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&tmp);
+        let workspaces = Workspaces::new(tmp.join("workspaces"));
+        assert!(workspaces.list().unwrap().is_empty());
+
+        let mut store = Drawings::new(tmp.join("drawings.json"));
+        let levels = SymbolDrawings {
+            trendlines: vec![],
+            levels: vec![5.0],
+        };
+        fs::create_dir_all(&tmp).unwrap();
+        store
+            .save("ETHUSDT", Interval::Day, levels.clone())
+            .unwrap();
+
+        let mut ws = workspace();
+        ws.collect_drawings(&store);
+        assert_eq!(ws.drawings.len(), 1);
+        workspaces.save("eth", &ws).unwrap();
+        workspaces.save("btc", &workspace()).unwrap();
+        assert_eq!(workspaces.list().unwrap(), vec!["btc", "eth"]);
+
+        let loaded = workspaces.load("eth").unwrap();
+        assert_eq!(loaded, ws);
+
+        let mut other = Drawings::new(tmp.join("other.json"));
+        loaded.restore_drawings(&mut other).unwrap();
+        assert_eq!(other.get("ETHUSDT", Interval::Day), levels);
+
+        fs::remove_dir_all(&tmp).unwrap();
+    }
+}
@@ -1 +1,2 @@
 pub mod rest;
+pub mod simulation;
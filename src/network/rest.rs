@@ -1,4 +1,14 @@
-use tracing::debug;
+use std::time::Duration;
+
+use reqwest::{
+    header::{HeaderValue, CONTENT_TYPE},
+    Method,
+};
+use tracing::{debug, warn};
+
+use crate::netstrat::status::Status;
+
+use super::simulation::Simulation;
 
 #[derive(Clone, Debug)]
 pub struct Rest {
@@ -15,7 +25,7 @@ impl Rest {
     pub async fn get(&self, url: &str) -> Result<reqwest::Response, reqwest::Error> {
         let req = self.c.get(url);
 
-        self.execute_request(req).await
+        self.execute_request(req, false).await
     }
 
     pub async fn get_with_params(
@@ -25,22 +35,117 @@ impl Rest {
     ) -> Result<reqwest::Response, reqwest::Error> {
         let req = self.c.get(url).query(params);
 
-        self.execute_request(req).await
+        self.execute_request(req, false).await
+    }
+
+    /// Sends get request with additional headers.
+    /// Header values are marked as sensitive so they are not printed to logs.
+    pub async fn get_with_headers(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<reqwest::Response, reqwest::Error> {
+        self.send_with_headers(Method::GET, url, headers).await
+    }
+
+    /// Sends post request with the json body to an url containing secrets.
+    /// The url is left out of logs and of the returned error.
+    pub async fn post_json_secret(
+        &self,
+        url: &str,
+        body: String,
+    ) -> Result<reqwest::Response, reqwest::Error> {
+        let req = self
+            .c
+            .post(url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body);
+
+        self.execute_request(req, true).await
+    }
+
+    /// Sends request of any method with additional headers marked as sensitive.
+    pub async fn send_with_headers(
+        &self,
+        method: Method,
+        url: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<reqwest::Response, reqwest::Error> {
+        let mut req = self.c.request(method, url);
+        for (k, v) in headers {
+            if let Ok(mut val) = HeaderValue::from_str(v) {
+                val.set_sensitive(true);
+                req = req.header(*k, val);
+            }
+        }
+
+        self.execute_request(req, false).await
     }
 
     async fn execute_request(
         &self,
         req: reqwest::RequestBuilder,
+        hide_url: bool,
     ) -> Result<reqwest::Response, reqwest::Error> {
+        let (delay, fault) = Simulation::current().next();
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+        // Zero timeout makes the client fail with a real timeout error.
+        let req = match fault {
+            Some(fault) => {
+                warn!("Simulating request {fault:?} after {delay:?}.");
+                req.timeout(Duration::ZERO)
+            }
+            None => req,
+        };
+
         let req_builded = req.build()?;
-        debug!(
-            "Sending request: method: {:?}; url: {:?}; headers: {:?}; body: {:?}.",
-            req_builded.method(),
-            req_builded.url().as_str(),
-            req_builded.headers(),
-            req_builded.body(),
-        );
-
-        self.c.execute(req_builded).await
+        debug!("{}", request_line(&req_builded, hide_url));
+
+        let res = match self.c.execute(req_builded).await {
+            Err(err) if hide_url => Err(err.without_url()),
+            res => res,
+        };
+        match &res {
+            Ok(_) => Status::report_success(),
+            Err(err) => Status::report_error(&err.to_string()),
+        }
+
+        res
+    }
+}
+
+/// Debug line of the request, with the url redacted if it contains secrets.
+fn request_line(req: &reqwest::Request, hide_url: bool) -> String {
+    let url = match hide_url {
+        true => "<redacted>",
+        false => req.url().as_str(),
+    };
+    format!(
+        "Sending request: method: {:?}; url: {:?}; headers: {:?}; body: {:?}.",
+        req.method(),
+        url,
+        req.headers(),
+        req.body(),
+    )
+}
+
+#[cfg(test)]
+mod rest_tests {
+    use super::*;
+
+    #[test]
+    fn test_request_line() {
+        let req = reqwest::Client::new()
+            .post("https://api.telegram.org/bot123:secret/sendMessage")
+            .body("{}")
+            .build()
+            .unwrap();
+
+        assert!(request_line(&req, false).contains("bot123:secret"));
+        let line = request_line(&req, true);
+        assert!(!line.contains("secret"), "token is not logged: {line}");
+        assert!(line.contains("POST"));
     }
 }
@@ -0,0 +1,107 @@
+use std::{sync::RwLock, time::Duration};
+
+use rand::Rng;
+
+/// Time a simulated disconnect hangs before the request fails.
+const DISCONNECT_DELAY: Duration = Duration::from_secs(5);
+
+static SIMULATION: RwLock<Simulation> = RwLock::new(Simulation::disabled());
+
+/// Developer mode degrading every rest request with artificial latency, errors and
+/// disconnects, so retries and resumed downloads can be exercised without a bad network.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Simulation {
+    pub enabled: bool,
+    pub latency_ms: u64,
+    /// Latency is increased by a random duration up to jitter.
+    pub jitter_ms: u64,
+    /// Share of requests failing immediately.
+    pub error_rate: f64,
+    /// Share of requests failing after the disconnect delay.
+    pub disconnect_rate: f64,
+}
+
+impl Default for Simulation {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+/// Failure injected into a request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fault {
+    Error,
+    Disconnect,
+}
+
+impl Simulation {
+    const fn disabled() -> Self {
+        Self {
+            enabled: false,
+            latency_ms: 500,
+            jitter_ms: 500,
+            error_rate: 0.1,
+            disconnect_rate: 0.05,
+        }
+    }
+
+    /// Returns simulation settings applied to requests.
+    pub fn current() -> Self {
+        *SIMULATION.read().unwrap()
+    }
+
+    pub fn apply(self) {
+        *SIMULATION.write().unwrap() = self;
+    }
+
+    /// Picks the delay and the failure of the next request.
+    pub fn next(&self) -> (Duration, Option<Fault>) {
+        if !self.enabled {
+            return (Duration::ZERO, None);
+        }
+
+        let mut rng = rand::thread_rng();
+        let delay = Duration::from_millis(self.latency_ms + rng.gen_range(0..=self.jitter_ms));
+
+        self.pick(delay, rng.gen())
+    }
+
+    fn pick(&self, delay: Duration, roll: f64) -> (Duration, Option<Fault>) {
+        if roll < self.error_rate {
+            return (delay, Some(Fault::Error));
+        }
+        if roll < self.error_rate + self.disconnect_rate {
+            return (delay + DISCONNECT_DELAY, Some(Fault::Disconnect));
+        }
+
+        (delay, None)
+    }
+}
+
+#[cfg(test)]
+mod simulation_tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled() {
+        assert_eq!(Simulation::default().next(), (Duration::ZERO, None));
+    }
+
+    #[test]
+    fn test_pick() {
+        let sim = Simulation {
+            enabled: true,
+            error_rate: 0.2,
+            disconnect_rate: 0.3,
+            ..Default::default()
+        };
+        let delay = Duration::from_millis(10);
+
+        assert_eq!(sim.pick(delay, 0.1), (delay, Some(Fault::Error)));
+        assert_eq!(
+            sim.pick(delay, 0.4),
+            (delay + DISCONNECT_DELAY, Some(Fault::Disconnect))
+        );
+        assert_eq!(sim.pick(delay, 0.5), (delay, None));
+    }
+}
@@ -4,22 +4,36 @@ use serde::{Deserialize, Serialize};
 use serde_json;
 
 use crate::network::rest::Rest;
-use crate::sources::binance::interval::Interval;
+use crate::sources::binance::{interval::Interval, source::Source};
 
 use super::errors::ClientError;
 
 #[derive(Clone, Debug, Default)]
 pub struct Client {}
 
-const BASE_URL: &str = "https://api.binance.com";
 const PATH_KLINE: &str = "/api/v3/klines";
 const PATH_INFO: &str = "/api/v3/exchangeInfo";
+const PATH_TICKER: &str = "/api/v3/ticker/24hr";
+const PATH_DEPTH: &str = "/api/v3/depth";
+const PATH_TRADES: &str = "/api/v3/trades";
 
 #[derive(Debug, Deserialize, Default)]
 pub struct Info {
     pub symbols: Vec<Symbol>,
 }
 
+/// Part of the exchange info which is the same on every source.
+#[derive(Deserialize)]
+struct SymbolsStatus {
+    symbols: Vec<SymbolStatus>,
+}
+
+#[derive(Deserialize)]
+struct SymbolStatus {
+    symbol: String,
+    status: String,
+}
+
 #[derive(Deserialize)]
 struct KlineData(
     i64,    // Open time
@@ -41,13 +55,13 @@ pub struct Symbol {
     pub status: String,
 
     #[serde(rename = "baseAsset")]
-    base_asset: String,
+    pub base_asset: String,
 
     #[serde(rename = "baseAssetPrecision")]
     base_asset_precision: usize,
 
     #[serde(rename = "quoteAsset")]
-    quote_asset: String,
+    pub quote_asset: String,
 
     #[serde(rename = "quotePrecision")]
     quote_precision: usize,
@@ -78,15 +92,171 @@ pub struct Symbol {
 
     #[serde(rename = "isMarginTradingAllowed")]
     is_margin_trading_allowed: bool,
+
+    #[serde(default)]
+    filters: Vec<SymbolFilter>,
+}
+
+/// Trading rule of a symbol. Only fields used by the app are read.
+#[derive(Debug, Deserialize, Default, Clone)]
+struct SymbolFilter {
+    #[serde(rename = "filterType")]
+    filter_type: String,
+
+    #[serde(
+        rename = "tickSize",
+        default,
+        deserialize_with = "deserialize_opt_f64_str"
+    )]
+    tick_size: Option<f64>,
+
+    #[serde(
+        rename = "minNotional",
+        default,
+        deserialize_with = "deserialize_opt_f64_str"
+    )]
+    min_notional: Option<f64>,
+
+    #[serde(rename = "minQty", default, deserialize_with = "deserialize_opt_f64_str")]
+    min_qty: Option<f64>,
+
+    #[serde(rename = "maxQty", default, deserialize_with = "deserialize_opt_f64_str")]
+    max_qty: Option<f64>,
+
+    #[serde(
+        rename = "stepSize",
+        default,
+        deserialize_with = "deserialize_opt_f64_str"
+    )]
+    step_size: Option<f64>,
+}
+
+/// Quantity limits of orders from the lot size filter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LotSize {
+    pub min: f64,
+    pub max: f64,
+    pub step: f64,
 }
 
 impl Symbol {
     pub fn active(&self) -> bool {
         self.status == "TRADING"
     }
+
+    /// Minimal price step from the price filter.
+    pub fn tick_size(&self) -> Option<f64> {
+        self.filters
+            .iter()
+            .find(|f| f.filter_type == "PRICE_FILTER")
+            .and_then(|f| f.tick_size)
+    }
+
+    /// Minimal order value in the quote asset.
+    pub fn min_notional(&self) -> Option<f64> {
+        self.filters
+            .iter()
+            .filter(|f| f.filter_type == "MIN_NOTIONAL" || f.filter_type == "NOTIONAL")
+            .find_map(|f| f.min_notional)
+    }
+
+    pub fn lot_size(&self) -> Option<LotSize> {
+        let f = self.filters.iter().find(|f| f.filter_type == "LOT_SIZE")?;
+        Some(LotSize {
+            min: f.min_qty?,
+            max: f.max_qty?,
+            step: f.step_size?,
+        })
+    }
+}
+
+/// Price change statistics of a symbol for the last 24 hours.
+#[derive(Debug, Deserialize, Default, Clone, PartialEq)]
+pub struct Ticker {
+    pub symbol: String,
+
+    #[serde(
+        rename = "priceChangePercent",
+        deserialize_with = "deserialize_f64_str"
+    )]
+    pub price_change_percent: f64,
+
+    #[serde(rename = "lastPrice", deserialize_with = "deserialize_f64_str")]
+    pub last_price: f64,
+
+    #[serde(rename = "highPrice", deserialize_with = "deserialize_f64_str")]
+    pub high_price: f64,
+
+    #[serde(rename = "lowPrice", deserialize_with = "deserialize_f64_str")]
+    pub low_price: f64,
+
+    #[serde(deserialize_with = "deserialize_f64_str")]
+    pub volume: f64,
+
+    #[serde(rename = "quoteVolume", deserialize_with = "deserialize_f64_str")]
+    pub quote_volume: f64,
+
+    /// Number of trades.
+    pub count: i64,
+}
+
+/// Order book snapshot of a symbol. Price levels are sorted from the best price.
+#[derive(Debug, Deserialize, Default, Clone, PartialEq)]
+pub struct Depth {
+    /// Price and quantity of bid levels.
+    #[serde(deserialize_with = "deserialize_levels")]
+    pub bids: Vec<(f64, f64)>,
+
+    /// Price and quantity of ask levels.
+    #[serde(deserialize_with = "deserialize_levels")]
+    pub asks: Vec<(f64, f64)>,
+}
+
+/// Trade of a symbol on the exchange.
+#[derive(Debug, Deserialize, Default, Clone, PartialEq)]
+pub struct Trade {
+    pub id: i64,
+
+    #[serde(deserialize_with = "deserialize_f64_str")]
+    pub price: f64,
+
+    #[serde(deserialize_with = "deserialize_f64_str")]
+    pub qty: f64,
+
+    pub time: i64,
+
+    /// True if the buy order was resting in the book, so the trade was a market sell.
+    #[serde(rename = "isBuyerMaker")]
+    pub is_buyer_maker: bool,
 }
 
-#[derive(PartialEq, Debug, Clone, Copy, Default, Serialize)]
+fn deserialize_levels<'de, D>(deserializer: D) -> Result<Vec<(f64, f64)>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Vec::<(String, String)>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(price, qty)| Ok((price.parse()?, qty.parse()?)))
+        .collect::<Result<_, std::num::ParseFloatError>>()
+        .map_err(serde::de::Error::custom)
+}
+
+fn deserialize_f64_str<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    s.parse::<f64>().map_err(serde::de::Error::custom)
+}
+
+fn deserialize_opt_f64_str<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    deserialize_f64_str(deserializer).map(Some)
+}
+
+#[derive(PartialEq, Debug, Clone, Copy, Default, Serialize, Deserialize)]
 pub struct Kline {
     pub t_open: i64,
     pub open: f32,
@@ -142,13 +312,15 @@ impl PartialOrd for Kline {
 }
 
 impl Client {
-    pub async fn kline(
+    /// Loads klines from the given source.
+    pub async fn source_kline(
+        source: Source,
         symbol: String,
         interval: Interval,
         start_time: i64,
         limit: usize,
     ) -> Result<Vec<Kline>, ClientError> {
-        let url = format!("{}{}", BASE_URL, PATH_KLINE);
+        let url = format!("{}{}", source.base_url(), PATH_KLINE);
         let params = &[
             ("symbol", symbol.as_str()),
             ("interval", interval.as_str()),
@@ -165,11 +337,112 @@ impl Client {
             .collect())
     }
 
-    pub async fn info() -> Info {
-        let url = format!("{}{}", BASE_URL, PATH_INFO);
-        let resp = Rest::new().get(&url).await.unwrap();
-        let json_str = &resp.text().await.unwrap();
-        let res: Info = serde_json::from_str(json_str).unwrap();
-        res
+    /// Loads klines for [start_time, end_time) range requesting pages of limit size.
+    pub async fn kline_range(
+        symbol: String,
+        interval: Interval,
+        start_time: i64,
+        end_time: i64,
+        limit: usize,
+    ) -> Result<Vec<Kline>, ClientError> {
+        Client::source_kline_range(
+            Source::default(),
+            symbol,
+            interval,
+            start_time,
+            end_time,
+            limit,
+        )
+        .await
+    }
+
+    /// Loads klines for [start_time, end_time) range from the given source.
+    pub async fn source_kline_range(
+        source: Source,
+        symbol: String,
+        interval: Interval,
+        start_time: i64,
+        end_time: i64,
+        limit: usize,
+    ) -> Result<Vec<Kline>, ClientError> {
+        let mut res: Vec<Kline> = vec![];
+        let mut from = start_time;
+        while from < end_time {
+            let page = Client::source_kline(source, symbol.clone(), interval, from, limit).await?;
+            match page.last() {
+                Some(last) => from = last.t_close + 1,
+                None => break,
+            }
+
+            res.extend(page.into_iter().filter(|k| k.t_open < end_time));
+        }
+
+        Ok(res)
+    }
+
+    /// Checks that the source lists the symbol as trading.
+    pub async fn has_symbol(source: Source, symbol: String) -> Result<bool, ClientError> {
+        let url = format!("{}{}", source.base_url(), PATH_INFO);
+        let resp = Rest::new()
+            .get_with_params(&url, &[("symbol", symbol.as_str())])
+            .await?;
+        if !resp.status().is_success() {
+            return Ok(false);
+        }
+        let info = serde_json::from_str::<SymbolsStatus>(&resp.text().await?)?;
+
+        Ok(info
+            .symbols
+            .iter()
+            .any(|s| s.symbol == symbol && s.status == "TRADING"))
+    }
+
+    /// Loads 24 hour statistics of all symbols.
+    pub async fn tickers() -> Result<Vec<Ticker>, ClientError> {
+        let url = format!("{}{}", Source::default().base_url(), PATH_TICKER);
+        let resp = Rest::new().get(&url).await?;
+
+        Ok(serde_json::from_str(&resp.text().await?)?)
+    }
+
+    /// Loads the exchange info of a single symbol, none if it is not listed.
+    pub async fn symbol(symbol: String) -> Result<Option<Symbol>, ClientError> {
+        let url = format!("{}{}", Source::default().base_url(), PATH_INFO);
+        let resp = Rest::new()
+            .get_with_params(&url, &[("symbol", &symbol)])
+            .await?;
+        if !resp.status().is_success() {
+            return Ok(None);
+        }
+        let info: Info = serde_json::from_str(&resp.text().await?)?;
+
+        Ok(info.symbols.into_iter().find(|s| s.symbol == symbol))
+    }
+
+    /// Loads the order book of the symbol with up to limit levels on each side.
+    pub async fn depth(symbol: String, limit: usize) -> Result<Depth, ClientError> {
+        let url = format!("{}{}", Source::default().base_url(), PATH_DEPTH);
+        let resp = Rest::new()
+            .get_with_params(&url, &[("symbol", &symbol), ("limit", &limit.to_string())])
+            .await?;
+
+        Ok(serde_json::from_str(&resp.text().await?)?)
+    }
+
+    /// Loads up to limit latest trades of the symbol from the oldest to the newest.
+    pub async fn trades(symbol: String, limit: usize) -> Result<Vec<Trade>, ClientError> {
+        let url = format!("{}{}", Source::default().base_url(), PATH_TRADES);
+        let resp = Rest::new()
+            .get_with_params(&url, &[("symbol", &symbol), ("limit", &limit.to_string())])
+            .await?;
+
+        Ok(serde_json::from_str(&resp.text().await?)?)
+    }
+
+    pub async fn info() -> Result<Info, ClientError> {
+        let url = format!("{}{}", Source::default().base_url(), PATH_INFO);
+        let resp = Rest::new().get(&url).await?;
+
+        Ok(serde_json::from_str(&resp.text().await?)?)
     }
 }
@@ -0,0 +1,65 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+const ENV_API_KEY: &str = "BINANCE_API_KEY";
+const ENV_API_SECRET: &str = "BINANCE_API_SECRET";
+
+/// API key pair used for requests to private endpoints.
+#[derive(Clone)]
+pub struct Credentials {
+    pub api_key: String,
+    secret: String,
+}
+
+impl Credentials {
+    pub fn new(api_key: String, secret: String) -> Self {
+        Self { api_key, secret }
+    }
+
+    /// Reads credentials from BINANCE_API_KEY and BINANCE_API_SECRET environment variables.
+    pub fn from_env() -> Option<Self> {
+        let api_key = std::env::var(ENV_API_KEY).ok()?;
+        let secret = std::env::var(ENV_API_SECRET).ok()?;
+        if api_key.is_empty() || secret.is_empty() {
+            return None;
+        }
+
+        Some(Self::new(api_key, secret))
+    }
+
+    /// Computes HMAC SHA256 signature of the query string.
+    pub fn sign(&self, query: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes())
+            .expect("hmac accepts keys of any size");
+        mac.update(query.as_bytes());
+
+        hex::encode(mac.finalize().into_bytes())
+    }
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("api_key", &self.api_key)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod credentials_tests {
+    use super::*;
+
+    #[test]
+    fn test_sign() {
+        // example from binance api documentation
+        let c = Credentials::new(
+            "vmPUZE6mv9SD5VNHk4HlWFsOr6aKE2zvsw0MuIgwCIPy6utIco14y7Ju91duEh8A".to_string(),
+            "NhqPtmdSJYdKjVHjA7PZj4Mge3R5YNiP1e3UZjInClVN65XAbvqqM6A7H5fATj0j".to_string(),
+        );
+
+        assert_eq!(
+            c.sign("symbol=LTCBTC&side=BUY&type=LIMIT&timeInForce=GTC&quantity=1&price=0.1&recvWindow=5000&timestamp=1499827319559"),
+            "c8db56825ae71d6d79447849e617115f4a920fa2acdcab2b053c4b2838bd6b71"
+        );
+    }
+}
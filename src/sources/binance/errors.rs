@@ -11,5 +11,36 @@ quick_error! {
             from()
             display("{}", err)
         }
+        Api(code: i64, msg: String) {
+            display("binance error {}: {}", code, msg)
+        }
+        Order(err: OrderError) {
+            from()
+            display("{}", err)
+        }
+    }
+}
+
+quick_error! {
+    #[derive(Debug, PartialEq)]
+    pub enum OrderError {
+        NoLotSize(symbol: String) {
+            display("no lot size filter for {}", symbol)
+        }
+        QuantityOutOfRange(qty: f64, min: f64, max: f64) {
+            display("quantity {} is out of [{}, {}]", qty, min, max)
+        }
+        QuantityStep(qty: f64, step: f64) {
+            display("quantity {} is not a multiple of step {}", qty, step)
+        }
+        InvalidPrice(price: f64) {
+            display("limit price must be positive, got {}", price)
+        }
+        PriceTick(price: f64, tick: f64) {
+            display("price {} is not a multiple of tick {}", price, tick)
+        }
+        MinNotional(value: f64, min: f64) {
+            display("order value {} is below minimum {}", value, min)
+        }
     }
 }
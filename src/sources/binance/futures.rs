@@ -0,0 +1,137 @@
+use serde::Deserialize;
+
+use crate::network::rest::Rest;
+
+use super::{credentials::Credentials, errors::ClientError};
+
+const BASE_URL: &str = "https://fapi.binance.com";
+const PATH_INCOME: &str = "/fapi/v1/income";
+const PATH_POSITION_RISK: &str = "/fapi/v2/positionRisk";
+const INCOME_LIMIT: usize = 1000;
+
+/// Income history record of the USDⓈ-M futures account.
+#[derive(Debug, Deserialize, Default, Clone, PartialEq)]
+pub struct Income {
+    pub symbol: String,
+
+    #[serde(rename = "incomeType")]
+    pub income_type: String,
+
+    #[serde(deserialize_with = "deserialize_f64_str")]
+    pub income: f64,
+
+    pub asset: String,
+    pub time: i64,
+}
+
+/// Open position of the USDⓈ-M futures account.
+#[derive(Debug, Deserialize, Default, Clone, PartialEq)]
+pub struct Position {
+    pub symbol: String,
+
+    #[serde(rename = "positionAmt", deserialize_with = "deserialize_f64_str")]
+    pub amount: f64,
+
+    #[serde(rename = "entryPrice", deserialize_with = "deserialize_f64_str")]
+    pub entry_price: f64,
+
+    #[serde(rename = "markPrice", deserialize_with = "deserialize_f64_str")]
+    pub mark_price: f64,
+
+    #[serde(rename = "unRealizedProfit", deserialize_with = "deserialize_f64_str")]
+    pub unrealized_pnl: f64,
+}
+
+fn deserialize_f64_str<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    s.parse::<f64>().map_err(serde::de::Error::custom)
+}
+
+pub struct FuturesClient {}
+
+impl FuturesClient {
+    /// Loads all funding fee payments for the symbol starting from start_time.
+    pub async fn funding_history(
+        creds: Credentials,
+        symbol: String,
+        start_time: i64,
+    ) -> Result<Vec<Income>, ClientError> {
+        Self::income_history(creds, symbol, "FUNDING_FEE", start_time).await
+    }
+
+    /// Loads all realized profits and losses of closed positions for the symbol starting
+    /// from start_time.
+    pub async fn pnl_history(
+        creds: Credentials,
+        symbol: String,
+        start_time: i64,
+    ) -> Result<Vec<Income>, ClientError> {
+        Self::income_history(creds, symbol, "REALIZED_PNL", start_time).await
+    }
+
+    /// Loads the current position of the symbol. Returns None if there is no open position.
+    pub async fn position(
+        creds: Credentials,
+        symbol: String,
+    ) -> Result<Option<Position>, ClientError> {
+        let url = format!("{}{}", BASE_URL, PATH_POSITION_RISK);
+        let query = format!(
+            "symbol={}&timestamp={}",
+            symbol,
+            chrono::Utc::now().timestamp_millis(),
+        );
+        let signed_url = format!("{}?{}&signature={}", url, query, creds.sign(&query));
+
+        let resp = Rest::new()
+            .get_with_headers(&signed_url, &[("X-MBX-APIKEY", &creds.api_key)])
+            .await?;
+        let positions = serde_json::from_str::<Vec<Position>>(&resp.text().await?)?;
+
+        Ok(positions.into_iter().find(|p| p.amount != 0.0))
+    }
+
+    async fn income_history(
+        creds: Credentials,
+        symbol: String,
+        income_type: &str,
+        start_time: i64,
+    ) -> Result<Vec<Income>, ClientError> {
+        let url = format!("{}{}", BASE_URL, PATH_INCOME);
+        let rest = Rest::new();
+
+        let mut res: Vec<Income> = vec![];
+        let mut from = start_time;
+        loop {
+            let query = format!(
+                "symbol={}&incomeType={}&startTime={}&limit={}&timestamp={}",
+                symbol,
+                income_type,
+                from,
+                INCOME_LIMIT,
+                chrono::Utc::now().timestamp_millis(),
+            );
+            let signed_url = format!("{}?{}&signature={}", url, query, creds.sign(&query));
+
+            let resp = rest
+                .get_with_headers(&signed_url, &[("X-MBX-APIKEY", &creds.api_key)])
+                .await?;
+            let json_str = &resp.text().await?;
+            let page = serde_json::from_str::<Vec<Income>>(json_str)?;
+
+            let page_len = page.len();
+            if let Some(last) = page.last() {
+                from = last.time + 1;
+            }
+            res.extend(page);
+
+            if page_len < INCOME_LIMIT {
+                break;
+            }
+        }
+
+        Ok(res)
+    }
+}
@@ -1,18 +1,51 @@
 #[derive(Debug, Clone, Copy)]
 pub enum Interval {
     Minute,
+    FiveMinutes,
+    FifteenMinutes,
     Hour,
+    FourHours,
     Day,
 }
 
 impl Interval {
+    /// All supported intervals from the shortest.
+    pub const ALL: [Interval; 6] = [
+        Interval::Minute,
+        Interval::FiveMinutes,
+        Interval::FifteenMinutes,
+        Interval::Hour,
+        Interval::FourHours,
+        Interval::Day,
+    ];
+
     pub fn as_str(&self) -> &str {
         match self {
             Interval::Minute => "1m",
+            Interval::FiveMinutes => "5m",
+            Interval::FifteenMinutes => "15m",
             Interval::Hour => "1h",
+            Interval::FourHours => "4h",
             Interval::Day => "1d",
         }
     }
+
+    /// Interval duration in milliseconds.
+    pub fn millis(&self) -> i64 {
+        match self {
+            Interval::Minute => 60 * 1000,
+            Interval::FiveMinutes => 5 * 60 * 1000,
+            Interval::FifteenMinutes => 15 * 60 * 1000,
+            Interval::Hour => 60 * 60 * 1000,
+            Interval::FourHours => 4 * 60 * 60 * 1000,
+            Interval::Day => 60 * 60 * 24 * 1000,
+        }
+    }
+
+    /// Parses interval from its binance representation.
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|i| i.as_str() == s)
+    }
 }
 
 impl PartialEq for Interval {
@@ -20,3 +53,17 @@ impl PartialEq for Interval {
         format!("{:?}", self) == format!("{:?}", other)
     }
 }
+
+#[cfg(test)]
+mod interval_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        Interval::ALL
+            .into_iter()
+            .for_each(|i| assert_eq!(Interval::parse(i.as_str()), Some(i)));
+        assert_eq!(Interval::parse("3m"), None);
+        assert_eq!(Interval::FourHours.millis(), 4 * Interval::Hour.millis());
+    }
+}
@@ -1,7 +1,12 @@
 mod client;
 mod interval;
+mod source;
 
 pub use self::client::*;
 pub use self::interval::*;
+pub use self::source::*;
 
-pub mod errors;
\ No newline at end of file
+pub mod credentials;
+pub mod errors;
+pub mod futures;
+pub mod trading;
@@ -0,0 +1,32 @@
+/// Exchange serving klines with the binance spot api.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Source {
+    #[default]
+    Binance,
+    BinanceUs,
+}
+
+impl Source {
+    /// Sources which can stand in for the primary one.
+    pub fn alternates() -> [Source; 1] {
+        [Source::BinanceUs]
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Source::Binance => "Binance",
+            Source::BinanceUs => "Binance.US",
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        match self {
+            Source::Binance => "https://api.binance.com",
+            Source::BinanceUs => "https://api.binance.us",
+        }
+    }
+
+    pub fn is_primary(&self) -> bool {
+        *self == Source::default()
+    }
+}
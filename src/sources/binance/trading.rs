@@ -0,0 +1,374 @@
+use reqwest::Method;
+use serde::Deserialize;
+
+use crate::{netstrat::symbol_info::decimals, network::rest::Rest};
+
+use super::{
+    client::Symbol,
+    credentials::Credentials,
+    errors::{ClientError, OrderError},
+    source::Source,
+};
+
+const PATH_ORDER: &str = "/api/v3/order";
+const PATH_OPEN_ORDERS: &str = "/api/v3/openOrders";
+const PATH_MY_TRADES: &str = "/api/v3/myTrades";
+const TRADES_LIMIT: usize = 50;
+/// Relative tolerance of step checks for float quantities and prices.
+const STEP_TOLERANCE: f64 = 1e-6;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrderSide {
+    Buy,
+    Sell,
+}
+
+impl OrderSide {
+    pub fn as_str(&self) -> &str {
+        match self {
+            OrderSide::Buy => "BUY",
+            OrderSide::Sell => "SELL",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrderType {
+    Market,
+    /// Good till cancelled limit order at the price.
+    Limit(f64),
+}
+
+/// Spot order to be submitted to the exchange.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewOrder {
+    pub symbol: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub qty: f64,
+}
+
+impl NewOrder {
+    /// Checks the order against trading rules of the symbol, so it is not rejected
+    /// by the exchange. Value of market orders is not known and is not checked.
+    pub fn validate(&self, symbol: &Symbol) -> Result<(), OrderError> {
+        let lot = symbol
+            .lot_size()
+            .ok_or_else(|| OrderError::NoLotSize(self.symbol.clone()))?;
+        if self.qty < lot.min || self.qty > lot.max {
+            return Err(OrderError::QuantityOutOfRange(self.qty, lot.min, lot.max));
+        }
+        if !is_multiple(self.qty, lot.step) {
+            return Err(OrderError::QuantityStep(self.qty, lot.step));
+        }
+
+        if let OrderType::Limit(price) = self.order_type {
+            if price.is_nan() || price <= 0.0 {
+                return Err(OrderError::InvalidPrice(price));
+            }
+            if let Some(tick) = symbol.tick_size() {
+                if !is_multiple(price, tick) {
+                    return Err(OrderError::PriceTick(price, tick));
+                }
+            }
+            if let Some(min) = symbol.min_notional() {
+                if self.qty * price < min {
+                    return Err(OrderError::MinNotional(self.qty * price, min));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Query of the order request without the timestamp. Quantity and price are
+    /// written with the precision of the lot step and the price tick.
+    fn query(&self, symbol: &Symbol) -> String {
+        let qty_decimals = symbol.lot_size().map_or(8, |lot| decimals(lot.step));
+        let mut query = format!(
+            "symbol={}&side={}&quantity={:.*}&newOrderRespType=RESULT",
+            self.symbol,
+            self.side.as_str(),
+            qty_decimals,
+            self.qty,
+        );
+        match self.order_type {
+            OrderType::Market => query.push_str("&type=MARKET"),
+            OrderType::Limit(price) => {
+                let price_decimals = symbol.tick_size().map_or(8, decimals);
+                query.push_str(&format!(
+                    "&type=LIMIT&timeInForce=GTC&price={price:.price_decimals$}"
+                ));
+            }
+        }
+
+        query
+    }
+}
+
+fn is_multiple(value: f64, step: f64) -> bool {
+    if step <= 0.0 {
+        return true;
+    }
+    let steps = value / step;
+    (steps - steps.round()).abs() <= STEP_TOLERANCE * steps.abs().max(1.0)
+}
+
+/// Order of the account as returned by the exchange.
+#[derive(Debug, Deserialize, Default, Clone, PartialEq)]
+pub struct Order {
+    pub symbol: String,
+
+    #[serde(rename = "orderId")]
+    pub order_id: i64,
+
+    #[serde(deserialize_with = "deserialize_f64_str")]
+    pub price: f64,
+
+    #[serde(rename = "origQty", deserialize_with = "deserialize_f64_str")]
+    pub orig_qty: f64,
+
+    #[serde(rename = "executedQty", deserialize_with = "deserialize_f64_str")]
+    pub executed_qty: f64,
+
+    pub status: String,
+
+    #[serde(rename = "type")]
+    pub order_type: String,
+
+    pub side: String,
+
+    /// Creation time of open orders, transaction time of placed and cancelled orders.
+    #[serde(alias = "transactTime", default)]
+    pub time: i64,
+}
+
+/// Fill of an order of the account.
+#[derive(Debug, Deserialize, Default, Clone, PartialEq)]
+pub struct Fill {
+    pub symbol: String,
+    pub id: i64,
+
+    #[serde(rename = "orderId")]
+    pub order_id: i64,
+
+    #[serde(deserialize_with = "deserialize_f64_str")]
+    pub price: f64,
+
+    #[serde(deserialize_with = "deserialize_f64_str")]
+    pub qty: f64,
+
+    #[serde(deserialize_with = "deserialize_f64_str")]
+    pub commission: f64,
+
+    #[serde(rename = "commissionAsset")]
+    pub commission_asset: String,
+
+    pub time: i64,
+
+    #[serde(rename = "isBuyer")]
+    pub is_buyer: bool,
+}
+
+#[derive(Deserialize)]
+struct ApiError {
+    code: i64,
+    msg: String,
+}
+
+fn deserialize_f64_str<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    s.parse::<f64>().map_err(serde::de::Error::custom)
+}
+
+/// Client of signed spot trading endpoints. Every request acts on the real account.
+pub struct TradingClient {}
+
+impl TradingClient {
+    /// Validates and submits the order. Returns the order as accepted by the exchange.
+    pub async fn place_order(
+        creds: Credentials,
+        order: NewOrder,
+        symbol: Symbol,
+    ) -> Result<Order, ClientError> {
+        order.validate(&symbol)?;
+        TradingClient::signed(Method::POST, &creds, PATH_ORDER, order.query(&symbol)).await
+    }
+
+    pub async fn cancel_order(
+        creds: Credentials,
+        symbol: String,
+        order_id: i64,
+    ) -> Result<Order, ClientError> {
+        let query = format!("symbol={symbol}&orderId={order_id}");
+        TradingClient::signed(Method::DELETE, &creds, PATH_ORDER, query).await
+    }
+
+    pub async fn open_orders(
+        creds: Credentials,
+        symbol: String,
+    ) -> Result<Vec<Order>, ClientError> {
+        let query = format!("symbol={symbol}");
+        TradingClient::signed(Method::GET, &creds, PATH_OPEN_ORDERS, query).await
+    }
+
+    /// Loads the latest fills of the account in the symbol.
+    pub async fn fills(creds: Credentials, symbol: String) -> Result<Vec<Fill>, ClientError> {
+        let query = format!("symbol={symbol}&limit={TRADES_LIMIT}");
+        TradingClient::signed(Method::GET, &creds, PATH_MY_TRADES, query).await
+    }
+
+    /// Sends the query signed with the current timestamp and parses the response.
+    /// Error responses are returned as api errors with the code and message of the exchange.
+    async fn signed<T: serde::de::DeserializeOwned>(
+        method: Method,
+        creds: &Credentials,
+        path: &str,
+        query: String,
+    ) -> Result<T, ClientError> {
+        let query = format!(
+            "{query}&timestamp={}",
+            chrono::Utc::now().timestamp_millis()
+        );
+        let url = format!(
+            "{}{path}?{query}&signature={}",
+            Source::default().base_url(),
+            creds.sign(&query)
+        );
+
+        let resp = Rest::new()
+            .send_with_headers(method, &url, &[("X-MBX-APIKEY", &creds.api_key)])
+            .await?;
+        let status = resp.status();
+        let json_str = resp.text().await?;
+        if !status.is_success() {
+            if let Ok(err) = serde_json::from_str::<ApiError>(&json_str) {
+                return Err(ClientError::Api(err.code, err.msg));
+            }
+        }
+
+        Ok(serde_json::from_str(&json_str)?)
+    }
+}
+
+#[cfg(test)]
+mod trading_tests {
+    use super::*;
+
+    fn symbol() -> Symbol {
+        serde_json::from_str(
+            r#"{
+                "symbol": "BTCUSDT",
+                "status": "TRADING",
+                "baseAsset": "BTC",
+                "baseAssetPrecision": 8,
+                "quoteAsset": "USDT",
+                "quotePrecision": 8,
+                "quoteAssetPrecision": 8,
+                "baseCommissionPrecision": 8,
+                "quoteCommissionPrecision": 8,
+                "icebergAllowed": true,
+                "ocoAllowed": true,
+                "quoteOrderQtyMarketAllowed": true,
+                "allowTrailingStop": true,
+                "isSpotTradingAllowed": true,
+                "isMarginTradingAllowed": true,
+                "filters": [
+                    {"filterType": "PRICE_FILTER", "minPrice": "0.01", "tickSize": "0.01000000"},
+                    {
+                        "filterType": "LOT_SIZE",
+                        "minQty": "0.00001000",
+                        "maxQty": "9000.00000000",
+                        "stepSize": "0.00001000"
+                    },
+                    {"filterType": "NOTIONAL", "minNotional": "5.00000000"}
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn order(qty: f64, order_type: OrderType) -> NewOrder {
+        NewOrder {
+            symbol: "BTCUSDT".to_string(),
+            side: OrderSide::Buy,
+            order_type,
+            qty,
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let symbol = symbol();
+        assert_eq!(order(0.0123, OrderType::Market).validate(&symbol), Ok(()));
+        assert_eq!(
+            order(0.00012, OrderType::Limit(50000.01)).validate(&symbol),
+            Ok(())
+        );
+
+        assert!(matches!(
+            order(0.000001, OrderType::Market).validate(&symbol),
+            Err(OrderError::QuantityOutOfRange(..))
+        ));
+        assert!(matches!(
+            order(0.000015, OrderType::Market).validate(&symbol),
+            Err(OrderError::QuantityStep(..))
+        ));
+        assert!(matches!(
+            order(0.01, OrderType::Limit(100.005)).validate(&symbol),
+            Err(OrderError::PriceTick(..))
+        ));
+        assert!(matches!(
+            order(0.01, OrderType::Limit(100.0)).validate(&symbol),
+            Err(OrderError::MinNotional(..))
+        ));
+        assert!(matches!(
+            order(0.01, OrderType::Limit(0.0)).validate(&symbol),
+            Err(OrderError::InvalidPrice(..))
+        ));
+        assert!(matches!(
+            order(1.0, OrderType::Market).validate(&Symbol::default()),
+            Err(OrderError::NoLotSize(..))
+        ));
+    }
+
+    #[test]
+    fn test_query() {
+        let symbol = symbol();
+        assert_eq!(
+            order(0.0123, OrderType::Market).query(&symbol),
+            "symbol=BTCUSDT&side=BUY&quantity=0.01230&newOrderRespType=RESULT&type=MARKET"
+        );
+
+        let mut limit = order(1.5, OrderType::Limit(20000.1));
+        limit.side = OrderSide::Sell;
+        assert_eq!(
+            limit.query(&symbol),
+            "symbol=BTCUSDT&side=SELL&quantity=1.50000&newOrderRespType=RESULT\
+             &type=LIMIT&timeInForce=GTC&price=20000.10"
+        );
+    }
+
+    #[test]
+    fn test_parse_order() {
+        let order: Order = serde_json::from_str(
+            r#"{
+                "symbol": "BTCUSDT",
+                "orderId": 28,
+                "price": "0.00000000",
+                "origQty": "10.00000000",
+                "executedQty": "10.00000000",
+                "status": "FILLED",
+                "type": "MARKET",
+                "side": "SELL",
+                "transactTime": 1507725176595
+            }"#,
+        )
+        .unwrap();
+        assert_eq!((order.order_id, order.time), (28, 1507725176595));
+        assert_eq!(order.executed_qty, 10.0);
+    }
+}
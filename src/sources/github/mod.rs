@@ -0,0 +1,76 @@
+use serde::Deserialize;
+
+use crate::network::rest::Rest;
+
+use self::errors::ClientError;
+
+pub mod errors;
+
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/qzarx1/netstrat/releases/latest";
+
+/// Published release of the app.
+#[derive(Debug, Deserialize, Default, Clone, PartialEq)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Release notes in markdown.
+    #[serde(default)]
+    pub body: Option<String>,
+    pub html_url: String,
+}
+
+impl Release {
+    /// Compares the release tag, e.g. "v0.2.0", with the version numerically.
+    pub fn is_newer_than(&self, version: &str) -> bool {
+        parse_version(&self.tag_name) > parse_version(version)
+    }
+}
+
+fn parse_version(v: &str) -> Vec<u64> {
+    v.trim_start_matches('v')
+        .split(['.', '-', '+'])
+        .map_while(|part| part.parse().ok())
+        .collect()
+}
+
+pub struct GithubClient {}
+
+impl GithubClient {
+    pub async fn latest_release() -> Result<Release, ClientError> {
+        let resp = Rest::new()
+            .get_with_headers(
+                LATEST_RELEASE_URL,
+                &[
+                    ("User-Agent", "netstrat"),
+                    ("Accept", "application/vnd.github+json"),
+                ],
+            )
+            .await?
+            .error_for_status()?;
+
+        Ok(serde_json::from_str(&resp.text().await?)?)
+    }
+}
+
+#[cfg(test)]
+mod github_tests {
+    use super::*;
+
+    fn release(tag: &str) -> Release {
+        Release {
+            tag_name: tag.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_is_newer_than() {
+        assert!(release("v0.2.0").is_newer_than("0.1.0"));
+        assert!(release("0.1.10").is_newer_than("0.1.9"));
+        assert!(release("v1.0").is_newer_than("0.9.9"));
+        assert!(!release("v0.1.0").is_newer_than("0.1.0"));
+        assert!(!release("v0.1.0-rc1").is_newer_than("0.1.0"));
+        assert!(!release("latest").is_newer_than("0.1.0"));
+    }
+}
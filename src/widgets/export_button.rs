@@ -0,0 +1,40 @@
+use egui::{Response, Ui, Widget};
+use tracing::error;
+
+use crate::netstrat::{
+    export::{table_csv, Table},
+    toasts::Toasts,
+};
+
+/// Button exporting analytics table to csv file when clicked.
+/// Table is built lazily only when export is requested.
+pub struct ExportButton<'a> {
+    name: String,
+    table: Box<dyn FnOnce() -> Table + 'a>,
+}
+
+impl<'a> ExportButton<'a> {
+    pub fn new(name: impl ToString, table: impl FnOnce() -> Table + 'a) -> Self {
+        Self {
+            name: name.to_string(),
+            table: Box::new(table),
+        }
+    }
+}
+
+impl<'a> Widget for ExportButton<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let btn = ui.button("export");
+        if btn.clicked() {
+            match table_csv(&self.name, &(self.table)()) {
+                Ok(file_name) => Toasts::info(format!("Exported {file_name}")),
+                Err(err) => {
+                    error!("Failed to export {}: {err}.", self.name);
+                    Toasts::error(format!("Failed to export {}", self.name), err);
+                }
+            }
+        }
+
+        btn
+    }
+}
@@ -0,0 +1,140 @@
+use egui::{
+    plot::{HLine, LineStyle, PlotUi, Text, Value},
+    Align2, Color32, CursorIcon, RichText, Ui,
+};
+use tracing::info;
+
+use crate::netstrat::alert::Alert;
+
+use super::{
+    candles::format_price,
+    pointer::{PlotPointer, GRAB_DISTANCE},
+};
+
+/// Alert lines drawn on the candles plot. Lines are created from the plot context menu
+/// and dragged vertically to edit alert levels.
+#[derive(Default)]
+pub struct AlertLines {
+    alerts: Vec<Alert>,
+    last: Option<f64>,
+    hovered: Option<usize>,
+    dragging: Option<usize>,
+    /// Price under the pointer and the hovered line when the context menu was opened.
+    menu: Option<(f64, Option<usize>)>,
+}
+
+impl AlertLines {
+    /// Checks alerts against the latest price. Returns levels of alerts which have just triggered.
+    pub fn check(&mut self, price: f64) -> Vec<f64> {
+        self.last = Some(price);
+        self.alerts
+            .iter_mut()
+            .filter_map(|a| a.check(price).then_some(a.price))
+            .collect()
+    }
+
+    pub fn triggered(&self) -> impl Iterator<Item = &Alert> {
+        self.alerts.iter().filter(|a| a.triggered)
+    }
+
+    pub fn add(&mut self, price: f64) {
+        info!("Adding alert at {price}.");
+        self.alerts
+            .push(Alert::new(price, self.last.unwrap_or(price)));
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Plot should not pan while an alert line is grabbed.
+    pub fn allow_drag(&self) -> bool {
+        self.hovered.is_none() && self.dragging.is_none()
+    }
+
+    /// Draws alert lines and moves the grabbed one with the pointer.
+    /// Alert prices are mapped to the plot with y_map and back with unmap.
+    pub fn show(
+        &mut self,
+        plot_ui: &mut PlotUi,
+        y_map: &dyn Fn(f64) -> f64,
+        unmap: &dyn Fn(f64) -> f64,
+    ) {
+        let pointer = PlotPointer::read(plot_ui, self.dragging.is_some());
+        let right = plot_ui.plot_bounds().max()[0];
+
+        self.hovered = pointer.pos.and_then(|pos| {
+            self.alerts.iter().position(|a| {
+                let y = plot_ui
+                    .screen_from_plot(Value::new(right, y_map(a.price)))
+                    .y;
+                (y - pos.y).abs() <= GRAB_DISTANCE
+            })
+        });
+        if pointer.pressed && self.dragging.is_none() {
+            self.dragging = self.hovered;
+        }
+
+        if let (Some(i), Some(pos)) = (self.dragging, pointer.pos) {
+            let price = unmap(plot_ui.plot_from_screen(pos).y);
+            self.alerts[i].move_to(price, self.last.unwrap_or(price));
+        }
+        if let (Some(i), false) = (self.dragging, pointer.down) {
+            info!("Moved alert to {}.", self.alerts[i].price);
+            self.dragging = None;
+        }
+        if self.hovered.is_some() || self.dragging.is_some() {
+            plot_ui.ctx().output().cursor_icon = CursorIcon::ResizeVertical;
+        }
+
+        if pointer.secondary_down {
+            self.menu = pointer
+                .pos
+                .map(|pos| (unmap(plot_ui.plot_from_screen(pos).y), self.hovered));
+        }
+
+        self.alerts.iter().for_each(|a| {
+            let color = match a.triggered {
+                true => Color32::GRAY,
+                false => Color32::GOLD,
+            };
+            plot_ui.hline(
+                HLine::new(y_map(a.price))
+                    .color(color)
+                    .style(LineStyle::dashed_dense())
+                    .name("alerts"),
+            );
+            plot_ui.text(
+                Text::new(
+                    Value::new(right, y_map(a.price)),
+                    RichText::new(format!("🔔 {}", format_price(a.price))).color(color),
+                )
+                .anchor(Align2::RIGHT_BOTTOM),
+            );
+        });
+    }
+
+    /// Draws context menu entries for the point where the menu was opened.
+    pub fn menu_ui(&mut self, ui: &mut Ui) {
+        let (price, hovered) = match self.menu {
+            Some(menu) => menu,
+            None => return,
+        };
+
+        if ui
+            .button(format!("🔔 alert at {}", format_price(price)))
+            .clicked()
+        {
+            self.add(price);
+            ui.close_menu();
+        }
+        if let Some(i) = hovered {
+            if ui.button("remove alert").clicked() {
+                info!("Removing alert at {}.", self.alerts[i].price);
+                self.alerts.remove(i);
+                self.menu = None;
+                ui.close_menu();
+            }
+        }
+    }
+}
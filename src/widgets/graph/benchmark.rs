@@ -0,0 +1,159 @@
+use egui::{Grid, TextEdit, Ui};
+use poll_promise::Promise;
+use tracing::{error, info};
+
+use crate::{
+    netstrat::{
+        correlation::{beta, Beta},
+        export::analytics::beta_table,
+    },
+    sources::binance::{errors::ClientError, Client, Interval, Kline},
+    widgets::ExportButton,
+};
+
+/// Time range of the charted klines the benchmark is loaded for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Range {
+    interval: Interval,
+    start: i64,
+    end: i64,
+    limit: usize,
+}
+
+/// Beta and correlation of the charted symbol against a benchmark symbol
+/// loaded over the same time range.
+pub struct Benchmark {
+    input: String,
+    symbol: String,
+    klines: Vec<Kline>,
+    stats: Option<Beta>,
+    range: Option<Range>,
+    promise: Option<Promise<Result<Vec<Kline>, ClientError>>>,
+}
+
+impl Default for Benchmark {
+    fn default() -> Self {
+        Self {
+            input: String::new(),
+            symbol: "BTCUSDT".to_string(),
+            klines: vec![],
+            stats: None,
+            range: None,
+            promise: None,
+        }
+    }
+}
+
+impl Benchmark {
+    /// Reloads the benchmark for the range of newly charted klines.
+    pub fn set_range(&mut self, interval: Interval, klines: &[Kline], limit: usize) {
+        let range = match (klines.first(), klines.last()) {
+            (Some(first), Some(last)) => Range {
+                interval,
+                start: first.t_open,
+                end: last.t_close,
+                limit,
+            },
+            _ => return,
+        };
+        if self.range == Some(range) {
+            return;
+        }
+
+        self.range = Some(range);
+        self.load();
+    }
+
+    fn load(&mut self) {
+        let range = match self.range {
+            Some(range) => range,
+            None => return,
+        };
+        let symbol = self.symbol.clone();
+        info!("Loading benchmark {symbol}: {range:?}.");
+
+        self.promise = Some(Promise::spawn_async(async move {
+            Client::kline_range(symbol, range.interval, range.start, range.end, range.limit).await
+        }));
+    }
+
+    /// Checks the benchmark download. Returns true if the benchmark klines changed.
+    pub fn poll(&mut self) -> bool {
+        let res = match self.promise.as_ref().and_then(|p| p.ready()) {
+            Some(res) => res,
+            None => return false,
+        };
+        match res {
+            Ok(klines) => self.klines = klines.clone(),
+            Err(err) => {
+                error!("Failed to load benchmark {}: {err}.", self.symbol);
+                self.klines = vec![];
+            }
+        }
+        self.promise = None;
+
+        true
+    }
+
+    /// Recomputes stats of the charted klines.
+    pub fn update(&mut self, klines: &[Kline]) {
+        self.stats = beta(klines, &self.klines);
+    }
+
+    /// Draws the stats of the charted symbol and the benchmark input.
+    pub fn ui(&mut self, ui: &mut Ui, symbol: &str) {
+        Grid::new(ui.id().with("benchmark stats"))
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("benchmark");
+                ui.horizontal(|ui| {
+                    ui.label(&self.symbol);
+                    if self.promise.is_some() {
+                        ui.spinner();
+                    }
+                });
+                ui.end_row();
+
+                let stats = self.stats;
+                let value = |v: Option<f64>| v.map_or("-".to_string(), |v| format!("{v:.3}"));
+                ui.label("beta");
+                ui.label(value(stats.map(|s| s.beta)));
+                ui.end_row();
+                ui.label("correlation");
+                ui.label(value(stats.map(|s| s.corr)));
+                ui.end_row();
+                ui.label("returns");
+                ui.label(stats.map_or("-".to_string(), |s| s.returns.to_string()));
+                ui.end_row();
+            });
+
+        if let Some(stats) = self.stats {
+            ui.add(ExportButton::new(
+                format!("{symbol}-{}-beta", self.symbol),
+                || beta_table(symbol, &self.symbol, stats),
+            ));
+        }
+
+        ui.horizontal(|ui| {
+            ui.add(
+                TextEdit::singleline(&mut self.input)
+                    .hint_text(self.symbol.as_str())
+                    .desired_width(80.0),
+            );
+            let symbol = self.input.to_uppercase();
+            if ui
+                .add_enabled(
+                    !symbol.is_empty() && symbol != self.symbol,
+                    egui::Button::new("apply"),
+                )
+                .clicked()
+            {
+                self.symbol = symbol;
+                self.input = String::new();
+                self.klines = vec![];
+                self.stats = None;
+                self.load();
+            }
+        });
+    }
+}
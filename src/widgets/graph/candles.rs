@@ -1,15 +1,47 @@
 use chrono::{DateTime, Utc};
 use crossbeam::channel::{unbounded, Sender};
 use egui::{
-    plot::{BoxElem, BoxPlot, BoxSpread, LinkedAxisGroup, Plot, PlotBounds},
-    Color32, Id, Response, Stroke, Vec2, Widget,
+    plot::{
+        BoxElem, BoxPlot, BoxSpread, HLine, Legend, Line, LineStyle, LinkedAxisGroup, MarkerShape,
+        Plot, PlotBounds, Points, VLine, Value, Values,
+    },
+    Button, Color32, DragValue, Id, Response, Stroke, Ui, Vec2, Widget,
 };
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info};
 
-use crate::netstrat::{bounds::Bounds, data::Data};
+use crate::netstrat::{
+    bounds::Bounds,
+    chart_type::ChartType,
+    data::Data,
+    export::{klines_tsv, visible_klines},
+    power::LowPower,
+    price_scale::PriceScale,
+    theme::ChartColors,
+    toasts::Toasts,
+};
+
+use super::{
+    alerts::AlertLines,
+    drawings::DrawingTools,
+    indicator::{builtin, IndicatorManager, Target},
+    last_price,
+    layer_cache::LayerCache,
+    profile::Profile,
+    series::{map_y, x, SeriesLine},
+    shading::Shading,
+};
 
 const BOUNDS_SEND_DELAY_MILLIS: i64 = 300;
+/// Day separators are not drawn if there are more days loaded, as they would cover the plot.
+const MAX_DAY_SEPARATORS: usize = 100;
+/// Formats price keeping 4 significant digits for prices below 1000.
+pub fn format_price(price: f64) -> String {
+    let digits = 3i32
+        .saturating_sub(price.abs().log10().floor() as i32)
+        .clamp(0, 8) as usize;
+    format!("{price:.digits$}")
+}
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 struct CachedPlotData {
@@ -18,9 +50,29 @@ struct CachedPlotData {
 }
 
 pub struct Candles {
+    pub indicators: IndicatorManager,
+    pub profile: Profile,
+    pub scale: PriceScale,
+    pub chart_type: ChartType,
+    pub shading: Shading,
+    pub alerts: AlertLines,
+    pub drawings: DrawingTools,
+    /// Indicators skip the forming kline if set.
+    pub confirmed_only: bool,
+    /// Name of the alternate source klines are loaded from.
+    source: Option<String>,
     data: Data,
     val: Vec<BoxElem>,
+    closes: Vec<Value>,
+    comparisons: Vec<SeriesLine>,
+    day_starts: Vec<i64>,
+    mapped: LayerCache<(PriceScale, f64), Vec<BoxElem>>,
+    anomalies: Vec<Value>,
     axes_group: LinkedAxisGroup,
+    /// Time bounds to show on the next frame.
+    view: Option<Bounds>,
+    /// Changed with every view, so the plot starts with a new memory.
+    view_id: usize,
     bounds_pub: Sender<Bounds>,
     incremental_drag_diff: f32,
     last_time_drag_happened: DateTime<Utc>,
@@ -33,9 +85,31 @@ impl Default for Candles {
         let (s_bounds, _) = unbounded();
 
         Self {
+            indicators: {
+                let mut indicators = IndicatorManager::default();
+                builtin()
+                    .into_iter()
+                    .for_each(|(_, create)| indicators.add(create(), false));
+                indicators
+            },
+            profile: Default::default(),
+            scale: Default::default(),
+            chart_type: Default::default(),
+            shading: Default::default(),
+            alerts: Default::default(),
+            drawings: Default::default(),
+            confirmed_only: false,
+            source: None,
             data: Default::default(),
             val: Default::default(),
+            closes: Default::default(),
+            comparisons: Default::default(),
+            day_starts: Default::default(),
+            mapped: Default::default(),
+            anomalies: Default::default(),
             axes_group: LinkedAxisGroup::new(false, false),
+            view: None,
+            view_id: 0,
             bounds_pub: s_bounds,
             last_time_drag_happened: Utc::now(),
             drag_happened: Default::default(),
@@ -87,8 +161,184 @@ impl Candles {
             })
             .collect();
 
+        let confirmed = match self.confirmed_only {
+            true => Data::confirmed(&data.vals, Utc::now().timestamp_millis()),
+            false => &data.vals,
+        };
+        match confirmed.len() {
+            // nothing is confirmed yet, so indicators of the previous klines are cleared
+            0 => self.indicators.set_data(Data::default()),
+            n if n == data.vals.len() => self.indicators.set_data(data.clone()),
+            _ => {
+                let mut confirmed = Data::new(confirmed.to_vec());
+                confirmed.session = data.session.clone();
+                self.indicators.set_data(confirmed);
+            }
+        }
+        self.profile.set_data(data.clone());
+        self.closes = data
+            .vals
+            .iter()
+            .map(|k| Value::new(x(k), k.close as f64))
+            .collect();
+        self.day_starts = data.day_starts();
+        self.drawings.set_step(data.step());
         self.data = data;
         self.val = val;
+        self.mapped.invalidate();
+        self.anomalies = vec![];
+    }
+
+    /// Draws price scale mode controls.
+    pub fn scale_ui(&mut self, ui: &mut egui::Ui) {
+        ui.selectable_value(&mut self.scale, PriceScale::Auto, "auto");
+        if ui
+            .selectable_label(matches!(self.scale, PriceScale::Fixed { .. }), "fixed")
+            .clicked()
+        {
+            self.scale = PriceScale::Fixed {
+                min: self.data.min_y(),
+                max: self.data.max_y(),
+            };
+        }
+        ui.selectable_value(&mut self.scale, PriceScale::Percent, "percent");
+        ui.selectable_value(&mut self.scale, PriceScale::Log, "log");
+
+        if let PriceScale::Fixed { min, max } = &mut self.scale {
+            let speed = ((*max - *min).abs() / 100.0).max(f64::EPSILON);
+            ui.horizontal(|ui| {
+                ui.add(DragValue::new(min).speed(speed));
+                ui.label("min");
+            });
+            ui.horizontal(|ui| {
+                ui.add(DragValue::new(max).speed(speed));
+                ui.label("max");
+            });
+            if *min > *max {
+                std::mem::swap(min, max);
+            }
+        }
+    }
+
+    /// Close of the first kline visible on the previous frame, used as the percentage base.
+    fn percent_base(&self) -> Option<f64> {
+        self.data
+            .vals
+            .iter()
+            .find(|k| x(k) >= self.bounds.0 as f64)
+            .or_else(|| self.data.vals.first())
+            .map(|k| k.close as f64)
+            .filter(|c| *c != 0.0)
+    }
+
+    /// Sets close prices of compared symbols. They are normalized to the price of the
+    /// first visible kline so they start from the same point.
+    pub fn set_comparisons(&mut self, lines: Vec<SeriesLine>) {
+        self.comparisons = lines;
+    }
+
+    /// Returns time span currently visible on the plot.
+    pub fn bounds(&self) -> Bounds {
+        self.bounds
+    }
+
+    /// Shows the time bounds on the next frame fitting prices of klines inside them.
+    /// Egui plots can not be moved from outside, so the plot starts with a new memory
+    /// and all plots linked to it get a new axes group with no bounds shared yet.
+    pub fn set_view(&mut self, bounds: Bounds, axes_group: LinkedAxisGroup) {
+        self.view = Some(bounds);
+        self.view_id += 1;
+        self.axes_group = axes_group;
+    }
+
+    pub fn view_pending(&self) -> bool {
+        self.view.is_some()
+    }
+
+    /// Time span of all klines with a kline wide margin on both sides.
+    pub fn data_bounds(&self) -> Option<Bounds> {
+        let (first, last) = (self.data.vals.first()?, self.data.vals.last()?);
+        let step = self.data.step();
+
+        Some(Bounds(first.t_open - step, last.t_close + step))
+    }
+
+    /// Price range of klines inside the bounds with a margin.
+    fn price_range(&self, bounds: Bounds) -> (f64, f64) {
+        let (min, max) = self
+            .data
+            .vals
+            .iter()
+            .filter(|k| k.t_close >= bounds.0 && k.t_open <= bounds.1)
+            .fold((f64::MAX, f64::MIN), |(min, max), k| {
+                (min.min(k.low as f64), max.max(k.high as f64))
+            });
+        if min > max {
+            return (self.data.min_y(), self.data.max_y());
+        }
+
+        let margin = (max - min) * 0.05;
+        (min - margin, max + margin)
+    }
+
+    /// Labels the price series with the source when klines come from an alternate one.
+    pub fn set_source(&mut self, source: Option<String>) {
+        self.source = source;
+    }
+
+    /// Plot pans only while nothing drawn on it is being edited.
+    fn allow_drag(&self) -> bool {
+        self.alerts.allow_drag() && self.drawings.allow_drag()
+    }
+
+    /// Draws context menu entries copying the kline under the pointer and klines measured
+    /// with the ruler, or visible ones if nothing was measured, as tsv.
+    fn copy_menu_ui(&self, ui: &mut Ui) {
+        let hovered = self.drawings.menu_time().and_then(|t| {
+            self.data
+                .vals
+                .iter()
+                .find(|k| k.t_open as f64 <= t && t <= k.t_close as f64)
+        });
+        let (range, label) = match self.drawings.measured() {
+            Some(measured) => (measured, "measured"),
+            None => (self.bounds, "visible"),
+        };
+        let selected = visible_klines(&self.data.vals, range);
+
+        let mut copied = None;
+        if let Some(k) = hovered {
+            if ui.button("📋 copy candle").clicked() {
+                copied = Some(vec![*k]);
+            }
+        }
+        if ui
+            .add_enabled(
+                !selected.is_empty(),
+                Button::new(format!("📋 copy {} {label} candles", selected.len())),
+            )
+            .clicked()
+        {
+            copied = Some(selected);
+        }
+
+        if let Some(klines) = copied {
+            info!("Copying {} klines.", klines.len());
+            ui.output().copied_text = klines_tsv(&klines);
+            Toasts::info(format!("Copied {} candles", klines.len()));
+            ui.close_menu();
+        }
+    }
+
+    /// Marks klines with the given indexes as volume anomalies.
+    pub fn set_anomalies(&mut self, idxs: &[usize]) {
+        self.anomalies = idxs
+            .iter()
+            .filter_map(|i| {
+                let k = self.data.vals.get(*i)?;
+                Some(Value::new(x(k), k.low as f64))
+            })
+            .collect();
     }
 }
 
@@ -109,57 +359,218 @@ impl Widget for &mut Candles {
 
             self.drag_happened = false;
         }
-        Plot::new("candles")
+        let step = self.data.step();
+        let view = self.view.take();
+        let mut plot = Plot::new(("candles", self.view_id))
+            .legend(Legend::default())
             .link_axis(self.axes_group.clone())
             .label_formatter(|_, v| -> String { format!("{}", Data::format_ts(v.x)) })
-            .x_axis_formatter(|v, _range| Data::format_ts(v))
-            .include_x(self.data.max_x())
-            .include_x(self.data.min_x())
-            .include_y(self.data.max_y())
-            .include_y(self.data.min_y())
-            .show(ui, |plot_ui| {
-                plot_ui.box_plot(
-                    BoxPlot::new(self.val.clone())
-                        .element_formatter(Box::new(|el, _| -> String {
+            .x_axis_formatter(move |v, range| {
+                Data::format_axis_ts(v, range.end() - range.start(), step)
+            })
+            .allow_drag(self.allow_drag());
+
+        let base = self.percent_base().unwrap_or(1.0);
+        let y_map = self.scale.y_map(base);
+        plot = match (view, self.scale) {
+            (_, PriceScale::Fixed { min, max }) => plot.include_y(min).include_y(max),
+            (Some(bounds), _) => {
+                let (min, max) = self.price_range(bounds);
+                plot.include_y(y_map(min)).include_y(y_map(max))
+            }
+            // Base moves with the visible range, so bounds must not depend on it
+            // or the plot would reset its bounds on every base change.
+            (None, PriceScale::Percent) => plot.include_y(0.0),
+            (None, _) => plot
+                .include_y(y_map(self.data.max_y()))
+                .include_y(y_map(self.data.min_y())),
+        };
+        plot = match view {
+            Some(bounds) => plot.include_x(bounds.0 as f64).include_x(bounds.1 as f64),
+            None => plot
+                .include_x(self.data.max_x())
+                .include_x(self.data.min_x()),
+        };
+        match self.scale {
+            PriceScale::Percent => {
+                plot = plot.y_axis_formatter(|v, _| format!("{v:+.2}%"));
+            }
+            PriceScale::Log => {
+                plot = plot.y_axis_formatter(|v, _| format_price(v.exp()));
+            }
+            _ => {}
+        }
+
+        let val = match self.scale.is_linear() {
+            true => self.val.clone(),
+            false => {
+                let val = &self.val;
+                self.mapped
+                    .get((self.scale, base), || {
+                        val.iter()
+                            .map(|el| {
+                                let mut el = el.clone();
+                                let s = &mut el.spread;
+                                for v in [
+                                    &mut s.lower_whisker,
+                                    &mut s.quartile1,
+                                    &mut s.median,
+                                    &mut s.quartile3,
+                                    &mut s.upper_whisker,
+                                ] {
+                                    *v = y_map(*v);
+                                }
+                                el
+                            })
+                            .collect()
+                    })
+                    .clone()
+            }
+        };
+
+        let closes = match self.chart_type {
+            ChartType::Line | ChartType::Area => map_y(&self.closes, &y_map),
+            _ => vec![],
+        };
+        let fill = match self.scale {
+            PriceScale::Fixed { min, .. } => min,
+            _ => y_map(self.data.min_y()),
+        } as f32;
+        let unmap = self.scale.unmap(base);
+        let down = ChartColors::current().down;
+        let price_name = match &self.source {
+            Some(source) => format!("price from {source}"),
+            None => "price".to_string(),
+        };
+
+        let plot = plot.show(ui, |plot_ui| {
+            self.shading.show(
+                plot_ui,
+                &self.data,
+                (y_map(self.data.min_y()), y_map(self.data.max_y())),
+            );
+            if self.scale == PriceScale::Percent {
+                plot_ui.hline(
+                    HLine::new(0.0)
+                        .color(Color32::GRAY)
+                        .style(LineStyle::dashed_loose()),
+                );
+            }
+            if self.day_starts.len() <= MAX_DAY_SEPARATORS {
+                self.day_starts.iter().for_each(|ts| {
+                    plot_ui.vline(
+                        VLine::new(*ts as f64)
+                            .color(Color32::GRAY.linear_multiply(0.3))
+                            .style(LineStyle::dashed_loose()),
+                    )
+                });
+            }
+
+            match self.chart_type {
+                ChartType::Line => plot_ui.line(
+                    Line::new(Values::from_values(closes))
+                        .color(Color32::LIGHT_BLUE)
+                        .name(&price_name),
+                ),
+                ChartType::Area => plot_ui.line(
+                    Line::new(Values::from_values(closes))
+                        .color(Color32::LIGHT_BLUE)
+                        .fill(fill)
+                        .name(&price_name),
+                ),
+                _ => plot_ui.box_plot(
+                    BoxPlot::new(val)
+                        .element_formatter(Box::new(move |el, _| -> String {
                             format!(
                                 "open: {:.8}\nclose: {:.8}\nhigh: {:.8}\nlow: {:.8}\n{}",
-                                {
-                                    match el.fill == Color32::LIGHT_RED {
+                                unmap({
+                                    match el.fill == down {
                                         true => el.spread.quartile3,
                                         false => el.spread.quartile1,
                                     }
-                                },
-                                {
-                                    match el.fill == Color32::LIGHT_RED {
+                                }),
+                                unmap({
+                                    match el.fill == down {
                                         true => el.spread.quartile1,
                                         false => el.spread.quartile3,
                                     }
-                                },
-                                el.spread.upper_whisker,
-                                el.spread.lower_whisker,
+                                }),
+                                unmap(el.spread.upper_whisker),
+                                unmap(el.spread.lower_whisker),
                                 Data::format_ts(el.argument),
                             )
                         }))
+                        .name(&price_name)
                         .vertical(),
+                ),
+            }
+
+            if !self.anomalies.is_empty() {
+                let anomalies = match self.scale.is_linear() {
+                    true => self.anomalies.clone(),
+                    false => map_y(&self.anomalies, &y_map),
+                };
+                plot_ui.points(
+                    Points::new(Values::from_values(anomalies))
+                        .shape(MarkerShape::Up)
+                        .radius(4.0)
+                        .color(Color32::GOLD.linear_multiply(0.6))
+                        .name("volume anomalies"),
                 );
+            }
 
-                let plot_bounds = plot_ui.plot_bounds();
-                self.bounds = Bounds(plot_bounds.min()[0] as i64, plot_bounds.max()[0] as i64);
+            if let Some(base) = self.percent_base() {
+                self.comparisons.iter().for_each(|c| {
+                    let first = c.vals.iter().find(|v| v.x >= self.bounds.0 as f64);
+                    let own = match first.or_else(|| c.vals.first()) {
+                        Some(v) if v.y != 0.0 => v.y,
+                        _ => return,
+                    };
+                    c.mapped(&|v| y_map(v / own * base)).show(plot_ui);
+                });
+            }
+
+            self.indicators
+                .enabled(Target::Overlay)
+                .for_each(|i| i.series().show(plot_ui, &y_map));
+            self.drawings.show(plot_ui, &y_map, &unmap);
+            self.alerts.show(plot_ui, &y_map, &unmap);
+
+            let plot_bounds = plot_ui.plot_bounds();
+            self.bounds = Bounds(plot_bounds.min()[0] as i64, plot_bounds.max()[0] as i64);
 
-                let drag_diff = plot_ui.pointer_coordinate_drag_delta().x;
-                if drag_diff.abs() > 0.0 {
-                    self.incremental_drag_diff += drag_diff;
+            let drag_diff = plot_ui.pointer_coordinate_drag_delta().x;
+            if drag_diff.abs() > 0.0 && self.allow_drag() {
+                self.incremental_drag_diff += drag_diff;
 
-                    // TODO: use step to count min drag diff
-                    if self.incremental_drag_diff > (60 * 1000 * 5) as f32 {
-                        self.drag_happened = true;
-                        self.last_time_drag_happened = Utc::now();
-                        self.incremental_drag_diff = 0.0;
-                    }
+                // TODO: use step to count min drag diff
+                if self.incremental_drag_diff > (60 * 1000 * 5) as f32 {
+                    self.drag_happened = true;
+                    self.last_time_drag_happened = Utc::now();
+                    self.incremental_drag_diff = 0.0;
                 }
+            }
 
+            if !LowPower::is_on(plot_ui.ctx()) {
                 plot_ui.ctx().request_repaint();
-            })
-            .response
+            }
+
+            let mut shapes = self.profile.shapes(plot_ui, self.scale, base);
+            shapes.extend(last_price::show(plot_ui, &self.data, &y_map));
+            shapes
+        });
+
+        ui.painter()
+            .with_clip_rect(plot.response.rect)
+            .extend(plot.inner);
+
+        plot.response.context_menu(|ui| {
+            if let Some(price) = self.drawings.menu_ui(ui) {
+                self.alerts.add(price);
+            }
+            self.alerts.menu_ui(ui);
+            ui.separator();
+            self.copy_menu_ui(ui);
+        })
     }
 }
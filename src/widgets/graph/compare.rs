@@ -0,0 +1,163 @@
+use egui::{plot::Value, Color32, TextEdit, Ui, WidgetText};
+use poll_promise::Promise;
+use tracing::{error, info};
+
+use crate::sources::binance::{errors::ClientError, Client, Interval, Kline};
+
+use super::series::{x, SeriesLine};
+
+const COLORS: [Color32; 4] = [
+    Color32::GOLD,
+    Color32::LIGHT_BLUE,
+    Color32::from_rgb(200, 120, 255),
+    Color32::from_rgb(255, 160, 80),
+];
+
+/// Time range of the charted klines the compared symbols are loaded for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Range {
+    interval: Interval,
+    start: i64,
+    end: i64,
+    limit: usize,
+}
+
+struct Comparison {
+    symbol: String,
+    klines: Vec<Kline>,
+    promise: Option<Promise<Result<Vec<Kline>, ClientError>>>,
+}
+
+impl Comparison {
+    fn load(&mut self, range: Range) {
+        let symbol = self.symbol.clone();
+        info!("Loading compared symbol {symbol}: {range:?}.");
+
+        self.promise = Some(Promise::spawn_async(async move {
+            Client::kline_range(symbol, range.interval, range.start, range.end, range.limit).await
+        }));
+    }
+}
+
+/// Secondary symbols drawn on the candles plot as close price lines over the same time range.
+#[derive(Default)]
+pub struct Compare {
+    input: String,
+    symbols: Vec<Comparison>,
+    range: Option<Range>,
+}
+
+impl Compare {
+    /// Reloads compared symbols for the range of newly charted klines.
+    pub fn set_range(&mut self, interval: Interval, klines: &[Kline], limit: usize) {
+        let range = match (klines.first(), klines.last()) {
+            (Some(first), Some(last)) => Range {
+                interval,
+                start: first.t_open,
+                end: last.t_close,
+                limit,
+            },
+            _ => return,
+        };
+        if self.range == Some(range) {
+            return;
+        }
+
+        self.range = Some(range);
+        self.symbols.iter_mut().for_each(|c| c.load(range));
+    }
+
+    /// Checks downloads of compared symbols. Returns true if lines changed.
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        self.symbols.iter_mut().for_each(|c| {
+            let res = match c.promise.as_ref().and_then(|p| p.ready()) {
+                Some(res) => res,
+                None => return,
+            };
+            match res {
+                Ok(klines) => c.klines = klines.clone(),
+                Err(err) => error!("Failed to load compared symbol {}: {err}.", c.symbol),
+            }
+            c.promise = None;
+            changed = true;
+        });
+
+        changed
+    }
+
+    pub fn symbols(&self) -> impl Iterator<Item = &str> {
+        self.symbols.iter().map(|c| c.symbol.as_str())
+    }
+
+    /// Loaded klines of the compared symbol.
+    pub fn klines(&self, symbol: &str) -> Option<&[Kline]> {
+        self.symbols
+            .iter()
+            .find(|c| c.symbol == symbol)
+            .map(|c| c.klines.as_slice())
+    }
+
+    pub fn lines(&self) -> Vec<SeriesLine> {
+        self.symbols
+            .iter()
+            .zip(COLORS.iter().cycle())
+            .map(|(c, color)| {
+                SeriesLine::new(
+                    c.symbol.clone(),
+                    *color,
+                    c.klines
+                        .iter()
+                        .map(|k| Value::new(x(k), k.close as f64))
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// Draws compared symbols list. Returns true if lines changed.
+    pub fn ui(&mut self, ui: &mut Ui) -> bool {
+        let mut removed = None;
+        self.symbols.iter().enumerate().for_each(|(i, c)| {
+            ui.horizontal(|ui| {
+                ui.label(&c.symbol);
+                if c.promise.is_some() {
+                    ui.spinner();
+                }
+                if ui.button("🗑").clicked() {
+                    removed = Some(i);
+                }
+            });
+        });
+        if let Some(i) = removed {
+            self.symbols.remove(i);
+        }
+
+        ui.horizontal(|ui| {
+            ui.add(
+                TextEdit::singleline(&mut self.input)
+                    .hint_text(WidgetText::from("symbol").italics())
+                    .desired_width(80.0),
+            );
+            let symbol = self.input.to_uppercase();
+            let exists = self.symbols.iter().any(|c| c.symbol == symbol);
+            if ui
+                .add_enabled(!symbol.is_empty() && !exists, egui::Button::new("add"))
+                .clicked()
+            {
+                let mut c = Comparison {
+                    symbol,
+                    klines: vec![],
+                    promise: None,
+                };
+                if let Some(range) = self.range {
+                    c.load(range);
+                }
+                self.symbols.push(c);
+                self.input = String::new();
+            }
+        });
+
+        removed.is_some()
+    }
+}
@@ -0,0 +1,86 @@
+use egui::{Color32, ComboBox, DragValue, Ui};
+
+use crate::{netstrat::correlation::rolling, sources::binance::Kline};
+
+use super::{
+    compare::Compare,
+    series::{values, Series, SeriesLine},
+};
+
+/// Rolling correlation of returns of the charted symbol with one of the compared symbols
+/// drawn in a pane below the candles, showing when the relationship breaks down.
+pub struct RollingCorrelation {
+    /// Compared symbol the correlation is computed with, no pane if none.
+    symbol: Option<String>,
+    window: usize,
+    series: Series,
+}
+
+impl Default for RollingCorrelation {
+    fn default() -> Self {
+        Self {
+            symbol: None,
+            window: 30,
+            series: Default::default(),
+        }
+    }
+}
+
+impl RollingCorrelation {
+    /// Recomputes the series for the charted klines.
+    /// The symbol is dropped if it is not compared anymore.
+    pub fn update(&mut self, klines: &[Kline], compare: &Compare) {
+        let symbol = match &self.symbol {
+            Some(symbol) => symbol,
+            None => return,
+        };
+        let other = match compare.klines(symbol) {
+            Some(other) => other,
+            None => {
+                self.symbol = None;
+                return;
+            }
+        };
+
+        self.series = Series {
+            lines: vec![SeriesLine::new(
+                format!("corr {symbol}({})", self.window),
+                Color32::GOLD,
+                values(klines, &rolling(klines, other, self.window)),
+            )],
+            levels: vec![0.0],
+            include_y: vec![-1.0, 1.0],
+            ..Default::default()
+        };
+    }
+
+    pub fn series(&self) -> Option<&Series> {
+        self.symbol.as_ref().map(|_| &self.series)
+    }
+
+    /// Draws the symbol and window controls. Returns true if they were changed.
+    pub fn ui(&mut self, ui: &mut Ui, compare: &Compare) -> bool {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label("rolling correlation");
+            ComboBox::from_id_source(ui.id().with("rolling correlation"))
+                .selected_text(self.symbol.as_deref().unwrap_or("none"))
+                .show_ui(ui, |ui| {
+                    changed |= ui
+                        .selectable_value(&mut self.symbol, None, "none")
+                        .changed();
+                    compare.symbols().for_each(|symbol| {
+                        changed |= ui
+                            .selectable_value(&mut self.symbol, Some(symbol.to_string()), symbol)
+                            .changed();
+                    });
+                });
+            changed |= ui
+                .add(DragValue::new(&mut self.window).clamp_range(2..=500))
+                .changed();
+            ui.label("window");
+        });
+
+        changed
+    }
+}
@@ -0,0 +1,405 @@
+use egui::{
+    plot::{HLine, Line, LineStyle, MarkerShape, PlotUi, Points, Text, Value, Values},
+    Align2, Color32, CursorIcon, Pos2, RichText, Ui,
+};
+use tracing::{error, info};
+
+use crate::{
+    netstrat::{
+        bounds::Bounds,
+        drawings::{measure::Measurement, Drawings, Point, SymbolDrawings, Trendline},
+    },
+    sources::binance::Interval,
+};
+
+use super::{
+    candles::format_price,
+    pointer::{segment_distance, PlotPointer, GRAB_DISTANCE},
+};
+
+const TRENDLINE_COLOR: Color32 = Color32::LIGHT_BLUE;
+const LEVEL_COLOR: Color32 = Color32::LIGHT_GRAY;
+const MEASURE_COLOR: Color32 = Color32::WHITE;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tool {
+    Trendline,
+    Level,
+    Measure,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Handle {
+    Start,
+    End,
+}
+
+/// Part of a drawing which can be grabbed with the pointer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Grab {
+    Trendline(usize, Handle),
+    Level(usize),
+}
+
+/// Drawing tools of the candles plot. Drawings are kept per symbol and interval and saved on every edit.
+/// A trendline is drawn by dragging while the tool is active and adjusted by dragging its ends.
+/// Levels are placed by a click with the level tool or from the context menu and dragged vertically.
+/// The ruler measures between the points of a drag and is not kept after release,
+/// only its time span is remembered to select klines, e.g. for copying.
+/// Double click is not used as it resets the plot bounds.
+pub struct DrawingTools {
+    store: Drawings,
+    symbol: String,
+    interval: Interval,
+    drawings: SymbolDrawings,
+    tool: Option<Tool>,
+    hovered: Option<Grab>,
+    grabbed: Option<Grab>,
+    /// Point and the drawing under the pointer when the context menu was opened.
+    menu: Option<(Point, Option<Grab>)>,
+    /// Points of the ruler while it is dragged.
+    measure: Option<(Point, Point)>,
+    /// Time bounds of the last released ruler.
+    measured: Option<Bounds>,
+    /// Duration of charted klines to count bars with the ruler.
+    step: i64,
+}
+
+impl Default for DrawingTools {
+    fn default() -> Self {
+        let mut store = Drawings::default();
+        if let Err(err) = store.load() {
+            error!("Failed to load drawings: {err}.");
+        }
+
+        Self {
+            store,
+            symbol: Default::default(),
+            interval: Interval::Minute,
+            drawings: Default::default(),
+            tool: None,
+            hovered: None,
+            grabbed: None,
+            menu: None,
+            measure: None,
+            measured: None,
+            step: 0,
+        }
+    }
+}
+
+impl DrawingTools {
+    /// Switches to drawings of the chart. Nothing changes if the chart is already shown.
+    pub fn set_chart(&mut self, symbol: &str, interval: Interval) {
+        if self.symbol == symbol && self.interval == interval {
+            return;
+        }
+
+        self.symbol = symbol.to_string();
+        self.interval = interval;
+        self.drawings = self.store.get(symbol, interval);
+        self.hovered = None;
+        self.grabbed = None;
+        self.menu = None;
+        self.measured = None;
+    }
+
+    /// Time in milliseconds of the point the context menu was opened at.
+    pub fn menu_time(&self) -> Option<f64> {
+        self.menu.map(|(p, _)| p.t)
+    }
+
+    pub fn measured(&self) -> Option<Bounds> {
+        self.measured
+    }
+
+    pub fn set_step(&mut self, step: i64) {
+        self.step = step;
+    }
+
+    /// Plot should not pan while a tool is active or a drawing is grabbed.
+    pub fn allow_drag(&self) -> bool {
+        self.tool.is_none() && self.hovered.is_none() && self.grabbed.is_none()
+    }
+
+    fn save(&mut self) {
+        if let Err(err) = self
+            .store
+            .save(&self.symbol, self.interval, self.drawings.clone())
+        {
+            error!("Failed to save drawings: {err}.");
+        }
+    }
+
+    fn remove(&mut self, grab: Grab) {
+        match grab {
+            Grab::Trendline(i, _) => {
+                self.drawings.trendlines.remove(i);
+            }
+            Grab::Level(i) => {
+                self.drawings.levels.remove(i);
+            }
+        }
+    }
+
+    /// Draws tool controls.
+    pub fn ui(&mut self, ui: &mut Ui) {
+        [
+            (
+                Tool::Trendline,
+                "╱ trendline",
+                "drag on the chart to draw a trendline",
+            ),
+            (
+                Tool::Level,
+                "― level",
+                "click on the chart to place a price level",
+            ),
+            (
+                Tool::Measure,
+                "📏 measure",
+                "drag on the chart to measure bars, time and price change and select candles to copy",
+            ),
+        ]
+        .into_iter()
+        .for_each(|(tool, label, hint)| {
+            let active = self.tool == Some(tool);
+            if ui
+                .selectable_label(active, label)
+                .on_hover_text(hint)
+                .clicked()
+            {
+                self.tool = match active {
+                    true => None,
+                    false => Some(tool),
+                };
+                ui.close_menu();
+            }
+        });
+        if ui
+            .add_enabled(!self.drawings.is_empty(), egui::Button::new("clear"))
+            .clicked()
+        {
+            info!(
+                "Clearing drawings of {} {}.",
+                self.symbol,
+                self.interval.as_str()
+            );
+            self.drawings = SymbolDrawings::default();
+            self.save();
+            ui.close_menu();
+        }
+    }
+
+    /// Draws context menu entries for the point where the menu was opened.
+    /// Returns the price of a level an alert was requested for.
+    pub fn menu_ui(&mut self, ui: &mut Ui) -> Option<f64> {
+        let (Point { price, .. }, grab) = self.menu?;
+
+        let mut alert = None;
+        match grab {
+            Some(Grab::Level(i)) => {
+                let level = self.drawings.levels[i];
+                if ui.button("🔔 alert at this level").clicked() {
+                    alert = Some(level);
+                    ui.close_menu();
+                }
+                if ui.button("remove level").clicked() {
+                    self.remove(Grab::Level(i));
+                    self.menu = None;
+                    self.save();
+                    ui.close_menu();
+                }
+            }
+            Some(grab @ Grab::Trendline(..)) => {
+                if ui.button("remove trendline").clicked() {
+                    self.remove(grab);
+                    self.menu = None;
+                    self.save();
+                    ui.close_menu();
+                }
+            }
+            None => {
+                if ui
+                    .button(format!("― level at {}", format_price(price)))
+                    .clicked()
+                {
+                    info!("Adding level at {price}.");
+                    self.drawings.levels.push(price);
+                    self.save();
+                    ui.close_menu();
+                }
+            }
+        }
+
+        alert
+    }
+
+    /// Draws drawings and edits them with the pointer.
+    /// Prices are mapped to the plot with y_map and back with unmap.
+    pub fn show(
+        &mut self,
+        plot_ui: &mut PlotUi,
+        y_map: &dyn Fn(f64) -> f64,
+        unmap: &dyn Fn(f64) -> f64,
+    ) {
+        let pointer = PlotPointer::read(plot_ui, self.grabbed.is_some());
+        let right = plot_ui.plot_bounds().max()[0];
+        let screen = |p: &Point| plot_ui.screen_from_plot(Value::new(p.t, y_map(p.price)));
+        let ends: Vec<(Pos2, Pos2)> = self
+            .drawings
+            .trendlines
+            .iter()
+            .map(|l| (screen(&l.start), screen(&l.end)))
+            .collect();
+        let levels: Vec<f32> = self
+            .drawings
+            .levels
+            .iter()
+            .map(|l| plot_ui.screen_from_plot(Value::new(right, y_map(*l))).y)
+            .collect();
+
+        let handle = |pos: Pos2| {
+            ends.iter().enumerate().find_map(|(i, (a, b))| {
+                match (pos.distance(*a), pos.distance(*b)) {
+                    (d, _) if d <= GRAB_DISTANCE => Some(Grab::Trendline(i, Handle::Start)),
+                    (_, d) if d <= GRAB_DISTANCE => Some(Grab::Trendline(i, Handle::End)),
+                    _ => None,
+                }
+            })
+        };
+        let level = |pos: Pos2| {
+            levels
+                .iter()
+                .position(|y| (y - pos.y).abs() <= GRAB_DISTANCE)
+                .map(Grab::Level)
+        };
+        self.hovered = pointer
+            .pos
+            .and_then(|pos| handle(pos).or_else(|| level(pos)));
+
+        let at = |pos| {
+            let v = plot_ui.plot_from_screen(pos);
+            Point {
+                t: v.x,
+                price: unmap(v.y),
+            }
+        };
+        if pointer.secondary_down {
+            self.menu = pointer.pos.map(|pos| {
+                let line = ends
+                    .iter()
+                    .position(|(a, b)| segment_distance(pos, *a, *b) <= GRAB_DISTANCE)
+                    .map(|i| Grab::Trendline(i, Handle::End));
+                (at(pos), level(pos).or(line))
+            });
+        }
+
+        if let (true, None, Some(pos)) = (pointer.pressed, self.grabbed, pointer.pos) {
+            let p = at(pos);
+            self.grabbed = match (self.hovered, self.tool) {
+                (_, Some(Tool::Measure)) => {
+                    self.measure = Some((p, p));
+                    None
+                }
+                (Some(hovered), _) => Some(hovered),
+                (None, Some(Tool::Trendline)) => {
+                    self.drawings
+                        .trendlines
+                        .push(Trendline { start: p, end: p });
+                    Some(Grab::Trendline(
+                        self.drawings.trendlines.len() - 1,
+                        Handle::End,
+                    ))
+                }
+                (None, Some(Tool::Level)) => {
+                    self.drawings.levels.push(p.price);
+                    Some(Grab::Level(self.drawings.levels.len() - 1))
+                }
+                (None, None) => None,
+            };
+        }
+
+        if let (Some((start, _)), Some(pos)) = (self.measure, pointer.pos) {
+            self.measure = Some((start, at(pos)));
+        }
+        if let (Some((start, end)), false) = (self.measure, pointer.down) {
+            let (left, right) = (start.t.min(end.t), start.t.max(end.t));
+            self.measured = (right > left).then_some(Bounds(left as i64, right as i64));
+            self.measure = None;
+            self.tool = None;
+        }
+
+        if let (Some(grab), Some(pos)) = (self.grabbed, pointer.pos) {
+            let p = at(pos);
+            match grab {
+                Grab::Trendline(i, Handle::Start) => self.drawings.trendlines[i].start = p,
+                Grab::Trendline(i, Handle::End) => self.drawings.trendlines[i].end = p,
+                Grab::Level(i) => self.drawings.levels[i] = p.price,
+            }
+        }
+        if let (Some(grab), false) = (self.grabbed, pointer.down) {
+            // A click without dragging does not leave a zero length line behind.
+            if let Grab::Trendline(i, _) = grab {
+                let l = self.drawings.trendlines[i];
+                if screen(&l.start).distance(screen(&l.end)) <= GRAB_DISTANCE {
+                    self.remove(grab);
+                }
+            }
+            self.grabbed = None;
+            self.tool = None;
+            self.save();
+        }
+
+        let cursor = match (self.hovered.or(self.grabbed), self.tool) {
+            (Some(Grab::Level(_)), _) => Some(CursorIcon::ResizeVertical),
+            (Some(_), _) => Some(CursorIcon::Grab),
+            (None, Some(_)) if pointer.pos.is_some() => Some(CursorIcon::Crosshair),
+            _ => None,
+        };
+        if let Some(cursor) = cursor {
+            plot_ui.ctx().output().cursor_icon = cursor;
+        }
+
+        self.drawings.trendlines.iter().for_each(|l| {
+            let vals = [l.start, l.end].map(|p| Value::new(p.t, y_map(p.price)));
+            plot_ui.line(
+                Line::new(Values::from_values(vals.to_vec()))
+                    .color(TRENDLINE_COLOR)
+                    .name("trendlines"),
+            );
+            plot_ui.points(
+                Points::new(Values::from_values(vals.to_vec()))
+                    .shape(MarkerShape::Circle)
+                    .radius(3.0)
+                    .color(TRENDLINE_COLOR),
+            );
+        });
+        if let Some((start, end)) = self.measure {
+            let vals = [start, end].map(|p| Value::new(p.t, y_map(p.price)));
+            plot_ui.line(
+                Line::new(Values::from_values(vals.to_vec()))
+                    .color(MEASURE_COLOR)
+                    .style(LineStyle::dashed_dense()),
+            );
+            plot_ui.text(
+                Text::new(
+                    vals[1],
+                    RichText::new(Measurement::new(start, end, self.step).to_string())
+                        .color(MEASURE_COLOR),
+                )
+                .anchor(Align2::LEFT_BOTTOM),
+            );
+        }
+        self.drawings.levels.iter().for_each(|l| {
+            plot_ui.hline(HLine::new(y_map(*l)).color(LEVEL_COLOR).name("levels"));
+            plot_ui.text(
+                Text::new(
+                    Value::new(right, y_map(*l)),
+                    RichText::new(format_price(*l)).color(LEVEL_COLOR),
+                )
+                .anchor(Align2::RIGHT_TOP),
+            );
+        });
+    }
+}
@@ -1,10 +1,11 @@
-use std::fs::File;
+use std::path::PathBuf;
 
 use chrono::{Date, NaiveDateTime, NaiveTime, Utc};
 use crossbeam::channel::{unbounded, Receiver, Sender};
 
 use egui::{
-    plot::LinkedAxisGroup, CentralPanel, ProgressBar, Response, TopBottomPanel, Ui, Widget,
+    plot::LinkedAxisGroup, CentralPanel, Color32, DragValue, ProgressBar, Rect, Response, TextEdit,
+    TopBottomPanel, Ui, Widget,
 };
 use egui_extras::{Size, StripBuilder};
 use poll_promise::Promise;
@@ -12,25 +13,136 @@ use tracing::{debug, error, info, trace};
 
 use crate::{
     netstrat::{
+        archive::{
+            anomaly::{detect, Anomaly},
+            Archive,
+        },
         bounds::{Bounds, BoundsSet},
+        chart_type::{Brick, ChartType},
+        convert::{convert, invert},
         data::Data,
-        graph::{props::Props, state::State},
+        export::{
+            dialog::pick_destination,
+            image::{Capture, CaptureRequest},
+            visible_klines, Column, ExportOptions,
+        },
+        graph::{
+            defaults::SymbolClasses, failover::Failover, follow::Follow, props::Props, state::State,
+        },
+        layout::WindowLayout,
+        notify::{webhook, AlertPayload, Telegram},
+        price_scale::PriceScale,
+        session::Session,
+        settings::Settings,
+        shortcuts::Action,
+        status::Feed,
+        theme::ChartColors,
+        toasts::Toasts,
     },
-    sources::binance::{errors::ClientError, Client, Kline},
+    sources::binance::{errors::ClientError, Client, Interval, Kline, Source, Symbol},
+    widgets::Shortcuts,
     windows::{AppWindow, TimeRangeChooser},
 };
 
-use super::{candles::Candles, volume::Volume};
+use super::{
+    benchmark::Benchmark,
+    candles::Candles,
+    compare::Compare,
+    correlation::RollingCorrelation,
+    indicator::Target,
+    pane::Pane,
+    replay::Replay,
+    series::x,
+    stream::{Outcome, StreamedExport},
+    venues::VenueVolumes,
+    volume::Volume,
+};
+
+const MAX_SHOWN_ANOMALIES: usize = 20;
+
+/// Klines with extra columns waiting for the destination picked in the save dialog.
+struct PendingExport {
+    klines: Vec<Kline>,
+    columns: Vec<Column>,
+    destination: Promise<Option<PathBuf>>,
+}
 
 #[derive(Default)]
 struct ExportState {
     triggered: bool,
+    /// Options of the last export from the time range window, also used to export
+    /// visible klines.
+    options: ExportOptions,
+    pending: Option<PendingExport>,
+    /// Range export written page by page without charting the klines.
+    stream: Option<StreamedExport>,
+}
+
+/// Saving of the candles and volume plots as an image.
+struct ImageState {
+    /// Screen rect of the plots in the last frame.
+    rect: Rect,
+}
+
+impl Default for ImageState {
+    fn default() -> Self {
+        Self {
+            rect: Rect::NOTHING,
+        }
+    }
+}
+
+/// Highlighting of klines with outlying volume.
+struct AnomaliesProps {
+    enabled: bool,
+    z_score: f64,
+}
+
+impl Default for AnomaliesProps {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            z_score: 3.0,
+        }
+    }
+}
+
+/// Switching to an alternate source when the primary one keeps failing for the symbol.
+#[derive(Default)]
+struct FailoverState {
+    failover: Failover,
+    /// Alternate source found to list the symbol, offered to the user.
+    offer: Option<Source>,
+    check_promise: Option<(Source, Promise<Result<bool, ClientError>>)>,
+}
+
+/// Conversion of the displayed prices into another quote currency.
+#[derive(Default)]
+struct ConversionState {
+    quote: String,
+    applied_quote: String,
+    rates_promise: Option<Promise<Result<Vec<Kline>, ClientError>>>,
 }
 
 pub struct Graph {
     candles: Candles,
     volume: Volume,
+    axes_group: LinkedAxisGroup,
+    anomalies: AnomaliesProps,
+    data_anomalies: Vec<Anomaly>,
+    session: Session,
+    chart_type: ChartType,
+    compare: Compare,
+    correlation: RollingCorrelation,
+    benchmark: Benchmark,
+    replay: Replay,
+    follow: Follow,
+    /// Theme colors klines were drawn with.
+    colors: ChartColors,
+    venues: VenueVolumes,
+    data: Data,
     symbol: String,
+    quote_asset: String,
     symbol_pub: Sender<String>,
 
     pub time_range_window: Box<dyn AppWindow>,
@@ -38,16 +150,20 @@ pub struct Graph {
     klines: Vec<Kline>,
     state: State,
     export_state: ExportState,
+    image: ImageState,
+    conversion: ConversionState,
+    failover: FailoverState,
     klines_promise: Option<Promise<Result<Vec<Kline>, ClientError>>>,
-    symbol_sub: Receiver<String>,
+    symbol_sub: Receiver<Symbol>,
     show_sub: Receiver<Props>,
-    export_sub: Receiver<Props>,
+    export_sub: Receiver<(Props, ExportOptions)>,
     drag_sub: Receiver<Bounds>,
 }
 
 impl Default for Graph {
     fn default() -> Self {
         let (s_symbols, r_symbols) = unbounded();
+        let (_, r_symbol_info) = unbounded();
         let (s_props, r_props) = unbounded();
         let (s_export, r_export) = unbounded();
         let (_, r_bounds) = unbounded();
@@ -56,31 +172,48 @@ impl Default for Graph {
             symbol_pub: s_symbols,
             time_range_window: Box::new(TimeRangeChooser::new(
                 false,
-                r_symbols.clone(),
+                r_symbols,
                 s_props,
                 s_export,
                 Props::default(),
             )),
 
-            symbol_sub: r_symbols,
+            symbol_sub: r_symbol_info,
             show_sub: r_props,
             export_sub: r_export,
             drag_sub: r_bounds,
 
             symbol: Default::default(),
+            quote_asset: Default::default(),
             candles: Default::default(),
             volume: Default::default(),
+            axes_group: LinkedAxisGroup::new(true, false),
+            anomalies: Default::default(),
+            data_anomalies: Default::default(),
+            session: Default::default(),
+            chart_type: Default::default(),
+            compare: Default::default(),
+            correlation: Default::default(),
+            benchmark: Default::default(),
+            replay: Default::default(),
+            follow: Default::default(),
+            colors: Default::default(),
+            venues: Default::default(),
+            data: Default::default(),
 
             klines: Default::default(),
             state: Default::default(),
             klines_promise: Default::default(),
             export_state: Default::default(),
+            image: Default::default(),
+            conversion: Default::default(),
+            failover: Default::default(),
         }
     }
 }
 
 impl Graph {
-    pub fn new(symbol_chan: Receiver<String>) -> Self {
+    pub fn new(symbol_chan: Receiver<Symbol>) -> Self {
         let (s_symbols, r_symbols) = unbounded();
         let (s_props, r_props) = unbounded();
         let (s_export, r_export) = unbounded();
@@ -102,11 +235,513 @@ impl Graph {
                 Props::default(),
             )),
             candles: Candles::new(axes_group.clone(), s_bounds),
-            volume: Volume::new(axes_group),
+            volume: Volume::new(axes_group.clone()),
+            axes_group,
             ..Default::default()
         }
     }
 
+    /// Sets loaded klines to the widgets converting them into the chosen quote currency if needed.
+    fn apply_klines(&mut self) {
+        if self.klines.is_empty() {
+            return;
+        }
+
+        let quote = self.conversion.quote.clone();
+        if quote.is_empty() || quote == self.quote_asset {
+            self.conversion.applied_quote = String::new();
+            self.set_data(Data::new(self.klines.clone()));
+            return;
+        }
+
+        let direct = format!("{}{}", self.quote_asset, quote);
+        let inverse = format!("{}{}", quote, self.quote_asset);
+        let interval = self.state.props.interval;
+        let limit = self.state.props.limit;
+        let start_time = self.klines.first().unwrap().t_open;
+        let end_time = self.klines.last().unwrap().t_close;
+
+        info!("Starting conversion rates download: {direct} or {inverse}.");
+
+        self.conversion.applied_quote = quote;
+        self.conversion.rates_promise = Some(Promise::spawn_async(async move {
+            match Client::kline_range(direct, interval, start_time, end_time, limit).await {
+                Ok(rates) if !rates.is_empty() => Ok(rates),
+                _ => Client::kline_range(inverse, interval, start_time, end_time, limit)
+                    .await
+                    .map(|rates| invert(&rates)),
+            }
+        }));
+    }
+
+    fn set_data(&mut self, mut data: Data) {
+        data.session = self.session.clone();
+        let settings = Settings::loaded();
+        self.candles.confirmed_only = settings.confirmed_only;
+        let now = Utc::now().timestamp_millis();
+        let checked = match settings.confirmed_only {
+            true => Data::confirmed(&data.vals, now),
+            false => &data.vals,
+        };
+        if let Some(k) = checked.last() {
+            self.candles
+                .alerts
+                .check(k.close as f64)
+                .iter()
+                .for_each(|price| {
+                    info!("Alert at {price} triggered for {}.", self.symbol);
+                    Toasts::alert(format!("{} reached {price}", self.symbol));
+                    Telegram::notify(&settings, format!("{} reached {price}", self.symbol));
+                    if !settings.alert_webhook.is_empty() {
+                        let payload = AlertPayload::new(
+                            &self.symbol,
+                            self.state.props.interval.as_str(),
+                            *price,
+                            k.close as f64,
+                            now,
+                        );
+                        webhook(&settings.alert_webhook, &payload);
+                    }
+                });
+        }
+        self.candles
+            .drawings
+            .set_chart(&self.symbol, self.state.props.interval);
+        self.data = data;
+        self.correlation.update(&self.data.vals, &self.compare);
+        self.benchmark.update(&self.data.vals);
+        self.apply_chart_type();
+    }
+
+    /// Draws klines transformed for the chosen chart type while the raw data stays for export.
+    /// Only klines revealed so far are drawn in replay mode.
+    fn apply_chart_type(&mut self) {
+        if self.data.vals.is_empty() {
+            return;
+        }
+
+        let mut data = Data::new(self.chart_type.apply(self.replay.visible(&self.data.vals)));
+        data.session = self.session.clone();
+        if let Some(k) = data.vals.last() {
+            self.follow.set_newest(x(k) as i64);
+        }
+        self.volume.set_data(data.clone());
+        self.candles.chart_type = self.chart_type;
+        self.candles.set_data(data);
+        self.apply_anomalies();
+    }
+
+    /// Moves all plots to the time bounds.
+    fn set_view(&mut self, bounds: Bounds) {
+        self.axes_group = LinkedAxisGroup::new(true, false);
+        self.candles.set_view(bounds, self.axes_group.clone());
+        self.volume.set_axes_group(self.axes_group.clone());
+    }
+
+    /// Moves all plots to show every charted kline.
+    fn fit_view(&mut self) {
+        if let Some(bounds) = self.candles.data_bounds() {
+            info!("Fitting view of {} to {bounds:?}.", self.symbol);
+            self.set_view(bounds);
+        }
+    }
+
+    /// Keeps the newest kline in view or stops following once it is panned away.
+    fn apply_follow(&mut self) {
+        if self.candles.view_pending() {
+            return;
+        }
+
+        let visible = self.candles.bounds();
+        if self.follow.check(visible) {
+            info!("Stopped following the newest kline of {}.", self.symbol);
+        }
+        if let Some(bounds) = self.follow.update(visible) {
+            self.set_view(bounds);
+        }
+    }
+
+    /// Draws buttons switching the interval without opening the time range window.
+    fn interval_ui(&mut self, ui: &mut Ui) {
+        let current = self.interval();
+        Interval::ALL.into_iter().for_each(|i| {
+            if ui.selectable_label(i == current, i.as_str()).clicked() {
+                self.set_interval(i);
+            }
+        });
+        ui.separator();
+    }
+
+    /// Draws chart type controls. Returns true if the chart type was changed.
+    fn chart_type_ui(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = false;
+        ChartType::all().into_iter().for_each(|t| {
+            let selected = std::mem::discriminant(&self.chart_type) == std::mem::discriminant(&t);
+            if ui.selectable_label(selected, t.as_str()).clicked() && !selected {
+                self.chart_type = t;
+                changed = true;
+            }
+        });
+
+        if let ChartType::Renko(brick) = &mut self.chart_type {
+            ui.separator();
+            let fixed = self
+                .data
+                .vals
+                .last()
+                .map_or(1.0, |k| k.close as f64 / 100.0);
+            ui.horizontal(|ui| {
+                if ui
+                    .selectable_label(matches!(brick, Brick::Atr(_)), "ATR")
+                    .clicked()
+                {
+                    *brick = Brick::Atr(14);
+                    changed = true;
+                }
+                if ui
+                    .selectable_label(matches!(brick, Brick::Fixed(_)), "fixed")
+                    .clicked()
+                {
+                    *brick = Brick::Fixed(fixed);
+                    changed = true;
+                }
+            });
+            ui.horizontal(|ui| match brick {
+                Brick::Atr(period) => {
+                    changed |= ui
+                        .add(DragValue::new(period).clamp_range(2..=200))
+                        .changed();
+                    ui.label("period");
+                }
+                Brick::Fixed(size) => {
+                    changed |= ui
+                        .add(
+                            DragValue::new(size)
+                                .speed(fixed / 10.0)
+                                .clamp_range(f64::EPSILON..=f64::MAX),
+                        )
+                        .changed();
+                    ui.label("brick size");
+                }
+            });
+        }
+
+        changed
+    }
+
+    /// Draws trading session controls. Returns true if the session was changed.
+    fn session_ui(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = false;
+        Session::presets().into_iter().for_each(|s| {
+            let name = s.name.clone();
+            changed |= ui.selectable_value(&mut self.session, s, name).changed();
+        });
+
+        ui.separator();
+        let s = &mut self.session;
+        for (label, minutes) in [("open", &mut s.open), ("close", &mut s.close)] {
+            ui.horizontal(|ui| {
+                let (mut h, mut m) = (*minutes / 60, *minutes % 60);
+                changed |= ui.add(DragValue::new(&mut h).clamp_range(0..=23)).changed();
+                ui.label(":");
+                changed |= ui.add(DragValue::new(&mut m).clamp_range(0..=59)).changed();
+                ui.label(format!("{label} UTC"));
+                *minutes = h * 60 + m;
+            });
+        }
+        ui.horizontal(|ui| {
+            ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"]
+                .iter()
+                .zip(s.week_days.iter_mut())
+                .for_each(|(name, on)| {
+                    changed |= ui.checkbox(on, *name).changed();
+                });
+        });
+
+        changed
+    }
+
+    fn apply_anomalies(&mut self) {
+        // Renko bricks do not match klines one to one, so anomalies can not be marked on them.
+        let idxs = match (self.anomalies.enabled, self.chart_type) {
+            (_, ChartType::Renko(_)) | (false, _) => vec![],
+            (true, _) => self.data.volume_anomalies(self.anomalies.z_score),
+        };
+
+        self.candles.set_anomalies(&idxs);
+        self.volume.set_anomalies(&idxs);
+    }
+
+    /// Checks downloaded klines for anomalies and writes them to the archive unless disabled.
+    /// Klines of an alternate source are not archived so they do not mix with the primary ones.
+    fn record_klines(&mut self) {
+        if self.failover.failover.is_substituted() {
+            return;
+        }
+
+        let interval = self.state.props.interval;
+        self.data_anomalies = detect(&self.klines, interval.millis());
+        if !Settings::loaded().archive_klines {
+            return;
+        }
+
+        Archive::default().record(
+            self.symbol.clone(),
+            interval,
+            self.klines.clone(),
+            self.data_anomalies.clone(),
+        );
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    pub fn interval(&self) -> Interval {
+        self.state.props.interval
+    }
+
+    /// Loaded klines count and how they are kept up to date.
+    pub fn feed(&self) -> (usize, Feed) {
+        let feed = match (self.klines_promise.is_some(), self.follow.enabled) {
+            (true, _) => Feed::Loading,
+            (false, true) => Feed::Following,
+            (false, false) => Feed::Idle,
+        };
+
+        (self.klines.len(), feed)
+    }
+
+    pub fn indicators(&self) -> Vec<String> {
+        self.candles.indicators.enabled_names()
+    }
+
+    pub fn scale(&self) -> PriceScale {
+        self.candles.scale
+    }
+
+    /// Enables layout indicators and the price scale and reloads klines if the layout
+    /// interval differs.
+    pub fn apply_layout(&mut self, layout: &WindowLayout) {
+        self.candles.indicators.enable_only(&layout.indicators);
+        if let Some(scale) = layout.scale {
+            self.candles.scale = scale;
+        }
+
+        if let Some(interval) = layout.interval.as_deref().and_then(Interval::parse) {
+            self.set_interval(interval);
+        }
+    }
+
+    /// Switches the chart to the interval keeping the visible time span and reloads klines.
+    pub fn set_interval(&mut self, interval: Interval) {
+        if interval == self.state.props.interval {
+            return;
+        }
+
+        let mut chooser = self.time_range_window.layout();
+        chooser.interval = Some(interval.as_str().to_string());
+        self.time_range_window.apply_layout(&chooser);
+
+        if self.symbol.is_empty() {
+            self.state.props.interval = interval;
+            return;
+        }
+
+        let visible = self.candles.bounds();
+        let props = match visible.0 < visible.1 {
+            true => self.state.props.with_span(visible, interval),
+            false => Props {
+                interval,
+                ..Default::default()
+            },
+        };
+        info!(
+            "Switching {} to interval {}.",
+            self.symbol,
+            interval.as_str()
+        );
+
+        self.klines = vec![];
+        self.state = State::default();
+        self.start_download(props, false);
+    }
+
+    /// Stacks volumes of other venues in the volume pane. Venues are not stacked while
+    /// klines come from an alternate source, as it is one of the venues.
+    fn apply_venues(&mut self) {
+        match self.failover.failover.is_substituted() {
+            true => self.volume.set_venues("", vec![]),
+            false => self
+                .volume
+                .set_venues(Source::default().as_str(), self.venues.klines()),
+        }
+    }
+
+    /// Checks whether the alternate source lists the charted symbol.
+    fn check_source(&mut self, source: Source) {
+        info!(
+            "Checking {} on {} after repeated errors.",
+            self.symbol,
+            source.as_str()
+        );
+
+        let symbol = self.symbol.clone();
+        self.failover.check_promise = Some((
+            source,
+            Promise::spawn_async(Client::has_symbol(source, symbol)),
+        ));
+    }
+
+    /// Reloads klines of the current range from the source.
+    fn switch_source(&mut self, source: Source) {
+        info!("Switching {} to {}.", self.symbol, source.as_str());
+
+        self.failover.failover.switch(source);
+        self.failover.offer = None;
+        self.apply_venues();
+        self.candles
+            .set_source((!source.is_primary()).then(|| source.as_str().to_string()));
+
+        let props = self.state.props.clone();
+        self.klines = vec![];
+        self.state = State::default();
+        self.start_download(props, false);
+    }
+
+    /// Name of the file klines of the props range are exported to.
+    fn range_name(&self, props: &Props) -> String {
+        format!(
+            "{}-{}-{}-{:?}",
+            self.symbol,
+            props.start_time(),
+            props.end_time(),
+            props.interval,
+        )
+    }
+
+    /// Downloads the props range straight into the export file.
+    fn stream_export(&mut self, props: Props) {
+        if let Some(stream) = self.export_state.stream.take() {
+            stream.cancel();
+        }
+        self.export_state.stream = Some(StreamedExport::new(
+            &self.range_name(&props),
+            &self.symbol,
+            self.failover.failover.source,
+            &props,
+            &self.export_state.options,
+        ));
+    }
+
+    fn poll_stream(&mut self) {
+        let outcome = match self.export_state.stream.as_mut().and_then(|s| s.poll()) {
+            Some(outcome) => outcome,
+            None => return,
+        };
+        self.export_state.stream = None;
+        match outcome {
+            Outcome::Exported(file_name) => {
+                Toasts::info(format!("Exported {file_name}"));
+                Telegram::notify(&Settings::loaded(), format!("Exported {file_name}"));
+            }
+            Outcome::Cancelled => info!("Export cancelled."),
+            Outcome::Failed(err) => {
+                error!("Failed to export klines: {err}.");
+                Toasts::error("Failed to export klines", err);
+            }
+        }
+    }
+
+    /// Exports loaded klines currently visible on the plot.
+    fn export_visible(&mut self) {
+        let bounds = self.candles.bounds();
+        let klines = visible_klines(&self.klines, bounds);
+        let name = format!(
+            "{}-{}-{}-{:?}",
+            self.symbol,
+            Data::format_ts(bounds.0 as f64),
+            Data::format_ts(bounds.1 as f64),
+            self.state.props.interval,
+        );
+
+        info!("Exporting {} visible klines...", klines.len());
+        self.request_export(&name, klines);
+    }
+
+    /// Asks where to save the klines, they are exported once the destination is picked.
+    fn request_export(&mut self, name: &str, klines: Vec<Kline>) {
+        let options = &self.export_state.options;
+        let columns = match options.indicators {
+            true => self.candles.indicators.columns(&klines),
+            false => vec![],
+        };
+        let destination = pick_destination(name, options.format.as_str());
+        self.export_state.pending = Some(PendingExport {
+            klines,
+            columns,
+            destination,
+        });
+    }
+
+    fn poll_export(&mut self) {
+        let picked = match &self.export_state.pending {
+            Some(pending) => match pending.destination.ready() {
+                Some(picked) => picked.clone(),
+                None => return,
+            },
+            None => return,
+        };
+        let pending = match self.export_state.pending.take() {
+            Some(pending) => pending,
+            None => return,
+        };
+        let path = match picked {
+            Some(path) => path,
+            None => {
+                info!("Export cancelled.");
+                return;
+            }
+        };
+
+        let name = path.to_string_lossy();
+        let options = &self.export_state.options;
+        match options.format.export(
+            &name,
+            &pending.klines,
+            &pending.columns,
+            &options.template,
+        ) {
+            Ok(file_name) => {
+                Toasts::info(format!("Exported {file_name}"));
+                Telegram::notify(&Settings::loaded(), format!("Exported {file_name}"));
+            }
+            Err(err) => {
+                error!("Failed to export klines: {err}.");
+                Toasts::error("Failed to export klines", err);
+            }
+        }
+    }
+
+    /// Requests saving of the candles and volume plots as they are on the screen.
+    fn save_image(&self, ui: &Ui) {
+        let name = format!(
+            "{}-{}-{}",
+            self.symbol,
+            self.state.props.interval.as_str(),
+            Utc::now().format("%Y%m%d-%H%M%S"),
+        );
+
+        info!("Saving chart image {name}...");
+        Capture::request(
+            ui.ctx(),
+            CaptureRequest {
+                name,
+                rect: self.image.rect,
+            },
+        );
+    }
+
     fn start_download(&mut self, props: Props, export: bool) {
         self.export_state.triggered = export;
 
@@ -123,11 +758,15 @@ impl Graph {
         let symbol = self.symbol.to_string();
         let interval = props.interval.clone();
         let limit = self.state.loading.pages.page_size();
+        let source = self.failover.failover.source;
 
         debug!("Setting left edge to: {start_time}.");
 
+        self.state
+            .loading
+            .report_request(Utc::now().timestamp_millis());
         self.klines_promise = Some(Promise::spawn_async(async move {
-            Client::kline(symbol, interval, start_time, limit).await
+            Client::source_kline(source, symbol, interval, start_time, limit).await
         }));
     }
 }
@@ -157,12 +796,21 @@ impl Widget for &mut Graph {
             .recv_timeout(std::time::Duration::from_millis(1));
 
         match export_wrapped {
-            Ok(props) => {
-                info!("Got props for export: {props:?}.");
+            Ok((props, options)) => {
+                info!("Got props for export with {options:?}: {props:?}.");
 
-                self.klines = vec![];
-                self.state = State::default();
-                self.start_download(props, true);
+                let (visible_only, indicators) = (options.visible_only, options.indicators);
+                self.export_state.options = options;
+                if visible_only {
+                    self.export_visible();
+                } else if indicators {
+                    // indicator series are computed from charted klines
+                    self.klines = vec![];
+                    self.state = State::default();
+                    self.start_download(props, true);
+                } else {
+                    self.stream_export(props);
+                }
             }
             Err(_) => {}
         }
@@ -173,20 +821,36 @@ impl Widget for &mut Graph {
 
         match symbol_wrapped {
             Ok(symbol) => {
-                info!("Got symbol: {symbol}.");
+                info!("Got symbol: {}.", symbol.symbol);
 
                 self.klines = vec![];
-                self.symbol = symbol.clone();
-                self.symbol_pub.send(symbol).unwrap();
+                self.candles.alerts.clear();
+                self.candles
+                    .drawings
+                    .set_chart(&symbol.symbol, self.state.props.interval);
+                self.failover = FailoverState::default();
+                self.replay = Replay::default();
+                self.candles.set_source(None);
+                self.symbol = symbol.symbol.clone();
+                self.quote_asset = symbol.quote_asset;
+                self.symbol_pub.send(symbol.symbol).unwrap();
 
+                let interval = self.state.props.interval;
+                let props = SymbolClasses::loaded()
+                    .props(&self.symbol, Utc::now().timestamp_millis())
+                    .unwrap_or(Props {
+                        interval,
+                        ..Default::default()
+                    });
                 self.state = State::default();
-                self.state.apply_props(&Props::default());
+                self.state.apply_props(&props);
                 let start_time = self.state.props.start_time().timestamp_millis().clone();
                 let interval = self.state.props.interval.clone();
                 let limit = self.state.loading.pages.page_size();
                 let symbol = self.symbol.clone();
+                let source = self.failover.failover.source;
                 self.klines_promise = Some(Promise::spawn_async(async move {
-                    Client::kline(symbol, interval, start_time, limit).await
+                    Client::source_kline(source, symbol, interval, start_time, limit).await
                 }));
             }
             Err(_) => {}
@@ -201,9 +865,15 @@ impl Widget for &mut Graph {
             .recv_timeout(std::time::Duration::from_millis(1));
 
         match show_wrapped {
-            Ok(props) => {
+            Ok(mut props) => {
                 info!("Got show button pressed: {props:?}");
 
+                let visible = self.candles.bounds();
+                if props.interval != self.state.props.interval && visible.0 < visible.1 {
+                    props = props.with_span(visible, props.interval);
+                    info!("Interval changed, keeping visible span: {visible:?}.");
+                }
+
                 self.klines = vec![];
                 self.state = State::default();
                 self.start_download(props, false);
@@ -218,39 +888,132 @@ impl Widget for &mut Graph {
                         data.iter().for_each(|k| {
                             self.klines.push(k.clone());
                         });
+                        let now = Utc::now().timestamp_millis();
+                        self.state.loading.report_page(data.len(), now);
 
                         if let Some(_) = self.state.loading.turn_page() {
+                            self.state.loading.report_request(now);
                             let start = self.state.loading.left_edge();
                             let symbol = self.symbol.clone();
                             let interval = self.state.props.interval.clone();
                             let limit = self.state.loading.pages.page_size();
+                            let source = self.failover.failover.source;
 
                             self.klines_promise = Some(Promise::spawn_async(async move {
-                                Client::kline(symbol, interval, start, limit).await
+                                Client::source_kline(source, symbol, interval, start, limit).await
                             }));
                         } else {
                             self.klines_promise = None;
-                            let data = Data::new(self.klines.clone());
-                            self.volume.set_data(data.clone());
-                            self.candles.set_data(data);
+                            self.failover.failover.report_success();
+                            self.compare.set_range(
+                                self.state.props.interval,
+                                &self.klines,
+                                self.state.props.limit,
+                            );
+                            self.benchmark.set_range(
+                                self.state.props.interval,
+                                &self.klines,
+                                self.state.props.limit,
+                            );
+                            self.venues.set_range(
+                                &self.symbol,
+                                self.state.props.interval,
+                                &self.klines,
+                                self.state.props.limit,
+                            );
+                            self.record_klines();
+                            self.apply_klines();
                             ui.ctx().request_repaint();
                         }
                     }
                     Err(err) => {
                         error!("Failed to get klines data: {err}");
+                        Toasts::error(format!("Failed to load {} klines", self.symbol), err);
                         self.state.report_loading_error();
                         self.klines_promise = None;
+                        if let Some(source) = self.failover.failover.report_error() {
+                            self.check_source(source);
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some((source, promise)) = &self.failover.check_promise {
+            if let Some(res) = promise.ready() {
+                let source = *source;
+                match res {
+                    Ok(true) if Settings::loaded().auto_failover => self.switch_source(source),
+                    Ok(true) => self.failover.offer = Some(source),
+                    Ok(false) => info!("{} is not listed on {}.", self.symbol, source.as_str()),
+                    Err(err) => error!("Failed to check {}: {err}.", source.as_str()),
+                }
+                self.failover.check_promise = None;
+            }
+        }
+
+        if self.compare.poll() {
+            self.candles.set_comparisons(self.compare.lines());
+            self.correlation.update(&self.data.vals, &self.compare);
+        }
+
+        if self.benchmark.poll() {
+            self.benchmark.update(&self.data.vals);
+        }
+
+        if self.venues.poll() {
+            self.apply_venues();
+        }
+
+        self.poll_stream();
+
+        if Shortcuts::triggered(ui.ctx(), Action::SaveImage) && !self.klines.is_empty() {
+            self.save_image(ui);
+        }
+        Interval::ALL
+            .into_iter()
+            .filter(|i| Shortcuts::triggered(ui.ctx(), Action::Interval(*i)))
+            .for_each(|i| self.set_interval(i));
+
+        let colors = ChartColors::current();
+        if colors != self.colors {
+            self.colors = colors;
+            self.apply_chart_type();
+        }
+
+        self.apply_follow();
+
+        if let Some(promise) = &self.conversion.rates_promise {
+            if let Some(res) = promise.ready() {
+                let converted = match res {
+                    Ok(rates) => convert(&self.klines, rates),
+                    Err(err) => {
+                        error!("Failed to get conversion rates: {err}");
+                        Toasts::error("Failed to load conversion rates", err);
+                        vec![]
+                    }
+                };
+                self.conversion.rates_promise = None;
+
+                match converted.is_empty() {
+                    true => {
+                        self.conversion.applied_quote = String::new();
+                        self.set_data(Data::new(self.klines.clone()));
                     }
+                    false => self.set_data(Data::new(converted)),
                 }
+                ui.ctx().request_repaint();
             }
         }
 
         if self.state.loading.progress() < 1.0 && !self.state.loading.has_error {
+            let progress = self.state.loading.progress();
+            let summary = self.state.loading.summary(Utc::now().timestamp_millis());
             return ui
                 .centered_and_justified(|ui| {
                     ui.add(
-                        ProgressBar::new(self.state.loading.progress())
-                            .show_percentage()
+                        ProgressBar::new(progress)
+                            .text(format!("{:.0}% · {summary}", progress * 100.0))
                             .animate(true),
                     )
                 })
@@ -260,44 +1023,249 @@ impl Widget for &mut Graph {
         if self.state.loading.progress() == 1.0 && self.export_state.triggered {
             info!("Exporting data...");
 
-            let name = format!(
-                "{}-{}-{}-{:?}",
-                self.symbol,
-                self.state.props.start_time(),
-                self.state.props.end_time(),
-                self.state.props.interval,
-            );
-            let f = File::create(format!("{}.csv", name)).unwrap();
-
-            let mut wtr = csv::Writer::from_writer(f);
-            self.klines.iter().for_each(|el| {
-                wtr.serialize(el).unwrap();
-            });
-            wtr.flush().unwrap();
+            let name = self.range_name(&self.state.props);
+            self.request_export(&name, self.klines.clone());
 
             self.export_state.triggered = false;
+        }
+        self.poll_export();
 
-            info!("Exported to file: {}.csv", name);
+        if self.replay.handle_keys(ui.ctx(), &self.data.vals) {
+            self.apply_chart_type();
         }
 
-        TopBottomPanel::top("graph toolbar")
-            .show_inside(ui, |ui| self.time_range_window.toggle_btn(ui));
+        TopBottomPanel::top("graph toolbar").show_inside(ui, |ui| {
+            ui.horizontal(|ui| {
+                self.time_range_window.toggle_btn(ui);
+                self.interval_ui(ui);
+                ui.menu_button("chart", |ui| {
+                    if self.chart_type_ui(ui) {
+                        self.apply_chart_type();
+                    }
+                    ui.separator();
+                    if self.venues.ui(ui) {
+                        self.apply_venues();
+                    }
+                });
+                ui.menu_button("indicators", |ui| {
+                    self.candles.indicators.ui(ui);
+                    ui.separator();
+                    self.candles.profile.props_ui(ui);
+                    let mut changed = ui
+                        .checkbox(&mut self.anomalies.enabled, "Volume anomalies")
+                        .changed();
+                    ui.horizontal(|ui| {
+                        changed |= ui
+                            .add(
+                                DragValue::new(&mut self.anomalies.z_score)
+                                    .speed(0.1)
+                                    .clamp_range(0.5..=10.0),
+                            )
+                            .changed();
+                        ui.label("z-score");
+                    });
+                    if changed {
+                        self.apply_anomalies();
+                    }
+                });
+                ui.menu_button("session", |ui| {
+                    if self.session_ui(ui) && !self.data.vals.is_empty() {
+                        self.set_data(self.data.clone());
+                    }
+                    ui.separator();
+                    ui.label("shading");
+                    self.candles.shading.ui(ui);
+                });
+                ui.menu_button("draw", |ui| {
+                    self.candles.drawings.ui(ui);
+                });
+                ui.menu_button("scale", |ui| {
+                    self.candles.scale_ui(ui);
+                });
+                ui.menu_button("compare", |ui| {
+                    if self.compare.ui(ui) {
+                        self.candles.set_comparisons(self.compare.lines());
+                        self.correlation.update(&self.data.vals, &self.compare);
+                    }
+                    ui.separator();
+                    if self.correlation.ui(ui, &self.compare) {
+                        self.correlation.update(&self.data.vals, &self.compare);
+                    }
+                });
+                ui.menu_button("stats", |ui| {
+                    self.benchmark.ui(ui, &self.symbol);
+                });
+                ui.menu_button("quote", |ui| {
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            TextEdit::singleline(&mut self.conversion.quote)
+                                .desired_width(60.0)
+                                .hint_text(self.quote_asset.as_str()),
+                        );
+                        if ui.button("apply").clicked() {
+                            self.conversion.quote = self.conversion.quote.to_uppercase();
+                            self.apply_klines();
+                            ui.close_menu();
+                        }
+                    });
+                });
+                ui.menu_button("replay", |ui| {
+                    if self.replay.ui(ui, &self.data.vals) {
+                        self.apply_chart_type();
+                    }
+                });
+                self.replay.status_ui(ui, &self.data.vals);
+                if ui
+                    .add_enabled(!self.klines.is_empty(), egui::Button::new("fit"))
+                    .on_hover_text("show all loaded klines, also on double click")
+                    .clicked()
+                {
+                    self.fit_view();
+                }
+                if ui
+                    .selectable_label(self.follow.enabled, "follow")
+                    .on_hover_text("keep the newest kline in view")
+                    .clicked()
+                {
+                    self.follow.set_enabled(!self.follow.enabled);
+                }
+                if self.conversion.rates_promise.is_some() {
+                    ui.spinner();
+                }
+                if !self.conversion.applied_quote.is_empty() {
+                    ui.label(format!("in {}", self.conversion.applied_quote));
+                }
+                if ui
+                    .add_enabled(!self.klines.is_empty(), egui::Button::new("export visible"))
+                    .clicked()
+                {
+                    self.export_visible();
+                }
+                let cancelled = match &mut self.export_state.stream {
+                    Some(stream) => stream.ui(ui),
+                    None => false,
+                };
+                if cancelled {
+                    if let Some(stream) = self.export_state.stream.take() {
+                        stream.cancel();
+                    }
+                }
+                if ui
+                    .add_enabled(
+                        !self.klines.is_empty(),
+                        egui::Button::new("save chart image"),
+                    )
+                    .clicked()
+                {
+                    self.save_image(ui);
+                }
+                if let Some(source) = self.failover.offer {
+                    ui.colored_label(
+                        Color32::LIGHT_RED,
+                        format!("⚠ {} keeps failing", Source::default().as_str()),
+                    );
+                    if ui
+                        .button(format!("load from {}", source.as_str()))
+                        .clicked()
+                    {
+                        self.switch_source(source);
+                    }
+                }
+                if self.failover.failover.is_substituted() {
+                    let source = self.failover.failover.source;
+                    ui.colored_label(Color32::GOLD, format!("⚠ from {}", source.as_str()))
+                        .on_hover_text(format!(
+                            "klines are loaded from {} instead of {}",
+                            source.as_str(),
+                            Source::default().as_str()
+                        ));
+                    if ui
+                        .button(format!("back to {}", Source::default().as_str()))
+                        .clicked()
+                    {
+                        self.switch_source(Source::default());
+                    }
+                }
+                let triggered: Vec<String> = self
+                    .candles
+                    .alerts
+                    .triggered()
+                    .map(|a| format!("{:.8}", a.price))
+                    .collect();
+                if !triggered.is_empty() {
+                    ui.colored_label(
+                        Color32::GOLD,
+                        format!("🔔 {} alerts triggered", triggered.len()),
+                    )
+                    .on_hover_text(triggered.join("\n"));
+                }
+                if !self.data_anomalies.is_empty() {
+                    ui.colored_label(
+                        Color32::GOLD,
+                        format!("⚠ {} data anomalies", self.data_anomalies.len()),
+                    )
+                    .on_hover_text(
+                        self.data_anomalies
+                            .iter()
+                            .take(MAX_SHOWN_ANOMALIES)
+                            .map(|a| a.to_string())
+                            .collect::<Vec<String>>()
+                            .join("\n"),
+                    );
+                }
+            });
+        });
 
         CentralPanel::default()
             .show_inside(ui, |ui| {
                 self.time_range_window.show(ui);
 
-                StripBuilder::new(ui)
-                    .size(Size::relative(0.8))
+                let correlation = self.correlation.series();
+                let panes = self.candles.indicators.enabled(Target::Pane).count()
+                    + correlation.is_some() as usize;
+
+                let mut builder = StripBuilder::new(ui)
                     .size(Size::remainder())
-                    .vertical(|mut strip| {
+                    .size(Size::relative(0.2));
+                for _ in 0..panes {
+                    builder = builder.size(Size::relative(0.2));
+                }
+
+                let mut rect = Rect::NOTHING;
+                let mut fit = false;
+                builder.vertical(|mut strip| {
+                    strip.cell(|ui| {
+                        let resp = ui.add(&mut self.candles);
+                        fit |= resp.double_clicked();
+                        rect = rect.union(resp.rect);
+                    });
+                    strip.cell(|ui| {
+                        let resp = ui.add(&self.volume);
+                        fit |= resp.double_clicked();
+                        rect = rect.union(resp.rect);
+                    });
+                    self.candles.indicators.enabled(Target::Pane).for_each(|i| {
                         strip.cell(|ui| {
-                            ui.add(&mut self.candles);
+                            fit |= ui
+                                .add(Pane::new(i, self.axes_group.clone()))
+                                .double_clicked();
                         });
+                    });
+                    if let Some(series) = correlation {
                         strip.cell(|ui| {
-                            ui.add(&self.volume);
+                            let pane = Pane::series(
+                                "correlation pane".to_string(),
+                                series,
+                                self.axes_group.clone(),
+                            );
+                            fit |= ui.add(pane).double_clicked();
                         });
-                    })
+                    }
+                });
+                self.image.rect = rect;
+                if fit {
+                    self.fit_view();
+                }
             })
             .response
     }
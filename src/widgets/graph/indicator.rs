@@ -0,0 +1,257 @@
+use std::{collections::HashMap, path::Path};
+
+use egui::Ui;
+use tracing::warn;
+
+use crate::{
+    netstrat::{
+        data::Data,
+        export::Column,
+        script::{Script, SCRIPTS_DIR},
+    },
+    sources::binance::Kline,
+};
+
+use super::{
+    overlay::{Ichimoku, Pivots, Psar, SuperTrend, Vwap},
+    pane::{Adx, Atr, Macd, Obv, Rsi},
+    scripted::ScriptIndicator,
+    series::{x, Series},
+};
+
+/// Where indicator series are drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Target {
+    /// Inside the candles plot.
+    Overlay,
+    /// In a separate plot below the volume sharing the x axis with candles.
+    Pane,
+}
+
+/// Indicator is a study computing series from loaded data.
+pub trait Indicator {
+    fn name(&self) -> &str;
+    fn target(&self) -> Target;
+    fn compute(&self, data: &Data) -> Series;
+    /// Draws parameters controls. Returns true if the parameters were changed.
+    fn props_ui(&mut self, ui: &mut Ui) -> bool;
+}
+
+type Constructor = fn() -> Box<dyn Indicator>;
+
+/// Returns names and constructors of all builtin indicators with default parameters.
+pub fn builtin() -> Vec<(&'static str, Constructor)> {
+    vec![
+        ("Ichimoku", || Box::new(Ichimoku::default())),
+        ("Parabolic SAR", || Box::new(Psar::default())),
+        ("SuperTrend", || Box::new(SuperTrend::default())),
+        ("Pivot points", || Box::new(Pivots::default())),
+        ("VWAP", || Box::new(Vwap::default())),
+        ("RSI", || Box::new(Rsi::default())),
+        ("MACD", || Box::new(Macd::default())),
+        ("ATR", || Box::new(Atr::default())),
+        ("OBV", || Box::new(Obv::default())),
+        ("ADX", || Box::new(Adx::default())),
+    ]
+}
+
+/// Indicator added to a chart together with its computed series.
+pub struct Instance {
+    pub id: usize,
+    pub enabled: bool,
+    indicator: Box<dyn Indicator>,
+    series: Series,
+}
+
+impl Instance {
+    pub fn name(&self) -> &str {
+        self.indicator.name()
+    }
+
+    pub fn target(&self) -> Target {
+        self.indicator.target()
+    }
+
+    pub fn series(&self) -> &Series {
+        &self.series
+    }
+}
+
+enum Action {
+    Up(usize),
+    Down(usize),
+    Remove(usize),
+}
+
+/// IndicatorManager holds indicator instances of a chart and lets the user
+/// add, remove, reorder and configure them.
+#[derive(Default)]
+pub struct IndicatorManager {
+    instances: Vec<Instance>,
+    data: Data,
+    next_id: usize,
+    scripts: Option<Vec<Script>>,
+}
+
+impl IndicatorManager {
+    pub fn add(&mut self, indicator: Box<dyn Indicator>, enabled: bool) {
+        self.instances.push(Instance {
+            id: self.next_id,
+            enabled,
+            series: indicator.compute(&self.data),
+            indicator,
+        });
+        self.next_id += 1;
+    }
+
+    pub fn set_data(&mut self, data: Data) {
+        self.instances
+            .iter_mut()
+            .for_each(|i| i.series = i.indicator.compute(&data));
+        self.data = data;
+    }
+
+    /// Returns enabled instances drawn with the target in the user defined order.
+    pub fn enabled(&self, target: Target) -> impl Iterator<Item = &Instance> {
+        self.instances
+            .iter()
+            .filter(move |i| i.enabled && i.target() == target)
+    }
+
+    pub fn enabled_names(&self) -> Vec<String> {
+        self.instances
+            .iter()
+            .filter(|i| i.enabled)
+            .map(|i| i.name().to_string())
+            .collect()
+    }
+
+    /// Returns lines, dots and histograms of enabled instances as columns aligned with the klines.
+    /// Values are matched to klines by time, lines split into segments become one column.
+    pub fn columns(&self, klines: &[Kline]) -> Vec<Column> {
+        let rows: HashMap<u64, usize> = klines
+            .iter()
+            .enumerate()
+            .map(|(i, k)| (x(k).to_bits(), i))
+            .collect();
+
+        let mut res: Vec<Column> = vec![];
+        let mut put = |name: &str, time: f64, v: f64| {
+            let row = match rows.get(&time.to_bits()) {
+                Some(row) => *row,
+                None => return,
+            };
+            let idx = match res.iter().position(|c| c.name == name) {
+                Some(idx) => idx,
+                None => {
+                    res.push(Column {
+                        name: name.to_string(),
+                        vals: vec![None; klines.len()],
+                    });
+                    res.len() - 1
+                }
+            };
+            res[idx].vals[row] = Some(v);
+        };
+
+        self.instances.iter().filter(|i| i.enabled).for_each(|i| {
+            let series = i.series();
+            series.lines.iter().chain(&series.points).for_each(|l| {
+                l.vals.iter().for_each(|v| put(&l.name, v.x, v.y));
+            });
+            let histogram = format!("{} histogram", i.name());
+            series
+                .histogram
+                .iter()
+                .for_each(|b| put(&histogram, b.argument, b.value));
+        });
+
+        res
+    }
+
+    /// Enables only instances with the given names adding missing builtin or script indicators.
+    pub fn enable_only(&mut self, names: &[String]) {
+        self.instances
+            .iter_mut()
+            .for_each(|i| i.enabled = names.iter().any(|n| n == i.name()));
+
+        let missing: Vec<String> = names
+            .iter()
+            .filter(|n| !self.instances.iter().any(|i| i.name() == n.as_str()))
+            .cloned()
+            .collect();
+        missing.into_iter().for_each(|name| {
+            if let Some((_, create)) = builtin().into_iter().find(|(b, _)| *b == name) {
+                self.add(create(), true);
+                return;
+            }
+            match self.scripts().into_iter().find(|s| s.name == name) {
+                Some(script) => self.add(Box::new(ScriptIndicator::new(script)), true),
+                None => warn!("Unknown indicator: {name}."),
+            }
+        });
+    }
+
+    fn scripts(&mut self) -> Vec<Script> {
+        self.scripts
+            .get_or_insert_with(|| Script::load_dir(Path::new(SCRIPTS_DIR)))
+            .clone()
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui) {
+        let mut action = None;
+        let last = self.instances.len().saturating_sub(1);
+        self.instances.iter_mut().enumerate().for_each(|(idx, i)| {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut i.enabled, i.indicator.name());
+                if ui.add_enabled(idx > 0, egui::Button::new("⬆")).clicked() {
+                    action = Some(Action::Up(idx));
+                }
+                if ui.add_enabled(idx < last, egui::Button::new("⬇")).clicked() {
+                    action = Some(Action::Down(idx));
+                }
+                if ui.button("🗑").clicked() {
+                    action = Some(Action::Remove(idx));
+                }
+            });
+
+            let changed = ui.horizontal(|ui| i.indicator.props_ui(ui)).inner;
+            if changed {
+                i.series = i.indicator.compute(&self.data);
+            }
+        });
+
+        match action {
+            Some(Action::Up(idx)) => self.instances.swap(idx, idx - 1),
+            Some(Action::Down(idx)) => self.instances.swap(idx, idx + 1),
+            Some(Action::Remove(idx)) => {
+                self.instances.remove(idx);
+            }
+            None => {}
+        }
+
+        ui.menu_button("add", |ui| {
+            builtin().into_iter().for_each(|(name, create)| {
+                if ui.button(name).clicked() {
+                    self.add(create(), true);
+                    ui.close_menu();
+                }
+            });
+
+            ui.separator();
+            let scripts = self.scripts();
+            if scripts.is_empty() {
+                ui.label(format!("put *.rhai scripts into {SCRIPTS_DIR}/"));
+            }
+            scripts.into_iter().for_each(|script| {
+                if ui.button(format!("📜 {}", script.name)).clicked() {
+                    self.add(Box::new(ScriptIndicator::new(script)), true);
+                    ui.close_menu();
+                }
+            });
+            if ui.button("reload scripts").clicked() {
+                self.scripts = None;
+            }
+        });
+    }
+}
@@ -0,0 +1,42 @@
+use egui::{
+    plot::{HLine, LineStyle, PlotUi, Value},
+    Align2, Color32, FontId, Rect, Shape, Vec2,
+};
+
+use crate::netstrat::data::Data;
+
+use super::candles::format_price;
+
+const TAG_PADDING: Vec2 = Vec2::new(4.0, 2.0);
+
+/// Draws a dashed line at the close of the last kline colored by its direction.
+/// Returns shapes of the tag with the price at the right edge of the plot.
+pub fn show(plot_ui: &mut PlotUi, data: &Data, y_map: &dyn Fn(f64) -> f64) -> Vec<Shape> {
+    let k = match data.vals.last() {
+        Some(k) => k,
+        None => return vec![],
+    };
+    let (price, color) = (k.close as f64, Data::k_color(k));
+    let y = y_map(price);
+
+    plot_ui.hline(
+        HLine::new(y)
+            .color(color.linear_multiply(0.6))
+            .style(LineStyle::dashed_dense()),
+    );
+
+    let right = plot_ui.plot_bounds().max()[0];
+    let pos = plot_ui.screen_from_plot(Value::new(right, y));
+    let galley = plot_ui.ctx().fonts().layout_no_wrap(
+        format_price(price),
+        FontId::monospace(12.0),
+        Color32::BLACK,
+    );
+    let rect = Align2::RIGHT_CENTER
+        .anchor_rect(Rect::from_min_size(pos, galley.size() + TAG_PADDING * 2.0));
+
+    vec![
+        Shape::rect_filled(rect, 2.0, color),
+        Shape::galley(rect.min + TAG_PADDING, galley),
+    ]
+}
@@ -0,0 +1,54 @@
+/// Keeps a rarely changing chart layer and rebuilds it only when its key changes,
+/// so static shapes are not recomputed on every frame.
+pub struct LayerCache<K, V> {
+    key: Option<K>,
+    layer: V,
+}
+
+impl<K, V: Default> Default for LayerCache<K, V> {
+    fn default() -> Self {
+        Self {
+            key: None,
+            layer: Default::default(),
+        }
+    }
+}
+
+impl<K: PartialEq, V> LayerCache<K, V> {
+    /// Returns the layer built for the key calling build only on a cache miss.
+    pub fn get(&mut self, key: K, build: impl FnOnce() -> V) -> &V {
+        if self.key.as_ref() != Some(&key) {
+            self.layer = build();
+            self.key = Some(key);
+        }
+
+        &self.layer
+    }
+
+    /// Drops the cached layer after the data it was built from changed.
+    pub fn invalidate(&mut self) {
+        self.key = None;
+    }
+}
+
+#[cfg(test)]
+mod layer_cache_tests {
+    use super::*;
+
+    #[test]
+    fn test_rebuilds_on_key_change() {
+        let mut cache: LayerCache<u32, Vec<u32>> = Default::default();
+        let mut builds = 0;
+        let mut build = |v| {
+            builds += 1;
+            vec![v]
+        };
+
+        assert_eq!(cache.get(1, || build(1)), &[1]);
+        assert_eq!(cache.get(1, || build(2)), &[1]);
+        assert_eq!(cache.get(2, || build(2)), &[2]);
+        cache.invalidate();
+        assert_eq!(cache.get(2, || build(3)), &[3]);
+        assert_eq!(builds, 3);
+    }
+}
@@ -1,4 +1,22 @@
+pub mod alerts;
+pub mod benchmark;
 pub mod candles;
+pub mod compare;
+pub mod correlation;
+pub mod drawings;
 pub mod graph;
+pub mod indicator;
+pub mod last_price;
+pub mod layer_cache;
+pub mod overlay;
+pub mod pointer;
+pub mod pane;
+pub mod profile;
+pub mod replay;
+pub mod scripted;
+pub mod series;
+pub mod shading;
+pub mod stream;
 pub mod time_input;
+pub mod venues;
 pub mod volume;
\ No newline at end of file
@@ -0,0 +1,413 @@
+use egui::{plot::Value, Color32, DragValue, Ui};
+
+use crate::{
+    netstrat::{
+        data::Data,
+        indicators::{
+            ichimoku::ichimoku,
+            pivots::{pivots, PivotPeriod},
+            psar::psar,
+            supertrend::supertrend,
+            vwap::vwap,
+        },
+    },
+    sources::binance::Kline,
+};
+
+use super::{
+    indicator::{Indicator, Target},
+    series::{values, x, x_at, Series, SeriesLine},
+};
+
+/// Ichimoku Kinko Hyo with the cloud between senkou spans.
+pub struct Ichimoku {
+    tenkan: usize,
+    kijun: usize,
+    senkou_b: usize,
+    displacement: usize,
+}
+
+impl Default for Ichimoku {
+    fn default() -> Self {
+        Self {
+            tenkan: 9,
+            kijun: 26,
+            senkou_b: 52,
+            displacement: 26,
+        }
+    }
+}
+
+impl Indicator for Ichimoku {
+    fn name(&self) -> &str {
+        "Ichimoku"
+    }
+
+    fn target(&self) -> Target {
+        Target::Overlay
+    }
+
+    fn compute(&self, data: &Data) -> Series {
+        let klines = &data.vals;
+        let (tenkan, kijun, senkou_b) = (self.tenkan, self.kijun, self.senkou_b);
+        let res = ichimoku(klines, tenkan, kijun, senkou_b, self.displacement);
+
+        Series {
+            areas: cloud(
+                &res.senkou_a
+                    .iter()
+                    .zip(res.senkou_b.iter())
+                    .enumerate()
+                    .filter_map(|(i, (a, b))| Some((x_at(klines, i)?, (*a)?, (*b)?)))
+                    .collect::<Vec<(f64, f64, f64)>>(),
+            ),
+            lines: vec![
+                SeriesLine::new(
+                    format!("tenkan({tenkan})"),
+                    Color32::LIGHT_BLUE,
+                    values(klines, &res.tenkan),
+                ),
+                SeriesLine::new(
+                    format!("kijun({kijun})"),
+                    Color32::LIGHT_RED,
+                    values(klines, &res.kijun),
+                ),
+                SeriesLine::new(
+                    "senkou a".to_string(),
+                    Color32::LIGHT_GREEN,
+                    values(klines, &res.senkou_a),
+                ),
+                SeriesLine::new(
+                    format!("senkou b({senkou_b})"),
+                    Color32::from_rgb(200, 100, 100),
+                    values(klines, &res.senkou_b),
+                ),
+                SeriesLine::new(
+                    "chikou".to_string(),
+                    Color32::GRAY,
+                    values(klines, &res.chikou),
+                ),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn props_ui(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = ui
+            .add(DragValue::new(&mut self.tenkan).clamp_range(1..=200))
+            .changed();
+        ui.label("tenkan");
+        changed |= ui
+            .add(DragValue::new(&mut self.kijun).clamp_range(1..=200))
+            .changed();
+        ui.label("kijun");
+        changed |= ui
+            .add(DragValue::new(&mut self.senkou_b).clamp_range(1..=200))
+            .changed();
+        ui.label("senkou b");
+        changed |= ui
+            .add(DragValue::new(&mut self.displacement).clamp_range(0..=200))
+            .changed();
+        ui.label("displacement");
+
+        changed
+    }
+}
+
+/// Parabolic SAR drawn as dots.
+pub struct Psar {
+    step: f64,
+    max: f64,
+}
+
+impl Default for Psar {
+    fn default() -> Self {
+        Self {
+            step: 0.02,
+            max: 0.2,
+        }
+    }
+}
+
+impl Indicator for Psar {
+    fn name(&self) -> &str {
+        "Parabolic SAR"
+    }
+
+    fn target(&self) -> Target {
+        Target::Overlay
+    }
+
+    fn compute(&self, data: &Data) -> Series {
+        Series {
+            points: vec![SeriesLine::new(
+                format!("PSAR({}, {})", self.step, self.max),
+                Color32::LIGHT_YELLOW,
+                values(&data.vals, &psar(&data.vals, self.step, self.max)),
+            )],
+            ..Default::default()
+        }
+    }
+
+    fn props_ui(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = ui
+            .add(
+                DragValue::new(&mut self.step)
+                    .speed(0.001)
+                    .clamp_range(0.001..=1.0),
+            )
+            .changed();
+        ui.label("step");
+        changed |= ui
+            .add(
+                DragValue::new(&mut self.max)
+                    .speed(0.01)
+                    .clamp_range(0.001..=1.0),
+            )
+            .changed();
+        ui.label("max");
+
+        changed
+    }
+}
+
+/// SuperTrend line colored by the trend direction.
+pub struct SuperTrend {
+    period: usize,
+    multiplier: f64,
+}
+
+impl Default for SuperTrend {
+    fn default() -> Self {
+        Self {
+            period: 10,
+            multiplier: 3.0,
+        }
+    }
+}
+
+impl Indicator for SuperTrend {
+    fn name(&self) -> &str {
+        "SuperTrend"
+    }
+
+    fn target(&self) -> Target {
+        Target::Overlay
+    }
+
+    fn compute(&self, data: &Data) -> Series {
+        Series {
+            lines: trend_lines(
+                &data.vals,
+                &supertrend(&data.vals, self.period, self.multiplier),
+            ),
+            ..Default::default()
+        }
+    }
+
+    fn props_ui(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = ui
+            .add(DragValue::new(&mut self.period).clamp_range(2..=200))
+            .changed();
+        ui.label("period");
+        changed |= ui
+            .add(
+                DragValue::new(&mut self.multiplier)
+                    .speed(0.1)
+                    .clamp_range(0.1..=10.0),
+            )
+            .changed();
+        ui.label("multiplier");
+
+        changed
+    }
+}
+
+/// Classic pivot levels of the previous day or week.
+pub struct Pivots {
+    period: PivotPeriod,
+}
+
+impl Default for Pivots {
+    fn default() -> Self {
+        Self {
+            period: PivotPeriod::Day,
+        }
+    }
+}
+
+impl Indicator for Pivots {
+    fn name(&self) -> &str {
+        "Pivot points"
+    }
+
+    fn target(&self) -> Target {
+        Target::Overlay
+    }
+
+    fn compute(&self, data: &Data) -> Series {
+        Series {
+            lines: pivots(&data.vals, self.period, &data.session)
+                .iter()
+                .flat_map(|p| {
+                    p.levels().map(|(name, level)| {
+                        SeriesLine::new(
+                            name.to_string(),
+                            match name {
+                                "P" => Color32::GOLD,
+                                _ if name.starts_with('R') => Color32::LIGHT_RED,
+                                _ => Color32::LIGHT_GREEN,
+                            },
+                            vec![
+                                Value::new(p.start as f64, level),
+                                Value::new(p.end as f64, level),
+                            ],
+                        )
+                    })
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn props_ui(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = ui
+            .selectable_value(&mut self.period, PivotPeriod::Day, "daily")
+            .changed();
+        changed |= ui
+            .selectable_value(&mut self.period, PivotPeriod::Week, "weekly")
+            .changed();
+
+        changed
+    }
+}
+
+/// Volume weighted average price reset at every trading session open.
+#[derive(Default)]
+pub struct Vwap {}
+
+impl Indicator for Vwap {
+    fn name(&self) -> &str {
+        "VWAP"
+    }
+
+    fn target(&self) -> Target {
+        Target::Overlay
+    }
+
+    fn compute(&self, data: &Data) -> Series {
+        Series {
+            lines: session_lines(
+                "VWAP",
+                Color32::LIGHT_YELLOW,
+                data,
+                &vwap(&data.vals, &data.session),
+            ),
+            ..Default::default()
+        }
+    }
+
+    fn props_ui(&mut self, _: &mut Ui) -> bool {
+        false
+    }
+}
+
+/// Splits series into separate lines per trading session so lines are not joined across resets.
+fn session_lines(
+    name: &str,
+    color: Color32,
+    data: &Data,
+    series: &[Option<f64>],
+) -> Vec<SeriesLine> {
+    let mut res: Vec<SeriesLine> = vec![];
+    let mut current = None;
+    data.vals.iter().zip(series).for_each(|(k, v)| {
+        let (v, start) = match (v, data.session.start(k.t_open)) {
+            (Some(v), Some(start)) => (*v, start),
+            _ => return,
+        };
+
+        let pt = Value::new(x(k), v);
+        match (current == Some(start), res.last_mut()) {
+            (true, Some(line)) => line.vals.push(pt),
+            _ => res.push(SeriesLine::new(name.to_string(), color, vec![pt])),
+        }
+        current = Some(start);
+    });
+
+    res
+}
+
+/// Splits SuperTrend into lines of continuous trend colored by its direction.
+fn trend_lines(klines: &[Kline], trend: &[Option<(f64, bool)>]) -> Vec<SeriesLine> {
+    let mut res: Vec<SeriesLine> = vec![];
+    let mut prev_up = None;
+    trend.iter().enumerate().for_each(|(i, v)| {
+        let (level, up) = match v {
+            Some(v) => *v,
+            None => return,
+        };
+
+        let pt = Value::new(x(&klines[i]), level);
+        match (prev_up == Some(up), res.last_mut()) {
+            (true, Some(line)) => line.vals.push(pt),
+            _ => res.push(SeriesLine::new(
+                match up {
+                    true => "SuperTrend up".to_string(),
+                    false => "SuperTrend down".to_string(),
+                },
+                match up {
+                    true => Color32::LIGHT_GREEN,
+                    false => Color32::LIGHT_RED,
+                },
+                vec![pt],
+            )),
+        }
+        prev_up = Some(up);
+    });
+
+    res
+}
+
+/// Builds polygons filling the area between 2 lines given as (x, a, b) points.
+/// Segments where lines cross are split at the crossing so every polygon is convex.
+fn cloud(pts: &[(f64, f64, f64)]) -> Vec<SeriesLine> {
+    let area = |vals, color| SeriesLine::new("cloud".to_string(), color, vals);
+    let color = |a: f64, b: f64| match a >= b {
+        true => Color32::LIGHT_GREEN,
+        false => Color32::LIGHT_RED,
+    };
+
+    let mut res = vec![];
+    pts.windows(2).for_each(|w| {
+        let (x0, a0, b0) = w[0];
+        let (x1, a1, b1) = w[1];
+        let (d0, d1) = (a0 - b0, a1 - b1);
+
+        if d0 * d1 >= 0.0 {
+            res.push(area(
+                vec![
+                    Value::new(x0, a0),
+                    Value::new(x1, a1),
+                    Value::new(x1, b1),
+                    Value::new(x0, b0),
+                ],
+                color(a0 + a1, b0 + b1),
+            ));
+            return;
+        }
+
+        let t = d0 / (d0 - d1);
+        let cross = Value::new(x0 + t * (x1 - x0), a0 + t * (a1 - a0));
+        res.push(area(
+            vec![Value::new(x0, a0), cross, Value::new(x0, b0)],
+            color(a0, b0),
+        ));
+        res.push(area(
+            vec![cross, Value::new(x1, a1), Value::new(x1, b1)],
+            color(a1, b1),
+        ));
+    });
+
+    res
+}
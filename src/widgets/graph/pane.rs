@@ -0,0 +1,319 @@
+use std::ops::RangeInclusive;
+
+use egui::{
+    plot::{Bar, Legend, LinkedAxisGroup, Plot},
+    Color32, DragValue, Ui, Widget,
+};
+
+use crate::netstrat::{
+    data::Data,
+    indicators::{adx::adx, atr::atr, closes, macd::macd, obv::obv, rsi::rsi},
+};
+
+use super::{
+    indicator::{Indicator, Instance, Target},
+    series::{values, x, Series, SeriesLine},
+};
+
+const RSI_OVERBOUGHT: f64 = 70.0;
+const RSI_OVERSOLD: f64 = 30.0;
+const ADX_TRENDING: f64 = 25.0;
+
+fn period_ui(ui: &mut Ui, period: &mut usize) -> bool {
+    let changed = ui
+        .add(DragValue::new(period).clamp_range(2..=200))
+        .changed();
+    ui.label("period");
+
+    changed
+}
+
+/// Relative Strength Index.
+pub struct Rsi {
+    period: usize,
+}
+
+impl Default for Rsi {
+    fn default() -> Self {
+        Self { period: 14 }
+    }
+}
+
+impl Indicator for Rsi {
+    fn name(&self) -> &str {
+        "RSI"
+    }
+
+    fn target(&self) -> Target {
+        Target::Pane
+    }
+
+    fn compute(&self, data: &Data) -> Series {
+        Series {
+            lines: vec![SeriesLine::new(
+                format!("RSI({})", self.period),
+                Color32::LIGHT_BLUE,
+                values(&data.vals, &rsi(&closes(data), self.period)),
+            )],
+            levels: vec![RSI_OVERSOLD, RSI_OVERBOUGHT],
+            include_y: vec![0.0, 100.0],
+            ..Default::default()
+        }
+    }
+
+    fn props_ui(&mut self, ui: &mut Ui) -> bool {
+        period_ui(ui, &mut self.period)
+    }
+}
+
+/// Moving Average Convergence Divergence with the histogram colored by sign.
+pub struct Macd {
+    fast: usize,
+    slow: usize,
+    signal: usize,
+}
+
+impl Default for Macd {
+    fn default() -> Self {
+        Self {
+            fast: 12,
+            slow: 26,
+            signal: 9,
+        }
+    }
+}
+
+impl Indicator for Macd {
+    fn name(&self) -> &str {
+        "MACD"
+    }
+
+    fn target(&self) -> Target {
+        Target::Pane
+    }
+
+    fn compute(&self, data: &Data) -> Series {
+        let (fast, slow, signal) = (self.fast, self.slow, self.signal);
+        let res = macd(&closes(data), fast, slow, signal);
+
+        Series {
+            histogram: data
+                .vals
+                .iter()
+                .zip(res.histogram)
+                .filter_map(|(k, v)| {
+                    let v = v?;
+                    Some(
+                        Bar::new(x(k), v)
+                            .width((k.t_open - k.t_close) as f64 * 0.9)
+                            .fill(match v < 0.0 {
+                                true => Color32::LIGHT_RED.linear_multiply(0.5),
+                                false => Color32::LIGHT_GREEN.linear_multiply(0.5),
+                            }),
+                    )
+                })
+                .collect(),
+            lines: vec![
+                SeriesLine::new(
+                    format!("MACD({fast}, {slow})"),
+                    Color32::LIGHT_BLUE,
+                    values(&data.vals, &res.macd),
+                ),
+                SeriesLine::new(
+                    format!("signal({signal})"),
+                    Color32::GOLD,
+                    values(&data.vals, &res.signal),
+                ),
+            ],
+            levels: vec![0.0],
+            ..Default::default()
+        }
+    }
+
+    fn props_ui(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = ui
+            .add(DragValue::new(&mut self.fast).clamp_range(2..=200))
+            .changed();
+        ui.label("fast");
+        changed |= ui
+            .add(DragValue::new(&mut self.slow).clamp_range(2..=200))
+            .changed();
+        ui.label("slow");
+        changed |= ui
+            .add(DragValue::new(&mut self.signal).clamp_range(2..=200))
+            .changed();
+        ui.label("signal");
+
+        changed
+    }
+}
+
+/// Average True Range.
+pub struct Atr {
+    period: usize,
+}
+
+impl Default for Atr {
+    fn default() -> Self {
+        Self { period: 14 }
+    }
+}
+
+impl Indicator for Atr {
+    fn name(&self) -> &str {
+        "ATR"
+    }
+
+    fn target(&self) -> Target {
+        Target::Pane
+    }
+
+    fn compute(&self, data: &Data) -> Series {
+        Series {
+            lines: vec![SeriesLine::new(
+                format!("ATR({})", self.period),
+                Color32::LIGHT_BLUE,
+                values(&data.vals, &atr(&data.vals, self.period)),
+            )],
+            ..Default::default()
+        }
+    }
+
+    fn props_ui(&mut self, ui: &mut Ui) -> bool {
+        period_ui(ui, &mut self.period)
+    }
+}
+
+/// On-Balance Volume.
+#[derive(Default)]
+pub struct Obv {}
+
+impl Indicator for Obv {
+    fn name(&self) -> &str {
+        "OBV"
+    }
+
+    fn target(&self) -> Target {
+        Target::Pane
+    }
+
+    fn compute(&self, data: &Data) -> Series {
+        Series {
+            lines: vec![SeriesLine::new(
+                "OBV".to_string(),
+                Color32::LIGHT_BLUE,
+                values(
+                    &data.vals,
+                    &obv(&data.vals).into_iter().map(Some).collect::<Vec<_>>(),
+                ),
+            )],
+            ..Default::default()
+        }
+    }
+
+    fn props_ui(&mut self, _: &mut Ui) -> bool {
+        false
+    }
+}
+
+/// Average Directional Index with +DI and -DI.
+pub struct Adx {
+    period: usize,
+}
+
+impl Default for Adx {
+    fn default() -> Self {
+        Self { period: 14 }
+    }
+}
+
+impl Indicator for Adx {
+    fn name(&self) -> &str {
+        "ADX"
+    }
+
+    fn target(&self) -> Target {
+        Target::Pane
+    }
+
+    fn compute(&self, data: &Data) -> Series {
+        let res = adx(&data.vals, self.period);
+
+        Series {
+            lines: vec![
+                SeriesLine::new(
+                    format!("ADX({})", self.period),
+                    Color32::LIGHT_BLUE,
+                    values(&data.vals, &res.adx),
+                ),
+                SeriesLine::new(
+                    "+DI".to_string(),
+                    Color32::LIGHT_GREEN,
+                    values(&data.vals, &res.plus_di),
+                ),
+                SeriesLine::new(
+                    "-DI".to_string(),
+                    Color32::LIGHT_RED,
+                    values(&data.vals, &res.minus_di),
+                ),
+            ],
+            levels: vec![ADX_TRENDING],
+            include_y: vec![0.0, 100.0],
+            ..Default::default()
+        }
+    }
+
+    fn props_ui(&mut self, ui: &mut Ui) -> bool {
+        period_ui(ui, &mut self.period)
+    }
+}
+
+/// Pane is a plot of an indicator instance rendered below the volume strip.
+/// It shares the x axis with candles and volume through the linked axes group.
+pub struct Pane<'a> {
+    id: String,
+    series: &'a Series,
+    axes_group: LinkedAxisGroup,
+}
+
+impl<'a> Pane<'a> {
+    pub fn new(instance: &'a Instance, axes_group: LinkedAxisGroup) -> Self {
+        Self::series(
+            format!("pane {}", instance.id),
+            instance.series(),
+            axes_group,
+        )
+    }
+
+    /// Pane of series computed outside of indicators.
+    pub fn series(id: String, series: &'a Series, axes_group: LinkedAxisGroup) -> Self {
+        Self {
+            id,
+            series,
+            axes_group,
+        }
+    }
+}
+
+impl Widget for Pane<'_> {
+    fn ui(self, ui: &mut Ui) -> egui::Response {
+        let series = self.series;
+        let mut plot = Plot::new(self.id)
+            .legend(Legend::default())
+            .link_axis(self.axes_group)
+            .x_axis_formatter(|v: f64, _: &RangeInclusive<f64>| Data::format_ts(v))
+            .label_formatter(|name, v| format!("{name} {:.2}\n{}", v.y, Data::format_ts(v.x)))
+            .allow_scroll(false)
+            .allow_boxed_zoom(false)
+            .allow_drag(false)
+            .allow_zoom(false)
+            .show_axes([false, true]);
+
+        for y in &series.include_y {
+            plot = plot.include_y(*y);
+        }
+
+        plot.show(ui, |plot_ui| series.show(plot_ui, &|v| v))
+            .response
+    }
+}
@@ -0,0 +1,41 @@
+use egui::{plot::PlotUi, Pos2};
+
+/// Distance in points from a drawn item within which it can be grabbed.
+pub const GRAB_DISTANCE: f32 = 5.0;
+
+/// Pointer state read once per frame for dragging items drawn on a plot.
+pub struct PlotPointer {
+    /// Screen position, set while the plot is hovered or while an item is grabbed.
+    pub pos: Option<Pos2>,
+    /// Primary button was pressed on this frame.
+    pub pressed: bool,
+    pub down: bool,
+    /// Context menu opens on release, so a point for it is taken while the button is held.
+    pub secondary_down: bool,
+}
+
+impl PlotPointer {
+    pub fn read(plot_ui: &PlotUi, grabbed: bool) -> Self {
+        let pointer = &plot_ui.ctx().input().pointer;
+
+        Self {
+            pos: pointer
+                .hover_pos()
+                .filter(|_| plot_ui.plot_hovered() || grabbed),
+            pressed: pointer.any_pressed() && pointer.primary_down(),
+            down: pointer.primary_down(),
+            secondary_down: pointer.secondary_down(),
+        }
+    }
+}
+
+/// Screen distance from the point to the segment between a and b.
+pub fn segment_distance(p: Pos2, a: Pos2, b: Pos2) -> f32 {
+    let ab = b - a;
+    let t = match ab.length_sq() {
+        l if l > 0.0 => ((p - a).dot(ab) / l).clamp(0.0, 1.0),
+        _ => 0.0,
+    };
+
+    p.distance(a + ab * t)
+}
@@ -0,0 +1,141 @@
+use egui::{
+    plot::{PlotUi, Value},
+    Color32, DragValue, Rect, Shape, Ui,
+};
+
+use crate::netstrat::{data::Data, price_scale::PriceScale, volume_profile::VolumeProfile};
+
+use super::layer_cache::LayerCache;
+
+/// Histogram bar in plot coordinates with its width relative to the longest bar.
+struct Bar {
+    low: f64,
+    high: f64,
+    share: f32,
+    color: Color32,
+}
+
+/// Share of the plot width taken by the longest bar.
+const WIDTH_SHARE: f32 = 0.2;
+
+/// Volume profile histogram drawn at the right edge of the candles plot.
+pub struct Profile {
+    pub enabled: bool,
+    bins: usize,
+    data: Data,
+    profile: Option<VolumeProfile>,
+    /// Bars mapped to the price scale, rebuilt when the data, bins or scale change.
+    layer: LayerCache<(PriceScale, f64), Vec<Bar>>,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bins: 50,
+            data: Default::default(),
+            profile: None,
+            layer: Default::default(),
+        }
+    }
+}
+
+impl Profile {
+    pub fn set_data(&mut self, data: Data) {
+        self.data = data;
+        self.compute();
+    }
+
+    /// Draws profile parameters controls. Returns true if the parameters were changed.
+    pub fn props_ui(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = ui.checkbox(&mut self.enabled, "Volume profile").changed();
+        ui.horizontal(|ui| {
+            changed |= ui
+                .add(DragValue::new(&mut self.bins).clamp_range(5..=500))
+                .changed();
+            ui.label("bins");
+        });
+
+        if changed {
+            self.compute();
+        }
+
+        changed
+    }
+
+    /// Returns histogram shapes in screen coordinates. Bars are cached in plot coordinates
+    /// and only placed on the screen here, so panning and zooming do not rebuild them.
+    /// Shapes are painted over the plot instead of being plot items so they never affect
+    /// plot bounds.
+    pub fn shapes(&mut self, plot_ui: &PlotUi, scale: PriceScale, base: f64) -> Vec<Shape> {
+        let profile = match (&self.profile, self.enabled) {
+            (Some(profile), true) => profile,
+            _ => return vec![],
+        };
+
+        let bars = self
+            .layer
+            .get((scale, base), || Profile::build_bars(profile, scale, base));
+
+        let bounds = plot_ui.plot_bounds();
+        let right = plot_ui
+            .screen_from_plot(Value::new(bounds.max()[0], bounds.min()[1]))
+            .x;
+        let left = plot_ui
+            .screen_from_plot(Value::new(bounds.min()[0], bounds.min()[1]))
+            .x;
+        let max_width = (right - left) * WIDTH_SHARE;
+
+        bars.iter()
+            .map(|b| {
+                let top = plot_ui
+                    .screen_from_plot(Value::new(bounds.max()[0], b.high))
+                    .y;
+                let bottom = plot_ui
+                    .screen_from_plot(Value::new(bounds.max()[0], b.low))
+                    .y;
+
+                Shape::rect_filled(
+                    Rect::from_min_max(
+                        [right - b.share * max_width, top + 0.5].into(),
+                        [right, bottom - 0.5].into(),
+                    ),
+                    0.0,
+                    b.color,
+                )
+            })
+            .collect()
+    }
+
+    fn build_bars(profile: &VolumeProfile, scale: PriceScale, base: f64) -> Vec<Bar> {
+        let y_map = scale.y_map(base);
+        let max_volume = profile.max_volume();
+
+        profile
+            .bins
+            .iter()
+            .enumerate()
+            .map(|(i, b)| {
+                let color = match i {
+                    i if i == profile.poc => Color32::GOLD,
+                    i if i >= profile.value_area.0 && i <= profile.value_area.1 => {
+                        Color32::LIGHT_BLUE
+                    }
+                    _ => Color32::GRAY,
+                };
+
+                Bar {
+                    low: y_map(b.low),
+                    high: y_map(b.high),
+                    share: (b.volume / max_volume) as f32,
+                    color: color.linear_multiply(0.25),
+                }
+            })
+            .collect()
+    }
+
+    fn compute(&mut self) {
+        self.profile = VolumeProfile::new(&self.data.vals, self.bins);
+        self.layer.invalidate();
+    }
+}
@@ -0,0 +1,167 @@
+use egui::{Color32, Context, Key, Modifiers, Ui};
+use tracing::info;
+
+use crate::{
+    netstrat::replay::{Simulator, Trade},
+    sources::binance::Kline,
+};
+
+const FAST_STEP: usize = 10;
+const SHOWN_TRADES: usize = 20;
+
+/// Replay mode revealing loaded klines one by one with a trade simulator filling
+/// orders at the close of the last revealed kline.
+#[derive(Default)]
+pub struct Replay {
+    active: bool,
+    cursor: usize,
+    simulator: Simulator,
+}
+
+impl Replay {
+    /// Klines revealed so far, all of them outside replay mode.
+    pub fn visible<'a>(&self, klines: &'a [Kline]) -> &'a [Kline] {
+        match self.active && !klines.is_empty() {
+            true => &klines[..=self.cursor.min(klines.len() - 1)],
+            false => klines,
+        }
+    }
+
+    fn start(&mut self, klines: &[Kline]) {
+        info!("Starting replay of {} klines.", klines.len());
+        *self = Self {
+            active: true,
+            cursor: klines.len() / 5,
+            simulator: Simulator::default(),
+        };
+    }
+
+    fn step(&mut self, klines: &[Kline], n: usize) -> bool {
+        let cursor = (self.cursor + n).min(klines.len().saturating_sub(1));
+        let changed = cursor != self.cursor;
+        self.cursor = cursor;
+
+        changed
+    }
+
+    fn trade(&mut self, klines: &[Kline], order: fn(&mut Simulator, i64, f64) -> Option<Trade>) {
+        let k = match self.visible(klines).last() {
+            Some(k) => k,
+            None => return,
+        };
+
+        if let Some(trade) = order(&mut self.simulator, k.t_close, k.close as f64) {
+            info!(
+                "Simulated trade: {trade}, PnL: {:.8}.",
+                self.simulator.total(k.close as f64)
+            );
+        }
+    }
+
+    /// Handles replay hotkeys. Returns true if more klines were revealed.
+    pub fn handle_keys(&mut self, ctx: &Context, klines: &[Kline]) -> bool {
+        if !self.active || ctx.wants_keyboard_input() {
+            return false;
+        }
+
+        let mut input = ctx.input_mut();
+        let mut changed = false;
+        if input.consume_key(Modifiers::NONE, Key::ArrowRight) {
+            changed |= self.step(klines, 1);
+        }
+        if input.consume_key(Modifiers::SHIFT, Key::ArrowRight) {
+            changed |= self.step(klines, FAST_STEP);
+        }
+        if input.consume_key(Modifiers::NONE, Key::B) {
+            self.trade(klines, Simulator::buy);
+        }
+        if input.consume_key(Modifiers::NONE, Key::S) {
+            self.trade(klines, Simulator::sell);
+        }
+        if input.consume_key(Modifiers::NONE, Key::F) {
+            self.trade(klines, Simulator::flat);
+        }
+
+        changed
+    }
+
+    /// Draws replay controls. Returns true if revealed klines changed.
+    pub fn ui(&mut self, ui: &mut Ui, klines: &[Kline]) -> bool {
+        if !self.active {
+            if ui
+                .add_enabled(!klines.is_empty(), egui::Button::new("▶ start replay"))
+                .clicked()
+            {
+                self.start(klines);
+                return true;
+            }
+            return false;
+        }
+
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            if ui.button("⏹ stop").clicked() {
+                info!("Stopping replay.");
+                *self = Self::default();
+                changed = true;
+            }
+            if ui.button("▶ step").clicked() {
+                changed |= self.step(klines, 1);
+            }
+            if ui.button(format!("⏩ {FAST_STEP}")).clicked() {
+                changed |= self.step(klines, FAST_STEP);
+            }
+        });
+        ui.horizontal(|ui| {
+            if ui.button("buy").clicked() {
+                self.trade(klines, Simulator::buy);
+            }
+            if ui.button("sell").clicked() {
+                self.trade(klines, Simulator::sell);
+            }
+            if ui.button("flat").clicked() {
+                self.trade(klines, Simulator::flat);
+            }
+        });
+        ui.label(format!(
+            "hotkeys: → step, shift+→ {FAST_STEP} steps, B buy, S sell, F flat"
+        ));
+
+        if !self.simulator.trades.is_empty() {
+            ui.separator();
+            self.simulator
+                .trades
+                .iter()
+                .rev()
+                .take(SHOWN_TRADES)
+                .for_each(|t| {
+                    ui.label(t.to_string());
+                });
+        }
+
+        changed
+    }
+
+    /// Draws replay progress, position and PnL while replay is active.
+    pub fn status_ui(&self, ui: &mut Ui, klines: &[Kline]) {
+        let last = match (self.active, self.visible(klines).last()) {
+            (true, Some(k)) => k.close as f64,
+            _ => return,
+        };
+
+        let pnl = self.simulator.total(last);
+        ui.label(format!(
+            "replay {}/{} position {}",
+            self.cursor + 1,
+            klines.len(),
+            self.simulator.position
+        ));
+        ui.colored_label(
+            match pnl < 0.0 {
+                true => Color32::LIGHT_RED,
+                false => Color32::LIGHT_GREEN,
+            },
+            format!("PnL {pnl:.8}"),
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use std::cell::RefCell;
+
+use egui::{Color32, Ui};
+use tracing::error;
+
+use crate::netstrat::{data::Data, script::Script};
+
+use super::{
+    indicator::{Indicator, Target},
+    series::{values, Series, SeriesLine},
+};
+
+const COLORS: [Color32; 5] = [
+    Color32::LIGHT_BLUE,
+    Color32::GOLD,
+    Color32::LIGHT_GREEN,
+    Color32::LIGHT_RED,
+    Color32::KHAKI,
+];
+
+/// Indicator computed by a user script.
+pub struct ScriptIndicator {
+    script: Script,
+    target: Target,
+    error: RefCell<Option<String>>,
+}
+
+impl ScriptIndicator {
+    /// Creates indicator from the script. Target is taken from the script output on no data.
+    pub fn new(script: Script) -> Self {
+        let target = match script.run(&[]) {
+            Ok(out) if out.overlay => Target::Overlay,
+            _ => Target::Pane,
+        };
+
+        Self {
+            script,
+            target,
+            error: RefCell::new(None),
+        }
+    }
+}
+
+impl Indicator for ScriptIndicator {
+    fn name(&self) -> &str {
+        &self.script.name
+    }
+
+    fn target(&self) -> Target {
+        self.target
+    }
+
+    fn compute(&self, data: &Data) -> Series {
+        match self.script.run(&data.vals) {
+            Ok(out) => {
+                self.error.replace(None);
+                Series {
+                    lines: out
+                        .series
+                        .iter()
+                        .zip(COLORS.iter().cycle())
+                        .map(|((name, vals), color)| {
+                            SeriesLine::new(name.clone(), *color, values(&data.vals, vals))
+                        })
+                        .collect(),
+                    ..Default::default()
+                }
+            }
+            Err(err) => {
+                error!("Failed to run script {}: {err}.", self.script.name);
+                self.error.replace(Some(err.to_string()));
+                Series::default()
+            }
+        }
+    }
+
+    fn props_ui(&mut self, ui: &mut Ui) -> bool {
+        if let Some(err) = self.error.borrow().as_ref() {
+            ui.colored_label(Color32::LIGHT_RED, err);
+        }
+
+        false
+    }
+}
@@ -0,0 +1,135 @@
+use egui::{
+    plot::{
+        Bar, BarChart, HLine, Line, LineStyle, MarkerShape, PlotUi, Points, Polygon, Value, Values,
+    },
+    Color32,
+};
+
+use crate::{netstrat::data::Data, sources::binance::Kline};
+
+const AREA_ALPHA: f32 = 0.15;
+
+/// Everything an indicator draws, computed from data.
+#[derive(Default, Clone)]
+pub struct Series {
+    pub lines: Vec<SeriesLine>,
+    /// Series drawn as separate dots.
+    pub points: Vec<SeriesLine>,
+    /// Convex filled areas.
+    pub areas: Vec<SeriesLine>,
+    pub histogram: Vec<Bar>,
+    /// Horizontal reference levels.
+    pub levels: Vec<f64>,
+    /// Values which should always be visible on the y axis.
+    pub include_y: Vec<f64>,
+}
+
+impl Series {
+    /// Draws series with y values mapped to the plot coordinates, e.g. clamped into
+    /// a fixed range or converted to a log scale.
+    pub fn show(&self, plot_ui: &mut PlotUi, y_map: &dyn Fn(f64) -> f64) {
+        let map = |l: &SeriesLine| l.mapped(y_map);
+
+        self.levels.iter().for_each(|l| {
+            plot_ui.hline(
+                HLine::new(y_map(*l))
+                    .color(Color32::GRAY)
+                    .style(LineStyle::dashed_loose()),
+            );
+        });
+
+        self.areas.iter().for_each(|a| {
+            plot_ui.polygon(
+                Polygon::new(Values::from_values(map(a).vals))
+                    .color(a.color)
+                    .fill_alpha(AREA_ALPHA)
+                    .width(0.0)
+                    .name(&a.name),
+            );
+        });
+
+        if !self.histogram.is_empty() {
+            plot_ui.bar_chart(
+                BarChart::new(self.histogram.clone())
+                    .element_formatter(Box::new(|b, _| {
+                        format!("{:.8}\n{}", b.value, Data::format_ts(b.argument))
+                    }))
+                    .name("histogram")
+                    .vertical(),
+            );
+        }
+
+        self.lines.iter().for_each(|l| map(l).show(plot_ui));
+        self.points.iter().for_each(|p| map(p).show_points(plot_ui));
+    }
+}
+
+/// Named line of an indicator ready to be drawn on a plot.
+#[derive(Clone)]
+pub struct SeriesLine {
+    pub name: String,
+    pub color: Color32,
+    pub vals: Vec<Value>,
+}
+
+impl SeriesLine {
+    pub fn new(name: String, color: Color32, vals: Vec<Value>) -> Self {
+        Self { name, color, vals }
+    }
+
+    /// Returns copy of the series with y values mapped.
+    pub fn mapped(&self, y_map: &dyn Fn(f64) -> f64) -> Self {
+        Self::new(self.name.clone(), self.color, map_y(&self.vals, y_map))
+    }
+
+    pub fn show(&self, plot_ui: &mut PlotUi) {
+        plot_ui.line(
+            Line::new(Values::from_values(self.vals.clone()))
+                .color(self.color)
+                .name(&self.name),
+        );
+    }
+
+    /// Draws series as separate dots instead of a line.
+    pub fn show_points(&self, plot_ui: &mut PlotUi) {
+        plot_ui.points(
+            Points::new(Values::from_values(self.vals.clone()))
+                .shape(MarkerShape::Circle)
+                .filled(true)
+                .radius(1.5)
+                .color(self.color)
+                .name(&self.name),
+        );
+    }
+}
+
+/// X coordinate of the kline on the plot.
+pub fn x(k: &Kline) -> f64 {
+    (k.t_open + k.t_close) as f64 / 2.0
+}
+
+/// X coordinate of the i-th kline. Indexes beyond klines are extrapolated
+/// with the klines duration, which is used for series displaced into the future.
+pub fn x_at(klines: &[Kline], i: usize) -> Option<f64> {
+    if let Some(k) = klines.get(i) {
+        return Some(x(k));
+    }
+
+    let last = klines.last()?;
+    let step = (last.t_close - last.t_open + 1) as f64;
+    Some(x(last) + (i + 1 - klines.len()) as f64 * step)
+}
+
+/// Maps series aligned with klines to plot values skipping empty ones.
+pub fn values(klines: &[Kline], series: &[Option<f64>]) -> Vec<Value> {
+    series
+        .iter()
+        .enumerate()
+        .filter_map(|(i, v)| Some(Value::new(x_at(klines, i)?, (*v)?)))
+        .collect()
+}
+
+/// Maps y of the values keeping x untouched.
+pub fn map_y(vals: &[Value], y_map: &dyn Fn(f64) -> f64) -> Vec<Value> {
+    vals.iter().map(|v| Value::new(v.x, y_map(v.y))).collect()
+}
@@ -0,0 +1,83 @@
+use egui::{
+    plot::{PlotUi, Polygon, Value, Values},
+    Color32, Ui,
+};
+
+use crate::netstrat::{data::Data, session::Session};
+
+/// Bands are not drawn if there are more of them in the loaded range, as they would cover the plot.
+const MAX_BANDS: usize = 500;
+const BAND_ALPHA: f32 = 0.08;
+
+struct ShadedSession {
+    session: Session,
+    color: Color32,
+    enabled: bool,
+}
+
+/// Vertical bands drawn behind candles for chosen time windows like exchange hours or weekends.
+pub struct Shading {
+    sessions: Vec<ShadedSession>,
+}
+
+impl Default for Shading {
+    fn default() -> Self {
+        let shaded = |session, color| ShadedSession {
+            session,
+            color,
+            enabled: false,
+        };
+
+        Self {
+            sessions: vec![
+                shaded(Session::us_equities(), Color32::LIGHT_BLUE),
+                shaded(Session::cme(), Color32::LIGHT_GREEN),
+                shaded(Session::weekends(), Color32::GRAY),
+            ],
+        }
+    }
+}
+
+impl Shading {
+    pub fn ui(&mut self, ui: &mut Ui) {
+        self.sessions.iter_mut().for_each(|s| {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut s.enabled, s.session.name.as_str());
+                ui.color_edit_button_srgba(&mut s.color);
+            });
+        });
+    }
+
+    /// Draws bands of enabled sessions over the loaded time range spanning the y range.
+    pub fn show(&self, plot_ui: &mut PlotUi, data: &Data, y_range: (f64, f64)) {
+        if data.vals.is_empty() {
+            return;
+        }
+
+        let (min, max) = y_range;
+        let (from, to) = (data.min_x() as i64, data.max_x() as i64);
+
+        self.sessions.iter().filter(|s| s.enabled).for_each(|s| {
+            let windows = s.session.windows(from, to);
+            if windows.len() > MAX_BANDS {
+                return;
+            }
+
+            windows.iter().for_each(|w| {
+                let (left, right) = (w.0.max(from) as f64, w.1.min(to) as f64);
+                plot_ui.polygon(
+                    Polygon::new(Values::from_values(vec![
+                        Value::new(left, min),
+                        Value::new(right, min),
+                        Value::new(right, max),
+                        Value::new(left, max),
+                    ]))
+                    .color(s.color)
+                    .fill_alpha(BAND_ALPHA)
+                    .width(0.0)
+                    .name(&s.session.name),
+                );
+            });
+        });
+    }
+}
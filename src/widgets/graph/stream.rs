@@ -0,0 +1,176 @@
+use std::path::PathBuf;
+
+use chrono::Utc;
+use egui::{ProgressBar, Ui};
+use poll_promise::Promise;
+use tracing::{error, info};
+
+use crate::{
+    netstrat::{
+        export::{
+            dialog::pick_destination, errors::ExportError, template::ExportTemplate,
+            writer::KlinesWriter, ExportOptions, KlinesFormat,
+        },
+        graph::{props::Props, state::State},
+    },
+    sources::binance::{errors::ClientError, Client, Kline, Source},
+};
+
+/// How a streamed export stopped.
+pub enum Outcome {
+    /// Name of the written file.
+    Exported(String),
+    /// The save dialog was closed without picking a destination.
+    Cancelled,
+    Failed(ExportError),
+}
+
+/// Export of a time range downloaded page by page with every page written to the file
+/// as soon as it arrives, so ranges too large to chart are exported with flat memory.
+/// The charted klines are left as they are.
+pub struct StreamedExport {
+    symbol: String,
+    source: Source,
+    format: KlinesFormat,
+    template: ExportTemplate,
+    state: State,
+    destination: Option<Promise<Option<PathBuf>>>,
+    writer: Option<KlinesWriter>,
+    promise: Option<Promise<Result<Vec<Kline>, ClientError>>>,
+}
+
+impl StreamedExport {
+    /// Asks for the destination of the export, the download starts once it is picked.
+    pub fn new(
+        name: &str,
+        symbol: &str,
+        source: Source,
+        props: &Props,
+        options: &ExportOptions,
+    ) -> Self {
+        let mut state = State::default();
+        state.apply_props(props);
+
+        Self {
+            symbol: symbol.to_string(),
+            source,
+            format: options.format,
+            template: options.template.clone(),
+            state,
+            destination: Some(pick_destination(name, options.format.as_str())),
+            writer: None,
+            promise: None,
+        }
+    }
+
+    fn request_page(&mut self) {
+        self.state
+            .loading
+            .report_request(Utc::now().timestamp_millis());
+
+        let start = self.state.loading.left_edge();
+        let symbol = self.symbol.clone();
+        let interval = self.state.props.interval;
+        let limit = self.state.loading.pages.page_size();
+        let source = self.source;
+        self.promise = Some(Promise::spawn_async(async move {
+            Client::source_kline(source, symbol, interval, start, limit).await
+        }));
+    }
+
+    /// Writes the arrived page and requests the next one.
+    /// Returns the outcome once the export stops.
+    pub fn poll(&mut self) -> Option<Outcome> {
+        match self.step() {
+            Ok(outcome) => outcome,
+            Err(err) => Some(Outcome::Failed(err)),
+        }
+    }
+
+    fn step(&mut self) -> Result<Option<Outcome>, ExportError> {
+        if let Some(destination) = &self.destination {
+            let picked = match destination.ready() {
+                Some(picked) => picked.clone(),
+                None => return Ok(None),
+            };
+            self.destination = None;
+            let path = match picked {
+                Some(path) => path,
+                None => return Ok(Some(Outcome::Cancelled)),
+            };
+
+            info!(
+                "Streaming {} pages of {} klines to {path:?}...",
+                self.state.loading.pages.len(),
+                self.symbol
+            );
+            let writer =
+                KlinesWriter::create(&path.to_string_lossy(), self.format, &self.template, &[])?;
+            if self.state.loading.pages.len() == 0 {
+                return Ok(Some(Outcome::Exported(writer.finish()?)));
+            }
+            self.writer = Some(writer);
+            self.request_page();
+            return Ok(None);
+        }
+
+        let klines = match self.promise.take().map(|p| p.try_take()) {
+            Some(Ok(res)) => res?,
+            Some(Err(promise)) => {
+                self.promise = Some(promise);
+                return Ok(None);
+            }
+            None => return Ok(None),
+        };
+        let writer = match &mut self.writer {
+            Some(writer) => writer,
+            None => return Ok(None),
+        };
+
+        writer.write(&klines, &[])?;
+        self.state
+            .loading
+            .report_page(klines.len(), Utc::now().timestamp_millis());
+
+        if self.state.loading.turn_page().is_some() {
+            self.request_page();
+            return Ok(None);
+        }
+
+        match self.writer.take() {
+            Some(writer) => Ok(Some(Outcome::Exported(writer.finish()?))),
+            None => Ok(None),
+        }
+    }
+
+    /// Stops the download and removes the partially written file.
+    pub fn cancel(self) {
+        if let Some(writer) = self.writer {
+            let file_name = writer.file_name().to_string();
+            drop(writer);
+            if let Err(err) = std::fs::remove_file(&file_name) {
+                error!("Failed to remove cancelled export {file_name}: {err}.");
+            }
+        }
+        info!("Export cancelled.");
+    }
+
+    /// Draws progress of the download. Returns true if the export is cancelled.
+    pub fn ui(&mut self, ui: &mut Ui) -> bool {
+        if self.writer.is_none() {
+            return false;
+        }
+
+        let progress = self.state.loading.progress();
+        let summary = self.state.loading.summary(Utc::now().timestamp_millis());
+        ui.add(
+            ProgressBar::new(progress)
+                .desired_width(240.0)
+                .text(format!("exporting {:.0}%", progress * 100.0))
+                .animate(true),
+        )
+        .on_hover_text(summary);
+
+        ui.button("cancel export").clicked()
+    }
+}
@@ -0,0 +1,151 @@
+use egui::{Color32, Ui};
+use poll_promise::Promise;
+use tracing::{error, info};
+
+use crate::sources::binance::{errors::ClientError, Client, Interval, Kline, Source};
+
+/// Time range of the charted klines volumes of other venues are loaded for.
+#[derive(Debug, Clone, PartialEq)]
+struct Range {
+    symbol: String,
+    interval: Interval,
+    start: i64,
+    end: i64,
+    limit: usize,
+}
+
+struct Venue {
+    source: Source,
+    klines: Vec<Kline>,
+    promise: Option<Promise<Result<Vec<Kline>, ClientError>>>,
+}
+
+impl Venue {
+    fn load(&mut self, range: Range) {
+        info!(
+            "Loading {} volume from {}.",
+            range.symbol,
+            self.source.as_str()
+        );
+
+        let source = self.source;
+        self.klines = vec![];
+        self.promise = Some(Promise::spawn_async(async move {
+            Client::source_kline_range(
+                source,
+                range.symbol,
+                range.interval,
+                range.start,
+                range.end,
+                range.limit,
+            )
+            .await
+        }));
+    }
+}
+
+/// Volume of the charted symbol on alternate sources summed with the primary one
+/// in the volume pane.
+pub struct VenueVolumes {
+    pub enabled: bool,
+    venues: Vec<Venue>,
+    range: Option<Range>,
+}
+
+impl Default for VenueVolumes {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            venues: Source::alternates()
+                .into_iter()
+                .map(|source| Venue {
+                    source,
+                    klines: vec![],
+                    promise: None,
+                })
+                .collect(),
+            range: None,
+        }
+    }
+}
+
+impl VenueVolumes {
+    /// Reloads venues for the range of newly charted klines if aggregation is enabled.
+    pub fn set_range(&mut self, symbol: &str, interval: Interval, klines: &[Kline], limit: usize) {
+        let range = match (klines.first(), klines.last()) {
+            (Some(first), Some(last)) => Range {
+                symbol: symbol.to_string(),
+                interval,
+                start: first.t_open,
+                end: last.t_close,
+                limit,
+            },
+            _ => return,
+        };
+        if self.range.as_ref() == Some(&range) {
+            return;
+        }
+
+        self.range = Some(range);
+        self.load();
+    }
+
+    fn load(&mut self) {
+        if let (true, Some(range)) = (self.enabled, &self.range) {
+            self.venues.iter_mut().for_each(|v| v.load(range.clone()));
+        }
+    }
+
+    /// Checks venue downloads. Returns true if volumes changed.
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        self.venues.iter_mut().for_each(|v| {
+            let res = match v.promise.as_ref().and_then(|p| p.ready()) {
+                Some(res) => res,
+                None => return,
+            };
+            match res {
+                Ok(klines) => v.klines = klines.clone(),
+                Err(err) => error!("Failed to load volume from {}: {err}.", v.source.as_str()),
+            }
+            v.promise = None;
+            changed = true;
+        });
+
+        changed
+    }
+
+    /// Loaded klines of alternate venues, empty while aggregation is disabled.
+    pub fn klines(&self) -> Vec<(String, Vec<Kline>)> {
+        match self.enabled {
+            true => self
+                .venues
+                .iter()
+                .map(|v| (v.source.as_str().to_string(), v.klines.clone()))
+                .collect(),
+            false => vec![],
+        }
+    }
+
+    /// Draws the aggregation toggle. Returns true if volumes changed.
+    pub fn ui(&mut self, ui: &mut Ui) -> bool {
+        let changed = ui
+            .checkbox(&mut self.enabled, "aggregate volume across exchanges")
+            .changed();
+        if changed {
+            self.load();
+        }
+
+        self.venues
+            .iter()
+            .filter(|v| self.enabled && v.promise.is_some())
+            .for_each(|v| {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.colored_label(Color32::GRAY, v.source.as_str());
+                });
+            });
+
+        changed
+    }
+}
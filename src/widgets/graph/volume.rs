@@ -2,17 +2,35 @@ use std::ops::RangeInclusive;
 
 use chrono::{DateTime, NaiveDateTime, Utc};
 use egui::{
-    plot::{Bar, BarChart, LinkedAxisGroup, Plot},
+    plot::{Bar, BarChart, Legend, LinkedAxisGroup, Plot},
     Color32, FontId, TextFormat, Vec2, Widget,
 };
 use tracing::debug;
 
-use crate::netstrat::data::Data;
+use crate::{
+    netstrat::{
+        data::Data,
+        venues::{aligned_volumes, shares},
+    },
+    sources::binance::Kline,
+};
+
+const VENUE_COLORS: [Color32; 3] = [
+    Color32::LIGHT_BLUE,
+    Color32::from_rgb(200, 120, 255),
+    Color32::from_rgb(255, 160, 80),
+];
 
 #[derive(Clone)]
 pub struct Volume {
     data: Data,
     val: Vec<Bar>,
+    anomalies: Vec<usize>,
+    /// Name of the venue of charted klines and klines of other venues stacked on top of it.
+    venues: (String, Vec<(String, Vec<Kline>)>),
+    name: String,
+    stacked: Vec<(String, Vec<Bar>)>,
+    max_vol: f64,
     axes_group: LinkedAxisGroup,
 }
 
@@ -21,6 +39,11 @@ impl Default for Volume {
         Self {
             data: Default::default(),
             val: Default::default(),
+            anomalies: Default::default(),
+            venues: Default::default(),
+            name: Default::default(),
+            stacked: Default::default(),
+            max_vol: Default::default(),
             axes_group: LinkedAxisGroup::new(false, false),
         }
     }
@@ -34,43 +57,118 @@ impl Volume {
         }
     }
 
+    pub fn set_axes_group(&mut self, axes_group: LinkedAxisGroup) {
+        self.axes_group = axes_group;
+    }
+
     pub fn set_data(&mut self, data: Data) {
-        let val = data
+        self.data = data;
+        self.anomalies = vec![];
+        self.compute();
+    }
+
+    /// Highlights bars with the given indexes as volume anomalies.
+    pub fn set_anomalies(&mut self, idxs: &[usize]) {
+        self.anomalies = idxs.to_vec();
+        self.compute();
+    }
+
+    /// Stacks volumes of other venues on the charted volume, bars are matched by open time.
+    pub fn set_venues(&mut self, primary: &str, venues: Vec<(String, Vec<Kline>)>) {
+        self.venues = (primary.to_string(), venues);
+        self.compute();
+    }
+
+    fn compute(&mut self) {
+        self.val = self
+            .data
             .vals
             .iter()
             .map(|k| {
                 Bar::new((k.t_open + k.t_close) as f64 / 2.0, k.volume as f64)
                     .width((k.t_open - k.t_close) as f64 * 0.9)
-                    .fill(Color32::LIGHT_GREEN.linear_multiply(0.5))
+                    .fill(Data::k_color(k).linear_multiply(0.5))
             })
             .collect();
 
-        self.data = data;
-        self.val = val;
+        self.anomalies.iter().for_each(|i| {
+            if let Some(b) = self.val.get_mut(*i) {
+                *b = b.clone().fill(Color32::GOLD.linear_multiply(0.6));
+            }
+        });
+
+        self.compute_venues();
+    }
+
+    fn compute_venues(&mut self) {
+        let (primary, venues) = &self.venues;
+        let klines = &self.data.vals;
+        let mut volumes = vec![klines.iter().map(|k| k.volume as f64).collect::<Vec<_>>()];
+        volumes.extend(venues.iter().map(|(_, v)| aligned_volumes(klines, v)));
+
+        self.max_vol = (0..klines.len())
+            .map(|i| volumes.iter().map(|v| v[i]).sum::<f64>())
+            .fold(0.0, f64::max);
+        if venues.is_empty() {
+            self.name = "volume".to_string();
+            self.stacked = vec![];
+            return;
+        }
+
+        let shares = shares(&volumes);
+        let share = |name: &str, i: usize| format!("{name} {:.1}%", shares[i] * 100.0);
+        self.name = share(primary, 0);
+        self.stacked = venues
+            .iter()
+            .zip(volumes.iter().skip(1))
+            .zip(VENUE_COLORS.iter().cycle())
+            .enumerate()
+            .map(|(i, (((name, _), vols), color))| {
+                let bars = klines
+                    .iter()
+                    .zip(vols)
+                    .map(|(k, v)| {
+                        Bar::new((k.t_open + k.t_close) as f64 / 2.0, *v)
+                            .width((k.t_open - k.t_close) as f64 * 0.9)
+                            .fill(color.linear_multiply(0.5))
+                    })
+                    .collect();
+                (share(name, i + 1), bars)
+            })
+            .collect();
     }
 }
 
 impl Widget for &Volume {
     fn ui(self, ui: &mut egui::Ui) -> egui::Response {
         Plot::new("volume")
+            .legend(Legend::default())
             .link_axis(self.axes_group.clone())
             .x_axis_formatter(|v: f64, _: &RangeInclusive<f64>| format_ts(v))
             .label_formatter(|_, v| format!("{}", format_ts(v.x)))
             .set_margin_fraction(Vec2::new(0.0, 0.5))
-            .include_y(self.data.max_vol())
+            .include_y(self.max_vol)
             .allow_scroll(false)
             .allow_boxed_zoom(false)
             .allow_drag(false)
             .allow_zoom(false)
             .show_axes([false, false])
             .show(ui, |plot_ui| {
-                plot_ui.bar_chart(
-                    BarChart::new(self.val.clone())
+                let chart = |name: &str, bars: &[Bar]| {
+                    BarChart::new(bars.to_vec())
                         .element_formatter(Box::new(|b, _| {
                             format!("{}\n{}", b.value, format_ts(b.argument))
                         }))
-                        .vertical(),
-                );
+                        .name(name)
+                        .vertical()
+                };
+
+                let mut charts = vec![chart(&self.name, &self.val)];
+                self.stacked.iter().for_each(|(name, bars)| {
+                    let stacked = chart(name, bars).stack_on(&charts.iter().collect::<Vec<_>>());
+                    charts.push(stacked);
+                });
+                charts.into_iter().for_each(|c| plot_ui.bar_chart(c));
             })
             .response
     }
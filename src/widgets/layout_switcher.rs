@@ -0,0 +1,94 @@
+use egui::{Color32, TextEdit, Ui, WidgetText};
+use tracing::{error, info};
+
+use crate::{
+    netstrat::layout::{Layout, Layouts, WindowLayout},
+    windows::AppWindow,
+};
+
+/// Top bar menu switching between named layouts of app windows and saving the current one.
+pub struct LayoutSwitcher {
+    layouts: Layouts,
+    name: String,
+    error: Option<String>,
+}
+
+impl Default for LayoutSwitcher {
+    fn default() -> Self {
+        let mut layouts = Layouts::default();
+        let error = layouts.load().err().map(|err| {
+            error!("Failed to load layouts: {err}.");
+            err.to_string()
+        });
+
+        Self {
+            layouts,
+            name: String::new(),
+            error,
+        }
+    }
+}
+
+impl LayoutSwitcher {
+    pub fn ui(&mut self, ui: &mut Ui, windows: &mut [Box<dyn AppWindow>]) {
+        ui.menu_button("layouts", |ui| {
+            let mut deleted = None;
+            self.layouts.layouts.iter().for_each(|layout| {
+                ui.horizontal(|ui| {
+                    if ui.button(&layout.name).clicked() {
+                        info!("Switching to layout {}.", layout.name);
+                        windows.iter_mut().for_each(|w| {
+                            w.apply_layout(
+                                &layout.windows.get(w.name()).cloned().unwrap_or_default(),
+                            )
+                        });
+                        ui.close_menu();
+                    }
+                    if ui.button("🗑").clicked() {
+                        deleted = Some(layout.name.clone());
+                    }
+                });
+            });
+            if let Some(name) = deleted {
+                self.error = self.layouts.delete(&name).err().map(|err| err.to_string());
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.add(
+                    TextEdit::singleline(&mut self.name)
+                        .hint_text(WidgetText::from("layout name").italics())
+                        .desired_width(100.0),
+                );
+                if ui
+                    .add_enabled(!self.name.is_empty(), egui::Button::new("save"))
+                    .clicked()
+                {
+                    let layout = Layout {
+                        name: self.name.clone(),
+                        windows: windows
+                            .iter()
+                            .map(|w| {
+                                (
+                                    w.name().to_string(),
+                                    WindowLayout {
+                                        symbol: None,
+                                        tabs: vec![],
+                                        ..w.layout()
+                                    },
+                                )
+                            })
+                            .collect(),
+                    };
+                    info!("Saving layout {}.", layout.name);
+                    self.error = self.layouts.save(layout).err().map(|err| err.to_string());
+                    self.name = String::new();
+                }
+            });
+
+            if let Some(err) = &self.error {
+                ui.colored_label(Color32::LIGHT_RED, err);
+            }
+        });
+    }
+}
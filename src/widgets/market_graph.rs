@@ -0,0 +1,533 @@
+use std::path::PathBuf;
+
+use chrono::Utc;
+use egui::{
+    color::Hsva, Align2, Color32, ComboBox, DragValue, Grid, Pos2, Sense, Slider, Stroke, TextEdit,
+    TextStyle, Ui, Vec2,
+};
+use poll_promise::Promise;
+use tracing::{error, info};
+
+use crate::{
+    netstrat::{
+        correlation::{parse_symbols, Loader},
+        export::{dialog::pick_destination, network::NetworkFormat},
+        market_graph::{Edge, MarketGraph, NodeFilter},
+        power::LowPower,
+        theme::ChartColors,
+        toasts::Toasts,
+        watchlist::Watchlists,
+    },
+    sources::binance::{errors::ClientError, Client, Interval, Symbol, Ticker},
+};
+
+const NODE_RADIUS: f32 = 6.0;
+/// Layout stops once no node moves further in a step.
+const SETTLED: f32 = 1e-4;
+/// Most symbols loaded into the network, larger graphs are unreadable.
+const MAX_NODES: usize = 100;
+
+/// Symbol of a clicked node.
+pub struct PickedNode {
+    pub symbol: String,
+    /// The node was double clicked, so its chart should be shown.
+    pub open: bool,
+}
+
+/// Where symbols of the network come from before they are filtered.
+#[derive(Debug, Clone, PartialEq)]
+enum NodeSource {
+    /// Symbols typed by the user.
+    Manual,
+    Watchlist(String),
+    /// All trading symbols.
+    Listed,
+}
+
+impl NodeSource {
+    fn label(&self) -> String {
+        match self {
+            NodeSource::Manual => "symbols".to_string(),
+            NodeSource::Watchlist(name) => format!("☆ {name}"),
+            NodeSource::Listed => "all listed".to_string(),
+        }
+    }
+}
+
+/// Force directed network of symbols with edges between symbols correlated
+/// above the threshold or along the minimum spanning tree of the correlations.
+/// Dragging the background pans, dragging a node moves it,
+/// scrolling zooms and clicking a node picks its symbol.
+pub struct MarketGraphView {
+    source: NodeSource,
+    /// Comma separated symbols of the network.
+    symbols: String,
+    filter: NodeFilter,
+    /// Symbol of the chart the network is linked to, its node is highlighted.
+    charted: String,
+    watchlists: Watchlists,
+    /// 24h tickers used by volume filters, loaded on demand.
+    tickers: Vec<Ticker>,
+    tickers_promise: Option<Promise<Result<Vec<Ticker>, ClientError>>>,
+    interval: Interval,
+    lookback: usize,
+    /// Smallest absolute correlation drawn as an edge.
+    threshold: f64,
+    /// Draws the minimum spanning tree instead of edges above the threshold.
+    spanning_tree: bool,
+    /// Colors nodes by their cluster and lists members of the clusters.
+    clusters: bool,
+    loader: Loader,
+    graph: Option<MarketGraph>,
+    settled: bool,
+    dragged: Option<usize>,
+    offset: Vec2,
+    zoom: f32,
+    /// Export waiting for its destination.
+    export: Option<(NetworkFormat, Promise<Option<PathBuf>>)>,
+}
+
+impl Default for MarketGraphView {
+    fn default() -> Self {
+        let mut watchlists = Watchlists::default();
+        if let Err(err) = watchlists.load() {
+            error!("Failed to load watchlists: {err}.");
+        }
+
+        Self {
+            source: NodeSource::Manual,
+            filter: NodeFilter::default(),
+            charted: String::new(),
+            watchlists,
+            tickers: vec![],
+            tickers_promise: None,
+            symbols: "BTCUSDT, ETHUSDT, BNBUSDT, SOLUSDT, XRPUSDT, ADAUSDT, DOGEUSDT, LTCUSDT"
+                .to_string(),
+            interval: Interval::Hour,
+            lookback: 200,
+            threshold: 0.5,
+            spanning_tree: false,
+            clusters: false,
+            loader: Loader::default(),
+            graph: None,
+            settled: false,
+            dragged: None,
+            offset: Vec2::ZERO,
+            zoom: 1.0,
+            export: None,
+        }
+    }
+}
+
+impl MarketGraphView {
+    /// Draws the network of symbols picked among the listed ones.
+    /// Returns the symbol of a clicked node.
+    pub fn ui(&mut self, ui: &mut Ui, listed: &[Symbol]) -> Option<PickedNode> {
+        self.poll_tickers(listed);
+        if let Some((symbols, matrix)) = self.loader.poll() {
+            self.graph = Some(MarketGraph::new(symbols, matrix));
+            self.settled = false;
+            self.offset = Vec2::ZERO;
+            self.zoom = 1.0;
+        }
+
+        self.poll_export();
+        self.controls_ui(ui, listed);
+        ui.separator();
+
+        let edges = match &self.graph {
+            Some(graph) => self.edges(graph),
+            None => {
+                ui.label("Load symbols to see the network of their correlations.");
+                return None;
+            }
+        };
+        let clusters = match (&self.graph, self.clusters) {
+            (Some(graph), true) => graph.clusters(&edges),
+            _ => vec![],
+        };
+        let graph = match &mut self.graph {
+            Some(graph) => graph,
+            None => return None,
+        };
+        if !clusters.is_empty() {
+            legend_ui(ui, &graph.symbols, &clusters);
+        }
+
+        let (resp, painter) = ui.allocate_painter(ui.available_size(), Sense::click_and_drag());
+        let rect = resp.rect;
+        let scale = rect.size().min_elem() * 0.45 * self.zoom;
+        let center = rect.center() + self.offset;
+        let to_screen = |p: Pos2| center + p.to_vec2() * scale;
+        let from_screen = |p: Pos2| Pos2::ZERO + (p - center) / scale;
+
+        let pointer = resp.hover_pos();
+        let hovered =
+            pointer.and_then(|p| graph.node_at(from_screen(p), NODE_RADIUS * 2.0 / scale));
+
+        if resp.drag_started() {
+            self.dragged = hovered;
+        }
+        if resp.dragged() {
+            match (self.dragged, pointer) {
+                (Some(i), Some(p)) => {
+                    graph.positions[i] = from_screen(p);
+                    self.settled = false;
+                }
+                _ => self.offset += resp.drag_delta(),
+            }
+        }
+        if resp.drag_released() {
+            self.dragged = None;
+        }
+        if resp.hovered() {
+            let input = ui.input();
+            let zoom = input.zoom_delta() * (1.0 + input.scroll_delta.y * 0.002);
+            if zoom != 1.0 {
+                self.zoom = (self.zoom * zoom).clamp(0.1, 10.0);
+            }
+        }
+
+        // the layout resumes on the first input after low-power mode
+        if !self.settled && !LowPower::is_on(ui.ctx()) {
+            self.settled = graph.step(&edges, self.dragged) < SETTLED;
+            ui.ctx().request_repaint();
+        }
+
+        let colors = ChartColors::current();
+        edges.iter().for_each(|e| {
+            let c = colors.kline(e.corr < 0.0);
+            let highlighted = hovered.is_some() && (hovered == Some(e.a) || hovered == Some(e.b));
+            let alpha = match highlighted {
+                true => 255,
+                false => (e.corr.abs() * 200.0) as u8,
+            };
+            painter.line_segment(
+                [
+                    to_screen(graph.positions[e.a]),
+                    to_screen(graph.positions[e.b]),
+                ],
+                Stroke::new(
+                    1.0 + 2.0 * e.corr.abs() as f32,
+                    Color32::from_rgba_unmultiplied(c.r(), c.g(), c.b(), alpha),
+                ),
+            );
+        });
+
+        let visuals = ui.visuals();
+        let font = TextStyle::Small.resolve(ui.style());
+        graph.positions.iter().enumerate().for_each(|(i, p)| {
+            let pos = to_screen(*p);
+            let stroke = match hovered == Some(i) {
+                true => visuals.widgets.hovered.fg_stroke,
+                false => visuals.widgets.inactive.fg_stroke,
+            };
+            let fill = match clusters.get(i) {
+                Some(cluster) => cluster_color(*cluster),
+                None => visuals.widgets.inactive.bg_fill,
+            };
+            if graph.symbols[i] == self.charted {
+                painter.circle_stroke(pos, NODE_RADIUS + 3.0, visuals.selection.stroke);
+            }
+            painter.circle(pos, NODE_RADIUS, fill, stroke);
+            painter.text(
+                pos - Vec2::new(0.0, NODE_RADIUS + 2.0),
+                Align2::CENTER_BOTTOM,
+                &graph.symbols[i],
+                font.clone(),
+                visuals.text_color(),
+            );
+        });
+
+        let picked = match (resp.clicked() || resp.double_clicked(), hovered) {
+            (true, Some(i)) => Some(PickedNode {
+                symbol: graph.symbols[i].clone(),
+                open: resp.double_clicked(),
+            }),
+            _ => None,
+        };
+        if let Some(i) = hovered {
+            resp.on_hover_text(hover_text(graph, &edges, i));
+        }
+
+        picked
+    }
+
+    pub fn set_charted(&mut self, symbol: &str) {
+        self.charted = symbol.to_string();
+    }
+
+    /// Starts downloads of filtered symbols, tickers are loaded first if the filter needs them.
+    fn load(&mut self, listed: &[Symbol]) {
+        if self.filter.needs_tickers() && self.tickers.is_empty() {
+            self.tickers_promise = Some(Promise::spawn_async(async { Client::tickers().await }));
+            return;
+        }
+
+        let candidates = match &self.source {
+            NodeSource::Manual => parse_symbols(&self.symbols),
+            NodeSource::Watchlist(name) => self
+                .watchlists
+                .get(name)
+                .map(|l| l.symbols.clone())
+                .unwrap_or_default(),
+            NodeSource::Listed => listed
+                .iter()
+                .filter(|s| s.active())
+                .map(|s| s.symbol.clone())
+                .collect(),
+        };
+        let mut symbols = self.filter.apply(&candidates, listed, &self.tickers);
+        if symbols.is_empty() {
+            Toasts::info("No symbols pass the network filter");
+            return;
+        }
+        if symbols.len() > MAX_NODES {
+            info!(
+                "Network limited to {MAX_NODES} of {} symbols.",
+                symbols.len()
+            );
+            symbols.truncate(MAX_NODES);
+        }
+
+        self.loader.start(symbols, self.interval, self.lookback);
+    }
+
+    /// Loads the network once tickers needed by the filter arrive.
+    fn poll_tickers(&mut self, listed: &[Symbol]) {
+        let res = match self.tickers_promise.as_ref().and_then(|p| p.ready()) {
+            Some(res) => res,
+            None => return,
+        };
+        match res {
+            Ok(tickers) => {
+                self.tickers = tickers.clone();
+                self.tickers_promise = None;
+                self.load(listed);
+            }
+            Err(err) => {
+                error!("Failed to load tickers for the network filter: {err}.");
+                Toasts::error("Failed to load 24h tickers", err);
+                self.tickers_promise = None;
+            }
+        }
+    }
+
+    fn source_ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            let selected = self.source.label();
+            let resp = ComboBox::from_id_source(ui.id().with("network source"))
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.source, NodeSource::Manual, "symbols");
+                    self.watchlists.lists.iter().for_each(|l| {
+                        let source = NodeSource::Watchlist(l.name.clone());
+                        let label = source.label();
+                        ui.selectable_value(&mut self.source, source, label);
+                    });
+                    ui.selectable_value(&mut self.source, NodeSource::Listed, "all listed");
+                });
+            if resp.response.clicked() {
+                if let Err(err) = self.watchlists.load() {
+                    error!("Failed to load watchlists: {err}.");
+                }
+            }
+
+            if self.source == NodeSource::Manual {
+                ui.add(
+                    TextEdit::singleline(&mut self.symbols)
+                        .hint_text("comma separated symbols")
+                        .desired_width(f32::INFINITY),
+                );
+            }
+        });
+    }
+
+    fn filter_ui(&mut self, ui: &mut Ui) {
+        Grid::new(ui.id().with("network filter"))
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("quote asset");
+                ui.add(
+                    TextEdit::singleline(&mut self.filter.quote)
+                        .hint_text("any")
+                        .desired_width(60.0),
+                );
+                ui.end_row();
+                ui.label("min 24h quote volume");
+                ui.add(
+                    DragValue::new(&mut self.filter.min_volume)
+                        .clamp_range(0.0..=f64::MAX)
+                        .speed(10_000.0),
+                );
+                ui.end_row();
+                ui.label("top by volume");
+                ui.add(DragValue::new(&mut self.filter.top).clamp_range(0..=MAX_NODES))
+                    .on_hover_text("0 keeps all symbols");
+                ui.end_row();
+            });
+        self.filter.quote = self.filter.quote.to_uppercase();
+
+        if ui
+            .add_enabled(
+                self.tickers_promise.is_none(),
+                egui::Button::new("reload tickers"),
+            )
+            .on_hover_text("volumes are from 24h tickers loaded with the first volume filter")
+            .clicked()
+        {
+            self.tickers = vec![];
+        }
+    }
+
+    fn controls_ui(&mut self, ui: &mut Ui, listed: &[Symbol]) {
+        self.source_ui(ui);
+        ui.horizontal(|ui| {
+            ComboBox::from_id_source(ui.id().with("network interval"))
+                .selected_text(self.interval.as_str())
+                .show_ui(ui, |ui| {
+                    Interval::ALL.into_iter().for_each(|i| {
+                        ui.selectable_value(&mut self.interval, i, i.as_str());
+                    });
+                });
+            ui.add(DragValue::new(&mut self.lookback).clamp_range(10..=5000));
+            ui.label("candles");
+
+            ui.menu_button("filter", |ui| self.filter_ui(ui));
+
+            let loading = self.loader.loading() || self.tickers_promise.is_some();
+            if ui
+                .add_enabled(!loading, egui::Button::new("load"))
+                .clicked()
+            {
+                self.load(listed);
+            }
+            if loading {
+                ui.ctx().request_repaint();
+                ui.spinner();
+            }
+
+            if ui
+                .checkbox(&mut self.spanning_tree, "spanning tree")
+                .on_hover_text("connect symbols along their strongest correlations only")
+                .changed()
+            {
+                self.settled = false;
+            }
+            if ui
+                .add_enabled(
+                    !self.spanning_tree,
+                    Slider::new(&mut self.threshold, 0.0..=1.0).text("|correlation| ≥"),
+                )
+                .changed()
+            {
+                self.settled = false;
+            }
+            ui.checkbox(&mut self.clusters, "clusters")
+                .on_hover_text("color symbols moving together");
+            if ui.button("reset view").clicked() {
+                self.offset = Vec2::ZERO;
+                self.zoom = 1.0;
+            }
+
+            ui.add_enabled_ui(self.graph.is_some() && self.export.is_none(), |ui| {
+                ui.menu_button("export", |ui| {
+                    NetworkFormat::all().into_iter().for_each(|format| {
+                        if ui.button(format.as_str()).clicked() {
+                            let name = format!(
+                                "network-{}-{}",
+                                self.interval.as_str(),
+                                Utc::now().format("%Y%m%d-%H%M%S")
+                            );
+                            self.export = Some((format, pick_destination(&name, format.as_str())));
+                            ui.close_menu();
+                        }
+                    });
+                });
+            });
+        });
+    }
+
+    fn edges(&self, graph: &MarketGraph) -> Vec<Edge> {
+        match self.spanning_tree {
+            true => graph.spanning_tree(),
+            false => graph.edges(self.threshold),
+        }
+    }
+
+    /// Exports the drawn edges with node positions once the destination is picked.
+    fn poll_export(&mut self) {
+        let picked = match &self.export {
+            Some((_, destination)) => match destination.ready() {
+                Some(picked) => picked.clone(),
+                None => return,
+            },
+            None => return,
+        };
+        let format = match self.export.take() {
+            Some((format, _)) => format,
+            None => return,
+        };
+        let (path, graph) = match (picked, &self.graph) {
+            (Some(path), Some(graph)) => (path, graph),
+            _ => {
+                info!("Export cancelled.");
+                return;
+            }
+        };
+
+        match format.export(&path.to_string_lossy(), graph, &self.edges(graph)) {
+            Ok(file_name) => Toasts::info(format!("Exported {file_name}")),
+            Err(err) => {
+                error!("Failed to export network: {err}.");
+                Toasts::error("Failed to export network", err);
+            }
+        }
+    }
+}
+
+/// Distinct colors spread around the hue circle by the golden ratio.
+fn cluster_color(cluster: usize) -> Color32 {
+    Hsva::new((cluster as f32 * 0.618_034).fract(), 0.6, 0.85, 1.0).into()
+}
+
+/// Lists members of every cluster with more than one symbol.
+fn legend_ui(ui: &mut Ui, symbols: &[String], clusters: &[usize]) {
+    let count = clusters.iter().max().map_or(0, |c| c + 1);
+    ui.horizontal_wrapped(|ui| {
+        (0..count).for_each(|cluster| {
+            let members: Vec<&str> = symbols
+                .iter()
+                .zip(clusters)
+                .filter(|(_, c)| **c == cluster)
+                .map(|(s, _)| s.as_str())
+                .collect();
+            if members.len() > 1 {
+                ui.colored_label(cluster_color(cluster), "⏺");
+                ui.label(members.join(", "));
+            }
+        });
+    });
+}
+
+/// Correlations of the node to its neighbours sorted by strength.
+fn hover_text(graph: &MarketGraph, edges: &[Edge], i: usize) -> String {
+    let mut neighbours: Vec<(&str, f64)> = edges
+        .iter()
+        .filter_map(|e| match (e.a == i, e.b == i) {
+            (true, _) => Some((graph.symbols[e.b].as_str(), e.corr)),
+            (_, true) => Some((graph.symbols[e.a].as_str(), e.corr)),
+            _ => None,
+        })
+        .collect();
+    neighbours.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()));
+
+    let mut text = format!(
+        "{}, click to chart, double click to show the chart",
+        graph.symbols[i]
+    );
+    neighbours.into_iter().for_each(|(symbol, corr)| {
+        text.push_str(&format!("\n{symbol}: {corr:+.2}"));
+    });
+    text
+}
@@ -1,8 +1,27 @@
+mod export_button;
 mod graph;
+mod layout_switcher;
+mod market_graph;
+mod pnl_chart;
+mod symbol_switcher;
+mod shortcuts;
+mod sparklines;
+mod status_bar;
 mod symbols;
 mod theme;
+mod toasts;
+mod workspace_menu;
 
+pub use self::export_button::ExportButton;
 pub use self::graph::graph::Graph;
 pub use self::graph::time_input::TimeInput;
+pub use self::layout_switcher::LayoutSwitcher;
+pub use self::market_graph::{MarketGraphView, PickedNode};
+pub use self::pnl_chart::PnlChart;
+pub use self::shortcuts::Shortcuts;
+pub use self::status_bar::StatusBar;
+pub use self::symbol_switcher::SymbolSwitcher;
 pub use self::symbols::Symbols;
 pub use self::theme::Theme;
+pub use self::toasts::ToastsOverlay;
+pub use self::workspace_menu::WorkspaceMenu;
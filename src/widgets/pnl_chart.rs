@@ -0,0 +1,95 @@
+use egui::{
+    plot::{HLine, Legend, Line, Plot, Value, Values},
+    Color32, Context, Ui, Window,
+};
+
+use crate::{
+    netstrat::{data::Data, export::Table, paper::PnlPoint},
+    widgets::ExportButton,
+};
+
+/// Window plotting realized, unrealized and total profit of a portfolio over time.
+pub struct PnlChart {
+    title: String,
+    pub visible: bool,
+}
+
+impl PnlChart {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            visible: false,
+        }
+    }
+
+    pub fn toggle_btn(&mut self, ui: &mut Ui) {
+        if ui.selectable_label(self.visible, "P&L chart").clicked() {
+            self.visible = !self.visible;
+        }
+    }
+
+    fn table(points: &[PnlPoint]) -> Table {
+        let mut table = Table::new(&["time", "realized", "unrealized", "total"]);
+        points.iter().for_each(|p| {
+            table.push(vec![
+                Data::format_ts(p.time as f64),
+                p.realized.to_string(),
+                p.unrealized.to_string(),
+                p.total().to_string(),
+            ]);
+        });
+
+        table
+    }
+
+    pub fn show(&mut self, ctx: &Context, points: &[PnlPoint]) {
+        let mut visible = self.visible;
+        Window::new(&self.title)
+            .open(&mut visible)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} points", points.len()));
+                    ui.add(ExportButton::new(self.title.replace(' ', "-"), || {
+                        PnlChart::table(points)
+                    }));
+                });
+
+                let line = |f: fn(&PnlPoint) -> f64| {
+                    Values::from_values(
+                        points
+                            .iter()
+                            .map(|p| Value::new(p.time as f64, f(p)))
+                            .collect(),
+                    )
+                };
+                Plot::new(ui.id().with("pnl"))
+                    .height(250.0)
+                    .legend(Legend::default())
+                    .x_axis_formatter(|v, _| Data::format_ts(v))
+                    .label_formatter(|name, v| {
+                        format!("{name} {:.2}\n{}", v.y, Data::format_ts(v.x))
+                    })
+                    .show(ui, |plot_ui| {
+                        plot_ui.hline(HLine::new(0.0).color(Color32::GRAY));
+                        plot_ui.line(
+                            Line::new(line(|p| p.realized))
+                                .color(Color32::LIGHT_BLUE)
+                                .name("realized"),
+                        );
+                        plot_ui.line(
+                            Line::new(line(|p| p.unrealized))
+                                .color(Color32::GOLD)
+                                .name("unrealized"),
+                        );
+                        plot_ui.line(
+                            Line::new(line(PnlPoint::total))
+                                .color(Color32::LIGHT_GREEN)
+                                .width(2.0)
+                                .name("total"),
+                        );
+                    });
+            });
+        self.visible = visible;
+    }
+}
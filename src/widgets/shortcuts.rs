@@ -0,0 +1,67 @@
+use egui::{Align2, Context, Grid, Id, Key, Modifiers, Window};
+use tracing::info;
+
+use crate::netstrat::shortcuts::{bindings, Action};
+
+const TRIGGERED_ID: &str = "triggered shortcuts";
+
+/// Consumes shortcut keys at the start of a frame so widgets can react to triggered actions.
+/// Shows the help overlay listing bindings and the egui inspection window in debug mode.
+#[derive(Default)]
+pub struct Shortcuts {
+    help: bool,
+    debug: bool,
+}
+
+impl Shortcuts {
+    pub fn update(&mut self, ctx: &Context) {
+        let triggered: Vec<Action> = bindings()
+            .into_iter()
+            .filter(|s| ctx.input_mut().consume_key(s.modifiers, s.key))
+            .map(|s| s.action)
+            .collect();
+        if !triggered.is_empty() {
+            info!("Triggered shortcuts: {triggered:?}.");
+        }
+
+        if triggered.contains(&Action::Help) {
+            self.help = !self.help;
+        }
+        if self.help && ctx.input_mut().consume_key(Modifiers::NONE, Key::Escape) {
+            self.help = false;
+        }
+        if triggered.contains(&Action::ToggleDebug) {
+            self.debug = !self.debug;
+        }
+        ctx.data().insert_temp(Id::new(TRIGGERED_ID), triggered);
+
+        Window::new("keyboard shortcuts")
+            .open(&mut self.help)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                Grid::new("shortcuts").striped(true).show(ui, |ui| {
+                    bindings().iter().for_each(|s| {
+                        ui.monospace(s.keys());
+                        ui.label(s.action.description());
+                        ui.end_row();
+                    });
+                });
+            });
+
+        Window::new("debug")
+            .open(&mut self.debug)
+            .show(ctx, |ui| ctx.inspection_ui(ui));
+        if ctx.debug_on_hover() != self.debug {
+            ctx.set_debug_on_hover(self.debug);
+        }
+    }
+
+    /// Returns true if the action was triggered on this frame.
+    pub fn triggered(ctx: &Context, action: Action) -> bool {
+        ctx.data()
+            .get_temp::<Vec<Action>>(Id::new(TRIGGERED_ID))
+            .is_some_and(|triggered| triggered.contains(&action))
+    }
+}
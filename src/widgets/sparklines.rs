@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use egui::{Pos2, Sense, Shape, Stroke, Ui, Vec2};
+use poll_promise::Promise;
+use tracing::error;
+
+use crate::{
+    netstrat::{
+        archive::Archive,
+        sparkline::{closes, normalize, span_start},
+        theme::ChartColors,
+    },
+    sources::binance::{errors::ClientError, Client, Interval, Kline},
+};
+
+const SIZE: Vec2 = Vec2::new(60.0, 14.0);
+/// Limit of hourly klines requested for a sparkline.
+const LIMIT: usize = 30;
+
+/// Tiny 24h price lines of symbols drawn from archived hourly klines.
+/// Missing or stale klines are downloaded once and added to the archive.
+#[derive(Default)]
+pub struct Sparklines {
+    archive: Archive,
+    /// Closes by symbol with the time they were loaded at.
+    closes: HashMap<String, (i64, Vec<f32>)>,
+    promises: HashMap<String, Promise<Result<Vec<Kline>, ClientError>>>,
+}
+
+impl Sparklines {
+    fn poll(&mut self, symbol: &str) {
+        let now = Utc::now().timestamp_millis();
+        if let Some(res) = self.promises.get(symbol).and_then(|p| p.ready()) {
+            match res {
+                Ok(klines) => {
+                    if let Err(err) = self.archive.store(symbol, Interval::Hour, klines) {
+                        error!("Failed to archive sparkline klines: {err}.");
+                    }
+                    let res = closes(klines, now).unwrap_or_default();
+                    self.closes.insert(symbol.to_string(), (now, res));
+                }
+                Err(err) => {
+                    error!("Failed to load sparkline of {symbol}: {err}.");
+                    self.closes.insert(symbol.to_string(), (now, vec![]));
+                }
+            }
+            self.promises.remove(symbol);
+        }
+
+        let fresh =
+            matches!(self.closes.get(symbol), Some((t, _)) if now - t < Interval::Hour.millis());
+        if fresh || self.promises.contains_key(symbol) {
+            return;
+        }
+
+        let archived = self
+            .archive
+            .load(symbol, Interval::Hour)
+            .unwrap_or_default();
+        match closes(&archived, now) {
+            Some(res) => {
+                self.closes.insert(symbol.to_string(), (now, res));
+            }
+            None => {
+                let symbol = symbol.to_string();
+                self.promises.insert(
+                    symbol.clone(),
+                    Promise::spawn_async(async move {
+                        Client::kline_range(symbol, Interval::Hour, span_start(now), now, LIMIT)
+                            .await
+                    }),
+                );
+            }
+        }
+    }
+
+    /// Draws the sparkline of the symbol colored by the direction of the day.
+    pub fn show(&mut self, ui: &mut Ui, symbol: &str) {
+        self.poll(symbol);
+
+        let (rect, _) = ui.allocate_exact_size(SIZE, Sense::hover());
+        let closes = match self.closes.get(symbol) {
+            Some((_, closes)) if closes.len() > 1 => closes,
+            Some(_) => return,
+            None => {
+                ui.put(rect, egui::Spinner::new().size(SIZE.y));
+                return;
+            }
+        };
+
+        let falling = closes.last() < closes.first();
+        let points: Vec<Pos2> = normalize(closes)
+            .into_iter()
+            .map(|(x, y)| rect.min + Vec2::new(x * rect.width(), y * rect.height()))
+            .collect();
+        ui.painter().add(Shape::line(
+            points,
+            Stroke::new(1.0, ChartColors::current().kline(falling)),
+        ));
+    }
+}
@@ -0,0 +1,36 @@
+use chrono::Utc;
+use egui::{Color32, Layout, Response, RichText, Ui, Widget};
+
+use crate::netstrat::status::{Connectivity, Status};
+
+/// Bottom bar showing whether the api is reachable and how fresh the charted data is.
+pub struct StatusBar;
+
+impl Widget for &mut StatusBar {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let status = Status::current();
+
+        ui.with_layout(Layout::left_to_right(), |ui| {
+            let (color, text) = match status.connectivity() {
+                Connectivity::Unknown => (Color32::GRAY, "connecting"),
+                Connectivity::Online => (Color32::GREEN, "online"),
+                Connectivity::Offline => (Color32::RED, "offline"),
+            };
+            let connectivity = ui.label(RichText::new(format!("● {text}")).color(color));
+            if let Some(err) = &status.last_error {
+                connectivity.on_hover_text(err);
+            }
+
+            ui.separator();
+            ui.label(format!(
+                "last fetch: {}",
+                status.freshness(Utc::now().timestamp_millis())
+            ));
+            ui.separator();
+            ui.label(format!("candles: {}", status.candles));
+            ui.separator();
+            ui.label(format!("feed: {}", status.feed.as_str()));
+        })
+        .response
+    }
+}
@@ -0,0 +1,119 @@
+use crossbeam::channel::Sender;
+use egui::{Align2, Context, Key, Modifiers, TextEdit, WidgetText, Window};
+use tracing::{error, info};
+
+use super::Shortcuts;
+use crate::{
+    netstrat::{fuzzy::rank, shortcuts::Action},
+    sources::binance::Symbol,
+};
+
+const MAX_MATCHES: usize = 10;
+
+/// Overlay opened with Ctrl+K which switches the charted symbol by typing its name.
+pub struct SymbolSwitcher {
+    open: bool,
+    query: String,
+    selected: usize,
+    symbols: Vec<Symbol>,
+    symbol_pub: Sender<Symbol>,
+}
+
+impl SymbolSwitcher {
+    pub fn new(symbol_pub: Sender<Symbol>) -> Self {
+        Self {
+            open: false,
+            query: String::new(),
+            selected: 0,
+            symbols: vec![],
+            symbol_pub,
+        }
+    }
+
+    pub fn has_symbols(&self) -> bool {
+        !self.symbols.is_empty()
+    }
+
+    pub fn set_symbols(&mut self, symbols: Vec<Symbol>) {
+        self.symbols = symbols;
+    }
+
+    pub fn open(&mut self) {
+        if !self.open {
+            self.toggle();
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.query = String::new();
+        self.selected = 0;
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        if Shortcuts::triggered(ctx, Action::SymbolSearch) {
+            self.toggle();
+        }
+
+        if !self.open {
+            return;
+        }
+
+        if ctx.input_mut().consume_key(Modifiers::NONE, Key::Escape) {
+            self.toggle();
+            return;
+        }
+
+        let matches: Vec<Symbol> = rank(&self.query, &self.symbols, |s| s.symbol.as_str())
+            .into_iter()
+            .take(MAX_MATCHES)
+            .cloned()
+            .collect();
+
+        if ctx.input_mut().consume_key(Modifiers::NONE, Key::ArrowDown) {
+            self.selected = (self.selected + 1).min(matches.len().saturating_sub(1));
+        }
+        if ctx.input_mut().consume_key(Modifiers::NONE, Key::ArrowUp) {
+            self.selected = self.selected.saturating_sub(1);
+        }
+        self.selected = self.selected.min(matches.len().saturating_sub(1));
+
+        let mut picked = None;
+        if ctx.input_mut().consume_key(Modifiers::NONE, Key::Enter) {
+            picked = matches.get(self.selected).cloned();
+        }
+
+        Window::new("symbol switcher")
+            .title_bar(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_TOP, [0.0, 60.0])
+            .show(ctx, |ui| {
+                let edit = ui.add(
+                    TextEdit::singleline(&mut self.query)
+                        .hint_text(WidgetText::from("type a symbol").italics()),
+                );
+                edit.request_focus();
+
+                if self.symbols.is_empty() {
+                    ui.spinner();
+                }
+
+                matches.iter().enumerate().for_each(|(i, s)| {
+                    if ui
+                        .selectable_label(i == self.selected, s.symbol.as_str())
+                        .clicked()
+                    {
+                        picked = Some(s.clone());
+                    }
+                });
+            });
+
+        if let Some(symbol) = picked {
+            match self.symbol_pub.send(symbol.clone()) {
+                Ok(_) => info!("Sent symbol from switcher: {}.", symbol.symbol),
+                Err(err) => error!("Failed to send symbol from switcher: {err}."),
+            }
+            self.toggle();
+        }
+    }
+}
@@ -1,14 +1,28 @@
+use std::collections::HashMap;
+
 use crossbeam::channel::{unbounded, Sender};
-use egui::{Label, Layout, Response, ScrollArea, TextEdit, Widget, WidgetText};
+use egui::{
+    Align, CollapsingHeader, ComboBox, Grid, Key, Label, Layout, Modifiers, Response, ScrollArea,
+    TextEdit, Ui, Widget, WidgetText,
+};
 use poll_promise::Promise;
 use tracing::{error, info};
 
-use crate::sources::binance::{Client, Info, Symbol};
+use super::sparklines::Sparklines;
+use crate::{
+    netstrat::{
+        fuzzy::rank, recent::Recent, symbol_info::rows, symbol_sort::SortMode, toasts::Toasts,
+        watchlist::Watchlists,
+    },
+    sources::binance::{errors::ClientError, Client, Info, Symbol, Ticker},
+};
 
 #[derive(Default)]
 struct FilterProps {
     value: String,
     active_only: bool,
+    /// Position of the match picked with arrow keys.
+    highlighted: Option<usize>,
 }
 
 pub struct Symbols {
@@ -16,49 +30,214 @@ pub struct Symbols {
     filter: FilterProps,
     loading: bool,
     selected_symbol: String,
-    symbols_promise: Option<Promise<Info>>,
-    symbol_pub: Sender<String>,
+    watchlists: Watchlists,
+    sparklines: Sparklines,
+    recent: Recent,
+    /// Name of the watchlist shown above all symbols.
+    watchlist: String,
+    new_watchlist: String,
+    symbols_promise: Option<Promise<Result<Info, ClientError>>>,
+    /// Error of the last symbols download, offered to retry.
+    symbols_error: Option<String>,
+    sort: SortMode,
+    /// 24h statistics by symbol.
+    tickers: HashMap<String, Ticker>,
+    tickers_promise: Option<Promise<Result<Vec<Ticker>, ClientError>>>,
+    symbol_pub: Sender<Symbol>,
 }
 
 impl Default for Symbols {
     fn default() -> Self {
         let (s, _) = unbounded();
+        let mut watchlists = Watchlists::default();
+        if let Err(err) = watchlists.load() {
+            error!("Failed to load watchlists: {err}.");
+        }
+
         Self {
             symbols: Default::default(),
             filter: Default::default(),
             loading: Default::default(),
             selected_symbol: Default::default(),
+            watchlist: watchlists.lists[0].name.clone(),
+            watchlists,
+            sparklines: Sparklines::default(),
+            recent: Recent::default(),
+            new_watchlist: String::new(),
             symbols_promise: Default::default(),
+            symbols_error: None,
+            sort: Default::default(),
+            tickers: Default::default(),
+            tickers_promise: None,
             symbol_pub: s,
         }
     }
 }
 
 impl Symbols {
-    pub fn new(symbol_pub: Sender<String>) -> Self {
+    pub fn new(symbol_pub: Sender<Symbol>) -> Self {
         Self {
             loading: true,
             symbols_promise: Some(Promise::spawn_async(async { Client::info().await })),
+            tickers_promise: Some(Promise::spawn_async(async { Client::tickers().await })),
             symbol_pub,
             ..Default::default()
         }
     }
+
+    /// Marks the symbol as charted, also when it was picked outside of the list.
+    pub fn set_charted(&mut self, symbol: &str) {
+        self.recent.push(symbol);
+        self.selected_symbol = symbol.to_string();
+    }
+
+    fn poll_tickers(&mut self) {
+        if let Some(res) = self.tickers_promise.as_ref().and_then(|p| p.ready()) {
+            match res {
+                Ok(tickers) => {
+                    info!("Loaded 24h tickers of {} symbols.", tickers.len());
+                    self.tickers = tickers
+                        .iter()
+                        .map(|t| (t.symbol.clone(), t.clone()))
+                        .collect();
+                }
+                Err(err) => {
+                    error!("Failed to load tickers: {err}.");
+                    Toasts::error("Failed to load 24h tickers", err);
+                }
+            }
+            self.tickers_promise = None;
+        }
+    }
+
+    /// Draws sorting mode controls with reloading of ticker data.
+    fn sort_ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ComboBox::from_id_source("symbols sort")
+                .selected_text(self.sort.as_str())
+                .show_ui(ui, |ui| {
+                    SortMode::all().into_iter().for_each(|m| {
+                        ui.selectable_value(&mut self.sort, m, m.as_str());
+                    });
+                })
+                .response
+                .on_hover_text("order of symbols while the filter is empty");
+            match self.tickers_promise.is_some() {
+                true => {
+                    ui.spinner();
+                }
+                false => {
+                    if ui
+                        .small_button("⟳")
+                        .on_hover_text("reload 24h stats")
+                        .clicked()
+                    {
+                        self.tickers_promise =
+                            Some(Promise::spawn_async(async { Client::tickers().await }));
+                    }
+                }
+            }
+        });
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    fn send(&mut self, symbol: Symbol) {
+        match self.symbol_pub.send(symbol.clone()) {
+            Ok(_) => info!("Sent symbol: {}.", symbol.symbol),
+            Err(err) => error!("Failed to send symbol: {err}."),
+        }
+        self.selected_symbol = symbol.symbol;
+    }
+
+    /// Draws the info pane of the selected symbol.
+    fn info_ui(&mut self, ui: &mut Ui) {
+        let symbol = match self
+            .symbols
+            .iter()
+            .find(|s| s.symbol == self.selected_symbol)
+        {
+            Some(symbol) => symbol,
+            None => return,
+        };
+
+        CollapsingHeader::new(format!("{} info", symbol.symbol))
+            .id_source("symbol info")
+            .show(ui, |ui| {
+                Grid::new("symbol info grid").striped(true).show(ui, |ui| {
+                    rows(symbol, self.tickers.get(&symbol.symbol))
+                        .into_iter()
+                        .for_each(|(label, value)| {
+                            ui.label(label);
+                            ui.label(value);
+                            ui.end_row();
+                        });
+                });
+            });
+    }
+
+    /// Draws controls choosing, adding and deleting watchlists.
+    fn watchlist_ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ComboBox::from_id_source("watchlist")
+                .selected_text(self.watchlist.as_str())
+                .show_ui(ui, |ui| {
+                    self.watchlists.lists.iter().for_each(|l| {
+                        ui.selectable_value(&mut self.watchlist, l.name.clone(), &l.name);
+                    });
+                });
+            if ui
+                .small_button("🗑")
+                .on_hover_text("delete watchlist")
+                .clicked()
+            {
+                if let Err(err) = self.watchlists.delete(&self.watchlist) {
+                    error!("Failed to save watchlists: {err}.");
+                }
+                self.watchlist = self.watchlists.lists[0].name.clone();
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.add(
+                TextEdit::singleline(&mut self.new_watchlist)
+                    .hint_text(WidgetText::from("new watchlist").italics())
+                    .desired_width(100.0),
+            );
+            if ui
+                .add_enabled(!self.new_watchlist.is_empty(), egui::Button::new("add"))
+                .clicked()
+            {
+                if let Err(err) = self.watchlists.add(&self.new_watchlist) {
+                    error!("Failed to save watchlists: {err}.");
+                }
+                self.watchlist = std::mem::take(&mut self.new_watchlist);
+            }
+        });
+    }
 }
 
 impl Widget for &mut Symbols {
     fn ui(self, ui: &mut egui::Ui) -> Response {
-        if let Some(promise) = &self.symbols_promise {
-            if let Some(result) = promise.ready() {
-                self.loading = false;
-
-                self.symbols = result
-                    .symbols
-                    .iter()
-                    .map(|s| -> Symbol { s.clone() })
-                    .collect();
+        if let Some(result) = self.symbols_promise.as_ref().and_then(|p| p.ready()) {
+            self.loading = false;
+            match result {
+                Ok(info) => {
+                    self.symbols = info.symbols.clone();
+                    self.symbols_error = None;
+                }
+                Err(err) => {
+                    error!("Failed to load symbols: {err}.");
+                    Toasts::error("Failed to load symbols", err);
+                    self.symbols_error = Some(err.to_string());
+                }
             }
+            self.symbols_promise = None;
         }
 
+        self.poll_tickers();
+
         if self.loading {
             return ui
                 .centered_and_justified(|ui| {
@@ -66,67 +245,168 @@ impl Widget for &mut Symbols {
                 })
                 .response;
         }
+        if let Some(err) = &self.symbols_error {
+            return ui
+                .vertical_centered(|ui| {
+                    ui.label(format!("Failed to load symbols: {err}"));
+                    if ui.button("retry").clicked() {
+                        self.loading = true;
+                        self.symbols_promise =
+                            Some(Promise::spawn_async(async { Client::info().await }));
+                    }
+                })
+                .response;
+        }
 
-        ui.with_layout(Layout::top_down(egui::Align::LEFT), |ui| {
-            ui.add(
-                TextEdit::singleline(&mut self.filter.value)
-                    .hint_text(WidgetText::from("filter symbols").italics()),
-            );
+        let mut picked = None;
+        let mut starred = None;
+        let response = ui
+            .with_layout(Layout::top_down(egui::Align::LEFT), |ui| {
+                self.info_ui(ui);
+                self.watchlist_ui(ui);
+                let listed = self
+                    .watchlists
+                    .get(&self.watchlist)
+                    .map(|l| l.symbols.clone())
+                    .unwrap_or_default();
+                listed.iter().for_each(|name| {
+                    if let Some(s) = self.symbols.iter().find(|s| &s.symbol == name) {
+                        ui.horizontal(|ui| {
+                            if ui
+                                .selectable_label(s.symbol == self.selected_symbol, name)
+                                .clicked()
+                            {
+                                picked = Some(s.clone());
+                            }
+                            self.sparklines.show(ui, name);
+                        });
+                    }
+                });
+                if !self.recent.symbols().is_empty() {
+                    ui.collapsing("recent", |ui| {
+                        self.recent.symbols().iter().for_each(|name| {
+                            if let Some(s) = self.symbols.iter().find(|s| &s.symbol == name) {
+                                if ui.selectable_label(false, name).clicked() {
+                                    picked = Some(s.clone());
+                                }
+                            }
+                        });
+                    });
+                }
+                ui.separator();
 
-            let filtered: Vec<&Symbol> = self
-                .symbols
-                .iter()
-                .filter(|s| {
-                    let match_value = s
-                        .symbol
-                        .to_lowercase()
-                        .contains(self.filter.value.to_lowercase().as_str());
-                    if self.filter.active_only {
-                        return match_value && s.active();
+                let filter_id = ui.make_persistent_id("symbols filter");
+                let mut moved = false;
+                if ui.memory().has_focus(filter_id) {
+                    if ui.input_mut().consume_key(Modifiers::NONE, Key::ArrowDown) {
+                        self.filter.highlighted =
+                            Some(self.filter.highlighted.map_or(0, |h| h + 1));
+                        moved = true;
                     }
-                    match_value
-                })
-                .collect();
-            ui.with_layout(Layout::top_down(egui::Align::RIGHT), |ui| {
-                ui.checkbox(&mut self.filter.active_only, "active only");
-                ui.add(Label::new(
-                    WidgetText::from(format!("{}/{}", filtered.len(), self.symbols.len())).small(),
-                ));
-            });
+                    if ui.input_mut().consume_key(Modifiers::NONE, Key::ArrowUp) {
+                        self.filter.highlighted =
+                            Some(self.filter.highlighted.map_or(0, |h| h.saturating_sub(1)));
+                        moved = true;
+                    }
+                }
+                let edit = ui.add(
+                    TextEdit::singleline(&mut self.filter.value)
+                        .id(filter_id)
+                        .hint_text(WidgetText::from("filter symbols").italics()),
+                );
+                if edit.changed() {
+                    self.filter.highlighted = Some(0).filter(|_| !self.filter.value.is_empty());
+                }
+                let entered = edit.lost_focus() && ui.input().key_pressed(Key::Enter);
+                self.sort_ui(ui);
+
+                let mut filtered: Vec<&Symbol> =
+                    rank(&self.filter.value, &self.symbols, |s| s.symbol.as_str())
+                        .into_iter()
+                        .filter(|s| !self.filter.active_only || s.active())
+                        .collect();
+                if self.filter.value.is_empty() {
+                    self.sort.sort(&mut filtered, &self.tickers);
+                }
+                self.filter.highlighted = self
+                    .filter
+                    .highlighted
+                    .map(|h| h.min(filtered.len().saturating_sub(1)));
+                if entered {
+                    picked = self
+                        .filter
+                        .highlighted
+                        .and_then(|h| filtered.get(h))
+                        .map(|s| (*s).clone());
+                }
+                ui.with_layout(Layout::top_down(egui::Align::RIGHT), |ui| {
+                    ui.checkbox(&mut self.filter.active_only, "active only");
+                    ui.add(Label::new(
+                        WidgetText::from(format!("{}/{}", filtered.len(), self.symbols.len()))
+                            .small(),
+                    ));
+                });
 
-            ui.add_space(5f32);
-
-            ScrollArea::vertical()
-                .auto_shrink([false; 2])
-                .max_height(ui.available_height())
-                .show(ui, |ui| {
-                    ui.with_layout(Layout::top_down(egui::Align::LEFT), |ui| {
-                        filtered.iter().for_each(|s| {
-                            let label = ui.selectable_label(
-                                s.symbol == self.selected_symbol,
-                                match s.active() {
-                                    true => WidgetText::from(s.symbol.to_string()).strong(),
-                                    false => WidgetText::from(s.symbol.to_string()).strikethrough(),
-                                },
-                            );
-
-                            if label.clicked() {
-                                let send_result = self.symbol_pub.send(s.symbol.clone());
-                                match send_result {
-                                    Ok(_) => {
-                                        info!("Sent symbol: {}.", s.symbol);
+                ui.add_space(5f32);
+
+                ScrollArea::vertical()
+                    .auto_shrink([false; 2])
+                    .max_height(ui.available_height())
+                    .show(ui, |ui| {
+                        ui.with_layout(Layout::top_down(egui::Align::LEFT), |ui| {
+                            filtered.iter().enumerate().for_each(|(i, s)| {
+                                ui.horizontal(|ui| {
+                                    let star = match self
+                                        .watchlists
+                                        .contains(&self.watchlist, &s.symbol)
+                                    {
+                                        true => "★",
+                                        false => "☆",
+                                    };
+                                    if ui
+                                        .small_button(star)
+                                        .on_hover_text(format!("toggle in {}", self.watchlist))
+                                        .clicked()
+                                    {
+                                        starred = Some(s.symbol.clone());
+                                    }
+
+                                    let highlighted = self.filter.highlighted == Some(i);
+                                    let label = ui.selectable_label(
+                                        s.symbol == self.selected_symbol || highlighted,
+                                        match s.active() {
+                                            true => WidgetText::from(s.symbol.to_string()).strong(),
+                                            false => WidgetText::from(s.symbol.to_string())
+                                                .strikethrough(),
+                                        },
+                                    );
+                                    if let Some(stat) =
+                                        self.tickers.get(&s.symbol).and_then(|t| self.sort.label(t))
+                                    {
+                                        ui.label(WidgetText::from(stat).small().weak());
                                     }
-                                    Err(err) => {
-                                        error!("Failed to send symbol: {err}.");
+                                    if highlighted && moved {
+                                        label.scroll_to_me(Some(Align::Center));
                                     }
-                                }
+                                    if label.clicked() {
+                                        picked = Some((*s).clone());
+                                    }
+                                });
+                            });
+                        })
+                    });
+            })
+            .response;
 
-                                self.selected_symbol = s.symbol.clone();
-                            };
-                        });
-                    })
-                });
-        })
-        .response
+        if let Some(symbol) = starred {
+            if let Err(err) = self.watchlists.toggle(&self.watchlist, &symbol) {
+                error!("Failed to save watchlists: {err}.");
+            }
+        }
+        if let Some(symbol) = picked {
+            self.send(symbol);
+        }
+
+        response
     }
 }
@@ -1,37 +1,88 @@
-use egui::{Response, Visuals, Widget};
+use std::path::Path;
 
-static LIGHT_MODE_SYMBOL: &str = "🔆";
-static DARK_MODE_SYMBOL: &str = "🌙";
+use egui::{Response, Widget};
+use tracing::{error, info};
 
+use super::Shortcuts;
+use crate::netstrat::{
+    shortcuts::Action,
+    theme::{Themes, THEMES_FILE},
+};
+
+static THEME_SYMBOL: &str = "🎨";
+static DEFAULT_THEME: &str = "dark";
+
+/// Menu switching between built-in palettes and the ones defined in the themes file.
 pub struct Theme {
-    dark_mode: bool,
+    themes: Themes,
+    selected: String,
+    applied: Option<String>,
 }
 
 impl Theme {
     pub fn new() -> Self {
-        Self { dark_mode: true }
+        Self {
+            themes: load(),
+            selected: DEFAULT_THEME.to_string(),
+            applied: None,
+        }
+    }
+
+    pub fn selected(&self) -> &str {
+        &self.selected
+    }
+
+    pub fn select(&mut self, name: &str) {
+        self.selected = name.to_string();
     }
 }
 
+fn load() -> Themes {
+    Themes::load(Path::new(THEMES_FILE)).unwrap_or_else(|err| {
+        error!("Failed to load themes: {err}.");
+        Themes::default()
+    })
+}
+
 impl Widget for &mut Theme {
     fn ui(self, ui: &mut egui::Ui) -> Response {
-        if self.dark_mode {
-            ui.ctx().set_visuals(Visuals::dark())
-        } else {
-            ui.ctx().set_visuals(Visuals::light())
+        if Shortcuts::triggered(ui.ctx(), Action::NextTheme) {
+            let palettes = &self.themes.palettes;
+            let next = palettes
+                .iter()
+                .position(|p| p.name == self.selected)
+                .map_or(0, |i| (i + 1) % palettes.len());
+            if let Some(palette) = palettes.get(next) {
+                self.selected = palette.name.clone();
+            }
         }
 
-        let btn = ui.button({
-            match self.dark_mode {
-                true => LIGHT_MODE_SYMBOL,
-                false => DARK_MODE_SYMBOL,
+        if self.applied.as_ref() != Some(&self.selected) {
+            let palette = self
+                .themes
+                .get(&self.selected)
+                .or_else(|| self.themes.get(DEFAULT_THEME));
+            if let Some(palette) = palette {
+                info!("Applying theme: {}.", palette.name);
+                palette.apply(ui.ctx());
             }
-        });
-
-        if btn.clicked() {
-            self.dark_mode = !self.dark_mode
-        };
+            self.applied = Some(self.selected.clone());
+        }
 
-        btn
+        ui.menu_button(THEME_SYMBOL, |ui| {
+            self.themes.palettes.iter().for_each(|p| {
+                ui.selectable_value(&mut self.selected, p.name.clone(), &p.name);
+            });
+            ui.separator();
+            if ui
+                .button("reload")
+                .on_hover_text(format!("read user themes from {THEMES_FILE}"))
+                .clicked()
+            {
+                self.themes = load();
+                self.applied = None;
+            }
+        })
+        .response
     }
 }
@@ -0,0 +1,59 @@
+use egui::{Align2, Area, Color32, Context, Frame, RichText, Vec2};
+
+use crate::netstrat::toasts::{ToastKind, Toasts};
+
+/// Offset from the bottom right corner keeping toasts above the status bar.
+const OFFSET: Vec2 = Vec2::new(-10.0, -40.0);
+const WIDTH: f32 = 280.0;
+
+/// Overlay drawing queued toasts in the bottom right corner with dismiss and expand buttons.
+pub struct ToastsOverlay;
+
+impl ToastsOverlay {
+    pub fn show(&self, ctx: &Context) {
+        let toasts = Toasts::current();
+        if toasts.is_empty() {
+            return;
+        }
+
+        let mut dismissed = None;
+        let mut toggled = None;
+        Area::new("toasts")
+            .anchor(Align2::RIGHT_BOTTOM, OFFSET)
+            .show(ctx, |ui| {
+                ui.set_max_width(WIDTH);
+                toasts.iter().for_each(|t| {
+                    Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            let (icon, color) = match t.kind {
+                                ToastKind::Info => ("ℹ", Color32::LIGHT_BLUE),
+                                ToastKind::Alert => ("🔔", Color32::GOLD),
+                                ToastKind::Error => ("⚠", Color32::RED),
+                            };
+                            ui.label(RichText::new(icon).color(color));
+                            ui.label(&t.message);
+                            if t.details.is_some() {
+                                let arrow = if t.expanded { "⏷" } else { "⏵" };
+                                if ui.small_button(arrow).on_hover_text("details").clicked() {
+                                    toggled = Some(t.id);
+                                }
+                            }
+                            if ui.small_button("✖").on_hover_text("dismiss").clicked() {
+                                dismissed = Some(t.id);
+                            }
+                        });
+                        if let Some(details) = t.details.as_ref().filter(|_| t.expanded) {
+                            ui.label(RichText::new(details).small());
+                        }
+                    });
+                });
+            });
+
+        if let Some(id) = toggled {
+            Toasts::toggle_expanded(id);
+        }
+        if let Some(id) = dismissed {
+            Toasts::dismiss(id);
+        }
+    }
+}
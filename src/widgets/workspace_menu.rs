@@ -0,0 +1,129 @@
+use egui::{Color32, TextEdit, Ui, WidgetText};
+use tracing::{error, info};
+
+use crate::{
+    netstrat::{
+        drawings::Drawings,
+        workspace::{errors::WorkspaceError, Workspace, Workspaces},
+    },
+    windows::AppWindow,
+};
+
+/// Top bar menu saving the whole analysis setup to a workspace file and restoring it.
+pub struct WorkspaceMenu {
+    workspaces: Workspaces,
+    names: Vec<String>,
+    name: String,
+    status: Option<Result<String, String>>,
+}
+
+impl Default for WorkspaceMenu {
+    fn default() -> Self {
+        let mut res = Self {
+            workspaces: Workspaces::default(),
+            names: vec![],
+            name: String::new(),
+            status: None,
+        };
+        res.refresh();
+
+        res
+    }
+}
+
+impl WorkspaceMenu {
+    fn refresh(&mut self) {
+        match self.workspaces.list() {
+            Ok(names) => self.names = names,
+            Err(err) => {
+                error!("Failed to list workspaces: {err}.");
+                self.status = Some(Err(err.to_string()));
+            }
+        }
+    }
+
+    fn save(&self, windows: &[Box<dyn AppWindow>]) -> Result<String, WorkspaceError> {
+        let mut workspace = Workspace {
+            windows: windows
+                .iter()
+                .map(|w| (w.name().to_string(), w.layout()))
+                .collect(),
+            drawings: vec![],
+        };
+        let mut store = Drawings::default();
+        store.load()?;
+        workspace.collect_drawings(&store);
+
+        self.workspaces.save(&self.name, &workspace)?;
+        info!("Saved workspace {}.", self.name);
+
+        Ok(format!(
+            "saved to {}",
+            self.workspaces.path(&self.name).display()
+        ))
+    }
+
+    fn load(&self, name: &str, windows: &mut [Box<dyn AppWindow>]) -> Result<(), WorkspaceError> {
+        let workspace = self.workspaces.load(name)?;
+        info!("Restoring workspace {name}.");
+
+        let mut store = Drawings::default();
+        store.load()?;
+        workspace.restore_drawings(&mut store)?;
+
+        windows.iter_mut().for_each(|w| {
+            w.apply_layout(&workspace.windows.get(w.name()).cloned().unwrap_or_default())
+        });
+
+        Ok(())
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui, windows: &mut [Box<dyn AppWindow>]) {
+        ui.menu_button("workspaces", |ui| {
+            let mut loaded = None;
+            self.names.iter().for_each(|name| {
+                if ui.button(name).clicked() {
+                    loaded = Some(name.clone());
+                }
+            });
+            if let Some(name) = loaded {
+                self.status = self
+                    .load(&name, windows)
+                    .err()
+                    .map(|err| Err(err.to_string()));
+                ui.close_menu();
+            }
+            if self.names.is_empty() {
+                ui.label(WidgetText::from("no saved workspaces").italics());
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.add(
+                    TextEdit::singleline(&mut self.name)
+                        .hint_text(WidgetText::from("workspace name").italics())
+                        .desired_width(100.0),
+                );
+                if ui
+                    .add_enabled(!self.name.is_empty(), egui::Button::new("save"))
+                    .on_hover_text("save windows, charts and drawings to a json file")
+                    .clicked()
+                {
+                    self.status = Some(self.save(windows).map_err(|err| err.to_string()));
+                    self.name = String::new();
+                    self.refresh();
+                }
+            });
+
+            match &self.status {
+                Some(Ok(msg)) => {
+                    ui.label(WidgetText::from(msg).small());
+                }
+                Some(Err(err)) => {
+                    ui.colored_label(Color32::LIGHT_RED, err);
+                }
+                None => {}
+            }
+        });
+    }
+}
@@ -0,0 +1,391 @@
+use std::path::Path;
+
+use chrono::Utc;
+use egui::{
+    plot::{Line, Plot, Value, Values},
+    ComboBox, DragValue, Grid, ScrollArea, TextEdit, TextStyle, Ui, Window,
+};
+use poll_promise::Promise;
+use tracing::{error, info};
+
+use super::AppWindow;
+use crate::{
+    netstrat::{
+        backtest::{
+            rules::{MaCross, Rules, ScriptRules},
+            run, Config, Report,
+        },
+        data::Data,
+        export::Table,
+        layout::WindowLayout,
+        script::{Script, STRATEGIES_DIR},
+        theme::ChartColors,
+        toasts::Toasts,
+    },
+    sources::binance::{errors::ClientError, Client, Interval, Kline},
+    widgets::ExportButton,
+};
+
+/// Klines requested per page.
+const LIMIT: usize = 1000;
+const SCRIPT_TEMPLATE: &str = r#"// entry and exit arrays of bools aligned with the klines
+let fast = ema(close, 12);
+let slow = ema(close, 26);
+
+#{
+    entry: crosses_above(fast, slow),
+    exit: crosses_below(fast, slow),
+}
+"#;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Strategy {
+    MaCross,
+    Script,
+}
+
+/// Window running a strategy over downloaded klines and showing its trades,
+/// equity curve and stats.
+pub struct BacktestWindow {
+    visible: bool,
+    symbol: String,
+    interval: Interval,
+    lookback: usize,
+    strategy: Strategy,
+    rules: MaCross,
+    /// Name and source of the edited strategy script.
+    script_name: String,
+    source: String,
+    /// Strategy scripts in the strategies dir, read when the picker is first opened.
+    strategies: Option<Vec<(String, String)>>,
+    /// Script compiled when the run was started.
+    script: Option<Script>,
+    config: Config,
+    /// Name of the tested strategy with the symbol the report is for.
+    tested: String,
+    report: Option<Report>,
+    promise: Option<Promise<Result<Vec<Kline>, ClientError>>>,
+}
+
+impl BacktestWindow {
+    pub fn new(visible: bool) -> Self {
+        Self {
+            visible,
+            symbol: "BTCUSDT".to_string(),
+            interval: Interval::Hour,
+            lookback: 2000,
+            strategy: Strategy::MaCross,
+            rules: MaCross::default(),
+            script_name: "script".to_string(),
+            source: SCRIPT_TEMPLATE.to_string(),
+            strategies: None,
+            script: None,
+            config: Config::default(),
+            tested: String::new(),
+            report: None,
+            promise: None,
+        }
+    }
+
+    fn start_download(&mut self) {
+        self.script = None;
+        if self.strategy == Strategy::Script {
+            match Script::compile(&self.script_name, &self.source) {
+                Ok(script) => self.script = Some(script),
+                Err(err) => {
+                    error!("Failed to compile strategy {}: {err}.", self.script_name);
+                    Toasts::error("Failed to compile strategy", err);
+                    return;
+                }
+            }
+        }
+
+        let symbol = self.symbol.to_uppercase();
+        let interval = self.interval;
+        let end = Utc::now().timestamp_millis();
+        let start = end - self.lookback as i64 * interval.millis();
+
+        info!(
+            "Loading {} klines of {symbol} for a backtest.",
+            self.lookback
+        );
+
+        self.promise = Some(Promise::spawn_async(async move {
+            Client::kline_range(symbol, interval, start, end, LIMIT).await
+        }));
+    }
+
+    fn poll(&mut self) {
+        let res = match self.promise.as_ref().and_then(|p| p.ready()) {
+            Some(res) => res,
+            None => return,
+        };
+        match res {
+            Ok(klines) if klines.is_empty() => {
+                info!("No klines to backtest {}.", self.symbol.to_uppercase());
+                Toasts::info("No klines in the backtest range");
+                self.script = None;
+            }
+            Ok(klines) => {
+                let data = Data::new(klines.clone());
+                let mut scripted = self.script.take().map(ScriptRules::new);
+                let rules: &mut dyn Rules = match scripted.as_mut() {
+                    Some(scripted) => scripted,
+                    None => &mut self.rules,
+                };
+                match run(&data, rules, &self.config) {
+                    Ok(report) => {
+                        info!(
+                            "Backtested {} over {} klines: {:?}.",
+                            rules.name(),
+                            klines.len(),
+                            report.stats
+                        );
+                        self.tested = format!("{} {}", self.symbol.to_uppercase(), rules.name());
+                        self.report = Some(report);
+                    }
+                    Err(err) => {
+                        error!("Failed to backtest {}: {err}.", rules.name());
+                        Toasts::error("Backtest failed", err);
+                    }
+                }
+            }
+            Err(err) => {
+                error!("Failed to load klines for a backtest: {err}.");
+                Toasts::error("Failed to load backtest klines", err);
+            }
+        }
+        self.promise = None;
+    }
+
+    fn table(&self) -> Table {
+        let mut table = Table::new(&[
+            "entry",
+            "entry price",
+            "exit",
+            "exit price",
+            "pnl",
+            "return",
+        ]);
+        self.report.iter().flat_map(|r| &r.trades).for_each(|t| {
+            table.push(vec![
+                Data::format_ts(t.entry_time as f64),
+                t.entry_price.to_string(),
+                Data::format_ts(t.exit_time as f64),
+                t.exit_price.to_string(),
+                t.pnl.to_string(),
+                t.ret.to_string(),
+            ]);
+        });
+
+        table
+    }
+
+    fn controls_ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.add(TextEdit::singleline(&mut self.symbol).desired_width(100.0));
+            ComboBox::from_id_source("backtest interval")
+                .selected_text(self.interval.as_str())
+                .show_ui(ui, |ui| {
+                    Interval::ALL.into_iter().for_each(|i| {
+                        ui.selectable_value(&mut self.interval, i, i.as_str());
+                    });
+                });
+            ui.add(DragValue::new(&mut self.lookback).clamp_range(10..=50_000));
+            ui.label("candles");
+        });
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.strategy, Strategy::MaCross, "EMA cross");
+            ui.selectable_value(&mut self.strategy, Strategy::Script, "script");
+        });
+        match self.strategy {
+            Strategy::MaCross => {
+                ui.horizontal(|ui| {
+                    ui.add(DragValue::new(&mut self.rules.fast).clamp_range(1..=200));
+                    ui.label("fast");
+                    ui.add(DragValue::new(&mut self.rules.slow).clamp_range(2..=400));
+                    ui.label("slow");
+                });
+            }
+            Strategy::Script => self.script_ui(ui),
+        }
+        ui.horizontal(|ui| {
+            ui.add(DragValue::new(&mut self.config.capital).clamp_range(1.0..=f64::MAX));
+            ui.label("capital");
+            let mut fee = self.config.fee * 100.0;
+            if ui
+                .add(DragValue::new(&mut fee).clamp_range(0.0..=5.0).speed(0.01))
+                .changed()
+            {
+                self.config.fee = fee / 100.0;
+            }
+            ui.label("fee %");
+
+            let loading = self.promise.is_some();
+            if ui.add_enabled(!loading, egui::Button::new("run")).clicked() {
+                self.start_download();
+            }
+            if loading {
+                ui.ctx().request_repaint();
+                ui.spinner();
+            }
+        });
+    }
+
+    fn script_ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.add(TextEdit::singleline(&mut self.script_name).desired_width(100.0));
+            let strategies = self
+                .strategies
+                .get_or_insert_with(|| Script::read_dir(Path::new(STRATEGIES_DIR)));
+            let mut picked = None;
+            ComboBox::from_id_source("backtest strategies")
+                .selected_text("load")
+                .show_ui(ui, |ui| {
+                    if strategies.is_empty() {
+                        ui.label(format!("put *.rhai strategies into {STRATEGIES_DIR}/"));
+                    }
+                    strategies.iter().for_each(|(name, src)| {
+                        if ui.selectable_label(false, name).clicked() {
+                            picked = Some((name.clone(), src.clone()));
+                        }
+                    });
+                });
+            if let Some((name, src)) = picked {
+                self.script_name = name;
+                self.source = src;
+            }
+            if ui.button("reload").clicked() {
+                self.strategies = None;
+            }
+        });
+        ScrollArea::vertical()
+            .id_source("backtest script")
+            .max_height(200.0)
+            .show(ui, |ui| {
+                ui.add(
+                    TextEdit::multiline(&mut self.source)
+                        .code_editor()
+                        .font(TextStyle::Monospace)
+                        .desired_rows(8)
+                        .desired_width(f32::INFINITY),
+                );
+            });
+    }
+}
+
+fn report_ui(ui: &mut Ui, report: &Report) {
+    let stats = report.stats;
+    Grid::new("backtest stats").num_columns(2).show(ui, |ui| {
+        ui.label("trades");
+        ui.label(stats.trades.to_string());
+        ui.end_row();
+        ui.label("win rate");
+        ui.label(format!("{:.1}%", stats.win_rate * 100.0));
+        ui.end_row();
+        ui.label("profit factor");
+        ui.label(
+            stats
+                .profit_factor
+                .map_or("-".to_string(), |pf| format!("{pf:.2}")),
+        );
+        ui.end_row();
+        ui.label("max drawdown");
+        ui.label(format!("{:.1}%", stats.max_drawdown * 100.0));
+        ui.end_row();
+        ui.label("total return");
+        ui.label(format!("{:+.1}%", stats.total_return * 100.0));
+        ui.end_row();
+    });
+
+    let colors = ChartColors::current();
+    let equity: Vec<Value> = report
+        .equity
+        .iter()
+        .map(|(t, e)| Value::new(*t as f64, *e))
+        .collect();
+    Plot::new("backtest equity")
+        .height(200.0)
+        .x_axis_formatter(|v, _| Data::format_ts(v))
+        .label_formatter(|_, v| format!("{:.2}\n{}", v.y, Data::format_ts(v.x)))
+        .show(ui, |plot_ui| {
+            plot_ui.line(
+                Line::new(Values::from_values(equity))
+                    .color(colors.kline(stats.total_return < 0.0))
+                    .name("equity"),
+            );
+        });
+
+    ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+        Grid::new("backtest trades").striped(true).show(ui, |ui| {
+            ["entry", "price", "exit", "price", "pnl", "return"]
+                .into_iter()
+                .for_each(|h| {
+                    ui.strong(h);
+                });
+            ui.end_row();
+            report.trades.iter().for_each(|t| {
+                ui.label(Data::format_ts(t.entry_time as f64));
+                ui.label(format!("{:.2}", t.entry_price));
+                ui.label(Data::format_ts(t.exit_time as f64));
+                ui.label(format!("{:.2}", t.exit_price));
+                ui.colored_label(colors.kline(t.pnl < 0.0), format!("{:+.2}", t.pnl));
+                ui.label(format!("{:+.2}%", t.ret * 100.0));
+                ui.end_row();
+            });
+        });
+    });
+}
+
+impl AppWindow for BacktestWindow {
+    fn toggle_btn(&mut self, ui: &mut Ui) {
+        if ui.button("backtest").clicked() {
+            self.visible = !self.visible
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        self.poll();
+
+        let mut visible = self.visible;
+        Window::new("backtest")
+            .open(&mut visible)
+            .min_width(450.0)
+            .show(ui.ctx(), |ui| {
+                self.controls_ui(ui);
+                ui.separator();
+
+                let report = match &self.report {
+                    Some(report) => report,
+                    None => {
+                        ui.label("Run a strategy to see its trades and stats.");
+                        return;
+                    }
+                };
+                ui.horizontal(|ui| {
+                    ui.heading(&self.tested);
+                    ui.add(ExportButton::new(
+                        format!("{}-backtest", self.tested.replace(' ', "-")),
+                        || self.table(),
+                    ));
+                });
+                report_ui(ui, report);
+            });
+        self.visible = visible;
+    }
+
+    fn name(&self) -> &str {
+        "backtest"
+    }
+
+    fn layout(&self) -> WindowLayout {
+        WindowLayout {
+            visible: self.visible,
+            ..Default::default()
+        }
+    }
+
+    fn apply_layout(&mut self, layout: &WindowLayout) {
+        self.visible = layout.visible;
+    }
+}
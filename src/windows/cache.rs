@@ -0,0 +1,235 @@
+use egui::{Color32, Grid, Ui, Window};
+use poll_promise::Promise;
+use tracing::{error, info};
+
+use super::AppWindow;
+use crate::{
+    netstrat::{
+        archive::{errors::ArchiveError, Archive, Entry},
+        data::Data,
+        layout::WindowLayout,
+    },
+    sources::binance::{errors::ClientError, Client, Kline},
+};
+
+const REDOWNLOAD_LIMIT: usize = 1000;
+
+type Redownload = (Entry, Promise<Result<Vec<Kline>, ClientError>>);
+
+/// Window listing the local klines archive with actions to keep its size under control.
+/// The archive is listed on a blocking thread as every file is parsed.
+pub struct CacheManager {
+    visible: bool,
+    archive: Archive,
+    entries: Vec<Entry>,
+    list_promise: Option<Promise<Result<Vec<Entry>, ArchiveError>>>,
+    redownload_promise: Option<Redownload>,
+    status: Option<String>,
+}
+
+impl CacheManager {
+    pub fn new(visible: bool) -> Self {
+        let mut res = Self {
+            visible,
+            archive: Archive::default(),
+            entries: vec![],
+            list_promise: None,
+            redownload_promise: None,
+            status: None,
+        };
+        if visible {
+            res.refresh();
+        }
+
+        res
+    }
+
+    fn refresh(&mut self) {
+        let archive = self.archive.clone();
+        self.list_promise = Some(Promise::spawn_blocking(move || archive.list()));
+    }
+
+    fn poll_list(&mut self) {
+        let res = match self.list_promise.as_ref().and_then(|p| p.ready()) {
+            Some(res) => res,
+            None => return,
+        };
+        match res {
+            Ok(entries) => self.entries = entries.clone(),
+            Err(err) => {
+                error!("Failed to list archive: {err}.");
+                self.status = Some(err.to_string());
+            }
+        }
+        self.list_promise = None;
+    }
+
+    fn start_redownload(&mut self, entry: Entry) {
+        info!(
+            "Starting re-download of {} {:?}.",
+            entry.symbol, entry.interval
+        );
+
+        let (symbol, interval, start, end) =
+            (entry.symbol.clone(), entry.interval, entry.start, entry.end);
+        self.redownload_promise = Some((
+            entry,
+            Promise::spawn_async(async move {
+                Client::kline_range(symbol, interval, start, end, REDOWNLOAD_LIMIT).await
+            }),
+        ));
+    }
+
+    fn format_size(size: u64) -> String {
+        match size {
+            s if s >= 1 << 20 => format!("{:.1} MB", s as f64 / (1 << 20) as f64),
+            s if s >= 1 << 10 => format!("{:.1} KB", s as f64 / (1 << 10) as f64),
+            s => format!("{s} B"),
+        }
+    }
+}
+
+impl AppWindow for CacheManager {
+    fn toggle_btn(&mut self, ui: &mut Ui) {
+        if ui.button("cache").clicked() {
+            self.visible = !self.visible;
+            if self.visible {
+                self.refresh();
+            }
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        self.poll_list();
+        if let Some((entry, promise)) = &self.redownload_promise {
+            if let Some(res) = promise.ready() {
+                let res = match res {
+                    Ok(klines) => self
+                        .archive
+                        .replace(&entry.symbol, entry.interval, klines)
+                        .map(|_| format!("re-downloaded {} klines", klines.len()))
+                        .map_err(|err| err.to_string()),
+                    Err(err) => Err(err.to_string()),
+                };
+                match res {
+                    Ok(msg) => self.status = Some(msg),
+                    Err(err) => {
+                        error!("Failed to re-download klines: {err}.");
+                        self.status = Some(err);
+                    }
+                }
+
+                self.redownload_promise = None;
+                self.refresh();
+            }
+        }
+
+        let mut visible = self.visible;
+        Window::new("cache")
+            .open(&mut visible)
+            .show(ui.ctx(), |ui| {
+                let total: u64 = self.entries.iter().map(|e| e.size).sum();
+                ui.horizontal(|ui| {
+                    ui.label(format!("total: {}", CacheManager::format_size(total)));
+                    if ui.button("refresh").clicked() {
+                        self.refresh();
+                    }
+                    if ui.button("vacuum").clicked() {
+                        match self.archive.vacuum() {
+                            Ok(freed) => {
+                                self.status =
+                                    Some(format!("freed {}", CacheManager::format_size(freed)))
+                            }
+                            Err(err) => {
+                                error!("Failed to vacuum archive: {err}.");
+                                self.status = Some(err.to_string());
+                            }
+                        }
+                        self.refresh();
+                    }
+                    if self.redownload_promise.is_some() || self.list_promise.is_some() {
+                        ui.spinner();
+                    }
+                });
+
+                if let Some(status) = &self.status {
+                    ui.colored_label(Color32::GRAY, status);
+                }
+
+                if self.entries.is_empty() {
+                    ui.label("Archive is empty.");
+                    return;
+                }
+
+                let mut to_delete = None;
+                let mut to_redownload = None;
+                Grid::new("cache entries")
+                    .striped(true)
+                    .num_columns(6)
+                    .show(ui, |ui| {
+                        ["symbol", "interval", "range", "klines", "size", ""]
+                            .iter()
+                            .for_each(|h| {
+                                ui.strong(*h);
+                            });
+                        ui.end_row();
+
+                        self.entries.iter().for_each(|e| {
+                            ui.label(&e.symbol);
+                            ui.label(e.interval.as_str());
+                            ui.label(format!(
+                                "{} - {}",
+                                Data::format_ts(e.start as f64),
+                                Data::format_ts(e.end as f64)
+                            ));
+                            ui.label(e.count.to_string());
+                            ui.label(CacheManager::format_size(e.size));
+                            ui.horizontal(|ui| {
+                                if ui.button("delete").clicked() {
+                                    to_delete = Some(e.clone());
+                                }
+                                if ui
+                                    .add_enabled(
+                                        self.redownload_promise.is_none(),
+                                        egui::Button::new("re-download"),
+                                    )
+                                    .clicked()
+                                {
+                                    to_redownload = Some(e.clone());
+                                }
+                            });
+                            ui.end_row();
+                        });
+                    });
+
+                if let Some(e) = to_delete {
+                    if let Err(err) = self.archive.delete(&e.symbol, e.interval) {
+                        error!("Failed to delete archive entry: {err}.");
+                        self.status = Some(err.to_string());
+                    }
+                    self.refresh();
+                }
+                if let Some(e) = to_redownload {
+                    self.start_redownload(e);
+                }
+            });
+        self.visible = visible;
+    }
+    fn name(&self) -> &str {
+        "cache"
+    }
+
+    fn layout(&self) -> WindowLayout {
+        WindowLayout {
+            visible: self.visible,
+            ..Default::default()
+        }
+    }
+
+    fn apply_layout(&mut self, layout: &WindowLayout) {
+        self.visible = layout.visible;
+        if self.visible {
+            self.refresh();
+        }
+    }
+}
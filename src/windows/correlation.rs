@@ -0,0 +1,174 @@
+use egui::{
+    Align2, Color32, ComboBox, DragValue, Grid, Sense, TextEdit, TextStyle, Ui, Vec2, Window,
+};
+
+use super::AppWindow;
+use crate::{
+    netstrat::{
+        correlation::{parse_symbols, Loader, Matrix},
+        export::analytics::correlation_table,
+        layout::WindowLayout,
+        theme::ChartColors,
+    },
+    sources::binance::Interval,
+    widgets::ExportButton,
+};
+
+const CELL: Vec2 = Vec2::new(56.0, 28.0);
+
+/// Window with correlations of returns between symbols over a lookback of klines
+/// drawn as a heatmap.
+pub struct CorrelationMatrix {
+    visible: bool,
+    /// Comma separated symbols to correlate.
+    symbols: String,
+    interval: Interval,
+    /// Number of klines returns are computed from.
+    lookback: usize,
+    /// Symbols of the computed matrix.
+    loaded: Vec<String>,
+    matrix: Matrix,
+    loader: Loader,
+}
+
+impl CorrelationMatrix {
+    pub fn new(visible: bool) -> Self {
+        Self {
+            visible,
+            symbols: "BTCUSDT, ETHUSDT, BNBUSDT, SOLUSDT".to_string(),
+            interval: Interval::Hour,
+            lookback: 200,
+            loaded: vec![],
+            matrix: vec![],
+            loader: Loader::default(),
+        }
+    }
+
+    fn cell_ui(ui: &mut Ui, value: Option<f64>) -> egui::Response {
+        let (rect, resp) = ui.allocate_exact_size(CELL, Sense::hover());
+        let (fill, text) = match value {
+            Some(v) => {
+                let c = ChartColors::current().kline(v < 0.0);
+                let alpha = (v.abs().min(1.0) * 255.0) as u8;
+                (
+                    Color32::from_rgba_unmultiplied(c.r(), c.g(), c.b(), alpha),
+                    format!("{v:+.2}"),
+                )
+            }
+            None => (Color32::TRANSPARENT, "-".to_string()),
+        };
+
+        let painter = ui.painter();
+        painter.rect_filled(rect, 2.0, fill);
+        painter.text(
+            rect.center(),
+            Align2::CENTER_CENTER,
+            text,
+            TextStyle::Small.resolve(ui.style()),
+            ui.visuals().text_color(),
+        );
+
+        resp
+    }
+}
+
+impl AppWindow for CorrelationMatrix {
+    fn toggle_btn(&mut self, ui: &mut Ui) {
+        if ui.button("correlation").clicked() {
+            self.visible = !self.visible
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        if let Some((symbols, matrix)) = self.loader.poll() {
+            self.loaded = symbols;
+            self.matrix = matrix;
+        }
+
+        let mut visible = self.visible;
+        Window::new("correlation")
+            .open(&mut visible)
+            .show(ui.ctx(), |ui| {
+                ui.add(
+                    TextEdit::singleline(&mut self.symbols)
+                        .hint_text("comma separated symbols")
+                        .desired_width(f32::INFINITY),
+                );
+                ui.horizontal(|ui| {
+                    ComboBox::from_id_source("correlation interval")
+                        .selected_text(self.interval.as_str())
+                        .show_ui(ui, |ui| {
+                            Interval::ALL.into_iter().for_each(|i| {
+                                ui.selectable_value(&mut self.interval, i, i.as_str());
+                            });
+                        });
+                    ui.add(DragValue::new(&mut self.lookback).clamp_range(10..=5000));
+                    ui.label("candles");
+
+                    let loading = self.loader.loading();
+                    if ui
+                        .add_enabled(!loading, egui::Button::new("load"))
+                        .clicked()
+                    {
+                        let symbols = parse_symbols(&self.symbols);
+                        self.loader.start(symbols, self.interval, self.lookback);
+                    }
+                    if loading {
+                        ui.ctx().request_repaint();
+                        ui.spinner();
+                    }
+                });
+                ui.separator();
+
+                if self.loaded.is_empty() {
+                    ui.label("Load symbols to see correlations of their returns.");
+                    return;
+                }
+
+                ui.add(ExportButton::new(
+                    format!("correlation-{}", self.loaded.join("-")),
+                    || correlation_table(&self.loaded, &self.matrix),
+                ));
+
+                Grid::new("correlation matrix")
+                    .spacing([2.0, 2.0])
+                    .show(ui, |ui| {
+                        ui.label("");
+                        self.loaded.iter().for_each(|s| {
+                            ui.label(s);
+                        });
+                        ui.end_row();
+
+                        self.matrix.iter().enumerate().for_each(|(i, row)| {
+                            ui.label(&self.loaded[i]);
+                            row.iter().enumerate().for_each(|(j, value)| {
+                                let hint = match value {
+                                    Some(v) => {
+                                        format!("{} / {}: {v:.4}", self.loaded[i], self.loaded[j])
+                                    }
+                                    None => "not enough aligned candles".to_string(),
+                                };
+                                Self::cell_ui(ui, *value).on_hover_text(hint);
+                            });
+                            ui.end_row();
+                        });
+                    });
+            });
+        self.visible = visible;
+    }
+
+    fn name(&self) -> &str {
+        "correlation"
+    }
+
+    fn layout(&self) -> WindowLayout {
+        WindowLayout {
+            visible: self.visible,
+            ..Default::default()
+        }
+    }
+
+    fn apply_layout(&mut self, layout: &WindowLayout) {
+        self.visible = layout.visible;
+    }
+}
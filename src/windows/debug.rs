@@ -0,0 +1,93 @@
+use egui::{Ui, Window};
+use tracing::{error, info, metadata::LevelFilter};
+
+use super::AppWindow;
+use crate::netstrat::{
+    layout::WindowLayout,
+    log::{log_dir, open_dir, LevelHandle, LEVELS},
+};
+
+/// Developer window changing the verbosity of logs without restarting the app.
+pub struct DebugWindow {
+    visible: bool,
+    level: LevelFilter,
+    handle: LevelHandle,
+}
+
+impl DebugWindow {
+    pub fn new(handle: LevelHandle, visible: bool) -> Self {
+        let level = handle.clone_current().unwrap_or(LevelFilter::INFO);
+
+        Self {
+            visible,
+            level,
+            handle,
+        }
+    }
+
+    fn set_level(&mut self, level: LevelFilter) {
+        match self.handle.reload(level) {
+            Ok(_) => {
+                self.level = level;
+                info!("Log level set to {level}.");
+            }
+            Err(err) => error!("Failed to set log level {level}: {err}."),
+        }
+    }
+}
+
+impl AppWindow for DebugWindow {
+    fn toggle_btn(&mut self, ui: &mut Ui) {
+        if ui.button("debug").clicked() {
+            self.visible = !self.visible
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        let mut visible = self.visible;
+        let mut picked = None;
+        Window::new("debug")
+            .open(&mut visible)
+            .show(ui.ctx(), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("log level");
+                    LEVELS.into_iter().for_each(|level| {
+                        let label = level.to_string().to_lowercase();
+                        if ui.selectable_label(level == self.level, label).clicked() {
+                            picked = Some(level);
+                        }
+                    });
+                });
+                let dir = log_dir();
+                if ui
+                    .button("open log folder")
+                    .on_hover_text(dir.display().to_string())
+                    .clicked()
+                {
+                    if let Err(err) = open_dir(&dir) {
+                        error!("Failed to open log folder {dir:?}: {err}.");
+                    }
+                }
+            });
+        self.visible = visible;
+
+        if let Some(level) = picked.filter(|l| *l != self.level) {
+            self.set_level(level);
+        }
+    }
+
+    fn name(&self) -> &str {
+        "debug"
+    }
+
+    fn layout(&self) -> WindowLayout {
+        WindowLayout {
+            visible: self.visible,
+            ..Default::default()
+        }
+    }
+
+    fn apply_layout(&mut self, layout: &WindowLayout) {
+        self.visible = layout.visible;
+    }
+}
@@ -0,0 +1,192 @@
+use egui::{Color32, DragValue, Grid, Label, Sense, TextEdit, Ui, Vec2, Window};
+use poll_promise::Promise;
+use tracing::{error, info};
+
+use super::AppWindow;
+use crate::{
+    netstrat::{
+        layout::WindowLayout,
+        order_flow::{levels, Level},
+        power::LowPower,
+        theme::ChartColors,
+    },
+    sources::binance::{errors::ClientError, Client, Depth},
+};
+
+/// Seconds between order book requests.
+const POLL_SECS: f64 = 1.0;
+const DEPTH_LIMIT: usize = 100;
+const BAR_WIDTH: f32 = 120.0;
+
+/// Depth of market window with the order book of a symbol polled while the window is open.
+pub struct DepthOfMarket {
+    visible: bool,
+    /// Symbol typed by the user, switched to when the input loses focus.
+    input: String,
+    symbol: String,
+    rows: usize,
+    depth: Depth,
+    depth_promise: Option<Promise<Result<Depth, ClientError>>>,
+    /// Time of the last order book request.
+    polled: Option<f64>,
+    error: Option<String>,
+}
+
+impl DepthOfMarket {
+    pub fn new(visible: bool) -> Self {
+        Self {
+            visible,
+            input: "BTCUSDT".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            rows: 15,
+            depth: Default::default(),
+            depth_promise: None,
+            polled: None,
+            error: None,
+        }
+    }
+
+    fn set_symbol(&mut self, symbol: String) {
+        if symbol == self.symbol {
+            return;
+        }
+
+        info!("Switched order book to {symbol}.");
+        self.input = symbol.clone();
+        self.symbol = symbol;
+        self.depth = Default::default();
+        self.depth_promise = None;
+        self.polled = None;
+    }
+
+    fn poll(&mut self, time: f64) {
+        if let Some(res) = self.depth_promise.as_ref().and_then(|p| p.ready()) {
+            match res {
+                Ok(depth) => {
+                    self.depth = depth.clone();
+                    self.error = None;
+                }
+                Err(err) => {
+                    error!("Failed to load order book of {}: {err}.", self.symbol);
+                    self.error = Some(err.to_string());
+                }
+            }
+            self.depth_promise = None;
+        }
+
+        let due = self.polled.is_none_or(|polled| time - polled >= POLL_SECS);
+        if self.depth_promise.is_some() || !due || self.symbol.is_empty() {
+            return;
+        }
+
+        self.polled = Some(time);
+        let symbol = self.symbol.clone();
+        self.depth_promise = Some(Promise::spawn_async(async move {
+            Client::depth(symbol, DEPTH_LIMIT).await
+        }));
+    }
+
+    fn level_ui(ui: &mut Ui, level: &Level, max: f64, color: Color32) {
+        ui.colored_label(color, level.price.to_string());
+        ui.add(Label::new(level.qty.to_string()).wrap(false));
+
+        let (mut rect, _) = ui.allocate_exact_size(Vec2::new(BAR_WIDTH, 14.0), Sense::hover());
+        rect.min.x = rect.max.x - (level.cumulative / max) as f32 * rect.width();
+        ui.painter()
+            .rect_filled(rect, 0.0, color.linear_multiply(0.4));
+        ui.end_row();
+    }
+
+    fn book_ui(&self, ui: &mut Ui) {
+        let (bids, asks) = (
+            levels(&self.depth.bids, self.rows),
+            levels(&self.depth.asks, self.rows),
+        );
+        let max = bids
+            .iter()
+            .chain(&asks)
+            .map(|l| l.cumulative)
+            .fold(f64::EPSILON, f64::max);
+        let colors = ChartColors::current();
+
+        Grid::new("dom levels").striped(true).show(ui, |ui| {
+            ["price", "qty", "cumulative"].into_iter().for_each(|h| {
+                ui.strong(h);
+            });
+            ui.end_row();
+
+            asks.iter()
+                .rev()
+                .for_each(|l| Self::level_ui(ui, l, max, colors.down));
+            if let (Some(bid), Some(ask)) = (bids.first(), asks.first()) {
+                ui.label(format!("spread {:.8}", ask.price - bid.price));
+                ui.end_row();
+            }
+            bids.iter()
+                .for_each(|l| Self::level_ui(ui, l, max, colors.up));
+        });
+    }
+}
+
+impl AppWindow for DepthOfMarket {
+    fn toggle_btn(&mut self, ui: &mut Ui) {
+        if ui.button("dom").clicked() {
+            self.visible = !self.visible
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        if !self.visible {
+            return;
+        }
+
+        self.poll(ui.input().time);
+        if !LowPower::is_on(ui.ctx()) {
+            ui.ctx().request_repaint();
+        }
+
+        let mut visible = self.visible;
+        Window::new("dom")
+            .open(&mut visible)
+            .min_width(300.0)
+            .show(ui.ctx(), |ui| {
+                ui.horizontal(|ui| {
+                    let input = ui.add(TextEdit::singleline(&mut self.input).desired_width(100.0));
+                    if input.lost_focus() {
+                        self.set_symbol(self.input.to_uppercase());
+                    }
+                    ui.add(DragValue::new(&mut self.rows).clamp_range(1..=DEPTH_LIMIT));
+                    ui.label("levels");
+                    if self.depth_promise.is_some() {
+                        ui.spinner();
+                    }
+                });
+
+                if let Some(err) = &self.error {
+                    ui.colored_label(Color32::LIGHT_RED, err);
+                }
+
+                self.book_ui(ui);
+            });
+        self.visible = visible;
+    }
+
+    fn name(&self) -> &str {
+        "dom"
+    }
+
+    fn layout(&self) -> WindowLayout {
+        WindowLayout {
+            visible: self.visible,
+            symbol: Some(self.symbol.clone()),
+            ..Default::default()
+        }
+    }
+
+    fn apply_layout(&mut self, layout: &WindowLayout) {
+        self.visible = layout.visible;
+        if let Some(symbol) = &layout.symbol {
+            self.set_symbol(symbol.clone());
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use chrono::{Duration, Utc};
+use egui::{
+    plot::{HLine, Legend, Line, Plot, Value, Values},
+    Color32, DragValue, TextEdit, Ui, Window,
+};
+use poll_promise::Promise;
+use tracing::{error, info};
+
+use super::AppWindow;
+use crate::{
+    netstrat::{
+        data::Data,
+        export::Table,
+        funding::{Funding, PositionPnl},
+        layout::WindowLayout,
+        toasts::Toasts,
+    },
+    sources::binance::{
+        credentials::Credentials,
+        errors::ClientError,
+        futures::{FuturesClient, Income, Position},
+    },
+    widgets::ExportButton,
+};
+
+/// Funding payments, realized pnl records and the open position of a symbol.
+type History = (Vec<Income>, Vec<Income>, Option<Position>);
+
+/// Window with the funding payments history of a perpetual futures symbol
+/// charted alongside the pnl of its positions.
+pub struct FundingHistory {
+    visible: bool,
+    creds: Option<Credentials>,
+    symbol: String,
+    days: i64,
+    funding: Funding,
+    pnl: PositionPnl,
+    incomes: Vec<Income>,
+    incomes_promise: Option<Promise<Result<History, ClientError>>>,
+    error: Option<String>,
+}
+
+impl FundingHistory {
+    pub fn new(visible: bool) -> Self {
+        Self {
+            visible,
+            creds: Credentials::from_env(),
+            symbol: "BTCUSDT".to_string(),
+            days: 30,
+            funding: Default::default(),
+            pnl: Default::default(),
+            incomes: Default::default(),
+            incomes_promise: None,
+            error: None,
+        }
+    }
+
+    fn table(&self) -> Table {
+        let mut table = Table::new(&["time", "symbol", "type", "asset", "income"]);
+        self.incomes.iter().for_each(|i| {
+            table.push(vec![
+                Data::format_ts(i.time as f64),
+                i.symbol.clone(),
+                i.income_type.clone(),
+                i.asset.clone(),
+                i.income.to_string(),
+            ]);
+        });
+
+        table
+    }
+
+    fn start_download(&mut self, creds: Credentials) {
+        let symbol = self.symbol.to_uppercase();
+        let start_time = (Utc::now() - Duration::days(self.days)).timestamp_millis();
+
+        info!("Starting funding history download for {symbol} from {start_time}.");
+
+        self.error = None;
+        self.incomes_promise = Some(Promise::spawn_async(async move {
+            futures::try_join!(
+                FuturesClient::funding_history(creds.clone(), symbol.clone(), start_time),
+                FuturesClient::pnl_history(creds.clone(), symbol.clone(), start_time),
+                FuturesClient::position(creds, symbol),
+            )
+        }));
+    }
+}
+
+impl AppWindow for FundingHistory {
+    fn toggle_btn(&mut self, ui: &mut Ui) {
+        if ui.button("funding").clicked() {
+            self.visible = !self.visible
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        if let Some(promise) = &self.incomes_promise {
+            if let Some(res) = promise.ready() {
+                match res {
+                    Ok((funding, pnl, position)) => {
+                        info!(
+                            "Loaded {} funding payments and {} pnl records.",
+                            funding.len(),
+                            pnl.len()
+                        );
+                        self.funding = Funding::new(funding);
+                        self.pnl = PositionPnl::new(pnl, funding, position.clone());
+                        self.incomes = funding.iter().chain(pnl).cloned().collect();
+                        self.incomes.sort_by_key(|i| i.time);
+                    }
+                    Err(err) => {
+                        error!("Failed to get funding history: {err}");
+                        Toasts::error("Failed to load funding history", err);
+                        self.error = Some(err.to_string());
+                    }
+                }
+                self.incomes_promise = None;
+            }
+        }
+
+        let mut visible = self.visible;
+        Window::new("funding")
+            .open(&mut visible)
+            .min_width(400.0)
+            .show(ui.ctx(), |ui| {
+                let creds = match &self.creds {
+                    Some(creds) => creds.clone(),
+                    None => {
+                        ui.label(
+                            "Set BINANCE_API_KEY and BINANCE_API_SECRET to load funding history.",
+                        );
+                        return;
+                    }
+                };
+
+                ui.horizontal(|ui| {
+                    ui.add(TextEdit::singleline(&mut self.symbol).desired_width(100.0));
+                    ui.add(DragValue::new(&mut self.days).clamp_range(1..=365));
+                    ui.label("days");
+
+                    let loading = self.incomes_promise.is_some();
+                    if ui
+                        .add_enabled(!loading, egui::Button::new("load"))
+                        .clicked()
+                    {
+                        self.start_download(creds);
+                    }
+                    if loading {
+                        ui.spinner();
+                    }
+
+                    ui.add(ExportButton::new(
+                        format!("{}-funding", self.symbol.to_uppercase()),
+                        || self.table(),
+                    ));
+                });
+
+                if let Some(err) = &self.error {
+                    ui.colored_label(Color32::LIGHT_RED, err);
+                }
+
+                ui.label(format!(
+                    "paid: {:.8}  received: {:.8}  total: {:.8}",
+                    self.funding.paid,
+                    self.funding.received,
+                    self.funding.total()
+                ));
+                ui.label(format!(
+                    "realized pnl: {:.8}  unrealized pnl: {:.8}  net: {:.8}",
+                    self.pnl.realized_total(),
+                    self.pnl.unrealized(),
+                    self.pnl.total()
+                ));
+                if let Some(p) = &self.pnl.position {
+                    ui.label(format!(
+                        "position: {}  entry: {}  mark: {}",
+                        p.amount, p.entry_price, p.mark_price
+                    ));
+                }
+
+                let total = self.funding.total();
+                let values = |series: &[(i64, f64)]| -> Vec<Value> {
+                    series
+                        .iter()
+                        .map(|(t, v)| Value::new(*t as f64, *v))
+                        .collect()
+                };
+                let (funding, realized, net) = (
+                    values(&self.funding.cumulative),
+                    values(&self.pnl.realized),
+                    values(&self.pnl.net),
+                );
+
+                Plot::new("funding")
+                    .height(250.0)
+                    .legend(Legend::default())
+                    .x_axis_formatter(|v, _| Data::format_ts(v))
+                    .label_formatter(|_, v| format!("{:.8}\n{}", v.y, Data::format_ts(v.x)))
+                    .show(ui, |plot_ui| {
+                        plot_ui.hline(HLine::new(0.0).color(Color32::GRAY));
+                        plot_ui.line(
+                            Line::new(Values::from_values(funding))
+                                .color(match total < 0.0 {
+                                    true => Color32::LIGHT_RED,
+                                    false => Color32::LIGHT_GREEN,
+                                })
+                                .name("cumulative funding"),
+                        );
+                        plot_ui.line(
+                            Line::new(Values::from_values(realized))
+                                .color(Color32::LIGHT_BLUE)
+                                .name("realized pnl"),
+                        );
+                        plot_ui.line(
+                            Line::new(Values::from_values(net))
+                                .color(Color32::GOLD)
+                                .name("pnl with funding"),
+                        );
+                    });
+            });
+        self.visible = visible;
+    }
+    fn name(&self) -> &str {
+        "funding"
+    }
+
+    fn layout(&self) -> WindowLayout {
+        WindowLayout {
+            visible: self.visible,
+            ..Default::default()
+        }
+    }
+
+    fn apply_layout(&mut self, layout: &WindowLayout) {
+        self.visible = layout.visible;
+    }
+}
@@ -1,13 +1,76 @@
-use crossbeam::channel::{Receiver, Sender};
+use crossbeam::channel::{unbounded, Receiver, Sender};
 use egui::{Layout, Ui, Window};
 use egui_extras::{Size, StripBuilder};
+use tracing::error;
 
 use super::window::AppWindow;
-use crate::widgets::{Graph, Symbols};
+use crate::{
+    netstrat::{layout::WindowLayout, shortcuts::Action, status::Status},
+    sources::binance::Symbol,
+    widgets::{Graph, MarketGraphView, PickedNode, Shortcuts, SymbolSwitcher, Symbols},
+};
 
-pub struct SymbolsGraph {
+/// Chart tab with its own graph state.
+struct Tab {
+    /// Keeps plot memory of the tab apart from other tabs.
+    id: usize,
     graph: Graph,
+    symbol_pub: Sender<Symbol>,
+    /// Symbol to chart once symbols are loaded.
+    pending_symbol: Option<String>,
+}
+
+impl Tab {
+    fn new(id: usize) -> Self {
+        let (s, r) = unbounded();
+        Self {
+            id,
+            graph: Graph::new(r),
+            symbol_pub: s,
+            pending_symbol: None,
+        }
+    }
+
+    fn layout(&self) -> WindowLayout {
+        WindowLayout {
+            visible: true,
+            symbol: Some(self.graph.symbol().to_string()).filter(|s| !s.is_empty()),
+            interval: Some(self.graph.interval().as_str().to_string()),
+            indicators: self.graph.indicators(),
+            scale: Some(self.graph.scale()),
+            tabs: vec![],
+            grid: None,
+        }
+    }
+
+    fn apply_layout(&mut self, layout: &WindowLayout) {
+        if layout.symbol.is_some() {
+            self.pending_symbol = layout.symbol.clone();
+        }
+        self.graph.apply_layout(layout);
+    }
+
+    fn title(&self) -> &str {
+        match self.graph.symbol() {
+            "" => "new tab",
+            symbol => symbol,
+        }
+    }
+}
+
+/// Window with chart tabs. Symbols picked in the list or in the switcher go to the active tab.
+/// The chart area can be switched to the correlation network of symbols.
+pub struct SymbolsGraph {
+    tabs: Vec<Tab>,
+    active: usize,
+    next_id: usize,
+    symbol_pub: Sender<Symbol>,
+    symbol_sub: Receiver<Symbol>,
     symbols: Symbols,
+    switcher: SymbolSwitcher,
+    network: MarketGraphView,
+    /// Shows the network instead of the active tab.
+    show_network: bool,
     visible: bool,
 }
 
@@ -19,8 +82,17 @@ impl AppWindow for SymbolsGraph {
     }
 
     fn show(&mut self, ui: &mut Ui) {
+        if !self.switcher.has_symbols() && !self.symbols.symbols().is_empty() {
+            self.switcher.set_symbols(self.symbols.symbols().to_vec());
+        }
+        self.handle_shortcuts(ui);
+        self.switcher.show(ui.ctx());
+        self.route_symbols();
+        self.send_pending();
+
+        let mut visible = self.visible;
         Window::new("graph")
-            .open(&mut self.visible)
+            .open(&mut visible)
             .min_height(500.0)
             .min_width(700.0)
             .show(ui.ctx(), |ui| {
@@ -33,20 +105,202 @@ impl AppWindow for SymbolsGraph {
                                 ui.add(&mut self.symbols);
                             });
                             strip.cell(|ui| {
-                                ui.add(&mut self.graph);
+                                self.tabs_ui(ui);
+                                if self.show_network {
+                                    let charted = self.tabs[self.active].graph.symbol();
+                                    self.network.set_charted(charted);
+                                    if let Some(picked) =
+                                        self.network.ui(ui, self.symbols.symbols())
+                                    {
+                                        self.chart_picked(picked);
+                                    }
+                                    return;
+                                }
+                                let tab = &mut self.tabs[self.active];
+                                ui.push_id(tab.id, |ui| {
+                                    ui.add(&mut tab.graph);
+                                });
+                                let (candles, feed) = tab.graph.feed();
+                                Status::report_chart(candles, feed);
                             });
                         })
                 })
             });
+        self.visible = visible;
+    }
+    fn name(&self) -> &str {
+        "graph"
+    }
+
+    fn layout(&self) -> WindowLayout {
+        WindowLayout {
+            visible: self.visible,
+            tabs: self.tabs.iter().map(Tab::layout).collect(),
+            ..self.tabs[self.active].layout()
+        }
+    }
+
+    /// Opens tabs of the layout. Layouts without tabs are applied to the active tab.
+    fn apply_layout(&mut self, layout: &WindowLayout) {
+        self.visible = layout.visible;
+        if layout.tabs.is_empty() {
+            self.tabs[self.active].apply_layout(layout);
+            return;
+        }
+
+        self.tabs = layout
+            .tabs
+            .iter()
+            .map(|l| {
+                let mut tab = Tab::new(self.next_id);
+                self.next_id += 1;
+                tab.apply_layout(l);
+                tab
+            })
+            .collect();
+        self.active = layout
+            .tabs
+            .iter()
+            .position(|t| t.symbol == layout.symbol && t.interval == layout.interval)
+            .unwrap_or_default();
     }
 }
 
 impl SymbolsGraph {
-    pub fn new(s: Sender<String>, r: Receiver<String>, visible: bool) -> Self {
+    pub fn new(s: Sender<Symbol>, r: Receiver<Symbol>, visible: bool) -> Self {
         Self {
-            graph: Graph::new(r),
-            symbols: Symbols::new(s),
+            tabs: vec![Tab::new(0)],
+            active: 0,
+            next_id: 1,
+            symbol_sub: r,
+            symbols: Symbols::new(s.clone()),
+            switcher: SymbolSwitcher::new(s.clone()),
+            symbol_pub: s,
+            network: MarketGraphView::default(),
+            show_network: false,
             visible: visible,
         }
     }
+
+    /// Sends symbols of restored tabs once symbols are loaded.
+    fn send_pending(&mut self) {
+        let symbols = self.symbols.symbols();
+        if symbols.is_empty() {
+            return;
+        }
+
+        self.tabs.iter_mut().for_each(|tab| {
+            if let Some(name) = tab.pending_symbol.take() {
+                match symbols.iter().find(|s| s.symbol == name) {
+                    Some(symbol) => {
+                        if let Err(err) = tab.symbol_pub.send(symbol.clone()) {
+                            error!("Failed to send symbol to tab: {err}.");
+                        }
+                    }
+                    None => error!("Symbol {name} is not listed."),
+                }
+            }
+        });
+    }
+
+    /// Sends the symbol picked in the network to the symbol channel like symbols picked
+    /// in the list, so it is charted in the active tab.
+    fn chart_picked(&mut self, picked: PickedNode) {
+        let symbol = self
+            .symbols
+            .symbols()
+            .iter()
+            .find(|s| s.symbol == picked.symbol)
+            .cloned();
+        match symbol {
+            Some(symbol) => {
+                if let Err(err) = self.symbol_pub.send(symbol) {
+                    error!("Failed to send picked symbol: {err}.");
+                }
+                if picked.open {
+                    self.show_network = false;
+                }
+            }
+            None => error!("Symbol {} is not listed.", picked.symbol),
+        }
+    }
+
+    /// Opens an empty tab and the symbol switcher to pick its symbol.
+    fn open_tab(&mut self) {
+        self.tabs.push(Tab::new(self.next_id));
+        self.next_id += 1;
+        self.active = self.tabs.len() - 1;
+        self.switcher.open();
+    }
+
+    /// Closes the tab keeping at least one open.
+    fn close_tab(&mut self, i: usize) {
+        if self.tabs.len() == 1 {
+            return;
+        }
+
+        self.tabs.remove(i);
+        if self.active > i || self.active == self.tabs.len() {
+            self.active -= 1;
+        }
+    }
+
+    fn handle_shortcuts(&mut self, ui: &Ui) {
+        if !self.visible {
+            return;
+        }
+
+        let ctx = ui.ctx();
+        if Shortcuts::triggered(ctx, Action::NewTab) {
+            self.open_tab();
+        }
+        if Shortcuts::triggered(ctx, Action::CloseTab) {
+            self.close_tab(self.active);
+        }
+        if Shortcuts::triggered(ctx, Action::NextTab) {
+            self.active = (self.active + 1) % self.tabs.len();
+        }
+        if Shortcuts::triggered(ctx, Action::PrevTab) {
+            self.active = (self.active + self.tabs.len() - 1) % self.tabs.len();
+        }
+    }
+
+    /// Forwards picked symbols to the active tab.
+    fn route_symbols(&mut self) {
+        while let Ok(symbol) = self.symbol_sub.try_recv() {
+            self.symbols.set_charted(&symbol.symbol);
+            if let Err(err) = self.tabs[self.active].symbol_pub.send(symbol) {
+                error!("Failed to send symbol to tab: {err}.");
+            }
+        }
+    }
+
+    fn tabs_ui(&mut self, ui: &mut Ui) {
+        let mut closed = None;
+        ui.horizontal(|ui| {
+            self.tabs.iter().enumerate().for_each(|(i, tab)| {
+                let selected = !self.show_network && i == self.active;
+                if ui.selectable_label(selected, tab.title()).clicked() {
+                    self.active = i;
+                    self.show_network = false;
+                }
+                if self.tabs.len() > 1 && ui.small_button("x").clicked() {
+                    closed = Some(i);
+                }
+            });
+            if ui.button("+").on_hover_text("open chart tab").clicked() {
+                self.open_tab();
+            }
+            if ui
+                .selectable_label(self.show_network, "🕸 network")
+                .on_hover_text("correlation network of symbols")
+                .clicked()
+            {
+                self.show_network = !self.show_network;
+            }
+        });
+        if let Some(i) = closed {
+            self.close_tab(i);
+        }
+    }
 }
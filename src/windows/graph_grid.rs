@@ -0,0 +1,267 @@
+use crossbeam::channel::{unbounded, Sender};
+use egui::{DragValue, TextEdit, Ui, WidgetText, Window};
+use egui_extras::{Size, StripBuilder};
+use poll_promise::Promise;
+use tracing::{error, info};
+
+use super::window::AppWindow;
+use crate::{
+    netstrat::{fuzzy::rank, layout::WindowLayout, toasts::Toasts},
+    sources::binance::{errors::ClientError, Client, Info, Symbol},
+    widgets::Graph,
+};
+
+const MAX_SIDE: usize = 4;
+const MAX_MATCHES: usize = 10;
+
+/// Graph of the grid with its own symbol picker.
+struct GridCell {
+    graph: Graph,
+    symbol_pub: Sender<Symbol>,
+    query: String,
+    /// Symbol of the applied layout sent to the graph once symbols are loaded.
+    pending_symbol: Option<String>,
+}
+
+impl GridCell {
+    fn new() -> Self {
+        let (s, r) = unbounded();
+        Self {
+            graph: Graph::new(r),
+            symbol_pub: s,
+            query: String::new(),
+            pending_symbol: None,
+        }
+    }
+
+    fn layout(&self) -> WindowLayout {
+        WindowLayout {
+            visible: true,
+            symbol: Some(self.graph.symbol().to_string()).filter(|s| !s.is_empty()),
+            interval: Some(self.graph.interval().as_str().to_string()),
+            indicators: self.graph.indicators(),
+            scale: Some(self.graph.scale()),
+            ..Default::default()
+        }
+    }
+
+    fn apply_layout(&mut self, layout: &WindowLayout) {
+        if layout.symbol.is_some() {
+            self.pending_symbol = layout.symbol.clone();
+        }
+        self.graph.apply_layout(layout);
+    }
+
+    fn send_symbol(&mut self, symbol: Symbol) {
+        match self.symbol_pub.send(symbol.clone()) {
+            Ok(_) => info!("Sent symbol to grid cell: {}.", symbol.symbol),
+            Err(err) => error!("Failed to send symbol to grid cell: {err}."),
+        }
+    }
+
+    fn picker_ui(&mut self, ui: &mut Ui, symbols: &[Symbol]) {
+        let title = match self.graph.symbol() {
+            "" => "pick symbol".to_string(),
+            symbol => symbol.to_string(),
+        };
+        ui.menu_button(title, |ui| {
+            ui.add(
+                TextEdit::singleline(&mut self.query)
+                    .hint_text(WidgetText::from("type a symbol").italics()),
+            );
+            if symbols.is_empty() {
+                ui.spinner();
+            }
+
+            let mut picked = None;
+            rank(&self.query, symbols, |s| s.symbol.as_str())
+                .into_iter()
+                .take(MAX_MATCHES)
+                .for_each(|s| {
+                    if ui.button(s.symbol.as_str()).clicked() {
+                        picked = Some(s.clone());
+                    }
+                });
+            if let Some(symbol) = picked {
+                self.send_symbol(symbol);
+                ui.close_menu();
+            }
+        });
+    }
+}
+
+/// Window splitting its area into a grid of independent graphs
+/// each with its own symbol and interval.
+pub struct GraphGrid {
+    visible: bool,
+    rows: usize,
+    cols: usize,
+    cells: Vec<GridCell>,
+    symbols: Vec<Symbol>,
+    symbols_promise: Option<Promise<Result<Info, ClientError>>>,
+    /// Error of the last symbols download, offered to retry.
+    symbols_error: Option<String>,
+}
+
+impl GraphGrid {
+    pub fn new(visible: bool) -> Self {
+        Self {
+            visible,
+            rows: 2,
+            cols: 2,
+            cells: vec![],
+            symbols: vec![],
+            symbols_promise: None,
+            symbols_error: None,
+        }
+    }
+
+    /// Creates missing cells keeping graphs of the existing ones.
+    fn resize(&mut self) {
+        let count = self.rows * self.cols;
+        self.cells.truncate(count);
+        while self.cells.len() < count {
+            self.cells.push(GridCell::new());
+        }
+    }
+
+    fn load_symbols(&mut self) {
+        if self.symbols_promise.is_none() && self.symbols.is_empty() && self.symbols_error.is_none()
+        {
+            info!("Loading symbols for graph grid.");
+            self.symbols_promise = Some(Promise::spawn_async(async { Client::info().await }));
+        }
+
+        if let Some(res) = self.symbols_promise.as_ref().and_then(|p| p.ready()) {
+            match res {
+                Ok(info) => self.symbols = info.symbols.clone(),
+                Err(err) => {
+                    error!("Failed to load symbols for graph grid: {err}.");
+                    Toasts::error("Failed to load symbols", err);
+                    self.symbols_error = Some(err.to_string());
+                }
+            }
+            self.symbols_promise = None;
+        }
+    }
+
+    /// Sends symbols of the applied layout to cells once symbols are loaded.
+    fn send_pending(&mut self) {
+        if self.symbols.is_empty() {
+            return;
+        }
+
+        let symbols = &self.symbols;
+        self.cells.iter_mut().for_each(|cell| {
+            if let Some(name) = cell.pending_symbol.take() {
+                match symbols.iter().find(|s| s.symbol == name) {
+                    Some(symbol) => cell.send_symbol(symbol.clone()),
+                    None => error!("Symbol {name} is not listed."),
+                }
+            }
+        });
+    }
+
+    fn grid_ui(&mut self, ui: &mut Ui) {
+        let (rows, cols) = (self.rows, self.cols);
+        let symbols = &self.symbols;
+        let mut cells = self.cells.iter_mut().enumerate();
+
+        StripBuilder::new(ui)
+            .sizes(Size::remainder(), rows)
+            .vertical(|mut strip| {
+                (0..rows).for_each(|_| {
+                    strip.strip(|builder| {
+                        builder
+                            .sizes(Size::remainder(), cols)
+                            .horizontal(|mut strip| {
+                                cells.by_ref().take(cols).for_each(|(i, cell)| {
+                                    strip.cell(|ui| {
+                                        ui.push_id(i, |ui| {
+                                            cell.picker_ui(ui, symbols);
+                                            ui.add(&mut cell.graph);
+                                        });
+                                    });
+                                });
+                            });
+                    });
+                });
+            });
+    }
+}
+
+impl AppWindow for GraphGrid {
+    fn toggle_btn(&mut self, ui: &mut Ui) {
+        if ui.button("grid").clicked() {
+            self.visible = !self.visible
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        if !self.visible {
+            return;
+        }
+
+        self.load_symbols();
+        self.resize();
+        self.send_pending();
+
+        let mut visible = self.visible;
+        Window::new("grid")
+            .open(&mut visible)
+            .min_height(500.0)
+            .min_width(700.0)
+            .show(ui.ctx(), |ui| {
+                ui.horizontal(|ui| {
+                    ui.add(DragValue::new(&mut self.rows).clamp_range(1..=MAX_SIDE));
+                    ui.label("rows");
+                    ui.add(DragValue::new(&mut self.cols).clamp_range(1..=MAX_SIDE));
+                    ui.label("columns");
+                    if let Some(err) = &self.symbols_error {
+                        ui.label(format!("Failed to load symbols: {err}"));
+                        if ui.button("retry").clicked() {
+                            self.symbols_error = None;
+                        }
+                    }
+                });
+                self.resize();
+                ui.separator();
+                self.grid_ui(ui);
+            });
+        self.visible = visible;
+    }
+
+    fn name(&self) -> &str {
+        "grid"
+    }
+
+    fn layout(&self) -> WindowLayout {
+        let first = self.cells.first().map(GridCell::layout).unwrap_or_default();
+        WindowLayout {
+            visible: self.visible,
+            tabs: self.cells.iter().map(GridCell::layout).collect(),
+            grid: Some([self.rows, self.cols]),
+            ..first
+        }
+    }
+
+    /// Splits the grid into charts of the layout. Layouts without charts are applied
+    /// to every cell.
+    fn apply_layout(&mut self, layout: &WindowLayout) {
+        self.visible = layout.visible;
+        if let Some([rows, cols]) = layout.grid {
+            self.rows = rows.clamp(1, MAX_SIDE);
+            self.cols = cols.clamp(1, MAX_SIDE);
+        }
+        self.resize();
+
+        match layout.tabs.is_empty() {
+            true => self.cells.iter_mut().for_each(|c| c.apply_layout(layout)),
+            false => self
+                .cells
+                .iter_mut()
+                .zip(&layout.tabs)
+                .for_each(|(c, l)| c.apply_layout(l)),
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use std::path::PathBuf;
+
+use egui::{ComboBox, DragValue, Grid, ScrollArea, TextEdit, Ui, Window};
+use tracing::{error, info};
+
+use super::AppWindow;
+use crate::{
+    netstrat::{
+        archive::Archive,
+        import::{Field, Mapping, Preview, TimeUnit},
+        layout::WindowLayout,
+    },
+    sources::binance::Interval,
+};
+
+const PREVIEW_ROWS: usize = 5;
+
+/// Wizard importing klines from csv files of other tools into the archive.
+/// Columns are mapped to kline fields by hand, guessed from headers on preview.
+pub struct CsvImport {
+    visible: bool,
+    path: String,
+    symbol: String,
+    interval: Interval,
+    mapping: Mapping,
+    preview: Option<Preview>,
+    archive: Archive,
+    status: Option<String>,
+}
+
+impl CsvImport {
+    pub fn new(visible: bool) -> Self {
+        Self {
+            visible,
+            path: String::new(),
+            symbol: String::new(),
+            interval: Interval::Minute,
+            mapping: Mapping::default(),
+            preview: None,
+            archive: Archive::default(),
+            status: None,
+        }
+    }
+
+    fn read_preview(&mut self) {
+        match Preview::read(
+            &PathBuf::from(&self.path),
+            self.mapping.has_headers,
+            PREVIEW_ROWS,
+        ) {
+            Ok(preview) => {
+                self.mapping.guess(&preview.headers);
+                self.preview = Some(preview);
+                self.status = None;
+            }
+            Err(err) => {
+                error!("Failed to read csv preview: {err}.");
+                self.preview = None;
+                self.status = Some(err.to_string());
+            }
+        }
+    }
+
+    fn import(&mut self) {
+        let symbol = self.symbol.to_uppercase();
+        let res = self
+            .mapping
+            .import(&PathBuf::from(&self.path), self.interval)
+            .map_err(|err| err.to_string())
+            .and_then(|klines| {
+                self.archive
+                    .store(&symbol, self.interval, &klines)
+                    .map(|_| klines.len())
+                    .map_err(|err| err.to_string())
+            });
+
+        self.status = Some(match res {
+            Ok(count) => {
+                info!("Imported {count} klines of {symbol} from {}.", self.path);
+                format!(
+                    "imported {count} klines into {symbol} {}",
+                    self.interval.as_str()
+                )
+            }
+            Err(err) => {
+                error!("Failed to import csv: {err}.");
+                err
+            }
+        });
+    }
+
+    fn mapping_ui(&mut self, ui: &mut Ui) {
+        let headers = match &self.preview {
+            Some(preview) => preview.headers.clone(),
+            None => return,
+        };
+
+        Grid::new("import mapping").num_columns(2).show(ui, |ui| {
+            Field::all().into_iter().for_each(|f| {
+                ui.label(f.as_str());
+                let mut col = self.mapping.columns.get(&f).copied();
+                ComboBox::from_id_source(format!("import column {}", f.as_str()))
+                    .selected_text(match col {
+                        Some(i) => headers.get(i).map(|h| h.as_str()).unwrap_or_default(),
+                        None => "none",
+                    })
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut col, None, "none");
+                        headers.iter().enumerate().for_each(|(i, h)| {
+                            ui.selectable_value(&mut col, Some(i), h);
+                        });
+                    });
+                match col {
+                    Some(i) => self.mapping.columns.insert(f, i),
+                    None => self.mapping.columns.remove(&f),
+                };
+                ui.end_row();
+            });
+
+            ui.label("timestamp");
+            ui.horizontal(|ui| {
+                ComboBox::from_id_source("import time unit")
+                    .selected_text(self.mapping.unit.as_str())
+                    .show_ui(ui, |ui| {
+                        TimeUnit::all().into_iter().for_each(|u| {
+                            let selected = u.as_str() == self.mapping.unit.as_str();
+                            if ui.selectable_label(selected, u.as_str()).clicked() && !selected {
+                                self.mapping.unit = u;
+                            }
+                        });
+                    });
+                if let TimeUnit::Text(format) = &mut self.mapping.unit {
+                    ui.add(TextEdit::singleline(format).desired_width(150.0))
+                        .on_hover_text("chrono format, e.g. %Y-%m-%d %H:%M:%S");
+                }
+            });
+            ui.end_row();
+
+            ui.label("timezone");
+            ui.add_enabled_ui(matches!(self.mapping.unit, TimeUnit::Text(_)), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("UTC");
+                    ui.add(
+                        DragValue::new(&mut self.mapping.utc_offset_hours)
+                            .clamp_range(-12..=14)
+                            .suffix("h"),
+                    );
+                })
+                .response
+                .on_hover_text("numeric timestamps are always UTC");
+            });
+            ui.end_row();
+        });
+    }
+
+    fn preview_ui(&self, ui: &mut Ui) {
+        let preview = match &self.preview {
+            Some(preview) => preview,
+            None => return,
+        };
+
+        ScrollArea::horizontal().show(ui, |ui| {
+            Grid::new("import preview")
+                .striped(true)
+                .num_columns(preview.headers.len())
+                .show(ui, |ui| {
+                    preview.headers.iter().enumerate().for_each(|(i, h)| {
+                        match self.mapping.columns.iter().find(|(_, c)| **c == i) {
+                            Some((f, _)) => ui.strong(format!("{h} → {}", f.as_str())),
+                            None => ui.label(h),
+                        };
+                    });
+                    ui.end_row();
+
+                    preview.rows.iter().for_each(|row| {
+                        row.iter().for_each(|v| {
+                            ui.label(v);
+                        });
+                        ui.end_row();
+                    });
+                });
+        });
+    }
+}
+
+impl AppWindow for CsvImport {
+    fn toggle_btn(&mut self, ui: &mut Ui) {
+        if ui.button("import").clicked() {
+            self.visible = !self.visible
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        if !self.visible {
+            return;
+        }
+
+        let dropped = ui.ctx().input().raw.dropped_files.first().cloned();
+        if let Some(path) = dropped.and_then(|f| f.path) {
+            self.path = path.to_string_lossy().to_string();
+            self.read_preview();
+        }
+
+        let mut visible = self.visible;
+        Window::new("import csv")
+            .open(&mut visible)
+            .show(ui.ctx(), |ui| {
+                ui.horizontal(|ui| {
+                    ui.add(
+                        TextEdit::singleline(&mut self.path)
+                            .hint_text("path to csv or drop a file"),
+                    );
+                    if ui
+                        .checkbox(&mut self.mapping.has_headers, "headers")
+                        .changed()
+                        | ui.button("preview").clicked()
+                    {
+                        self.read_preview();
+                    }
+                });
+
+                self.preview_ui(ui);
+                self.mapping_ui(ui);
+
+                ui.horizontal(|ui| {
+                    ui.add(
+                        TextEdit::singleline(&mut self.symbol)
+                            .hint_text("symbol")
+                            .desired_width(100.0),
+                    );
+                    ComboBox::from_id_source("import interval")
+                        .selected_text(self.interval.as_str())
+                        .show_ui(ui, |ui| {
+                            Interval::ALL.into_iter().for_each(|i| {
+                                ui.selectable_value(&mut self.interval, i, i.as_str());
+                            });
+                        });
+                    if ui
+                        .add_enabled(
+                            self.preview.is_some() && !self.symbol.is_empty(),
+                            egui::Button::new("import"),
+                        )
+                        .clicked()
+                    {
+                        self.import();
+                    }
+                });
+
+                if let Some(status) = &self.status {
+                    ui.label(status);
+                }
+            });
+        self.visible = visible;
+    }
+
+    fn name(&self) -> &str {
+        "import"
+    }
+
+    fn layout(&self) -> WindowLayout {
+        WindowLayout {
+            visible: self.visible,
+            ..Default::default()
+        }
+    }
+
+    fn apply_layout(&mut self, layout: &WindowLayout) {
+        self.visible = layout.visible;
+    }
+}
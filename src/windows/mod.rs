@@ -1,7 +1,37 @@
+mod backtest;
+mod cache;
+mod correlation;
+mod debug;
+mod dom;
+mod funding;
 mod graph;
+mod graph_grid;
+mod import;
+mod network_simulation;
+mod portfolio;
+mod screener;
+mod settings;
+mod tape;
 mod time_range_chooser;
+mod trading;
+mod update;
 mod window;
 
+pub use self::backtest::BacktestWindow;
+pub use self::cache::CacheManager;
+pub use self::correlation::CorrelationMatrix;
+pub use self::debug::DebugWindow;
+pub use self::dom::DepthOfMarket;
+pub use self::funding::FundingHistory;
 pub use self::graph::SymbolsGraph;
+pub use self::graph_grid::GraphGrid;
+pub use self::import::CsvImport;
+pub use self::network_simulation::NetworkSimulation;
+pub use self::portfolio::PaperTrading;
+pub use self::screener::Screener;
+pub use self::settings::SettingsWindow;
+pub use self::tape::TimeAndSales;
 pub use self::time_range_chooser::TimeRangeChooser;
+pub use self::trading::LiveTrading;
+pub use self::update::UpdateNotice;
 pub use self::window::AppWindow;
@@ -0,0 +1,85 @@
+use egui::{DragValue, Slider, Ui, Window};
+use tracing::info;
+
+use super::AppWindow;
+use crate::{netstrat::layout::WindowLayout, network::simulation::Simulation};
+
+/// Developer window controlling simulated network latency and failures.
+pub struct NetworkSimulation {
+    visible: bool,
+    simulation: Simulation,
+}
+
+impl NetworkSimulation {
+    pub fn new(visible: bool) -> Self {
+        Self {
+            visible,
+            simulation: Simulation::current(),
+        }
+    }
+}
+
+impl AppWindow for NetworkSimulation {
+    fn toggle_btn(&mut self, ui: &mut Ui) {
+        if ui.button("network").clicked() {
+            self.visible = !self.visible
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        let sim = &mut self.simulation;
+        let mut visible = self.visible;
+        let mut changed = false;
+        Window::new("network simulation")
+            .open(&mut visible)
+            .show(ui.ctx(), |ui| {
+                changed |= ui
+                    .checkbox(&mut sim.enabled, "simulate bad network")
+                    .changed();
+                ui.add_enabled_ui(sim.enabled, |ui| {
+                    ui.horizontal(|ui| {
+                        changed |= ui
+                            .add(DragValue::new(&mut sim.latency_ms).clamp_range(0..=30_000))
+                            .changed();
+                        ui.label("latency ms");
+                    });
+                    ui.horizontal(|ui| {
+                        changed |= ui
+                            .add(DragValue::new(&mut sim.jitter_ms).clamp_range(0..=30_000))
+                            .changed();
+                        ui.label("jitter ms");
+                    });
+                    changed |= ui
+                        .add(Slider::new(&mut sim.error_rate, 0.0..=1.0).text("error rate"))
+                        .changed();
+                    changed |= ui
+                        .add(
+                            Slider::new(&mut sim.disconnect_rate, 0.0..=1.0)
+                                .text("disconnect rate"),
+                        )
+                        .changed();
+                });
+            });
+        self.visible = visible;
+
+        if changed {
+            info!("Applying network simulation: {:?}.", self.simulation);
+            self.simulation.apply();
+        }
+    }
+
+    fn name(&self) -> &str {
+        "network"
+    }
+
+    fn layout(&self) -> WindowLayout {
+        WindowLayout {
+            visible: self.visible,
+            ..Default::default()
+        }
+    }
+
+    fn apply_layout(&mut self, layout: &WindowLayout) {
+        self.visible = layout.visible;
+    }
+}
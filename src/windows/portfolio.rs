@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use egui::{ComboBox, DragValue, Grid, ScrollArea, TextEdit, Ui, Window};
+use poll_promise::Promise;
+use tracing::{error, info};
+
+use super::AppWindow;
+use crate::{
+    netstrat::{
+        data::Data,
+        layout::WindowLayout,
+        paper::{OrderKind, Portfolio},
+        replay::Side,
+        theme::ChartColors,
+        toasts::Toasts,
+    },
+    sources::binance::{errors::ClientError, Client, Interval, Kline},
+    widgets::PnlChart,
+};
+
+/// Seconds between kline requests of a traded symbol.
+const POLL_SECS: f64 = 5.0;
+/// Minutes of klines requested, so limit orders see every kline since the last request.
+const FEED_MINUTES: i64 = 3;
+
+/// Paper trading window with an order ticket and the simulated portfolio.
+///
+/// Latest minute klines of symbols with open orders or positions are polled while the
+/// app runs, even with the window closed, and fed to the portfolio to fill orders.
+pub struct PaperTrading {
+    visible: bool,
+    portfolio: Portfolio,
+    symbol: String,
+    side: Side,
+    limit: bool,
+    qty: f64,
+    price: f64,
+    /// Time of the last kline request by symbol.
+    polled: HashMap<String, f64>,
+    feeds: HashMap<String, Promise<Result<Vec<Kline>, ClientError>>>,
+    pnl: PnlChart,
+}
+
+impl PaperTrading {
+    pub fn new(visible: bool) -> Self {
+        Self {
+            visible,
+            portfolio: Portfolio::default(),
+            symbol: "BTCUSDT".to_string(),
+            side: Side::Buy,
+            limit: false,
+            qty: 0.01,
+            price: 0.0,
+            polled: HashMap::new(),
+            feeds: HashMap::new(),
+            pnl: PnlChart::new("paper P&L"),
+        }
+    }
+
+    fn place(&mut self, symbol: String, side: Side, qty: f64, kind: OrderKind) {
+        let now = Utc::now().timestamp_millis();
+        match self.portfolio.place(&symbol, side, qty, kind, now) {
+            Ok(id) => {
+                info!("Placed paper order {id}: {side:?} {qty} {symbol} {kind:?}.");
+                // request klines right away, so market orders fill without waiting
+                self.polled.remove(&symbol);
+            }
+            Err(err) => {
+                error!("Failed to place paper order: {err}.");
+                Toasts::error("Failed to place paper order", err);
+            }
+        }
+    }
+
+    /// Feeds arrived klines to the portfolio and requests klines of symbols due.
+    fn poll_feeds(&mut self, time: f64) {
+        let arrived: Vec<String> = self
+            .feeds
+            .iter()
+            .filter(|(_, p)| p.ready().is_some())
+            .map(|(s, _)| s.clone())
+            .collect();
+        arrived.into_iter().for_each(|symbol| {
+            let res = match self.feeds.remove(&symbol).map(|p| p.block_and_take()) {
+                Some(res) => res,
+                None => return,
+            };
+            match res {
+                Ok(klines) => self
+                    .portfolio
+                    .update(&symbol, &klines)
+                    .into_iter()
+                    .for_each(|f| {
+                        info!("Paper order {} filled: {f:?}.", f.order);
+                        Toasts::info(format!(
+                            "Paper {:?} {} {} filled at {}",
+                            f.side, f.qty, f.symbol, f.price
+                        ));
+                    }),
+                Err(err) => error!("Failed to load paper trading klines of {symbol}: {err}."),
+            }
+        });
+
+        self.portfolio.symbols().into_iter().for_each(|symbol| {
+            if self.feeds.contains_key(&symbol) {
+                return;
+            }
+            let due = self
+                .polled
+                .get(&symbol)
+                .is_none_or(|polled| time - polled >= POLL_SECS);
+            if !due {
+                return;
+            }
+
+            self.polled.insert(symbol.clone(), time);
+            let end = Utc::now().timestamp_millis();
+            let start = end - FEED_MINUTES * Interval::Minute.millis();
+            let request = symbol.clone();
+            self.feeds.insert(
+                symbol,
+                Promise::spawn_async(async move {
+                    Client::kline_range(request, Interval::Minute, start, end, 10).await
+                }),
+            );
+        });
+    }
+
+    fn ticket_ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.add(TextEdit::singleline(&mut self.symbol).desired_width(100.0));
+            ui.selectable_value(&mut self.side, Side::Buy, "buy");
+            ui.selectable_value(&mut self.side, Side::Sell, "sell");
+            ComboBox::from_id_source("paper order kind")
+                .selected_text(match self.limit {
+                    true => "limit",
+                    false => "market",
+                })
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.limit, false, "market");
+                    ui.selectable_value(&mut self.limit, true, "limit");
+                });
+        });
+        ui.horizontal(|ui| {
+            ui.add(
+                DragValue::new(&mut self.qty)
+                    .clamp_range(0.0..=f64::MAX)
+                    .speed(0.001),
+            );
+            ui.label("quantity");
+            if self.limit {
+                ui.add(
+                    DragValue::new(&mut self.price)
+                        .clamp_range(0.0..=f64::MAX)
+                        .speed(0.01),
+                );
+                ui.label("price");
+            }
+            if ui.button("place").clicked() {
+                let kind = match self.limit {
+                    true => OrderKind::Limit(self.price),
+                    false => OrderKind::Market,
+                };
+                self.place(self.symbol.to_uppercase(), self.side, self.qty, kind);
+            }
+        });
+    }
+
+    fn portfolio_ui(&mut self, ui: &mut Ui) {
+        let colors = ChartColors::current();
+        let portfolio = &self.portfolio;
+        Grid::new("paper summary").num_columns(2).show(ui, |ui| {
+            ui.label("equity");
+            ui.label(format!("{:.2}", portfolio.equity()));
+            ui.end_row();
+            ui.label("realized");
+            ui.colored_label(
+                colors.kline(portfolio.realized() < 0.0),
+                format!("{:+.2}", portfolio.realized()),
+            );
+            ui.end_row();
+            ui.label("unrealized");
+            ui.colored_label(
+                colors.kline(portfolio.unrealized() < 0.0),
+                format!("{:+.2}", portfolio.unrealized()),
+            );
+            ui.end_row();
+            ui.label("fees");
+            ui.label(format!("{:.2}", portfolio.fees));
+            ui.end_row();
+        });
+
+        ui.separator();
+        ui.strong("positions");
+        let mut close = None;
+        Grid::new("paper positions").striped(true).show(ui, |ui| {
+            [
+                "symbol",
+                "qty",
+                "entry",
+                "last",
+                "unrealized",
+                "realized",
+                "",
+            ]
+            .into_iter()
+            .for_each(|h| {
+                ui.strong(h);
+            });
+            ui.end_row();
+            portfolio.positions.iter().for_each(|(symbol, p)| {
+                ui.label(symbol);
+                ui.label(p.qty.to_string());
+                ui.label(format!("{:.2}", p.entry));
+                ui.label(p.last.map_or("-".to_string(), |l| format!("{l:.2}")));
+                ui.colored_label(
+                    colors.kline(p.unrealized() < 0.0),
+                    format!("{:+.2}", p.unrealized()),
+                );
+                ui.label(format!("{:+.2}", p.realized));
+                if ui
+                    .add_enabled(p.qty != 0.0, egui::Button::new("close"))
+                    .clicked()
+                {
+                    close = Some((symbol.clone(), p.qty));
+                }
+                ui.end_row();
+            });
+        });
+        if let Some((symbol, qty)) = close {
+            let side = match qty > 0.0 {
+                true => Side::Sell,
+                false => Side::Buy,
+            };
+            self.place(symbol, side, qty.abs(), OrderKind::Market);
+        }
+
+        ui.separator();
+        ui.strong("open orders");
+        let mut cancel = None;
+        Grid::new("paper orders").striped(true).show(ui, |ui| {
+            self.portfolio.orders.iter().for_each(|o| {
+                ui.label(o.id.to_string());
+                ui.label(&o.symbol);
+                ui.label(format!("{:?}", o.side));
+                ui.label(o.qty.to_string());
+                ui.label(match o.kind {
+                    OrderKind::Market => "market".to_string(),
+                    OrderKind::Limit(price) => format!("limit {price}"),
+                });
+                if ui.button("cancel").clicked() {
+                    cancel = Some(o.id);
+                }
+                ui.end_row();
+            });
+        });
+        if let Some(id) = cancel {
+            match self.portfolio.cancel(id) {
+                Ok(order) => info!("Cancelled paper order {order:?}."),
+                Err(err) => error!("Failed to cancel paper order: {err}."),
+            }
+        }
+
+        ui.separator();
+        ui.strong("fills");
+        ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
+            Grid::new("paper fills").striped(true).show(ui, |ui| {
+                self.portfolio.fills.iter().rev().for_each(|f| {
+                    ui.label(Data::format_ts(f.time as f64));
+                    ui.label(&f.symbol);
+                    ui.label(format!("{:?}", f.side));
+                    ui.label(f.qty.to_string());
+                    ui.label(format!("{:.2}", f.price));
+                    ui.label(format!("fee {:.4}", f.fee));
+                    ui.end_row();
+                });
+            });
+        });
+    }
+}
+
+impl AppWindow for PaperTrading {
+    fn toggle_btn(&mut self, ui: &mut Ui) {
+        if ui.button("paper trading").clicked() {
+            self.visible = !self.visible
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        self.poll_feeds(ui.input().time);
+
+        let mut visible = self.visible;
+        Window::new("paper trading")
+            .open(&mut visible)
+            .min_width(400.0)
+            .show(ui.ctx(), |ui| {
+                self.ticket_ui(ui);
+                ui.separator();
+                self.pnl.toggle_btn(ui);
+                self.portfolio_ui(ui);
+            });
+        self.visible = visible;
+        if self.visible {
+            self.pnl.show(ui.ctx(), &self.portfolio.history);
+        }
+    }
+
+    fn name(&self) -> &str {
+        "paper trading"
+    }
+
+    fn layout(&self) -> WindowLayout {
+        WindowLayout {
+            visible: self.visible,
+            ..Default::default()
+        }
+    }
+
+    fn apply_layout(&mut self, layout: &WindowLayout) {
+        self.visible = layout.visible;
+    }
+}
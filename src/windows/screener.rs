@@ -0,0 +1,260 @@
+use crossbeam::channel::Sender;
+use egui::{ComboBox, DragValue, Grid, ProgressBar, ScrollArea, TextEdit, Ui, Window};
+use poll_promise::Promise;
+use tracing::{error, info};
+
+use super::AppWindow;
+use crate::{
+    netstrat::{
+        export::analytics::matches_table,
+        layout::WindowLayout,
+        screener::{Conditions, Market, Scan, RSI_PERIOD, SPIKE_LOOKBACK},
+        theme::ChartColors,
+        toasts::Toasts,
+    },
+    sources::binance::{Client, Interval, Symbol},
+    widgets::ExportButton,
+};
+
+/// Window scanning listed symbols against screener conditions in the background.
+/// Matched symbols are charted in the main chart when clicked.
+pub struct Screener {
+    visible: bool,
+    symbol_pub: Sender<Symbol>,
+    quote: String,
+    /// Conditions toggled on with their values kept while toggled off.
+    change: (bool, f64),
+    rsi: (bool, f64),
+    spike: (bool, f64),
+    interval: Interval,
+    market: Option<Promise<Market>>,
+    scan: Option<Scan>,
+}
+
+impl Screener {
+    pub fn new(symbol_pub: Sender<Symbol>, visible: bool) -> Self {
+        Self {
+            visible,
+            symbol_pub,
+            quote: "USDT".to_string(),
+            change: (true, 5.0),
+            rsi: (false, 30.0),
+            spike: (false, 3.0),
+            interval: Interval::FourHours,
+            market: None,
+            scan: None,
+        }
+    }
+
+    fn conditions(&self) -> Conditions {
+        let enabled = |(on, v): (bool, f64)| on.then_some(v);
+        Conditions {
+            quote: self.quote.trim().to_uppercase(),
+            min_change: enabled(self.change),
+            max_rsi: enabled(self.rsi),
+            volume_spike: enabled(self.spike),
+            interval: self.interval,
+        }
+    }
+
+    /// Loads symbols and tickers, the scan starts once both arrive.
+    fn start(&mut self) {
+        info!("Loading symbols and tickers for screening.");
+
+        self.scan = None;
+        self.market = Some(Promise::spawn_async(async {
+            (Client::info().await, Client::tickers().await)
+        }));
+    }
+
+    fn poll(&mut self) {
+        if self.market.as_ref().and_then(|p| p.ready()).is_some() {
+            let market = self.market.take().map(|p| p.block_and_take());
+            match market.map(|m| Scan::from_market(self.conditions(), m)) {
+                Some(Ok(scan)) => self.scan = Some(scan),
+                Some(Err(err)) => {
+                    error!("Failed to load symbols for screening: {err}.");
+                    Toasts::error("Failed to load symbols", err);
+                }
+                None => {}
+            }
+        }
+
+        if let Some(scan) = &mut self.scan {
+            let running = !scan.finished();
+            scan.poll();
+            if running && scan.finished() {
+                info!(
+                    "Screened {} symbols: {} matches, {} failed.",
+                    scan.total,
+                    scan.matches.len(),
+                    scan.failed
+                );
+                Toasts::info(format!("Screener found {} symbols", scan.matches.len()));
+            }
+        }
+    }
+
+    fn conditions_ui(&mut self, ui: &mut Ui) {
+        Grid::new("screener conditions")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("quote");
+                ui.add(TextEdit::singleline(&mut self.quote).desired_width(60.0))
+                    .on_hover_text("all symbols if empty");
+                ui.end_row();
+
+                ui.checkbox(&mut self.change.0, "24h change ≥");
+                ui.horizontal(|ui| {
+                    ui.add(DragValue::new(&mut self.change.1).speed(0.1));
+                    ui.label("%");
+                });
+                ui.end_row();
+
+                ui.checkbox(&mut self.rsi.0, format!("RSI({RSI_PERIOD}) ≤"));
+                ui.add(DragValue::new(&mut self.rsi.1).clamp_range(0.0..=100.0));
+                ui.end_row();
+
+                ui.checkbox(&mut self.spike.0, "volume spike ≥")
+                    .on_hover_text(format!(
+                        "last volume over the average of the previous {SPIKE_LOOKBACK} candles"
+                    ));
+                ui.horizontal(|ui| {
+                    ui.add(
+                        DragValue::new(&mut self.spike.1)
+                            .clamp_range(1.0..=100.0)
+                            .speed(0.1),
+                    );
+                    ui.label("×");
+                });
+                ui.end_row();
+
+                ui.label("candles");
+                ComboBox::from_id_source("screener interval")
+                    .selected_text(self.interval.as_str())
+                    .show_ui(ui, |ui| {
+                        Interval::ALL.into_iter().for_each(|i| {
+                            ui.selectable_value(&mut self.interval, i, i.as_str());
+                        });
+                    });
+                ui.end_row();
+            });
+    }
+
+    fn matches_ui(&mut self, ui: &mut Ui) {
+        let scan = match &self.scan {
+            Some(scan) => scan,
+            None => return,
+        };
+
+        if !scan.finished() {
+            ui.ctx().request_repaint();
+            let progress = match scan.total {
+                0 => 1.0,
+                total => scan.done() as f32 / total as f32,
+            };
+            ui.add(
+                ProgressBar::new(progress)
+                    .text(format!("{}/{} symbols", scan.done(), scan.total))
+                    .animate(true),
+            );
+        } else {
+            ui.horizontal(|ui| {
+                ui.add(ExportButton::new("screener", || {
+                    matches_table(&scan.matches)
+                }));
+                if scan.failed > 0 {
+                    ui.label(format!("{} symbols failed to load", scan.failed));
+                }
+            });
+        }
+
+        let colors = ChartColors::current();
+        let value = |v: Option<f64>| v.map_or("-".to_string(), |v| format!("{v:.1}"));
+        let mut picked = None;
+        ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+            Grid::new("screener matches").striped(true).show(ui, |ui| {
+                ["symbol", "24h change", "RSI", "volume spike"]
+                    .into_iter()
+                    .for_each(|h| {
+                        ui.strong(h);
+                    });
+                ui.end_row();
+                scan.matches.iter().for_each(|m| {
+                    if ui
+                        .link(&m.symbol.symbol)
+                        .on_hover_text("chart the symbol")
+                        .clicked()
+                    {
+                        picked = Some(m.symbol.clone());
+                    }
+                    ui.colored_label(colors.kline(m.change < 0.0), format!("{:+.2}%", m.change));
+                    ui.label(value(m.rsi));
+                    ui.label(value(m.spike));
+                    ui.end_row();
+                });
+            });
+        });
+
+        if let Some(symbol) = picked {
+            match self.symbol_pub.send(symbol.clone()) {
+                Ok(_) => info!("Charting screened symbol {}.", symbol.symbol),
+                Err(err) => error!("Failed to send screened symbol: {err}."),
+            }
+        }
+    }
+}
+
+impl AppWindow for Screener {
+    fn toggle_btn(&mut self, ui: &mut Ui) {
+        if ui.button("screener").clicked() {
+            self.visible = !self.visible
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        self.poll();
+
+        let mut visible = self.visible;
+        Window::new("screener")
+            .open(&mut visible)
+            .min_width(320.0)
+            .show(ui.ctx(), |ui| {
+                self.conditions_ui(ui);
+
+                ui.horizontal(|ui| {
+                    let scanning =
+                        self.market.is_some() || self.scan.as_ref().is_some_and(|s| !s.finished());
+                    if scanning {
+                        if ui.button("stop").clicked() {
+                            info!("Screening stopped.");
+                            self.market = None;
+                            self.scan = None;
+                        }
+                        ui.spinner();
+                    } else if ui.button("scan").clicked() {
+                        self.start();
+                    }
+                });
+                ui.separator();
+
+                self.matches_ui(ui);
+            });
+        self.visible = visible;
+    }
+
+    fn name(&self) -> &str {
+        "screener"
+    }
+
+    fn layout(&self) -> WindowLayout {
+        WindowLayout {
+            visible: self.visible,
+            ..Default::default()
+        }
+    }
+
+    fn apply_layout(&mut self, layout: &WindowLayout) {
+        self.visible = layout.visible;
+    }
+}
@@ -0,0 +1,264 @@
+use egui::{CollapsingHeader, Color32, ComboBox, Context, Grid, Slider, TextEdit, Ui, Window};
+use tracing::{error, info};
+
+use super::AppWindow;
+use crate::netstrat::{
+    export::template::{ExportTemplate, TemplateColumn, TimeFormat},
+    layout::WindowLayout,
+    settings::Settings,
+};
+
+/// Edit of a template column requested in the editor.
+enum ColumnEdit {
+    MoveUp(usize),
+    Remove(usize),
+    Add(&'static str),
+}
+
+/// Draws the template editor. Returns true if the template changed.
+fn template_ui(ui: &mut Ui, template: &mut ExportTemplate) -> bool {
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        ui.label("name");
+        changed |= ui.text_edit_singleline(&mut template.name).changed();
+    });
+    ui.horizontal(|ui| {
+        ui.label("times");
+        // combo box ids are global, so they are made unique per template
+        ComboBox::from_id_source(ui.id().with("times"))
+            .selected_text(template.time_format.as_str())
+            .show_ui(ui, |ui| {
+                TimeFormat::all().into_iter().for_each(|f| {
+                    changed |= ui
+                        .selectable_value(&mut template.time_format, f, f.as_str())
+                        .changed();
+                });
+            })
+            .response
+            .on_hover_text("parquet and xlsx keep typed dates");
+    });
+
+    let mut edit = None;
+    let last = template.columns.len().saturating_sub(1);
+    Grid::new("template columns").show(ui, |ui| {
+        template.columns.iter_mut().enumerate().for_each(|(i, c)| {
+            ui.label(&c.field);
+            changed |= ui
+                .add(
+                    TextEdit::singleline(&mut c.header)
+                        .hint_text("default header")
+                        .desired_width(120.0),
+                )
+                .changed();
+            if ui.add_enabled(i > 0, egui::Button::new("⏶")).clicked() {
+                edit = Some(ColumnEdit::MoveUp(i));
+            }
+            if ui.add_enabled(i < last, egui::Button::new("⏷")).clicked() {
+                edit = Some(ColumnEdit::MoveUp(i + 1));
+            }
+            if ui.button("✖").clicked() {
+                edit = Some(ColumnEdit::Remove(i));
+            }
+            ui.end_row();
+        });
+    });
+    let missing = template.missing();
+    if !missing.is_empty() {
+        ComboBox::from_id_source(ui.id().with("add column"))
+            .selected_text("add field")
+            .show_ui(ui, |ui| {
+                missing.into_iter().for_each(|field| {
+                    if ui.selectable_label(false, field).clicked() {
+                        edit = Some(ColumnEdit::Add(field));
+                    }
+                });
+            });
+    }
+
+    match edit {
+        Some(ColumnEdit::MoveUp(i)) => template.move_up(i),
+        Some(ColumnEdit::Remove(i)) => {
+            template.columns.remove(i);
+        }
+        Some(ColumnEdit::Add(field)) => template.columns.push(TemplateColumn {
+            field: field.to_string(),
+            header: String::new(),
+        }),
+        None => {}
+    }
+
+    changed || edit.is_some()
+}
+
+/// Window editing app preferences which are saved on every change.
+pub struct SettingsWindow {
+    visible: bool,
+    settings: Settings,
+    error: Option<String>,
+    /// Scale factor of the screen taken before the UI scale is first applied.
+    native_scale: Option<f32>,
+    /// UI scale and font size currently applied to egui.
+    applied: Option<(f32, f32)>,
+}
+
+impl SettingsWindow {
+    pub fn new(visible: bool) -> Self {
+        Self {
+            visible,
+            settings: Settings::loaded(),
+            error: None,
+            native_scale: None,
+            applied: None,
+        }
+    }
+
+    /// Applies the UI scale and font size once they are not being dragged,
+    /// as rescaling moves the slider away from the pointer.
+    fn apply_scale(&mut self, ctx: &Context) {
+        let target = (self.settings.ui_scale, self.settings.font_size);
+        if self.applied == Some(target) || ctx.input().pointer.any_down() {
+            return;
+        }
+
+        let native = *self.native_scale.get_or_insert(ctx.pixels_per_point());
+        info!("Applying ui scale {} and font size {}.", target.0, target.1);
+        ctx.set_pixels_per_point(native * target.0);
+        let mut style = (*ctx.style()).clone();
+        style.text_styles = self.settings.text_styles();
+        ctx.set_style(style);
+        self.applied = Some(target);
+    }
+}
+
+impl AppWindow for SettingsWindow {
+    fn toggle_btn(&mut self, ui: &mut Ui) {
+        if ui.button("settings").clicked() {
+            self.visible = !self.visible
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        self.apply_scale(ui.ctx());
+
+        let settings = &mut self.settings;
+        let mut visible = self.visible;
+        let mut changed = false;
+        Window::new("settings")
+            .open(&mut visible)
+            .show(ui.ctx(), |ui| {
+                changed |= ui
+                    .checkbox(&mut settings.check_updates, "check for updates on start")
+                    .changed();
+                changed |= ui
+                    .checkbox(
+                        &mut settings.auto_failover,
+                        "switch to an alternate source when loading keeps failing",
+                    )
+                    .changed();
+                changed |= ui
+                    .checkbox(
+                        &mut settings.confirmed_only,
+                        "evaluate indicators and alerts on closed candles only",
+                    )
+                    .on_hover_text("otherwise the forming candle is included, applied on next load")
+                    .changed();
+                changed |= ui
+                    .checkbox(&mut settings.archive_klines, "archive downloaded candles")
+                    .on_hover_text("candles and their anomalies are kept in the archive dir")
+                    .changed();
+                changed |= ui
+                    .checkbox(&mut settings.log_to_file, "write logs to a file")
+                    .on_hover_text("applied on restart")
+                    .changed();
+                ui.horizontal(|ui| {
+                    ui.label("alert webhook");
+                    changed |= ui
+                        .add(
+                            TextEdit::singleline(&mut settings.alert_webhook)
+                                .hint_text("https://... posted json on alerts"),
+                        )
+                        .changed();
+                });
+                CollapsingHeader::new("telegram").show(ui, |ui| {
+                    ui.label("messages on triggered alerts and finished exports");
+                    Grid::new("telegram settings").num_columns(2).show(ui, |ui| {
+                        ui.label("bot token");
+                        changed |= ui
+                            .add(TextEdit::singleline(&mut settings.telegram_token).password(true))
+                            .changed();
+                        ui.end_row();
+                        ui.label("chat id");
+                        changed |= ui
+                            .text_edit_singleline(&mut settings.telegram_chat_id)
+                            .changed();
+                        ui.end_row();
+                    });
+                });
+                ui.separator();
+                changed |= ui
+                    .add(Slider::new(&mut settings.ui_scale, 0.5..=3.0).text("ui scale"))
+                    .changed();
+                changed |= ui
+                    .add(Slider::new(&mut settings.font_size, 8.0..=32.0).text("font size"))
+                    .changed();
+                ui.separator();
+                CollapsingHeader::new("export templates").show(ui, |ui| {
+                    let mut removed = None;
+                    settings
+                        .export_templates
+                        .iter_mut()
+                        .enumerate()
+                        .for_each(|(i, template)| {
+                            CollapsingHeader::new(template.name.clone())
+                                .id_source(i)
+                                .show(ui, |ui| {
+                                    changed |= template_ui(ui, template);
+                                    if ui.button("remove template").clicked() {
+                                        removed = Some(i);
+                                    }
+                                });
+                        });
+                    if let Some(i) = removed {
+                        settings.export_templates.remove(i);
+                        changed = true;
+                    }
+                    if ui.button("add template").clicked() {
+                        let name = format!("template {}", settings.export_templates.len() + 1);
+                        settings.export_templates.push(ExportTemplate::new(&name));
+                        changed = true;
+                    }
+                });
+
+                if let Some(err) = &self.error {
+                    ui.colored_label(Color32::LIGHT_RED, err);
+                }
+            });
+        self.visible = visible;
+
+        if changed {
+            info!("Saving settings: {:?}.", self.settings);
+            self.error = match self.settings.save() {
+                Ok(_) => None,
+                Err(err) => {
+                    error!("Failed to save settings: {err}.");
+                    Some(err.to_string())
+                }
+            };
+        }
+    }
+
+    fn name(&self) -> &str {
+        "settings"
+    }
+
+    fn layout(&self) -> WindowLayout {
+        WindowLayout {
+            visible: self.visible,
+            ..Default::default()
+        }
+    }
+
+    fn apply_layout(&mut self, layout: &WindowLayout) {
+        self.visible = layout.visible;
+    }
+}
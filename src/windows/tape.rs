@@ -0,0 +1,169 @@
+use egui::{Color32, Grid, ScrollArea, TextEdit, Ui, Window};
+use poll_promise::Promise;
+use tracing::{error, info};
+
+use super::AppWindow;
+use crate::{
+    netstrat::{
+        data::Data, layout::WindowLayout, order_flow::Tape, power::LowPower, theme::ChartColors,
+    },
+    sources::binance::{errors::ClientError, Client, Trade},
+};
+
+/// Seconds between trades requests.
+const POLL_SECS: f64 = 1.0;
+const TRADES_LIMIT: usize = 100;
+/// Number of trades kept on the tape.
+const TAPE_CAPACITY: usize = 500;
+
+/// Time and sales window with the latest trades of a symbol polled while the window is open.
+pub struct TimeAndSales {
+    visible: bool,
+    /// Symbol typed by the user, switched to when the input loses focus.
+    input: String,
+    symbol: String,
+    tape: Tape,
+    trades_promise: Option<Promise<Result<Vec<Trade>, ClientError>>>,
+    /// Time of the last trades request.
+    polled: Option<f64>,
+    error: Option<String>,
+}
+
+impl TimeAndSales {
+    pub fn new(visible: bool) -> Self {
+        Self {
+            visible,
+            input: "BTCUSDT".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            tape: Tape::new(TAPE_CAPACITY),
+            trades_promise: None,
+            polled: None,
+            error: None,
+        }
+    }
+
+    fn set_symbol(&mut self, symbol: String) {
+        if symbol == self.symbol {
+            return;
+        }
+
+        info!("Switched tape to {symbol}.");
+        self.input = symbol.clone();
+        self.symbol = symbol;
+        self.tape.clear();
+        self.trades_promise = None;
+        self.polled = None;
+    }
+
+    fn poll(&mut self, time: f64) {
+        if let Some(res) = self.trades_promise.as_ref().and_then(|p| p.ready()) {
+            match res {
+                Ok(trades) => {
+                    self.tape.push(trades);
+                    self.error = None;
+                }
+                Err(err) => {
+                    error!("Failed to load trades of {}: {err}.", self.symbol);
+                    self.error = Some(err.to_string());
+                }
+            }
+            self.trades_promise = None;
+        }
+
+        let due = self.polled.is_none_or(|polled| time - polled >= POLL_SECS);
+        if self.trades_promise.is_some() || !due || self.symbol.is_empty() {
+            return;
+        }
+
+        self.polled = Some(time);
+        let symbol = self.symbol.clone();
+        self.trades_promise = Some(Promise::spawn_async(async move {
+            Client::trades(symbol, TRADES_LIMIT).await
+        }));
+    }
+
+    fn trades_ui(&self, ui: &mut Ui) {
+        let colors = ChartColors::current();
+        ScrollArea::vertical().show(ui, |ui| {
+            Grid::new("tape trades").striped(true).show(ui, |ui| {
+                ["time", "price", "qty"].into_iter().for_each(|h| {
+                    ui.strong(h);
+                });
+                ui.end_row();
+
+                self.tape.trades.iter().rev().for_each(|t| {
+                    let color = colors.kline(t.is_buyer_maker);
+                    ui.label(Data::format_ts(t.time as f64));
+                    ui.colored_label(color, t.price.to_string());
+                    ui.colored_label(color, t.qty.to_string());
+                    ui.end_row();
+                });
+            });
+        });
+    }
+}
+
+impl AppWindow for TimeAndSales {
+    fn toggle_btn(&mut self, ui: &mut Ui) {
+        if ui.button("tape").clicked() {
+            self.visible = !self.visible
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        if !self.visible {
+            return;
+        }
+
+        self.poll(ui.input().time);
+        if !LowPower::is_on(ui.ctx()) {
+            ui.ctx().request_repaint();
+        }
+
+        let mut visible = self.visible;
+        Window::new("tape")
+            .open(&mut visible)
+            .min_width(300.0)
+            .default_height(400.0)
+            .show(ui.ctx(), |ui| {
+                ui.horizontal(|ui| {
+                    let input = ui.add(TextEdit::singleline(&mut self.input).desired_width(100.0));
+                    if input.lost_focus() {
+                        self.set_symbol(self.input.to_uppercase());
+                    }
+                    if self.trades_promise.is_some() {
+                        ui.spinner();
+                    }
+                });
+
+                if let Some(err) = &self.error {
+                    ui.colored_label(Color32::LIGHT_RED, err);
+                }
+
+                let (bought, sold) = self.tape.volume();
+                ui.label(format!("bought: {bought}  sold: {sold}"));
+                ui.separator();
+                self.trades_ui(ui);
+            });
+        self.visible = visible;
+    }
+
+    fn name(&self) -> &str {
+        "tape"
+    }
+
+    fn layout(&self) -> WindowLayout {
+        WindowLayout {
+            visible: self.visible,
+            symbol: Some(self.symbol.clone()),
+            ..Default::default()
+        }
+    }
+
+    fn apply_layout(&mut self, layout: &WindowLayout) {
+        self.visible = layout.visible;
+        if let Some(symbol) = &layout.symbol {
+            self.set_symbol(symbol.clone());
+        }
+    }
+}
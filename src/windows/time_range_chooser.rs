@@ -3,13 +3,19 @@ use chrono::prelude::*;
 use super::AppWindow;
 use chrono::{Date, NaiveTime, Utc};
 use crossbeam::channel::{Receiver, Sender};
-use egui::{Ui, Window};
+use egui::{DragValue, TextEdit, Ui, WidgetText, Window};
 use tracing::{error, info, warn};
 
 use crate::{
     netstrat::{
         bounds::{Bounds, BoundsSet},
-        graph::props::Props,
+        export::{template::ExportTemplate, ExportOptions, KlinesFormat},
+        graph::{
+            defaults::{SymbolClass, SymbolClasses},
+            props::Props,
+        },
+        layout::WindowLayout,
+        settings::Settings,
     },
     sources::binance::Interval,
     widgets::TimeInput,
@@ -26,7 +32,18 @@ pub struct TimeRangeChooser {
     date_end: Date<Utc>,
     interval: Interval,
     props_pub: Sender<Props>,
-    export_pub: Sender<Props>,
+    export_pub: Sender<(Props, ExportOptions)>,
+    export_options: ExportOptions,
+    /// Export templates from settings, reloaded when the template list is opened.
+    templates: Vec<ExportTemplate>,
+    classes: Vec<ClassInput>,
+    classes_status: Option<String>,
+}
+
+/// Symbol class being edited with symbols as a comma separated list.
+struct ClassInput {
+    class: SymbolClass,
+    symbols: String,
 }
 
 impl TimeRangeChooser {
@@ -34,7 +51,7 @@ impl TimeRangeChooser {
         visible: bool,
         symbol_sub: Receiver<String>,
         props_pub: Sender<Props>,
-        export_pub: Sender<Props>,
+        export_pub: Sender<(Props, ExportOptions)>,
         props: Props,
     ) -> Self {
         Self {
@@ -44,6 +61,10 @@ impl TimeRangeChooser {
             visible,
             props_pub,
             export_pub,
+            export_options: Default::default(),
+            templates: vec![],
+            classes: vec![],
+            classes_status: None,
             date_start: props.date_start,
             date_end: props.date_end,
             interval: props.interval,
@@ -62,6 +83,112 @@ impl TimeRangeChooser {
 }
 
 impl TimeRangeChooser {
+    fn set_props(&mut self, props: &Props) {
+        self.date_start = props.date_start;
+        self.date_end = props.date_end;
+        self.interval = props.interval;
+        self.time_start_input = TimeInput::new(
+            props.time_start.hour(),
+            props.time_start.minute(),
+            props.time_start.second(),
+        );
+        self.time_end_input = TimeInput::new(
+            props.time_end.hour(),
+            props.time_end.minute(),
+            props.time_end.second(),
+        );
+    }
+
+    fn load_classes(&mut self) {
+        self.classes = SymbolClasses::loaded()
+            .classes
+            .into_iter()
+            .map(|class| ClassInput {
+                symbols: class.symbols.join(", "),
+                class,
+            })
+            .collect();
+    }
+
+    fn save_classes(&mut self) {
+        let mut classes = SymbolClasses::default();
+        classes.classes = self
+            .classes
+            .iter()
+            .map(|c| SymbolClass {
+                symbols: c
+                    .symbols
+                    .split(',')
+                    .map(|s| s.trim().to_uppercase())
+                    .filter(|s| !s.is_empty())
+                    .collect(),
+                ..c.class.clone()
+            })
+            .collect();
+
+        self.classes_status = Some(match classes.save() {
+            Ok(_) => "saved".to_string(),
+            Err(err) => {
+                error!("Failed to save symbol classes: {err}.");
+                err.to_string()
+            }
+        });
+    }
+
+    /// Draws editor of default props applied when a symbol of a class is selected.
+    fn classes_ui(&mut self, ui: &mut Ui) {
+        if self.classes.is_empty() && self.classes_status.is_none() {
+            self.load_classes();
+        }
+
+        let mut removed = None;
+        self.classes.iter_mut().enumerate().for_each(|(i, c)| {
+            ui.horizontal(|ui| {
+                ui.add(TextEdit::singleline(&mut c.class.name).desired_width(80.0));
+                egui::ComboBox::from_id_source(format!("class interval {i}"))
+                    .selected_text(c.class.interval.as_str())
+                    .show_ui(ui, |ui| {
+                        for interval in Interval::ALL {
+                            let s = interval.as_str().to_string();
+                            ui.selectable_value(&mut c.class.interval, s.clone(), s);
+                        }
+                    });
+                ui.add(DragValue::new(&mut c.class.hours).clamp_range(1..=24 * 365));
+                ui.label("hours");
+                if ui.button("🗑").clicked() {
+                    removed = Some(i);
+                }
+            });
+            ui.add(
+                TextEdit::singleline(&mut c.symbols)
+                    .hint_text(WidgetText::from("BTCUSDT, ETHUSDT").italics()),
+            );
+        });
+        if let Some(i) = removed {
+            self.classes.remove(i);
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("add class").clicked() {
+                self.classes.push(ClassInput {
+                    class: SymbolClass {
+                        name: "new class".to_string(),
+                        symbols: vec![],
+                        interval: Interval::Minute.as_str().to_string(),
+                        hours: 24,
+                    },
+                    symbols: String::new(),
+                });
+            }
+            if ui.button("save").clicked() {
+                self.save_classes();
+            }
+            if let Some(status) = &self.classes_status {
+                ui.label(status);
+            }
+        });
+    }
+
     fn parse_props(
         time_start_opt: Option<NaiveTime>,
         time_end_opt: Option<NaiveTime>,
@@ -120,14 +247,20 @@ impl AppWindow for TimeRangeChooser {
 
         match symbol_wrapped {
             Ok(symbol) => {
+                if let Some(props) =
+                    SymbolClasses::loaded().props(&symbol, Utc::now().timestamp_millis())
+                {
+                    self.set_props(&props);
+                }
                 self.symbol = symbol;
             }
             Err(_) => {}
         }
 
         // TODO: make window always on top; this is not implemented in egui yet
+        let mut visible = self.visible;
         Window::new(self.symbol.to_string())
-            .open(&mut self.visible)
+            .open(&mut visible)
             .drag_bounds(ui.max_rect())
             .resizable(false)
             .show(ui.ctx(), |ui| {
@@ -157,14 +290,18 @@ impl AppWindow for TimeRangeChooser {
                 });
                 ui.collapsing("interval", |ui| {
                     egui::ComboBox::from_label("pick data interval")
-                        .selected_text(format!("{:?}", self.interval))
+                        .selected_text(self.interval.as_str())
                         .show_ui(ui, |ui| {
-                            ui.selectable_value(&mut self.interval, Interval::Day, "Day");
-                            ui.selectable_value(&mut self.interval, Interval::Hour, "Hour");
-                            ui.selectable_value(&mut self.interval, Interval::Minute, "Minute");
+                            Interval::ALL.into_iter().for_each(|i| {
+                                ui.selectable_value(&mut self.interval, i, i.as_str());
+                            });
                         });
                 });
 
+                ui.collapsing("defaults per symbol class", |ui| {
+                    self.classes_ui(ui);
+                });
+
                 ui.add_space(5f32);
 
                 ui.horizontal(|ui| {
@@ -200,6 +337,38 @@ impl AppWindow for TimeRangeChooser {
                         }
                     }
 
+                    egui::ComboBox::from_id_source("export format")
+                        .selected_text(self.export_options.format.as_str())
+                        .show_ui(ui, |ui| {
+                            KlinesFormat::all().into_iter().for_each(|f| {
+                                ui.selectable_value(&mut self.export_options.format, f, f.as_str());
+                            });
+                        });
+                    let templates = &self.templates;
+                    let template = egui::ComboBox::from_id_source("export template")
+                        .selected_text(self.export_options.template.name.as_str())
+                        .show_ui(ui, |ui| {
+                            std::iter::once(ExportTemplate::default())
+                                .chain(templates.iter().cloned())
+                                .for_each(|t| {
+                                    let name = t.name.clone();
+                                    ui.selectable_value(&mut self.export_options.template, t, name);
+                                });
+                        });
+                    if template
+                        .response
+                        .on_hover_text("exported fields, templates are edited in settings")
+                        .clicked()
+                    {
+                        self.templates = Settings::loaded().export_templates;
+                    }
+                    ui.checkbox(&mut self.export_options.indicators, "indicators")
+                        .on_hover_text(
+                            "append series of enabled indicators as columns, \
+                             the period is charted first",
+                        );
+                    ui.checkbox(&mut self.export_options.visible_only, "visible range")
+                        .on_hover_text("export loaded klines shown on the chart, not the period");
                     if ui.button("export").clicked() {
                         let props = TimeRangeChooser::parse_props(
                             self.time_start_input.get_time(),
@@ -211,7 +380,9 @@ impl AppWindow for TimeRangeChooser {
                         match props {
                             Some(props) => {
                                 if props.is_valid() {
-                                    let send_result = self.export_pub.send(props.clone());
+                                    let send_result = self
+                                        .export_pub
+                                        .send((props.clone(), self.export_options.clone()));
                                     match send_result {
                                         Ok(_) => {
                                             info!("Sent props for export: {props:?}.");
@@ -237,5 +408,25 @@ impl AppWindow for TimeRangeChooser {
                     ui.label("invalid time format or start > end");
                 }
             });
+        self.visible = visible;
+    }
+
+    fn name(&self) -> &str {
+        "props"
+    }
+
+    fn layout(&self) -> WindowLayout {
+        WindowLayout {
+            visible: self.visible,
+            interval: Some(self.interval.as_str().to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn apply_layout(&mut self, layout: &WindowLayout) {
+        self.visible = layout.visible;
+        if let Some(interval) = layout.interval.as_deref().and_then(Interval::parse) {
+            self.interval = interval;
+        }
     }
 }
@@ -0,0 +1,460 @@
+use egui::{
+    Align2, Color32, ComboBox, DragValue, Grid, RichText, ScrollArea, TextEdit, Ui, Window,
+};
+use poll_promise::Promise;
+use tracing::{error, info};
+
+use super::AppWindow;
+use crate::{
+    netstrat::{
+        data::Data,
+        layout::WindowLayout,
+        paper::{self, fills_history, PnlPoint},
+        replay::Side,
+        toasts::Toasts,
+    },
+    sources::binance::{
+        credentials::Credentials,
+        errors::ClientError,
+        trading::{Fill, NewOrder, Order, OrderSide, OrderType, TradingClient},
+        Client, Symbol,
+    },
+    widgets::PnlChart,
+};
+
+type Pending<T> = Option<Promise<Result<T, ClientError>>>;
+
+/// Window submitting real spot orders with the API keys from the environment.
+///
+/// Orders are validated against the lot size and price filters of the symbol and
+/// are only sent after they are confirmed in a separate dialog.
+pub struct LiveTrading {
+    visible: bool,
+    creds: Option<Credentials>,
+    input: String,
+    symbol: Option<Symbol>,
+    side: OrderSide,
+    limit: bool,
+    qty: f64,
+    price: f64,
+    /// Order waiting for confirmation.
+    confirm: Option<NewOrder>,
+    orders: Vec<Order>,
+    fills: Vec<Fill>,
+    /// Profit of the loaded fills marked at fill prices.
+    history: Vec<PnlPoint>,
+    pnl: PnlChart,
+    symbol_promise: Pending<Option<Symbol>>,
+    orders_promise: Pending<Vec<Order>>,
+    fills_promise: Pending<Vec<Fill>>,
+    /// Placed or cancelled order.
+    order_promise: Pending<Order>,
+}
+
+impl LiveTrading {
+    pub fn new(visible: bool) -> Self {
+        Self {
+            visible,
+            creds: Credentials::from_env(),
+            input: "BTCUSDT".to_string(),
+            symbol: None,
+            side: OrderSide::Buy,
+            limit: true,
+            qty: 0.0,
+            price: 0.0,
+            confirm: None,
+            orders: vec![],
+            fills: vec![],
+            history: vec![],
+            pnl: PnlChart::new("trading P&L"),
+            symbol_promise: None,
+            orders_promise: None,
+            fills_promise: None,
+            order_promise: None,
+        }
+    }
+
+    fn load_symbol(&mut self) {
+        let symbol = self.input.to_uppercase();
+        info!("Loading trading rules of {symbol}.");
+
+        self.symbol = None;
+        self.orders = vec![];
+        self.fills = vec![];
+        self.history = vec![];
+        self.symbol_promise = Some(Promise::spawn_async(
+            async move { Client::symbol(symbol).await },
+        ));
+    }
+
+    /// Reloads open orders and fills of the loaded symbol.
+    fn refresh(&mut self, creds: &Credentials) {
+        let symbol = match &self.symbol {
+            Some(symbol) => symbol.symbol.clone(),
+            None => return,
+        };
+
+        let (c, s) = (creds.clone(), symbol.clone());
+        self.orders_promise = Some(Promise::spawn_async(async move {
+            TradingClient::open_orders(c, s).await
+        }));
+        let c = creds.clone();
+        self.fills_promise = Some(Promise::spawn_async(async move {
+            TradingClient::fills(c, symbol).await
+        }));
+    }
+
+    fn poll(&mut self, creds: &Credentials) {
+        if let Some(res) = self.symbol_promise.as_ref().and_then(|p| p.ready()) {
+            match res {
+                Ok(Some(symbol)) => {
+                    self.symbol = Some(symbol.clone());
+                    self.refresh(creds);
+                }
+                Ok(None) => Toasts::info(format!("{} is not listed", self.input.to_uppercase())),
+                Err(err) => {
+                    error!("Failed to load trading rules: {err}.");
+                    Toasts::error("Failed to load trading rules", err);
+                }
+            }
+            self.symbol_promise = None;
+        }
+
+        if let Some(res) = self.orders_promise.as_ref().and_then(|p| p.ready()) {
+            match res {
+                Ok(orders) => self.orders = orders.clone(),
+                Err(err) => {
+                    error!("Failed to load open orders: {err}.");
+                    Toasts::error("Failed to load open orders", err);
+                }
+            }
+            self.orders_promise = None;
+        }
+
+        if let Some(res) = self.fills_promise.as_ref().and_then(|p| p.ready()) {
+            match res {
+                Ok(fills) => {
+                    self.fills = fills.clone();
+                    self.history = self.fills_history();
+                }
+                Err(err) => {
+                    error!("Failed to load fills: {err}.");
+                    Toasts::error("Failed to load fills", err);
+                }
+            }
+            self.fills_promise = None;
+        }
+
+        if let Some(res) = self.order_promise.as_ref().and_then(|p| p.ready()) {
+            match res {
+                Ok(order) => {
+                    info!("Order {} is {}: {order:?}.", order.order_id, order.status);
+                    Toasts::info(format!(
+                        "Order {} {} {} {}",
+                        order.order_id, order.side, order.symbol, order.status
+                    ));
+                }
+                Err(err) => {
+                    error!("Order request failed: {err}.");
+                    Toasts::error("Order request failed", err);
+                }
+            }
+            self.order_promise = None;
+            self.refresh(creds);
+        }
+    }
+
+    /// Only commissions paid in the quote asset are counted, others are not priced.
+    fn fills_history(&self) -> Vec<PnlPoint> {
+        let quote = self.symbol.as_ref().map(|s| s.quote_asset.as_str());
+        let fills: Vec<paper::Fill> = self
+            .fills
+            .iter()
+            .map(|f| paper::Fill {
+                order: f.order_id as usize,
+                symbol: f.symbol.clone(),
+                side: match f.is_buyer {
+                    true => Side::Buy,
+                    false => Side::Sell,
+                },
+                qty: f.qty,
+                price: f.price,
+                fee: match Some(f.commission_asset.as_str()) == quote {
+                    true => f.commission,
+                    false => 0.0,
+                },
+                time: f.time,
+            })
+            .collect();
+
+        fills_history(&fills)
+    }
+
+    fn order(&self, symbol: &Symbol) -> NewOrder {
+        NewOrder {
+            symbol: symbol.symbol.clone(),
+            side: self.side,
+            order_type: match self.limit {
+                true => OrderType::Limit(self.price),
+                false => OrderType::Market,
+            },
+            qty: self.qty,
+        }
+    }
+
+    fn ticket_ui(&mut self, ui: &mut Ui, symbol: &Symbol) {
+        let lot = symbol.lot_size();
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.side, OrderSide::Buy, "buy");
+            ui.selectable_value(&mut self.side, OrderSide::Sell, "sell");
+            ComboBox::from_id_source("live order type")
+                .selected_text(match self.limit {
+                    true => "limit",
+                    false => "market",
+                })
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.limit, true, "limit");
+                    ui.selectable_value(&mut self.limit, false, "market");
+                });
+        });
+        ui.horizontal(|ui| {
+            ui.add(
+                DragValue::new(&mut self.qty)
+                    .clamp_range(0.0..=f64::MAX)
+                    .speed(lot.map_or(0.001, |lot| lot.step)),
+            );
+            ui.label(format!("quantity, {}", symbol.base_asset));
+            if self.limit {
+                ui.add(
+                    DragValue::new(&mut self.price)
+                        .clamp_range(0.0..=f64::MAX)
+                        .speed(symbol.tick_size().unwrap_or(0.01)),
+                );
+                ui.label(format!("price, {}", symbol.quote_asset));
+            }
+        });
+        if let Some(lot) = lot {
+            ui.label(format!(
+                "lot size: {} to {} by {}",
+                lot.min, lot.max, lot.step
+            ));
+        }
+
+        let order = self.order(symbol);
+        ui.horizontal(|ui| {
+            let valid = order.validate(symbol);
+            if ui
+                .add_enabled(
+                    valid.is_ok() && self.order_promise.is_none(),
+                    egui::Button::new("review order"),
+                )
+                .clicked()
+            {
+                self.confirm = Some(order);
+            }
+            if let Err(err) = valid {
+                ui.colored_label(Color32::LIGHT_RED, err.to_string());
+            }
+        });
+    }
+
+    /// Draws the confirmation dialog. The order is only submitted from here.
+    fn confirm_ui(&mut self, ui: &Ui, creds: &Credentials) {
+        let order = match &self.confirm {
+            Some(order) => order.clone(),
+            None => return,
+        };
+        let symbol = match &self.symbol {
+            Some(symbol) => symbol.clone(),
+            None => {
+                self.confirm = None;
+                return;
+            }
+        };
+
+        let mut confirmed = None;
+        Window::new("confirm real order")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                ui.label(
+                    RichText::new("This order is sent to the exchange with your API keys.")
+                        .color(Color32::LIGHT_RED)
+                        .strong(),
+                );
+                ui.label(format!(
+                    "{} {} {}",
+                    order.side.as_str(),
+                    order.qty,
+                    order.symbol
+                ));
+                ui.label(match order.order_type {
+                    OrderType::Market => "at market price".to_string(),
+                    OrderType::Limit(price) => format!(
+                        "limit {price} {}, value {:.2}",
+                        symbol.quote_asset,
+                        price * order.qty
+                    ),
+                });
+                ui.horizontal(|ui| {
+                    if ui.button("submit").clicked() {
+                        confirmed = Some(true);
+                    }
+                    if ui.button("cancel").clicked() {
+                        confirmed = Some(false);
+                    }
+                });
+            });
+
+        match confirmed {
+            Some(true) => {
+                info!("Submitting confirmed order {order:?}.");
+                let creds = creds.clone();
+                self.order_promise = Some(Promise::spawn_async(async move {
+                    TradingClient::place_order(creds, order, symbol).await
+                }));
+                self.confirm = None;
+            }
+            Some(false) => self.confirm = None,
+            None => {}
+        }
+    }
+
+    fn orders_ui(&mut self, ui: &mut Ui, creds: &Credentials) {
+        ui.horizontal(|ui| {
+            ui.strong("open orders");
+            if self.orders_promise.is_some() || self.fills_promise.is_some() {
+                ui.spinner();
+            } else if ui.button("refresh").clicked() {
+                self.refresh(creds);
+            }
+        });
+
+        let mut cancel = None;
+        Grid::new("live orders").striped(true).show(ui, |ui| {
+            self.orders.iter().for_each(|o| {
+                ui.label(Data::format_ts(o.time as f64));
+                ui.label(&o.side);
+                ui.label(&o.order_type);
+                ui.label(format!("{}/{}", o.executed_qty, o.orig_qty));
+                ui.label(o.price.to_string());
+                if ui
+                    .add_enabled(self.order_promise.is_none(), egui::Button::new("cancel"))
+                    .clicked()
+                {
+                    cancel = Some((o.symbol.clone(), o.order_id));
+                }
+                ui.end_row();
+            });
+        });
+        if let Some((symbol, order_id)) = cancel {
+            info!("Cancelling order {order_id} of {symbol}.");
+            let creds = creds.clone();
+            self.order_promise = Some(Promise::spawn_async(async move {
+                TradingClient::cancel_order(creds, symbol, order_id).await
+            }));
+        }
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.strong("fills");
+            self.pnl.toggle_btn(ui);
+        });
+        ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
+            Grid::new("live fills").striped(true).show(ui, |ui| {
+                self.fills.iter().rev().for_each(|f| {
+                    ui.label(Data::format_ts(f.time as f64));
+                    ui.label(match f.is_buyer {
+                        true => "BUY",
+                        false => "SELL",
+                    });
+                    ui.label(f.qty.to_string());
+                    ui.label(f.price.to_string());
+                    ui.label(format!("fee {} {}", f.commission, f.commission_asset));
+                    ui.end_row();
+                });
+            });
+        });
+    }
+}
+
+impl AppWindow for LiveTrading {
+    fn toggle_btn(&mut self, ui: &mut Ui) {
+        if ui.button("trading").clicked() {
+            self.visible = !self.visible
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        let creds = self.creds.clone();
+        if let Some(creds) = &creds {
+            self.poll(creds);
+            if self.visible {
+                self.confirm_ui(ui, creds);
+            }
+        }
+
+        let mut visible = self.visible;
+        Window::new("trading")
+            .open(&mut visible)
+            .min_width(400.0)
+            .show(ui.ctx(), |ui| {
+                let creds = match &creds {
+                    Some(creds) => creds,
+                    None => {
+                        ui.label("Set BINANCE_API_KEY and BINANCE_API_SECRET to place orders.");
+                        return;
+                    }
+                };
+
+                ui.horizontal(|ui| {
+                    ui.add(TextEdit::singleline(&mut self.input).desired_width(100.0));
+                    let loading = self.symbol_promise.is_some();
+                    if ui
+                        .add_enabled(!loading, egui::Button::new("load"))
+                        .clicked()
+                    {
+                        self.load_symbol();
+                    }
+                    if loading || self.order_promise.is_some() {
+                        ui.ctx().request_repaint();
+                        ui.spinner();
+                    }
+                });
+
+                let symbol = match &self.symbol {
+                    Some(symbol) => symbol.clone(),
+                    None => {
+                        ui.label("Load a symbol to see its trading rules.");
+                        return;
+                    }
+                };
+                ui.heading(&symbol.symbol);
+                self.ticket_ui(ui, &symbol);
+                ui.separator();
+                self.orders_ui(ui, creds);
+            });
+        self.visible = visible;
+        if !self.visible {
+            self.confirm = None;
+            return;
+        }
+        self.pnl.show(ui.ctx(), &self.history);
+    }
+
+    fn name(&self) -> &str {
+        "trading"
+    }
+
+    fn layout(&self) -> WindowLayout {
+        WindowLayout {
+            visible: self.visible,
+            ..Default::default()
+        }
+    }
+
+    fn apply_layout(&mut self, layout: &WindowLayout) {
+        self.visible = layout.visible;
+    }
+}
@@ -0,0 +1,113 @@
+use egui::{Color32, RichText, ScrollArea, Ui, Window};
+use poll_promise::Promise;
+use tracing::{error, info};
+
+use super::AppWindow;
+use crate::{
+    netstrat::{layout::WindowLayout, settings::Settings},
+    sources::github::{errors::ClientError, GithubClient, Release},
+};
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Notice about a newer release with its notes.
+/// The releases feed is checked once on start unless disabled in settings.
+pub struct UpdateNotice {
+    visible: bool,
+    release: Option<Release>,
+    release_promise: Option<Promise<Result<Release, ClientError>>>,
+}
+
+impl UpdateNotice {
+    pub fn new(visible: bool) -> Self {
+        let release_promise = match Settings::loaded().check_updates {
+            true => {
+                info!("Checking for updates of version {VERSION}.");
+                Some(Promise::spawn_async(GithubClient::latest_release()))
+            }
+            false => None,
+        };
+
+        Self {
+            visible,
+            release: None,
+            release_promise,
+        }
+    }
+
+    fn poll(&mut self) {
+        let res = match self.release_promise.as_ref().and_then(|p| p.ready()) {
+            Some(res) => res,
+            None => return,
+        };
+
+        match res {
+            Ok(release) if release.is_newer_than(VERSION) => {
+                info!("New version is available: {}.", release.tag_name);
+                self.release = Some(release.clone());
+            }
+            Ok(release) => info!("Version is up to date, latest: {}.", release.tag_name),
+            Err(err) => error!("Failed to check for updates: {err}."),
+        }
+        self.release_promise = None;
+    }
+}
+
+impl AppWindow for UpdateNotice {
+    fn toggle_btn(&mut self, ui: &mut Ui) {
+        self.poll();
+
+        if let Some(release) = &self.release {
+            let text = RichText::new(format!("⬆ {}", release.tag_name)).color(Color32::GOLD);
+            if ui
+                .button(text)
+                .on_hover_text("new version available")
+                .clicked()
+            {
+                self.visible = !self.visible
+            }
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        let release = match &self.release {
+            Some(release) => release,
+            None => return,
+        };
+
+        let mut visible = self.visible;
+        Window::new("new version available")
+            .open(&mut visible)
+            .show(ui.ctx(), |ui| {
+                ui.label(format!(
+                    "{} is available, current version is {VERSION}.",
+                    release.name.as_ref().unwrap_or(&release.tag_name)
+                ));
+                ui.hyperlink_to("open release page", &release.html_url);
+                ui.separator();
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    ui.label(release.body.as_deref().unwrap_or("no release notes"));
+                });
+                ui.separator();
+                ui.label(
+                    RichText::new("checking for updates can be turned off in settings").small(),
+                );
+            });
+        self.visible = visible;
+    }
+
+    fn name(&self) -> &str {
+        "update"
+    }
+
+    fn layout(&self) -> WindowLayout {
+        WindowLayout {
+            visible: self.visible,
+            ..Default::default()
+        }
+    }
+
+    fn apply_layout(&mut self, layout: &WindowLayout) {
+        self.visible = layout.visible;
+    }
+}
@@ -1,6 +1,13 @@
 use egui::Ui;
 
+use crate::netstrat::layout::WindowLayout;
+
 pub trait AppWindow {
     fn toggle_btn(&mut self, ui: &mut Ui);
     fn show(&mut self, ui: &mut Ui);
+
+    /// Name identifying the window in saved layouts.
+    fn name(&self) -> &str;
+    fn layout(&self) -> WindowLayout;
+    fn apply_layout(&mut self, layout: &WindowLayout);
 }